@@ -65,6 +65,10 @@ pub struct Args {
     /// 持久化配置 - 持久化方式
     #[arg(long, default_value = "always")] 
     pub appendfsync: String,
+
+    /// 客户端查询缓冲区上限（字节），超过时关闭连接
+    #[arg(long, default_value = "1073741824")]
+    pub client_query_buffer_limit: usize,
 }
 
 impl Args {
@@ -190,6 +194,15 @@ impl Args {
                 self.appendfsync = afs.clone();
             }
         }
+
+        // client-query-buffer-limit
+        if self.client_query_buffer_limit == 1024 * 1024 * 1024 {
+            if let Some(limit) = config_map.get("client-query-buffer-limit") {
+                if let Ok(limit) = limit.parse() {
+                    self.client_query_buffer_limit = limit;
+                }
+            }
+        }
     }
 }
 
@@ -245,8 +258,8 @@ fn parse_config_line(line: &str) -> Option<(String, String)> {
     let val = iter.next()?.trim();
     
     if key.is_empty() || val.is_empty() {
-        return None;
+        None
     } else {
-        return Some((key.to_string(), val.to_string()));
+        Some((key.to_string(), val.to_string()))
     }
 }
\ No newline at end of file
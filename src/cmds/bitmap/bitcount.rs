@@ -0,0 +1,61 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::bitmap};
+
+pub struct Bitcount {
+    key: Vec<u8>,
+    range: Option<(i64, i64)>,
+    bit_mode: bool, // 区间单位是否为位（默认为字节）
+}
+
+impl Bitcount {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'bitcount' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+
+        let parse = |value: &String| value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"));
+        let (range, bit_mode) = match args.len() {
+            2 => (None, false),
+            4 => (Some((parse(&args[2])?, parse(&args[3])?)), false),
+            5 => {
+                let bit_mode = match args[4].to_uppercase().as_str() {
+                    "BYTE" => false,
+                    "BIT" => true,
+                    _ => return Err(Error::msg("ERR syntax error")),
+                };
+                (Some((parse(&args[2])?, parse(&args[3])?)), bit_mode)
+            },
+            _ => return Err(Error::msg("ERR syntax error")),
+        };
+
+        Ok(Bitcount { key, range, bit_mode })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let value = match db.get(&self.key) {
+            Some(Structure::String(value)) => value,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Integer(0)),
+        };
+
+        let total_bits = value.len() as i64 * 8;
+        let bits = match self.range {
+            None => bitmap::normalize_range(0, -1, total_bits),
+            Some((start, end)) if self.bit_mode => bitmap::normalize_range(start, end, total_bits),
+            Some((start, end)) => bitmap::normalize_range(start, end, value.len() as i64)
+                .map(|(start, end)| (start * 8, end * 8 + 7)),
+        };
+
+        match bits {
+            Some((start, end)) => Ok(Frame::Integer(bitmap::count_bits(value, start, end) as i64)),
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
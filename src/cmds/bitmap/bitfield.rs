@@ -0,0 +1,209 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::bitmap};
+
+/**
+ * 溢出策略
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Overflow {
+    Wrap, // 回绕（默认）
+    Sat, // 饱和到最大值或最小值
+    Fail, // 不执行并返回 nil
+}
+
+/**
+ * 整数类型，如 i8、u16
+ */
+#[derive(Clone, Copy)]
+struct Encoding {
+    signed: bool,
+    bits: u32,
+}
+
+impl Encoding {
+
+    fn parse(value: &str) -> Result<Self, Error> {
+        let invalid = || Error::msg("ERR Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is.");
+        let signed = match value.as_bytes().first() {
+            Some(b'i') | Some(b'I') => true,
+            Some(b'u') | Some(b'U') => false,
+            _ => return Err(invalid()),
+        };
+        let bits = value[1..].parse::<u32>().map_err(|_| invalid())?;
+        let max = if signed { 64 } else { 63 };
+        if bits == 0 || bits > max {
+            return Err(invalid());
+        }
+        Ok(Encoding { signed, bits })
+    }
+
+    fn min(&self) -> i128 {
+        if self.signed { -(1i128 << (self.bits - 1)) } else { 0 }
+    }
+
+    fn max(&self) -> i128 {
+        if self.signed { (1i128 << (self.bits - 1)) - 1 } else { (1i128 << self.bits) - 1 }
+    }
+
+    fn get(&self, bytes: &[u8], offset: u64) -> i64 {
+        if self.signed {
+            bitmap::get_signed(bytes, offset, self.bits)
+        } else {
+            bitmap::get_unsigned(bytes, offset, self.bits) as i64
+        }
+    }
+
+    /**
+     * 按溢出策略处理结果，FAIL 溢出时返回 None
+     *
+     * @param value 计算结果
+     * @param overflow 溢出策略
+     */
+    fn fit(&self, value: i128, overflow: Overflow) -> Option<i64> {
+        if value >= self.min() && value <= self.max() {
+            return Some(value as i64);
+        }
+        match overflow {
+            Overflow::Fail => None,
+            Overflow::Sat => Some(if value > self.max() { self.max() } else { self.min() } as i64),
+            Overflow::Wrap => {
+                let mask = (1u128 << self.bits) - 1;
+                let wrapped = (value as u128) & mask;
+                if self.signed && wrapped & (1u128 << (self.bits - 1)) != 0 {
+                    Some((wrapped as i128 - (1i128 << self.bits)) as i64)
+                } else {
+                    Some(wrapped as i64)
+                }
+            }
+        }
+    }
+}
+
+enum Operation {
+    Get(Encoding, u64),
+    Set(Encoding, u64, i64, Overflow),
+    Incrby(Encoding, u64, i64, Overflow),
+}
+
+pub struct Bitfield {
+    key: Vec<u8>,
+    operations: Vec<Operation>,
+}
+
+impl Bitfield {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'bitfield' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+
+        let mut operations = Vec::new();
+        let mut overflow = Overflow::Wrap;
+        let mut idx = 2;
+        while idx < args.len() {
+            let subcommand = args[idx].to_uppercase();
+            let remaining = args.len() - idx - 1;
+            match subcommand.as_str() {
+                "GET" if remaining >= 2 => {
+                    let encoding = Encoding::parse(&args[idx + 1])?;
+                    let offset = Bitfield::parse_offset(&args[idx + 2], encoding)?;
+                    operations.push(Operation::Get(encoding, offset));
+                    idx += 3;
+                },
+                "SET" | "INCRBY" if remaining >= 3 => {
+                    let encoding = Encoding::parse(&args[idx + 1])?;
+                    let offset = Bitfield::parse_offset(&args[idx + 2], encoding)?;
+                    let value = args[idx + 3].parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+                    if subcommand == "SET" {
+                        operations.push(Operation::Set(encoding, offset, value, overflow));
+                    } else {
+                        operations.push(Operation::Incrby(encoding, offset, value, overflow));
+                    }
+                    idx += 4;
+                },
+                "OVERFLOW" if remaining >= 1 => {
+                    overflow = match args[idx + 1].to_uppercase().as_str() {
+                        "WRAP" => Overflow::Wrap,
+                        "SAT" => Overflow::Sat,
+                        "FAIL" => Overflow::Fail,
+                        _ => return Err(Error::msg("ERR Invalid OVERFLOW type specified")),
+                    };
+                    idx += 2;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+        }
+
+        Ok(Bitfield { key, operations })
+    }
+
+    /**
+     * 解析位段偏移，#N 表示第 N 个该类型的位段
+     *
+     * @param value 参数
+     * @param encoding 整数类型
+     */
+    fn parse_offset(value: &str, encoding: Encoding) -> Result<u64, Error> {
+        let invalid = || Error::msg("ERR bit offset is not an integer or out of range");
+        let offset = match value.strip_prefix('#') {
+            Some(index) => index.parse::<u64>().ok().and_then(|i| i.checked_mul(encoding.bits as u64)),
+            None => value.parse::<u64>().ok(),
+        };
+        match offset {
+            Some(offset) if offset + encoding.bits as u64 - 1 <= bitmap::MAX_BIT_OFFSET => Ok(offset),
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let mut value = match db.get(&self.key) {
+            Some(Structure::String(value)) => value.clone(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => Vec::new(),
+        };
+
+        let mut results = Vec::with_capacity(self.operations.len());
+        let mut changed = false;
+        for operation in self.operations {
+            match operation {
+                Operation::Get(encoding, offset) => {
+                    results.push(Frame::Integer(encoding.get(&value, offset)));
+                },
+                Operation::Set(encoding, offset, new_value, overflow) => {
+                    let old = encoding.get(&value, offset);
+                    match encoding.fit(new_value as i128, overflow) {
+                        Some(fitted) => {
+                            bitmap::set_bits(&mut value, offset, encoding.bits, fitted as u64);
+                            changed = true;
+                            results.push(Frame::Integer(old));
+                        },
+                        None => results.push(Frame::Null),
+                    }
+                },
+                Operation::Incrby(encoding, offset, increment, overflow) => {
+                    let old = encoding.get(&value, offset);
+                    match encoding.fit(old as i128 + increment as i128, overflow) {
+                        Some(fitted) => {
+                            bitmap::set_bits(&mut value, offset, encoding.bits, fitted as u64);
+                            changed = true;
+                            results.push(Frame::Integer(fitted));
+                        },
+                        None => results.push(Frame::Null),
+                    }
+                },
+            }
+        }
+
+        if changed {
+            db.insert(self.key, Structure::String(value));
+        }
+
+        Ok(Frame::Array(results))
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+#[derive(PartialEq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+pub struct Bitop {
+    operation: Operation,
+    destkey: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+}
+
+impl Bitop {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'bitop' command"));
+        }
+        let operation = match String::from_utf8_lossy(&args[1]).to_uppercase().as_str() {
+            "AND" => Operation::And,
+            "OR" => Operation::Or,
+            "XOR" => Operation::Xor,
+            "NOT" => Operation::Not,
+            _ => return Err(Error::msg("ERR syntax error")),
+        };
+        let destkey = args[2].clone(); // 目标键
+        let keys = args[3..].to_vec(); // 源键
+        if operation == Operation::Not && keys.len() != 1 {
+            return Err(Error::msg("ERR BITOP NOT must be called with a single source key."));
+        }
+        Ok(Bitop { operation, destkey, keys })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let mut sources = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            match db.get(key) {
+                Some(Structure::String(value)) => sources.push(value.clone()),
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Ok(Frame::Error(f.to_string()));
+                },
+                None => sources.push(Vec::new()),
+            }
+        }
+
+        // 结果长度为最长的源字符串长度，较短的字符串以 0 补齐
+        let len = sources.iter().map(|s| s.len()).max().unwrap_or(0);
+        let byte_at = |source: &Vec<u8>, i: usize| source.get(i).copied().unwrap_or(0);
+        let result: Vec<u8> = (0..len).map(|i| {
+            let mut iter = sources.iter();
+            let first = byte_at(iter.next().unwrap(), i);
+            match self.operation {
+                Operation::Not => !first,
+                Operation::And => iter.fold(first, |acc, s| acc & byte_at(s, i)),
+                Operation::Or => iter.fold(first, |acc, s| acc | byte_at(s, i)),
+                Operation::Xor => iter.fold(first, |acc, s| acc ^ byte_at(s, i)),
+            }
+        }).collect();
+
+        if result.is_empty() {
+            db.remove(&self.destkey);
+        } else {
+            db.insert(self.destkey.clone(), Structure::String(result));
+            db.persist(&self.destkey);
+        }
+        Ok(Frame::Integer(len as i64))
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::bitmap};
+
+pub struct Bitpos {
+    key: Vec<u8>,
+    bit: u8,
+    start: i64,
+    end: Option<i64>,
+    bit_mode: bool, // 区间单位是否为位（默认为字节）
+}
+
+impl Bitpos {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 3 || args.len() > 6 {
+            return Err(Error::msg("ERR wrong number of arguments for 'bitpos' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let bit = match args[2].as_str() {
+            "0" => 0,
+            "1" => 1,
+            _ => return Err(Error::msg("ERR The bit argument must be 1 or 0.")),
+        };
+
+        let parse = |value: &String| value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"));
+        let start = match args.get(3) {
+            Some(start) => parse(start)?,
+            None => 0,
+        };
+        let end = match args.get(4) {
+            Some(end) => Some(parse(end)?),
+            None => None,
+        };
+        let bit_mode = match args.get(5).map(|mode| mode.to_uppercase()) {
+            None => false,
+            Some(mode) if mode == "BYTE" => false,
+            Some(mode) if mode == "BIT" => true,
+            Some(_) => return Err(Error::msg("ERR syntax error")),
+        };
+
+        Ok(Bitpos { key, bit, start, end, bit_mode })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let value = match db.get(&self.key) {
+            Some(Structure::String(value)) => value,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            // 键不存在时视为全 0 的字符串
+            None => return Ok(Frame::Integer(if self.bit == 1 { -1 } else { 0 })),
+        };
+
+        let bits = if self.bit_mode {
+            bitmap::normalize_range(self.start, self.end.unwrap_or(-1), value.len() as i64 * 8)
+        } else {
+            bitmap::normalize_range(self.start, self.end.unwrap_or(-1), value.len() as i64)
+                .map(|(start, end)| (start * 8, end * 8 + 7))
+        };
+
+        let (start, end) = match bits {
+            Some(bits) => bits,
+            None => return Ok(Frame::Integer(-1)),
+        };
+
+        match bitmap::find_bit(value, self.bit, start, end) {
+            Some(position) => Ok(Frame::Integer(position as i64)),
+            // 查找 0 且未指定结束位置时，认为字符串右侧由 0 填充
+            None if self.bit == 0 && self.end.is_none() => Ok(Frame::Integer(end as i64 + 1)),
+            None => Ok(Frame::Integer(-1)),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::bitmap};
+
+pub struct Getbit {
+    key: Vec<u8>,
+    offset: u64,
+}
+
+impl Getbit {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'getbit' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let offset = bitmap::parse_offset(&args[2])?; // 位偏移
+        Ok(Getbit { key, offset })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::String(value)) => Ok(Frame::Integer(bitmap::get_bit(value, self.offset) as i64)),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
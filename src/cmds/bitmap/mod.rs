@@ -0,0 +1,6 @@
+pub mod setbit;
+pub mod getbit;
+pub mod bitcount;
+pub mod bitpos;
+pub mod bitop;
+pub mod bitfield;
\ No newline at end of file
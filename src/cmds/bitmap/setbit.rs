@@ -0,0 +1,46 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::bitmap};
+
+pub struct Setbit {
+    key: Vec<u8>,
+    offset: u64,
+    bit: u8,
+}
+
+impl Setbit {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'setbit' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let offset = bitmap::parse_offset(&args[2])?; // 位偏移
+        let bit = match args[3].as_str() {
+            "0" => 0,
+            "1" => 1,
+            _ => return Err(Error::msg("ERR bit is not an integer or out of range")),
+        };
+        Ok(Setbit { key, offset, bit })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::String(value)) => {
+                let old = bitmap::set_bit(value, self.offset, self.bit);
+                Ok(Frame::Integer(old as i64))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => {
+                let mut value = Vec::new();
+                bitmap::set_bit(&mut value, self.offset, self.bit);
+                db.insert(self.key, Structure::String(value));
+                Ok(Frame::Integer(0))
+            }
+        }
+    }
+}
@@ -3,14 +3,14 @@ use anyhow::Error;
 use crate::frame::Frame;
 
 pub struct Echo {
-    str: String,
+    str: Vec<u8>,
 }
 
 impl Echo {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let str = match frame.get_arg(1) {
-            Some(name) => name.to_string(),
+        let str = match frame.get_arg_bytes(1) {
+            Some(name) => name,
             None => return Err(Error::msg("ERR wrong number of arguments for 'echo' command")),
         };
         Ok(Echo {
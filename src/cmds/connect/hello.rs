@@ -0,0 +1,85 @@
+use anyhow::Error;
+
+use crate::{frame::{Frame, Protocol}, server::Handler};
+
+pub struct Hello {
+    protocol: Option<Protocol>,
+    auth: Option<(String, String)>,
+    name: Option<String>,
+}
+
+impl Hello {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+
+        let protocol = match args.get(1) {
+            Some(version) => {
+                let version = match version.parse::<i64>() {
+                    Ok(v) => v,
+                    Err(_) => return Err(Error::msg("ERR Protocol version is not an integer or out of range")),
+                };
+                match Protocol::from_version(version) {
+                    Some(protocol) => Some(protocol),
+                    None => return Err(Error::msg("NOPROTO unsupported protocol version")),
+                }
+            },
+            None => None,
+        };
+
+        let mut auth = None;
+        let mut name = None;
+        let mut idx = 2;
+        while idx < args.len() {
+            let option = args[idx].to_uppercase();
+            let remaining = args.len() - idx - 1;
+            match option.as_str() {
+                "AUTH" if remaining >= 2 => {
+                    auth = Some((args[idx + 1].clone(), args[idx + 2].clone()));
+                    idx += 3;
+                },
+                "SETNAME" if remaining >= 1 => {
+                    name = Some(args[idx + 1].clone());
+                    idx += 2;
+                },
+                _ => return Err(Error::msg(format!("ERR Syntax error in HELLO option '{}'", args[idx]))),
+            }
+        }
+
+        Ok(Hello { protocol, auth, name })
+    }
+
+    pub fn apply(self, handler: &mut Handler) -> Result<Frame, Error> {
+
+        if let Some((_username, password)) = &self.auth {
+            if let Err(e) = handler.login(password) {
+                return Ok(Frame::Error(e.to_string()));
+            }
+        }
+
+        if handler.get_args().requirepass.is_some() && !handler.get_session().get_certification() {
+            let f = "NOAUTH HELLO must be called with the client already authenticated, otherwise the HELLO <proto> AUTH <user> <pass> option can be used to authenticate the client and select the RESP protocol version at the same time";
+            return Ok(Frame::Error(f.to_string()));
+        }
+
+        if let Some(protocol) = self.protocol {
+            handler.set_protocol(protocol);
+        }
+
+        if self.name.is_some() {
+            handler.set_client_name(self.name);
+        }
+
+        let session = handler.get_session();
+        let role = if handler.get_args().is_slave() { "replica" } else { "master" };
+        Ok(Frame::Map(vec![
+            (Frame::BulkString(b"server".to_vec()), Frame::BulkString(b"rudis".to_vec())),
+            (Frame::BulkString(b"version".to_vec()), Frame::BulkString(env!("CARGO_PKG_VERSION").as_bytes().to_vec())),
+            (Frame::BulkString(b"proto".to_vec()), Frame::Integer(session.get_protocol().version())),
+            (Frame::BulkString(b"id".to_vec()), Frame::Integer(session.get_id() as i64)),
+            (Frame::BulkString(b"mode".to_vec()), Frame::BulkString(b"standalone".to_vec())),
+            (Frame::BulkString(b"role".to_vec()), Frame::BulkString(role.as_bytes().to_vec())),
+            (Frame::BulkString(b"modules".to_vec()), Frame::Array(Vec::new())),
+        ]))
+    }
+}
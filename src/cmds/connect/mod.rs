@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod client;
 pub mod echo;
+pub mod hello;
 pub mod ping;
 pub mod select;
\ No newline at end of file
@@ -0,0 +1,101 @@
+use anyhow::Error;
+
+use crate::{cmds::geo::search::parse_lon_lat, store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame, tools::geohash};
+
+/**
+ * GEOADD key [NX | XX] [CH] longitude latitude member [longitude latitude member ...]
+ *
+ * @param nx 只添加新成员，不更新已有成员
+ * @param xx 只更新已有成员，不添加新成员
+ * @param ch 返回新增与位置被修改的成员总数
+ */
+pub struct Geoadd {
+    key: Vec<u8>,
+    members: Vec<(f64, Vec<u8>)>, // 成员及其编码后的分数
+    nx: bool,
+    xx: bool,
+    ch: bool,
+}
+
+impl Geoadd {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geoadd' command"));
+        }
+
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let (mut nx, mut xx, mut ch) = (false, false, false);
+        let mut index = 2;
+        while index < args.len() {
+            match args[index].to_uppercase().as_str() {
+                "NX" => nx = true,
+                "XX" => xx = true,
+                "CH" => ch = true,
+                _ => break,
+            }
+            index += 1;
+        }
+
+        let remaining = args.len() - index;
+        if remaining == 0 || !remaining.is_multiple_of(3) {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        if nx && xx {
+            return Err(Error::msg("ERR XX and NX options at the same time are not compatible"));
+        }
+
+        let mut members = Vec::new();
+        for (chunk, raw) in args[index..].chunks(3).zip(bytes[index..].chunks(3)) {
+            let (lon, lat) = parse_lon_lat(&chunk[0], &chunk[1])?;
+            members.push((geohash::encode(lon, lat) as f64, raw[2].clone()));
+        }
+
+        Ok(Geoadd { key, members, nx, xx, ch })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let set = match db.get_mut(&self.key) {
+            Some(Structure::SortedSet(set)) => set,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                // XX 模式下不会添加成员，无需创建键
+                if self.xx {
+                    return Ok(Frame::Integer(0));
+                }
+                db.insert(self.key.clone(), Structure::SortedSet(SortedSet::new()));
+                match db.get_mut(&self.key) {
+                    Some(Structure::SortedSet(set)) => set,
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        let mut added_count = 0;
+        let mut updated_count = 0;
+        for (score, member) in self.members {
+            match set.score(&member) {
+                Some(_) if self.nx => {},
+                Some(current) => {
+                    if current != score {
+                        set.insert(member, score);
+                        updated_count += 1;
+                    }
+                },
+                None if self.xx => {},
+                None => {
+                    set.insert(member, score);
+                    added_count += 1;
+                }
+            }
+        }
+        db.remove_if_empty(&self.key);
+
+        let count = if self.ch { added_count + updated_count } else { added_count };
+        Ok(Frame::Integer(count as i64))
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Error;
+
+use crate::{cmds::geo::search::{format_distance, parse_unit}, store::db::{Db, Structure}, frame::Frame, tools::geohash};
+
+pub struct Geodist {
+    key: Vec<u8>,
+    member1: Vec<u8>,
+    member2: Vec<u8>,
+    unit: f64, // 单位对应的米数
+}
+
+impl Geodist {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 || args.len() > 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geodist' command"));
+        }
+        let key = args[1].clone(); // 键
+        let member1 = args[2].clone();
+        let member2 = args[3].clone();
+        let unit = match args.get(4) {
+            Some(unit) => parse_unit(&String::from_utf8_lossy(unit))?,
+            None => 1.0,
+        };
+        Ok(Geodist { key, member1, member2, unit })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                match (set.score(&self.member1), set.score(&self.member2)) {
+                    (Some(score1), Some(score2)) => {
+                        let (lon1, lat1) = geohash::decode(score1 as u64);
+                        let (lon2, lat2) = geohash::decode(score2 as u64);
+                        Ok(format_distance(geohash::distance(lon1, lat1, lon2, lat2) / self.unit))
+                    },
+                    _ => Ok(Frame::Null),
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Null),
+        }
+    }
+}
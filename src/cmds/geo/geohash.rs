@@ -0,0 +1,37 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::geohash};
+
+pub struct Geohash {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl Geohash {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geohash' command"));
+        }
+        let key = args[1].clone(); // 键
+        let members = args[2..].to_vec(); // 成员
+        Ok(Geohash { key, members })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let result = match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                self.members.iter().map(|member| match set.score(member) {
+                    Some(score) => Frame::BulkString(geohash::to_base32(score as u64).into_bytes()),
+                    None => Frame::Null,
+                }).collect()
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => self.members.iter().map(|_| Frame::Null).collect(),
+        };
+        Ok(Frame::Array(result))
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::geohash};
+
+pub struct Geopos {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl Geopos {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geopos' command"));
+        }
+        let key = args[1].clone(); // 键
+        let members = args[2..].to_vec(); // 成员
+        Ok(Geopos { key, members })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let result = match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                self.members.iter().map(|member| match set.score(member) {
+                    Some(score) => {
+                        let (lon, lat) = geohash::decode(score as u64);
+                        Frame::Array(vec![Frame::Double(lon), Frame::Double(lat)])
+                    },
+                    None => Frame::Null,
+                }).collect()
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => self.members.iter().map(|_| Frame::Null).collect(),
+        };
+        Ok(Frame::Array(result))
+    }
+}
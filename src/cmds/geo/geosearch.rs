@@ -0,0 +1,27 @@
+use anyhow::Error;
+
+use crate::{cmds::geo::search::GeoQuery, store::db::Db, frame::Frame};
+
+pub struct Geosearch {
+    key: Vec<u8>,
+    query: GeoQuery,
+}
+
+impl Geosearch {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 7 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geosearch' command"));
+        }
+        let key = args[1].clone(); // 键
+        let query = GeoQuery::parse(&args[2..], false)?;
+        Ok(Geosearch { key, query })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match self.query.search(db, &self.key) {
+            Ok(points) => Ok(self.query.to_frame(points)),
+            Err(f) => Ok(f),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Error;
+
+use crate::{cmds::geo::search::GeoQuery, store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
+
+pub struct Geosearchstore {
+    destination: Vec<u8>,
+    source: Vec<u8>,
+    query: GeoQuery,
+}
+
+impl Geosearchstore {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 8 {
+            return Err(Error::msg("ERR wrong number of arguments for 'geosearchstore' command"));
+        }
+        let destination = args[1].clone(); // 目标键
+        let source = args[2].clone(); // 源键
+        let query = GeoQuery::parse(&args[3..], true)?;
+        Ok(Geosearchstore { destination, source, query })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let points = match self.query.search(db, &self.source) {
+            Ok(points) => points,
+            Err(f) => return Ok(f),
+        };
+
+        // STOREDIST 时以距离作为分数，否则保留位置编码
+        let set = points.iter()
+            .map(|point| {
+                let score = if self.query.store_dist() { self.query.distance_in_unit(point) } else { point.hash as f64 };
+                (point.member.clone(), score)
+            })
+            .collect::<SortedSet>();
+        let len = set.len() as i64;
+        if set.is_empty() {
+            db.remove(&self.destination);
+        } else {
+            db.insert(self.destination, Structure::SortedSet(set));
+        }
+        Ok(Frame::Integer(len))
+    }
+}
@@ -0,0 +1,7 @@
+pub mod search;
+pub mod geoadd;
+pub mod geopos;
+pub mod geodist;
+pub mod geohash;
+pub mod geosearch;
+pub mod geosearchstore;
\ No newline at end of file
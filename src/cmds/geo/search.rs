@@ -0,0 +1,312 @@
+use anyhow::Error;
+
+use crate::{
+    frame::Frame,
+    store::{db::{Db, Structure}, sorted_set::ScoreBound},
+    tools::geohash,
+};
+
+/**
+ * 解析距离单位，返回每单位对应的米数
+ *
+ * @param unit 单位
+ */
+pub fn parse_unit(unit: &str) -> Result<f64, Error> {
+    match unit.to_lowercase().as_str() {
+        "m" => Ok(1.0),
+        "km" => Ok(1000.0),
+        "ft" => Ok(0.3048),
+        "mi" => Ok(1609.34),
+        _ => Err(Error::msg("ERR unsupported unit provided. please use M, KM, FT, MI")),
+    }
+}
+
+/**
+ * 解析并校验经纬度
+ *
+ * @param lon 经度参数
+ * @param lat 纬度参数
+ */
+pub fn parse_lon_lat(lon: &str, lat: &str) -> Result<(f64, f64), Error> {
+    let parse = |value: &str| value.parse::<f64>().ok().filter(|value| !value.is_nan()).ok_or_else(|| Error::msg("ERR value is not a valid float"));
+    let (lon, lat) = (parse(lon)?, parse(lat)?);
+    if !geohash::is_valid(lon, lat) {
+        return Err(Error::msg(format!("ERR invalid longitude,latitude pair {:.6},{:.6}", lon, lat)));
+    }
+    Ok((lon, lat))
+}
+
+/**
+ * 按 Redis 的格式输出距离，保留 4 位小数
+ */
+pub fn format_distance(distance: f64) -> Frame {
+    Frame::BulkString(format!("{:.4}", distance).into_bytes())
+}
+
+/**
+ * 搜索中心
+ */
+enum Origin {
+    Member(Vec<u8>),
+    LonLat(f64, f64),
+}
+
+/**
+ * 搜索区域，长度单位为米
+ */
+enum Shape {
+    Radius(f64),
+    Box(f64, f64),
+}
+
+/**
+ * 结果排序方式
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Sort {
+    None,
+    Asc,
+    Desc,
+}
+
+/**
+ * 搜索结果
+ *
+ * @param distance 到搜索中心的距离（米）
+ */
+pub struct GeoPoint {
+    pub member: Vec<u8>,
+    pub distance: f64,
+    pub hash: u64,
+    pub lon: f64,
+    pub lat: f64,
+}
+
+/**
+ * GEOSEARCH 与 GEOSEARCHSTORE 的搜索条件
+ *
+ * @param unit 距离单位对应的米数
+ * @param count 最多返回的数量，0 表示不限制
+ * @param any 找到足够数量的结果后立即停止搜索
+ */
+pub struct GeoQuery {
+    origin: Origin,
+    shape: Shape,
+    unit: f64,
+    sort: Sort,
+    count: usize,
+    any: bool,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+    store_dist: bool,
+}
+
+impl GeoQuery {
+
+    /**
+     * 解析搜索条件
+     *
+     * @param args 键之后的参数
+     * @param store 是否为 GEOSEARCHSTORE
+     */
+    pub fn parse(args: &[Vec<u8>], store: bool) -> Result<Self, Error> {
+        // 成员名保持原始字节，只有关键字与数值参数转换为字符串
+        let bytes = args;
+        let args = bytes.iter().map(|arg| String::from_utf8_lossy(arg).to_string()).collect::<Vec<_>>();
+        let syntax_error = || Error::msg("ERR syntax error");
+        let parse_length = |value: &str, name: &str| -> Result<f64, Error> {
+            let length = value.parse::<f64>().ok().filter(|length| !length.is_nan()).ok_or_else(|| Error::msg(format!("ERR need numeric {}", name)))?;
+            if length < 0.0 {
+                return Err(Error::msg(format!("ERR {} cannot be negative", name)));
+            }
+            Ok(length)
+        };
+
+        let mut origin = None;
+        let mut shape = None;
+        let mut unit = 1.0;
+        let mut sort = Sort::None;
+        let mut count = 0;
+        let mut any = false;
+        let (mut with_coord, mut with_dist, mut with_hash, mut store_dist) = (false, false, false, false);
+        let mut index = 0;
+        while index < args.len() {
+            let remaining = args.len() - index - 1;
+            match args[index].to_uppercase().as_str() {
+                "FROMMEMBER" if remaining >= 1 => {
+                    if origin.is_some() {
+                        return Err(Error::msg("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH"));
+                    }
+                    origin = Some(Origin::Member(bytes[index + 1].clone()));
+                    index += 1;
+                },
+                "FROMLONLAT" if remaining >= 2 => {
+                    if origin.is_some() {
+                        return Err(Error::msg("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH"));
+                    }
+                    let (lon, lat) = parse_lon_lat(&args[index + 1], &args[index + 2])?;
+                    origin = Some(Origin::LonLat(lon, lat));
+                    index += 2;
+                },
+                "BYRADIUS" if remaining >= 2 => {
+                    if shape.is_some() {
+                        return Err(Error::msg("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH"));
+                    }
+                    let radius = parse_length(&args[index + 1], "radius")?;
+                    unit = parse_unit(&args[index + 2])?;
+                    shape = Some(Shape::Radius(radius * unit));
+                    index += 2;
+                },
+                "BYBOX" if remaining >= 3 => {
+                    if shape.is_some() {
+                        return Err(Error::msg("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH"));
+                    }
+                    let width = parse_length(&args[index + 1], "width")?;
+                    let height = parse_length(&args[index + 2], "height")?;
+                    unit = parse_unit(&args[index + 3])?;
+                    shape = Some(Shape::Box(width * unit, height * unit));
+                    index += 3;
+                },
+                "ASC" => sort = Sort::Asc,
+                "DESC" => sort = Sort::Desc,
+                "COUNT" if remaining >= 1 => {
+                    count = match args[index + 1].parse::<i64>() {
+                        Ok(count) if count > 0 => count as usize,
+                        Ok(_) => return Err(Error::msg("ERR COUNT must be > 0")),
+                        Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+                    };
+                    index += 1;
+                    if args.get(index + 1).is_some_and(|arg| arg.eq_ignore_ascii_case("ANY")) {
+                        any = true;
+                        index += 1;
+                    }
+                },
+                "ANY" => return Err(Error::msg("ERR the ANY argument requires COUNT argument")),
+                "WITHCOORD" if !store => with_coord = true,
+                "WITHDIST" if !store => with_dist = true,
+                "WITHHASH" if !store => with_hash = true,
+                "STOREDIST" if store => store_dist = true,
+                _ => return Err(syntax_error()),
+            }
+            index += 1;
+        }
+
+        let origin = origin.ok_or_else(|| Error::msg("ERR exactly one of FROMMEMBER or FROMLONLAT can be specified for GEOSEARCH"))?;
+        let shape = shape.ok_or_else(|| Error::msg("ERR exactly one of BYRADIUS and BYBOX can be specified for GEOSEARCH"))?;
+        // 指定 COUNT 而不是 ANY 时，按距离升序返回最近的结果
+        if count > 0 && !any && sort == Sort::None {
+            sort = Sort::Asc;
+        }
+        Ok(GeoQuery { origin, shape, unit, sort, count, any, with_coord, with_dist, with_hash, store_dist })
+    }
+
+    /**
+     * 执行搜索
+     *
+     * @param db 数据库
+     * @param key 有序集合的键
+     */
+    pub fn search(&self, db: &mut Db, key: &[u8]) -> Result<Vec<GeoPoint>, Frame> {
+        let set = match db.get(key) {
+            Some(Structure::SortedSet(set)) => set,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Err(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Vec::new()),
+        };
+
+        let (lon, lat) = match &self.origin {
+            Origin::LonLat(lon, lat) => (*lon, *lat),
+            Origin::Member(member) => match set.score(member) {
+                Some(score) => geohash::decode(score as u64),
+                None => return Err(Frame::Error("ERR could not decode requested zset member".to_string())),
+            },
+        };
+        let (half_width, half_height) = match self.shape {
+            Shape::Radius(radius) => (radius, radius),
+            Shape::Box(width, height) => (width / 2.0, height / 2.0),
+        };
+
+        // 只扫描覆盖搜索区域的编码区间，再按实际距离过滤
+        let mut points = Vec::new();
+        'ranges: for (start, end) in geohash::search_ranges(lon, lat, half_width, half_height) {
+            let (first, last) = set.rank_range_by_score(ScoreBound::Inclusive(start as f64), ScoreBound::Exclusive(end as f64));
+            for (member, score) in set.iter_from(first).take(last - first) {
+                let hash = score as u64;
+                let (point_lon, point_lat) = geohash::decode(hash);
+                let distance = match self.shape {
+                    Shape::Radius(radius) => {
+                        let distance = geohash::distance(lon, lat, point_lon, point_lat);
+                        if distance > radius {
+                            continue;
+                        }
+                        distance
+                    },
+                    Shape::Box(..) => {
+                        if geohash::lat_distance(lat, point_lat) > half_height
+                            || geohash::distance(lon, point_lat, point_lon, point_lat) > half_width {
+                            continue;
+                        }
+                        geohash::distance(lon, lat, point_lon, point_lat)
+                    }
+                };
+                points.push(GeoPoint { member: member.to_vec(), distance, hash, lon: point_lon, lat: point_lat });
+                if self.any && points.len() >= self.count {
+                    break 'ranges;
+                }
+            }
+        }
+
+        match self.sort {
+            Sort::Asc => points.sort_by(|a, b| a.distance.total_cmp(&b.distance)),
+            Sort::Desc => points.sort_by(|a, b| b.distance.total_cmp(&a.distance)),
+            Sort::None => {},
+        }
+        if self.count > 0 {
+            points.truncate(self.count);
+        }
+        Ok(points)
+    }
+
+    /**
+     * 结果转换为以指定单位表示的距离
+     *
+     * @param point 搜索结果
+     */
+    pub fn distance_in_unit(&self, point: &GeoPoint) -> f64 {
+        point.distance / self.unit
+    }
+
+    pub fn store_dist(&self) -> bool {
+        self.store_dist
+    }
+
+    /**
+     * 将搜索结果转换为回复，附加信息按 距离、编码、坐标 的顺序排列
+     *
+     * @param points 搜索结果
+     */
+    pub fn to_frame(&self, points: Vec<GeoPoint>) -> Frame {
+        let detailed = self.with_dist || self.with_hash || self.with_coord;
+        let frames = points.into_iter().map(|point| {
+            if !detailed {
+                return Frame::BulkString(point.member);
+            }
+            let distance = self.distance_in_unit(&point);
+            let mut item = vec![Frame::BulkString(point.member)];
+            if self.with_dist {
+                item.push(format_distance(distance));
+            }
+            if self.with_hash {
+                item.push(Frame::Integer(point.hash as i64));
+            }
+            if self.with_coord {
+                item.push(Frame::Array(vec![Frame::Double(point.lon), Frame::Double(point.lat)]));
+            }
+            Frame::Array(item)
+        }).collect();
+        Frame::Array(frames)
+    }
+}
@@ -2,21 +2,21 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Hdel {
-    key: String,
-    fields: Vec<String>,
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
 }
 
 impl Hdel {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'hdel' command"));
         }
 
-        let key = args[1].to_string();
-        let fields = args[2..].iter().map(|arg| arg.to_string()).collect();
+        let key = args[1].clone();
+        let fields = args[2..].to_vec();
 
         Ok(Hdel {
             key,
@@ -25,7 +25,7 @@ impl Hdel {
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.get_mut(&self.key) {
+        let deleted_count = match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::Hash(hash) => {
@@ -38,17 +38,23 @@ impl Hdel {
                             }
                         }
 
-                        Ok(Frame::Integer(deleted_count as i64))
+                        deleted_count
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
-                        Ok(Frame::Error(f.to_string()))
+                        return Ok(Frame::Error(f.to_string()));
                     }
                 }
             },
             None => {
-                Ok(Frame::Integer(0))
+                return Ok(Frame::Integer(0));
             }
+        };
+
+        for field in &self.fields {
+            db.persist_field(&self.key, field);
         }
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Integer(deleted_count as i64))
     }
 }
\ No newline at end of file
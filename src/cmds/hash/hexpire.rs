@@ -0,0 +1,200 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+// 字段过期时间的上限（毫秒时间戳），与 Redis 保持一致
+const MAX_EXPIRE_TIME: u64 = (1 << 48) - 1;
+
+/**
+ * 当前 Unix 时间戳【毫秒】
+ */
+pub fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/**
+ * 时刻转为 Unix 时间戳【毫秒】
+ *
+ * @param time 时刻
+ */
+pub fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/**
+ * 解析 FIELDS numfields field [field ...] 参数
+ *
+ * @param frame 命令帧
+ * @param index FIELDS 关键字所在的位置
+ */
+pub fn parse_fields(frame: &Frame, index: usize) -> Result<Vec<Vec<u8>>, Error> {
+    let args = frame.get_args();
+    if !args.get(index).is_some_and(|arg| arg.eq_ignore_ascii_case("FIELDS")) {
+        return Err(Error::msg("ERR Mandatory argument FIELDS is missing or not at the right position"));
+    }
+    let numfields = match args.get(index + 1).map(|arg| arg.parse::<i64>()) {
+        Some(Ok(numfields)) if numfields > 0 => numfields as usize,
+        Some(Ok(_)) => return Err(Error::msg("ERR Parameter `numFields` should be greater than 0")),
+        _ => return Err(Error::msg("ERR value is not an integer or out of range")),
+    };
+    if args.len() - index - 2 != numfields {
+        return Err(Error::msg("ERR The `numfields` parameter must match the number of arguments"));
+    }
+    Ok((index + 2..args.len()).map(|i| frame.get_arg_bytes(i).unwrap()).collect())
+}
+
+/**
+ * 解析过期时间参数，统一转为 Unix 时间戳【毫秒】
+ *
+ * @param value 参数
+ * @param unit 单位换算为毫秒的倍数
+ * @param absolute 是否为时间戳
+ * @param command 命令名称，用于错误信息
+ */
+pub fn parse_expire_time(value: &str, unit: u64, absolute: bool, command: &str) -> Result<u64, Error> {
+    let value = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+    let invalid = || Error::msg(format!("ERR invalid expire time in '{}' command", command));
+    if value < 0 {
+        return Err(invalid());
+    }
+    let millis = (value as u64).checked_mul(unit).ok_or_else(invalid)?;
+    let expire_at = if absolute { millis } else { now_millis().checked_add(millis).ok_or_else(invalid)? };
+    if expire_at > MAX_EXPIRE_TIME {
+        return Err(invalid());
+    }
+    Ok(expire_at)
+}
+
+/**
+ * 设置条件
+ */
+#[derive(Clone, Copy)]
+pub enum ExpireCondition {
+    Nx, // 仅当字段没有过期时间时设置
+    Xx, // 仅当字段已有过期时间时设置
+    Gt, // 仅当新的过期时间大于当前过期时间时设置
+    Lt, // 仅当新的过期时间小于当前过期时间时设置
+}
+
+impl ExpireCondition {
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_uppercase().as_str() {
+            "NX" => Some(ExpireCondition::Nx),
+            "XX" => Some(ExpireCondition::Xx),
+            "GT" => Some(ExpireCondition::Gt),
+            "LT" => Some(ExpireCondition::Lt),
+            _ => None,
+        }
+    }
+
+    /**
+     * 检查是否满足设置条件，没有过期时间的字段视为永不过期
+     *
+     * @param current 当前过期时间
+     * @param expire_at 新的过期时间
+     */
+    fn is_satisfied(&self, current: Option<u64>, expire_at: u64) -> bool {
+        match (self, current) {
+            (ExpireCondition::Nx, current) => current.is_none(),
+            (ExpireCondition::Xx, current) => current.is_some(),
+            (ExpireCondition::Gt, Some(current)) => expire_at > current,
+            (ExpireCondition::Gt, None) => false,
+            (ExpireCondition::Lt, Some(current)) => expire_at < current,
+            (ExpireCondition::Lt, None) => true,
+        }
+    }
+}
+
+pub struct Hexpire {
+    key: Vec<u8>,
+    expire_at: u64, // 过期的 Unix 时间戳【毫秒】
+    condition: Option<ExpireCondition>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl Hexpire {
+
+    /**
+     * 解析 HEXPIRE、HPEXPIRE、HEXPIREAT、HPEXPIREAT 命令
+     *
+     * @param frame 命令帧
+     * @param command 命令名称（小写）
+     */
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 6 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let unit = if command.starts_with("hp") { 1 } else { 1000 };
+        let expire_at = parse_expire_time(&args[2], unit, command.ends_with("at"), command)?;
+        let condition = ExpireCondition::parse(&args[3]);
+        let fields = parse_fields(&frame, if condition.is_some() { 4 } else { 3 })?;
+        Ok(Hexpire { key, expire_at, condition, fields })
+    }
+
+    /**
+     * 转为等价的 HPEXPIREAT 命令，用于 AOF 与复制，避免重放时基于重放时刻计算过期时间
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![
+            Frame::BulkString(b"HPEXPIREAT".to_vec()),
+            Frame::BulkString(self.key.clone()),
+            Frame::BulkString(self.expire_at.to_string().into_bytes()),
+        ];
+        if let Some(condition) = self.condition {
+            let condition = match condition {
+                ExpireCondition::Nx => "NX",
+                ExpireCondition::Xx => "XX",
+                ExpireCondition::Gt => "GT",
+                ExpireCondition::Lt => "LT",
+            };
+            args.push(Frame::BulkString(condition.as_bytes().to_vec()));
+        }
+        args.push(Frame::BulkString(b"FIELDS".to_vec()));
+        args.push(Frame::BulkString(self.fields.len().to_string().into_bytes()));
+        args.extend(self.fields.iter().map(|field| Frame::BulkString(field.clone())));
+        Frame::Array(args)
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let exists = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => self.fields.iter().map(|field| hash.contains_key(field)).collect::<Vec<_>>(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(self.fields.iter().map(|_| Frame::Integer(-2)).collect())),
+        };
+
+        let now = now_millis();
+        let mut result = Vec::with_capacity(self.fields.len());
+        for (field, exists) in self.fields.iter().zip(exists) {
+            if !exists {
+                result.push(Frame::Integer(-2));
+                continue;
+            }
+            let current = db.field_expire_time(&self.key, field).map(to_millis);
+            if self.condition.is_some_and(|condition| !condition.is_satisfied(current, self.expire_at)) {
+                result.push(Frame::Integer(0));
+                continue;
+            }
+            if self.expire_at <= now {
+                // 过期时间已经过去，直接删除字段
+                if let Some(Structure::Hash(hash)) = db.get_mut(&self.key) {
+                    hash.remove(field);
+                }
+                db.persist_field(&self.key, field);
+                result.push(Frame::Integer(2));
+            } else {
+                let expire_time = UNIX_EPOCH + Duration::from_millis(self.expire_at);
+                db.expire_field(&self.key, field, expire_time);
+                result.push(Frame::Integer(1));
+            }
+        }
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Array(result))
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::Error;
+use crate::{cmds::hash::hexpire::{now_millis, parse_expire_time, parse_fields}, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * 字段过期选项
+ */
+pub enum FieldExpiration {
+    At(u64), // EX、PX、EXAT、PXAT：统一为过期的 Unix 时间戳【毫秒】
+    Persist, // PERSIST：移除过期时间
+}
+
+pub struct Hgetex {
+    key: Vec<u8>,
+    expiration: Option<FieldExpiration>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl Hgetex {
+
+    /**
+     * HGETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds | PXAT unix-time-milliseconds | PERSIST] FIELDS numfields field [field ...]
+     */
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'hgetex' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let option = args[2].to_uppercase();
+        let (expiration, index) = match option.as_str() {
+            "FIELDS" => (None, 2),
+            "PERSIST" => (Some(FieldExpiration::Persist), 3),
+            "EX" | "PX" | "EXAT" | "PXAT" => {
+                let value = args.get(3).ok_or_else(|| Error::msg("ERR syntax error"))?;
+                let unit = if option.starts_with('P') { 1 } else { 1000 };
+                let expire_at = parse_expire_time(value, unit, option.ends_with("AT"), "hgetex")?;
+                (Some(FieldExpiration::At(expire_at)), 4)
+            },
+            _ => return Err(Error::msg("ERR syntax error")),
+        };
+        let fields = parse_fields(&frame, index)?;
+        Ok(Hgetex { key, expiration, fields })
+    }
+
+    /**
+     * 转为过期时间为时间戳的等价命令，用于 AOF 与复制
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![
+            Frame::BulkString(b"HGETEX".to_vec()),
+            Frame::BulkString(self.key.clone()),
+        ];
+        match self.expiration {
+            Some(FieldExpiration::At(expire_at)) => {
+                args.push(Frame::BulkString(b"PXAT".to_vec()));
+                args.push(Frame::BulkString(expire_at.to_string().into_bytes()));
+            },
+            Some(FieldExpiration::Persist) => args.push(Frame::BulkString(b"PERSIST".to_vec())),
+            None => {},
+        }
+        args.push(Frame::BulkString(b"FIELDS".to_vec()));
+        args.push(Frame::BulkString(self.fields.len().to_string().into_bytes()));
+        args.extend(self.fields.iter().map(|field| Frame::BulkString(field.clone())));
+        Frame::Array(args)
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let values = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => self.fields.iter().map(|field| hash.get(field).cloned()).collect::<Vec<_>>(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(self.fields.iter().map(|_| Frame::Null).collect())),
+        };
+
+        for (field, value) in self.fields.iter().zip(&values) {
+            if value.is_none() {
+                continue;
+            }
+            match self.expiration {
+                Some(FieldExpiration::At(expire_at)) if expire_at <= now_millis() => {
+                    if let Some(Structure::Hash(hash)) = db.get_mut(&self.key) {
+                        hash.remove(field);
+                    }
+                    db.persist_field(&self.key, field);
+                },
+                Some(FieldExpiration::At(expire_at)) => {
+                    db.expire_field(&self.key, field, UNIX_EPOCH + Duration::from_millis(expire_at));
+                },
+                Some(FieldExpiration::Persist) => {
+                    db.persist_field(&self.key, field);
+                },
+                None => {},
+            }
+        }
+        db.remove_if_empty(&self.key);
+
+        Ok(Frame::Array(values.into_iter().map(|value| match value {
+            Some(value) => Frame::BulkString(value),
+            None => Frame::Null,
+        }).collect()))
+    }
+}
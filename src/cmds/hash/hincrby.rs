@@ -0,0 +1,54 @@
+use anyhow::Error;
+use crate::{store::{db::{Db, Structure}, hash::Hash}, frame::Frame};
+
+pub struct Hincrby {
+    key: Vec<u8>,
+    field: Vec<u8>,
+    increment: i64,
+}
+
+impl Hincrby {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'hincrby' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let field = frame.get_arg_bytes(2).unwrap(); // 字段
+        let increment = args[3].parse::<i64>().map_err(|_| {
+            Error::msg("ERR value is not an integer or out of range")
+        })?;
+        Ok(Hincrby { key, field, increment })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::Hash(hash)) => {
+                let current = match hash.get(&self.field) {
+                    Some(value) => match std::str::from_utf8(value).ok().and_then(|s| s.parse::<i64>().ok()) {
+                        Some(num) => num,
+                        None => return Ok(Frame::Error("ERR hash value is not an integer".to_string())),
+                    },
+                    None => 0,
+                };
+                match current.checked_add(self.increment) {
+                    Some(num) => {
+                        hash.insert(self.field, num.to_string().into_bytes());
+                        Ok(Frame::Integer(num))
+                    },
+                    None => Ok(Frame::Error("ERR increment or decrement would overflow".to_string())),
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => {
+                let hash = Hash::from([(self.field, self.increment.to_string().into_bytes())]);
+                db.insert(self.key.clone(), Structure::Hash(hash));
+                Ok(Frame::Integer(self.increment))
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::Error;
+use crate::{cmds::string::incrbyfloat::IncrbyFloat, store::{db::{Db, Structure}, hash::Hash}, frame::Frame};
+
+pub struct HincrbyFloat {
+    key: Vec<u8>,
+    field: Vec<u8>,
+    increment: f64,
+}
+
+impl HincrbyFloat {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'hincrbyfloat' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let field = frame.get_arg_bytes(2).unwrap(); // 字段
+        let increment = match args[3].parse::<f64>() {
+            Ok(increment) if increment.is_finite() => increment,
+            _ => return Err(Error::msg("ERR value is not a valid float")),
+        };
+        Ok(HincrbyFloat { key, field, increment })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let current = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => match hash.get(&self.field) {
+                Some(value) => match std::str::from_utf8(value).ok().and_then(|s| s.parse::<f64>().ok()) {
+                    Some(num) => num,
+                    None => return Ok(Frame::Error("ERR hash value is not a float".to_string())),
+                },
+                None => 0.0,
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => 0.0,
+        };
+
+        let result = current + self.increment;
+        if !result.is_finite() {
+            return Ok(Frame::Error("ERR increment would produce NaN or Infinity".to_string()));
+        }
+
+        let formatted = IncrbyFloat::format_float(result).into_bytes();
+        match db.get_mut(&self.key) {
+            Some(Structure::Hash(hash)) => {
+                hash.insert(self.field, formatted.clone());
+            },
+            _ => {
+                let hash = Hash::from([(self.field, formatted.clone())]);
+                db.insert(self.key.clone(), Structure::Hash(hash));
+            }
+        }
+        Ok(Frame::BulkString(formatted))
+    }
+}
@@ -2,20 +2,20 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Hlen {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Hlen {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
 
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'hlen' command"));
         }
 
-        let final_key = key.unwrap().to_string(); // 键
+        let final_key = key.unwrap(); // 键
 
         Ok(Hlen {
             key: final_key,
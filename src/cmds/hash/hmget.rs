@@ -2,20 +2,20 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Hmget {
-    key: String,
-    fields: Vec<String>,
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
 }
 
 impl Hmget {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'hmget' command"));
         }
 
-        let key = args[1].to_string();
-        let fields = args[2..].iter().map(|arg| arg.to_string()).collect();
+        let key = args[1].clone();
+        let fields = args[2..].to_vec();
 
         Ok(Hmget {
             key,
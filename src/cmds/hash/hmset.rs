@@ -4,41 +4,45 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Hmset {
-    key: String,
-    fields: HashMap<String, String>,
+    key: Vec<u8>,
+    fields: HashMap<Vec<u8>, Vec<u8>>,
 }
 
 impl Hmset {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'hmset' command"));
         }
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
-        if args.len() % 2 != 0 {
+        if !args.len().is_multiple_of(2) {
             return Err(Error::msg("ERR wrong number of arguments for 'hmset' command"));
         }
 
         let mut fields = HashMap::new();
 
         for i in (2..args.len()).step_by(2) {
-            let field = args[i].to_string();
-            let value = args[i + 1].to_string();
+            let field = args[i].clone();
+            let value = args[i + 1].clone();
             fields.insert(field, value);
         }
 
         Ok(Hmset {
-            key: key.unwrap().to_string(),
+            key: key.unwrap(),
             fields,
         })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        // 覆盖字段的同时清除字段的过期时间
+        for field in self.fields.keys() {
+            db.persist_field(&self.key, field);
+        }
         match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
@@ -55,7 +59,7 @@ impl Hmset {
                 }
             },
             None => {
-                db.insert(self.key, Structure::Hash(self.fields));
+                db.insert(self.key, Structure::Hash(self.fields.into()));
                 Ok(Frame::SimpleString("OK".to_string()))
             }
         }
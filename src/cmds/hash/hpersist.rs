@@ -0,0 +1,42 @@
+use anyhow::Error;
+use crate::{cmds::hash::hexpire::parse_fields, store::db::{Db, Structure}, frame::Frame};
+
+pub struct Hpersist {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl Hpersist {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'hpersist' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let fields = parse_fields(&frame, 2)?;
+        Ok(Hpersist { key, fields })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let exists = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => self.fields.iter().map(|field| hash.contains_key(field)).collect::<Vec<_>>(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(self.fields.iter().map(|_| Frame::Integer(-2)).collect())),
+        };
+
+        let result = self.fields.iter().zip(exists).map(|(field, exists)| {
+            if !exists {
+                Frame::Integer(-2)
+            } else if db.persist_field(&self.key, field) {
+                Frame::Integer(1)
+            } else {
+                Frame::Integer(-1)
+            }
+        }).collect();
+        Ok(Frame::Array(result))
+    }
+}
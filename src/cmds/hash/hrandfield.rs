@@ -0,0 +1,71 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::random};
+
+pub struct Hrandfield {
+    key: Vec<u8>,
+    count: Option<i64>, // 正数返回不重复的字段，负数允许重复
+    with_values: bool,
+}
+
+impl Hrandfield {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 || args.len() > 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'hrandfield' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let count = match args.get(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count > i64::MIN / 2 && count < i64::MAX / 2 => Some(count),
+                Ok(_) => return Err(Error::msg("ERR value is out of range")),
+                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            },
+            None => None,
+        };
+        let with_values = match args.get(3) {
+            Some(option) if option.eq_ignore_ascii_case("WITHVALUES") => true,
+            Some(_) => return Err(Error::msg("ERR syntax error")),
+            None => false,
+        };
+        Ok(Hrandfield { key, count, with_values })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let hash = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => hash,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                return Ok(if self.count.is_some() { Frame::Array(vec![]) } else { Frame::Null });
+            }
+        };
+
+        let entries = hash.iter().collect::<Vec<_>>();
+        let count = match self.count {
+            Some(count) => count,
+            None => {
+                let (field, _) = entries[random::index(entries.len())];
+                return Ok(Frame::BulkString(field.clone()));
+            }
+        };
+
+        let indexes = if count >= 0 {
+            random::sample(entries.len(), count as usize)
+        } else {
+            (0..count.unsigned_abs()).map(|_| random::index(entries.len())).collect()
+        };
+
+        let mut result = Vec::with_capacity(indexes.len() * if self.with_values { 2 } else { 1 });
+        for index in indexes {
+            let (field, value) = entries[index];
+            result.push(Frame::BulkString(field.clone()));
+            if self.with_values {
+                result.push(Frame::BulkString(value.clone()));
+            }
+        }
+        Ok(Frame::Array(result))
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Error;
+
+use crate::{cmds::key::scan::{scan_reply, ScanArgs}, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * HSCAN key cursor [MATCH pattern] [COUNT count]
+ *
+ * 以游标分批遍历哈希的字段与值，回复中字段与值交替排列
+ */
+pub struct Hscan {
+    key: Vec<u8>,
+    args: ScanArgs,
+}
+
+impl Hscan {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes_from_index(2);
+        let key = frame.get_arg_bytes(1);
+        if key.is_none() || args.is_empty() {
+            return Err(Error::msg("ERR wrong number of arguments for 'hscan' command"));
+        }
+        let key = key.unwrap(); // 键
+        Ok(Hscan { key, args: ScanArgs::parse(&args, false)? })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => {
+                let (cursor, fields) = hash.scan(self.args.cursor, self.args.count);
+                let result = fields.into_iter()
+                    .filter(|(field, _)| self.args.is_match(field))
+                    .flat_map(|(field, value)| [Frame::BulkString(field.clone()), Frame::BulkString(value.clone())])
+                    .collect();
+                Ok(scan_reply(cursor, result))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(scan_reply(0, Vec::new())),
+        }
+    }
+}
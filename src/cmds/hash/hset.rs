@@ -1,49 +1,45 @@
-use std::collections::HashMap;
-
 use anyhow::Error;
-use crate::{store::db::{Db, Structure}, frame::Frame};
+use crate::{store::{db::{Db, Structure}, hash::Hash}, frame::Frame};
 
 pub struct Hset {
-    key: String,
-    field: String,
-    value: String,
+    key: Vec<u8>,
+    fields: Vec<(Vec<u8>, Vec<u8>)>, // 按参数顺序保存，重复字段以最后一个为准
 }
 
 impl Hset {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let key = frame.get_arg(1);
-        let field = frame.get_arg(2);
-        let value = frame.get_arg(3);
+        let args = frame.get_args_bytes();
 
-        if key.is_none() || field.is_none() || value.is_none() {
+        if args.len() < 4 || !args.len().is_multiple_of(2) {
             return Err(Error::msg("ERR wrong number of arguments for 'hset' command"));
         }
 
-        let final_key = key.unwrap().to_string(); 
-        let final_field = field.unwrap().to_string(); 
-        let final_value = value.unwrap().to_string();
+        let key = args[1].clone();
+        let fields = args[2..].chunks(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
 
-        Ok(Hset {
-            key: final_key,
-            field: final_field,
-            value: final_value,
-        })
+        Ok(Hset { key, fields })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        // 覆盖字段的同时清除字段的过期时间
+        for (field, _) in &self.fields {
+            db.persist_field(&self.key, field);
+        }
         match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::Hash(hash) => {
-                        let field_exists  = hash.contains_key(&self.field);
-                        hash.insert(self.field, self.value);
-                        if field_exists {
-                            return Ok(Frame::Integer(0));
-                        } else {
-                            return Ok(Frame::Integer(1));
+                        let mut added = 0;
+                        for (field, value) in self.fields {
+                            if hash.insert(field, value).is_none() {
+                                added += 1;
+                            }
                         }
+                        Ok(Frame::Integer(added))
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
@@ -52,10 +48,11 @@ impl Hset {
                 }
             },
             None => {
-                let hash = HashMap::from([(self.field, self.value)]);
+                let hash = self.fields.into_iter().collect::<Hash>();
+                let added = hash.len() as i64;
                 db.insert(self.key.clone(), Structure::Hash(hash));
-                Ok(Frame::Integer(1))
+                Ok(Frame::Integer(added))
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,56 @@
+use anyhow::Error;
+use crate::{cmds::hash::hexpire::{now_millis, parse_fields, to_millis}, store::db::{Db, Structure}, frame::Frame};
+
+pub struct Httl {
+    key: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+    millis: bool, // 是否以毫秒为单位
+    absolute: bool, // 是否返回过期的时间戳
+}
+
+impl Httl {
+
+    /**
+     * 解析 HTTL、HPTTL、HEXPIRETIME、HPEXPIRETIME 命令
+     *
+     * @param frame 命令帧
+     * @param command 命令名称（小写）
+     */
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 5 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let fields = parse_fields(&frame, 2)?;
+        let millis = command.starts_with("hp");
+        let absolute = command.ends_with("time");
+        Ok(Httl { key, fields, millis, absolute })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let exists = match db.get(&self.key) {
+            Some(Structure::Hash(hash)) => self.fields.iter().map(|field| hash.contains_key(field)).collect::<Vec<_>>(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(self.fields.iter().map(|_| Frame::Integer(-2)).collect())),
+        };
+
+        let now = now_millis();
+        let result = self.fields.iter().zip(exists).map(|(field, exists)| {
+            if !exists {
+                return Frame::Integer(-2);
+            }
+            let expire_at = match db.field_expire_time(&self.key, field) {
+                Some(expire_time) => to_millis(expire_time),
+                None => return Frame::Integer(-1),
+            };
+            let value = if self.absolute { expire_at } else { expire_at.saturating_sub(now) };
+            let value = if self.millis { value } else { (value + 500) / 1000 };
+            Frame::Integer(value as i64)
+        }).collect();
+        Ok(Frame::Array(result))
+    }
+}
@@ -9,4 +9,12 @@ pub mod hvals;
 pub mod hset;
 pub mod hget;
 pub mod hdel;
-pub mod hlen;
\ No newline at end of file
+pub mod hlen;
+pub mod hincrby;
+pub mod hincrbyfloat;
+pub mod hrandfield;
+pub mod hexpire;
+pub mod httl;
+pub mod hpersist;
+pub mod hgetex;
+pub mod hscan;
@@ -0,0 +1,3 @@
+pub mod pfadd;
+pub mod pfcount;
+pub mod pfmerge;
\ No newline at end of file
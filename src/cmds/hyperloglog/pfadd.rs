@@ -0,0 +1,55 @@
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, hyperloglog::HyperLogLog}, frame::Frame};
+
+pub struct Pfadd {
+    key: Vec<u8>,
+    elements: Vec<Vec<u8>>,
+}
+
+impl Pfadd {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'pfadd' command"));
+        }
+        let key = args[1].clone(); // 键
+        let elements = args[2..].to_vec(); // 元素
+        Ok(Pfadd { key, elements })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::String(value)) => {
+                let mut hll = match HyperLogLog::from_bytes(value) {
+                    Some(hll) => hll,
+                    None => {
+                        let f = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+                        return Ok(Frame::Error(f.to_string()));
+                    }
+                };
+                let mut updated = false;
+                for element in &self.elements {
+                    updated |= hll.add(element);
+                }
+                if updated {
+                    *value = hll.to_bytes();
+                }
+                Ok(Frame::Integer(updated as i64))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => {
+                let mut hll = HyperLogLog::new();
+                for element in &self.elements {
+                    hll.add(element);
+                }
+                db.insert(self.key, Structure::String(hll.to_bytes()));
+                Ok(Frame::Integer(1))
+            }
+        }
+    }
+}
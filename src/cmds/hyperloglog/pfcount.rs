@@ -0,0 +1,50 @@
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, hyperloglog::HyperLogLog}, frame::Frame};
+
+pub struct Pfcount {
+    keys: Vec<Vec<u8>>,
+}
+
+impl Pfcount {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'pfcount' command"));
+        }
+        let keys = args[1..].to_vec(); // 键
+        Ok(Pfcount { keys })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        for key in &self.keys {
+            db.expire_if_needed(key);
+        }
+
+        let mut hlls = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            match db.records.get(key) {
+                Some(Structure::String(value)) => match HyperLogLog::from_bytes(value) {
+                    Some(hll) => hlls.push(hll),
+                    None => {
+                        let f = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+                        return Ok(Frame::Error(f.to_string()));
+                    }
+                },
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Ok(Frame::Error(f.to_string()));
+                },
+                None => {}
+            }
+        }
+
+        let count = match hlls.as_slice() {
+            [] => 0,
+            [hll] => hll.count(),
+            _ => HyperLogLog::count_union(&hlls.iter().collect::<Vec<_>>()),
+        };
+        Ok(Frame::Integer(count as i64))
+    }
+}
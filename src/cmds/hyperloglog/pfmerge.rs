@@ -0,0 +1,59 @@
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, hyperloglog::HyperLogLog}, frame::Frame};
+
+pub struct Pfmerge {
+    destkey: Vec<u8>,
+    sourcekeys: Vec<Vec<u8>>,
+}
+
+impl Pfmerge {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'pfmerge' command"));
+        }
+        let destkey = args[1].clone(); // 目标键
+        let sourcekeys = args[2..].to_vec(); // 源键
+        Ok(Pfmerge { destkey, sourcekeys })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        // 目标键已存在时，同样参与合并
+        let mut merged = match db.get(&self.destkey) {
+            Some(Structure::String(value)) => match HyperLogLog::from_bytes(value) {
+                Some(hll) => hll,
+                None => {
+                    let f = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+                    return Ok(Frame::Error(f.to_string()));
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => HyperLogLog::new(),
+        };
+
+        for key in &self.sourcekeys {
+            match db.get(key) {
+                Some(Structure::String(value)) => match HyperLogLog::from_bytes(value) {
+                    Some(hll) => merged.merge(&hll),
+                    None => {
+                        let f = "WRONGTYPE Key is not a valid HyperLogLog string value.";
+                        return Ok(Frame::Error(f.to_string()));
+                    }
+                },
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Ok(Frame::Error(f.to_string()));
+                },
+                None => {}
+            }
+        }
+
+        db.insert(self.destkey, Structure::String(merged.to_bytes()));
+        Ok(Frame::Ok)
+    }
+}
@@ -3,7 +3,7 @@ use anyhow::Error;
 use crate::{store::db::Db, frame::Frame};
 
 pub struct Del {
-    pub keys: Vec<String>,
+    pub keys: Vec<Vec<u8>>,
 }
 
 impl Del {
@@ -14,16 +14,16 @@ impl Del {
      * @param frame 命令帧
      */
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let keys = frame.get_args_from_index(1);
+        let keys = frame.get_args_bytes_from_index(1);
         if keys.is_empty() {
             return Err(Error::msg("ERR wrong number of arguments for 'del' command"));
         } 
         Ok(Del { 
-            keys: keys 
+            keys 
         })
     }
 
-    pub fn new(keys: Vec<String>) -> Self {
+    pub fn new(keys: Vec<Vec<u8>>) -> Self {
         Del { keys }
     }
 
@@ -2,23 +2,23 @@ use anyhow::Error;
 use crate::{store::db::Db, frame::Frame};
 
 pub struct Exists {
-    pub key: String,
+    pub key: Vec<u8>,
 }
 
 impl Exists {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'exists' command"));
         }
-        let key_str = key.unwrap().to_string(); // 键
+        let key_str = key.unwrap(); // 键
         Ok(Exists {
             key: key_str,
         })
     }
 
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Exists { key }
     }
 
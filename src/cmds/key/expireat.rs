@@ -4,7 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use crate::{store::db::Db, frame::Frame};
 
 pub struct ExpireAt {
-    key: String,
+    key: Vec<u8>,
     timestamp: u64,
 }
 
@@ -17,7 +17,7 @@ impl ExpireAt {
             return Err(Error::msg("ERR wrong number of arguments for 'expireat' command"));
         }
 
-        let key = args[1].to_string();
+        let key = frame.get_arg_bytes(1).unwrap();
         let timestamp = match args[2].parse::<u64>() {
             Ok(val) => val,
             Err(_) => {
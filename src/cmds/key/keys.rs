@@ -1,23 +1,24 @@
 use anyhow::Error;
-use crate::{store::db::Db, frame::Frame};
+use crate::{store::db::Db, frame::Frame, tools::pattern::Pattern};
 
 pub struct Keys {
-    pattern: String,
+    pattern: Pattern,
 }
 
 impl Keys {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args_from_index(1);
+        let args = frame.get_args_bytes_from_index(1);
         if args.len() != 1 {
             return Err(Error::msg("KEYS command requires exactly one argument"));
         }
-        Ok(Keys { pattern: args[0].clone() })
+        let pattern = Pattern::new(&args[0]); // 模式只编译一次
+        Ok(Keys { pattern })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
         let keys = db.keys(&self.pattern);
-        let results: Vec<Frame> = keys.into_iter().map(|key| Frame::BulkString(key)).collect();
+        let results: Vec<Frame> = keys.into_iter().map(Frame::BulkString).collect();
         Ok(Frame::Array(results))
     }
 }
\ No newline at end of file
@@ -12,4 +12,5 @@ pub mod r#type;
 pub mod keys;
 pub mod pexpireat;
 pub mod pexpire;
-pub mod r#move;
\ No newline at end of file
+pub mod r#move;
+pub mod scan;
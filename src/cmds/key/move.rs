@@ -2,7 +2,7 @@ use anyhow::Error;
 use crate::{frame::Frame, server::Handler};
 
 pub struct Move {
-    key: String,
+    key: Vec<u8>,
     db_index: usize,
 }
 
@@ -14,7 +14,7 @@ impl Move {
             return Err(Error::msg("ERR wrong number of arguments for 'move' command"));
         }
         
-        let key = args[1].to_string();
+        let key = frame.get_arg_bytes(1).unwrap();
         let db_index = match args[2].parse::<usize>() {
             Ok(num) => num,
             Err(_) => {
@@ -25,7 +25,7 @@ impl Move {
         Ok(Move { key, db_index })
     }
 
-    pub fn get_key(&self) -> &String {
+    pub fn get_key(&self) -> &Vec<u8> {
         &self.key
     }
 
@@ -157,14 +157,14 @@ impl Move {
         let (set_tx, set_rx) = tokio::sync::oneshot::channel();
         let set_message = crate::store::db::DatabaseMessage::Command { 
             sender: set_tx, 
-            command: crate::command::Command::Set(crate::cmds::string::set::Set { 
-                key: key.clone(), 
-                val: match &structure {
+            command: crate::command::Command::Set(crate::cmds::string::set::Set::new(
+                key.clone(),
+                match &structure {
                     crate::store::db::Structure::String(s) => s.clone(),
                     _ => return Ok(Frame::Error("Unsupported value type for MOVE command".to_string())),
                 },
-                ttl: None,
-            })
+                None,
+            ))
         };
         
         if target_db_sender.send(set_message).await.is_err() {
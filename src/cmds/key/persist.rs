@@ -2,17 +2,17 @@ use anyhow::Error;
 use crate::{store::db::Db, frame::Frame};
 
 pub struct Persist {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Persist {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'persist' command"));
         }
-        let key_str = key.unwrap().to_string(); // 键
+        let key_str = key.unwrap(); // 键
         Ok(Persist {
             key: key_str,
         })
@@ -3,7 +3,7 @@ use anyhow::Error;
 use crate::{store::db::Db, frame::Frame};
 
 pub struct Pexpire {
-    key: String,
+    key: Vec<u8>,
     ttl: u64
 }
 
@@ -17,7 +17,7 @@ impl Pexpire {
             return Err(Error::msg("ERR wrong number of arguments for 'pexpire' command"));
         }
 
-        let key = args[1].to_string();
+        let key = frame.get_arg_bytes(1).unwrap();
 
         let ttl = match args[2].parse::<u64>() {
             Ok(val) => val, // 毫秒
@@ -27,8 +27,8 @@ impl Pexpire {
         };
 
         Ok(Pexpire { 
-            key: key, 
-            ttl: ttl 
+            key, 
+            ttl 
         })
     }
 
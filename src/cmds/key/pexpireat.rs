@@ -4,7 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use crate::{store::db::Db, frame::Frame};
 
 pub struct PexpireAt {
-    key: String,
+    key: Vec<u8>,
     timestamp: u64,
 }
 
@@ -17,7 +17,7 @@ impl PexpireAt {
             return Err(Error::msg("ERR wrong number of arguments for 'pexpireat' command"));
         }
 
-        let key = args[1].to_string();
+        let key = frame.get_arg_bytes(1).unwrap();
         let timestamp = match args[2].parse::<u64>() {
             Ok(val) => val,
             Err(_) => {
@@ -29,11 +29,7 @@ impl PexpireAt {
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
         let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_millis() as u64;
-        let ttl = if self.timestamp > now {
-            self.timestamp - now
-        } else {
-            0
-        };
+        let ttl = self.timestamp.saturating_sub(now);
         db.expire(self.key.clone(), ttl);
         Ok(Frame::Ok)
     }
@@ -0,0 +1,114 @@
+use anyhow::Error;
+
+use crate::{cmds::key::r#type::Type, store::db::Db, frame::Frame, tools::pattern::Pattern};
+
+// 默认每次遍历的数量
+const DEFAULT_COUNT: usize = 10;
+
+/**
+ * SCAN 系列命令的公共参数
+ *
+ * @param cursor 游标
+ * @param pattern 只返回匹配该模式的元素
+ * @param count 每次遍历的数量，匹配前的数量，因此返回的元素可能更少
+ * @param kind 只返回该类型的键，仅 SCAN 支持
+ */
+pub struct ScanArgs {
+    pub cursor: u64,
+    pub pattern: Option<Pattern>,
+    pub count: usize,
+    pub kind: Option<String>,
+}
+
+impl ScanArgs {
+
+    /**
+     * 解析游标与 [MATCH pattern] [COUNT count] [TYPE type]
+     *
+     * @param args 从游标开始的参数
+     * @param allow_type 是否支持 TYPE 选项
+     */
+    pub fn parse(args: &[Vec<u8>], allow_type: bool) -> Result<Self, Error> {
+        let cursor = String::from_utf8_lossy(&args[0]).parse::<u64>().map_err(|_| Error::msg("ERR invalid cursor"))?;
+        let mut pattern = None;
+        let mut count = DEFAULT_COUNT;
+        let mut kind = None;
+        let mut index = 1;
+        while index < args.len() {
+            let value = args.get(index + 1).ok_or_else(|| Error::msg("ERR syntax error"))?;
+            match String::from_utf8_lossy(&args[index]).to_uppercase().as_str() {
+                // 模式只编译一次，* 视为未指定
+                "MATCH" => pattern = Some(Pattern::new(value)).filter(|pattern| !pattern.matches_all()),
+                "COUNT" => {
+                    count = match String::from_utf8_lossy(value).parse::<i64>() {
+                        Ok(count) if count >= 1 => count as usize,
+                        Ok(_) => return Err(Error::msg("ERR syntax error")),
+                        Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+                    };
+                },
+                "TYPE" if allow_type => kind = Some(String::from_utf8_lossy(value).to_lowercase()),
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 2;
+        }
+        Ok(ScanArgs { cursor, pattern, count, kind })
+    }
+
+    /**
+     * 元素是否匹配 MATCH 模式，未指定模式时总是匹配
+     */
+    pub fn is_match(&self, element: &[u8]) -> bool {
+        match &self.pattern {
+            Some(pattern) => pattern.is_match(element),
+            None => true,
+        }
+    }
+}
+
+/**
+ * 遍历结果的回复：[下一次的游标, [元素, ...]]
+ *
+ * @param cursor 下一次的游标
+ * @param elements 元素
+ */
+pub fn scan_reply(cursor: u64, elements: Vec<Frame>) -> Frame {
+    Frame::Array(vec![Frame::BulkString(cursor.to_string().into_bytes()), Frame::Array(elements)])
+}
+
+/**
+ * SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]
+ *
+ * 以游标分批遍历数据库中的键，整个遍历期间一直存在的键至少会被返回一次
+ */
+pub struct Scan {
+    args: ScanArgs,
+}
+
+impl Scan {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes_from_index(1);
+        if args.is_empty() {
+            return Err(Error::msg("ERR wrong number of arguments for 'scan' command"));
+        }
+        Ok(Scan { args: ScanArgs::parse(&args, true)? })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let (cursor, keys) = db.scan(self.args.cursor, self.args.count);
+        let mut result = Vec::new();
+        for key in keys {
+            if !self.args.is_match(&key) {
+                continue;
+            }
+            if let Some(kind) = &self.args.kind {
+                match db.get(&key) {
+                    Some(structure) if Type::name(structure) == kind => {},
+                    _ => continue,
+                }
+            }
+            result.push(Frame::BulkString(key));
+        }
+        Ok(scan_reply(cursor, result))
+    }
+}
@@ -3,53 +3,47 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Type {
-    pub key: String,
+    pub key: Vec<u8>,
 }
 
 impl Type {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'type' command"));
         }
-        let final_key = key.unwrap().to_string();
+        let final_key = key.unwrap();
         Ok(Type { 
             key: final_key 
         })
     }
 
-    pub fn new(key: String) -> Self {
+    pub fn new(key: Vec<u8>) -> Self {
         Type { key }
     }
 
+    /**
+     * 数据结构对应的类型名称
+     *
+     * @param structure 数据结构
+     */
+    pub fn name(structure: &Structure) -> &'static str {
+        match structure {
+            Structure::Set(_) => "set",
+            Structure::String(_) => "string",
+            Structure::SortedSet(_) => "zset",
+            Structure::Hash(_) => "hash",
+            Structure::List(_) => "list",
+            Structure::VectorCollection(_) => "vector",
+            Structure::Stream(_) => "stream",
+        }
+    }
+
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
         match db.get(&self.key) {
-            Some(structure) => {
-                match structure {
-                    Structure::Set(_) => {
-                        Ok(Frame::SimpleString("set".to_string()))
-                    },
-                    Structure::String(_) => {
-                        Ok(Frame::SimpleString("string".to_string()))
-                    },
-                    Structure::SortedSet(_) => {
-                        Ok(Frame::SimpleString("zset".to_string()))
-                    },
-                    Structure::Hash(_) => {
-                        Ok(Frame::SimpleString("hash".to_string()))
-                    },
-                    Structure::List(_) => {
-                        Ok(Frame::SimpleString("list".to_string()))
-                    },
-                    Structure::VectorCollection(_) => {
-                        Ok(Frame::SimpleString("vector".to_string()))
-                    }
-                }
-            },
-            None => {
-                Ok(Frame::SimpleString("none".to_string()))
-            }
+            Some(structure) => Ok(Frame::SimpleString(Self::name(structure).to_string())),
+            None => Ok(Frame::SimpleString("none".to_string())),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use crate::{cmds::listing::{blocking::parse_timeout, lmove::{Direction, Lmove}}, store::db::Db, frame::Frame};
+
+pub struct Blmove {
+    lmove: Lmove,
+    keys: Vec<Vec<u8>>, // 等待的源列表
+    timeout: Option<Duration>, // None 表示永久阻塞
+}
+
+impl Blmove {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 6 {
+            return Err(Error::msg("ERR wrong number of arguments for 'blmove' command"));
+        }
+        let source = frame.get_arg_bytes(1).unwrap(); // 源列表
+        let destination = frame.get_arg_bytes(2).unwrap(); // 目标列表
+        let from = Direction::parse(&args[3])?;
+        let to = Direction::parse(&args[4])?;
+        let timeout = parse_timeout(&args[5])?;
+        Ok(Blmove::new(source, destination, from, to, timeout))
+    }
+
+    pub fn new(source: Vec<u8>, destination: Vec<u8>, from: Direction, to: Direction, timeout: Option<Duration>) -> Self {
+        let keys = vec![source.clone()];
+        Blmove { lmove: Lmove::new(source, destination, from, to), keys, timeout }
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /**
+     * 以非阻塞方式执行，源列表为空时返回 Null
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        self.lmove.clone().apply(db)
+    }
+
+    /**
+     * 撤销执行，把移动的元素放回源列表
+     *
+     * @param db 数据库
+     * @param reply 执行结果
+     */
+    pub fn restore(&self, db: &mut Db, reply: Frame) {
+        if let Frame::BulkString(element) = reply {
+            self.lmove.restore(db, element);
+        }
+    }
+
+    /**
+     * 实际执行的命令，传播到 AOF 与从节点
+     *
+     * @param reply 执行结果
+     * @return 没有移动元素时返回 None
+     */
+    pub fn propagation_frame(&self, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::BulkString(_) => Some(self.lmove.to_frame()),
+            _ => None,
+        }
+    }
+}
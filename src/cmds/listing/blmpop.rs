@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use crate::{cmds::listing::{blocking::{parse_timeout, pop_first, unpop}, lmove::Direction}, store::db::Db, frame::Frame};
+
+pub struct Blmpop {
+    keys: Vec<Vec<u8>>,
+    direction: Direction,
+    count: usize,
+    timeout: Option<Duration>, // None 表示永久阻塞
+}
+
+impl Blmpop {
+
+    /**
+     * BLMPOP timeout numkeys key [key ...] LEFT|RIGHT [COUNT count]
+     */
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'blmpop' command"));
+        }
+        let timeout = parse_timeout(&args[1])?;
+        let numkeys = match args[2].parse::<i64>() {
+            Ok(n) if n > 0 => n as usize,
+            _ => return Err(Error::msg("ERR numkeys should be greater than 0")),
+        };
+        if args.len() < 4 + numkeys {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        let keys = (3..3 + numkeys).map(|i| frame.get_arg_bytes(i).unwrap()).collect();
+        let direction = Direction::parse(&args[3 + numkeys])?;
+
+        let mut count = None;
+        let mut index = 4 + numkeys;
+        while index < args.len() {
+            match args[index].to_uppercase().as_str() {
+                "COUNT" if count.is_none() && index + 1 < args.len() => {
+                    count = match args[index + 1].parse::<i64>() {
+                        Ok(n) if n > 0 => Some(n as usize),
+                        _ => return Err(Error::msg("ERR count should be greater than 0")),
+                    };
+                    index += 2;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+        }
+
+        Ok(Blmpop { keys, direction, count: count.unwrap_or(1), timeout })
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /**
+     * 以非阻塞方式执行，所有列表都为空时返回空数组
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        match pop_first(db, &self.keys, self.direction, self.count) {
+            Ok(Some((key, values))) => Ok(Frame::Array(vec![
+                Frame::BulkString(key),
+                Frame::Array(values.into_iter().map(Frame::BulkString).collect()),
+            ])),
+            Ok(None) => Ok(Frame::NullArray),
+            Err(f) => Ok(f),
+        }
+    }
+
+    /**
+     * 撤销执行，把弹出的元素放回列表
+     *
+     * @param db 数据库
+     * @param reply 执行结果
+     */
+    pub fn restore(&self, db: &mut Db, reply: Frame) {
+        if let Frame::Array(mut items) = reply {
+            if let (Some(Frame::Array(values)), Some(Frame::BulkString(key))) = (items.pop(), items.pop()) {
+                let values = values.into_iter().filter_map(|value| match value {
+                    Frame::BulkString(value) => Some(value),
+                    _ => None,
+                }).collect();
+                unpop(db, &key, self.direction, values);
+            }
+        }
+    }
+
+    /**
+     * 实际执行的命令，传播到 AOF 与从节点
+     *
+     * @param reply 执行结果
+     * @return 没有弹出元素时返回 None
+     */
+    pub fn propagation_frame(&self, reply: &Frame) -> Option<Frame> {
+        let (key, count) = match reply {
+            Frame::Array(items) => match items.as_slice() {
+                [Frame::BulkString(key), Frame::Array(values)] => (key, values.len()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let name = match self.direction {
+            Direction::Left => "LPOP",
+            Direction::Right => "RPOP",
+        };
+        Some(Frame::Array(vec![
+            Frame::BulkString(name.as_bytes().to_vec()),
+            Frame::BulkString(key.clone()),
+            Frame::BulkString(count.to_string().into_bytes()),
+        ]))
+    }
+}
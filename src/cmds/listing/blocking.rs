@@ -0,0 +1,87 @@
+use std::{collections::VecDeque, time::Duration};
+
+use anyhow::Error;
+use crate::{cmds::listing::lmove::Direction, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * 解析阻塞命令的超时时间（秒，支持小数）
+ *
+ * @param value 超时参数
+ * @return 0 表示永久阻塞，返回 None
+ */
+pub fn parse_timeout(value: &str) -> Result<Option<Duration>, Error> {
+    let seconds = match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() => seconds,
+        _ => return Err(Error::msg("ERR timeout is not a float or out of range")),
+    };
+    if seconds < 0.0 {
+        return Err(Error::msg("ERR timeout is negative"));
+    }
+    if seconds == 0.0 {
+        return Ok(None);
+    }
+    Duration::try_from_secs_f64(seconds)
+        .map(Some)
+        .map_err(|_| Error::msg("ERR timeout is not a float or out of range"))
+}
+
+/**
+ * 弹出的键和元素
+ */
+pub type Popped = (Vec<u8>, Vec<Vec<u8>>);
+
+/**
+ * 从第一个非空列表中弹出元素
+ *
+ * @param db 数据库
+ * @param keys 按顺序检查的键
+ * @param direction 弹出方向
+ * @param count 最多弹出的元素数量
+ * @return 所有列表都为空时返回 None
+ */
+pub fn pop_first(db: &mut Db, keys: &[Vec<u8>], direction: Direction, count: usize) -> Result<Option<Popped>, Frame> {
+    for key in keys {
+        let values = match db.get_mut(key) {
+            Some(Structure::List(list)) if !list.is_empty() => {
+                let count = count.min(list.len());
+                match direction {
+                    Direction::Left => list.drain(..count).collect::<Vec<_>>(),
+                    Direction::Right => {
+                        let start = list.len() - count;
+                        list.drain(start..).rev().collect::<Vec<_>>()
+                    }
+                }
+            },
+            Some(Structure::List(_)) | None => continue,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Err(Frame::Error(f.to_string()));
+            }
+        };
+        db.remove_if_empty(key);
+        return Ok(Some((key.clone(), values)));
+    }
+    Ok(None)
+}
+
+/**
+ * 把弹出的元素放回列表，用于撤销没能送达客户端的弹出
+ *
+ * @param db 数据库
+ * @param key 键
+ * @param direction 弹出方向
+ * @param values 按弹出顺序排列的元素
+ */
+pub fn unpop(db: &mut Db, key: &[u8], direction: Direction, values: Vec<Vec<u8>>) {
+    if !matches!(db.get(key), Some(Structure::List(_))) {
+        db.insert(key.to_vec(), Structure::List(VecDeque::new()));
+    }
+    if let Some(Structure::List(list)) = db.get_mut(key) {
+        for value in values.into_iter().rev() {
+            match direction {
+                Direction::Left => list.push_front(value),
+                Direction::Right => list.push_back(value),
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use crate::{cmds::listing::{blocking::{parse_timeout, pop_first, unpop}, lmove::Direction}, store::db::Db, frame::Frame};
+
+pub struct Blpop {
+    keys: Vec<Vec<u8>>,
+    timeout: Option<Duration>, // None 表示永久阻塞
+}
+
+impl Blpop {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'blpop' command"));
+        }
+        let keys = args[1..args.len() - 1].to_vec();
+        let timeout = parse_timeout(&String::from_utf8_lossy(&args[args.len() - 1]))?;
+        Ok(Blpop { keys, timeout })
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /**
+     * 以非阻塞方式执行，所有列表都为空时返回空数组
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        match pop_first(db, &self.keys, Direction::Left, 1) {
+            Ok(Some((key, mut values))) => Ok(Frame::Array(vec![
+                Frame::BulkString(key),
+                Frame::BulkString(values.remove(0)),
+            ])),
+            Ok(None) => Ok(Frame::NullArray),
+            Err(f) => Ok(f),
+        }
+    }
+
+    /**
+     * 撤销执行，把弹出的元素放回列表
+     *
+     * @param db 数据库
+     * @param reply 执行结果
+     */
+    pub fn restore(&self, db: &mut Db, reply: Frame) {
+        if let Frame::Array(mut items) = reply {
+            if let (Some(Frame::BulkString(value)), Some(Frame::BulkString(key))) = (items.pop(), items.pop()) {
+                unpop(db, &key, Direction::Left, vec![value]);
+            }
+        }
+    }
+
+    /**
+     * 实际执行的命令，传播到 AOF 与从节点
+     *
+     * @param reply 执行结果
+     * @return 没有弹出元素时返回 None
+     */
+    pub fn propagation_frame(&self, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::Array(items) => match items.first() {
+                Some(Frame::BulkString(key)) => Some(Frame::Array(vec![
+                    Frame::BulkString(b"LPOP".to_vec()),
+                    Frame::BulkString(key.clone()),
+                ])),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
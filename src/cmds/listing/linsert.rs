@@ -0,0 +1,48 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Linsert {
+    key: Vec<u8>,
+    before: bool, // BEFORE 或 AFTER
+    pivot: Vec<u8>,
+    element: Vec<u8>,
+}
+
+impl Linsert {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'linsert' command"));
+        }
+        let key = args[1].clone(); // 键
+        let before = match String::from_utf8_lossy(&args[2]).to_uppercase().as_str() {
+            "BEFORE" => true,
+            "AFTER" => false,
+            _ => return Err(Error::msg("ERR syntax error")),
+        };
+        let pivot = args[3].clone(); // 参照元素
+        let element = args[4].clone(); // 插入的元素
+        Ok(Linsert { key, before, pivot, element })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::List(list)) => {
+                match list.iter().position(|item| *item == self.pivot) {
+                    Some(index) => {
+                        let index = if self.before { index } else { index + 1 };
+                        list.insert(index, self.element);
+                        Ok(Frame::Integer(list.len() as i64))
+                    },
+                    None => Ok(Frame::Integer(-1)),
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
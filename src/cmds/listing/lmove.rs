@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+
+use anyhow::Error;
+use crate::{cmds::listing::blocking::unpop, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * 列表方向
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.to_uppercase().as_str() {
+            "LEFT" => Ok(Direction::Left),
+            "RIGHT" => Ok(Direction::Right),
+            _ => Err(Error::msg("ERR syntax error")),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::Left => "LEFT",
+            Direction::Right => "RIGHT",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Lmove {
+    source: Vec<u8>,
+    destination: Vec<u8>,
+    from: Direction,
+    to: Direction,
+}
+
+impl Lmove {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'lmove' command"));
+        }
+        let source = frame.get_arg_bytes(1).unwrap(); // 源列表
+        let destination = frame.get_arg_bytes(2).unwrap(); // 目标列表
+        let from = Direction::parse(&args[3])?;
+        let to = Direction::parse(&args[4])?;
+        Ok(Lmove { source, destination, from, to })
+    }
+
+    pub fn new(source: Vec<u8>, destination: Vec<u8>, from: Direction, to: Direction) -> Self {
+        Lmove { source, destination, from, to }
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let f = "ERR Operation against a key holding the wrong kind of value";
+
+        // 先检查目标列表的类型，避免弹出后无法写入
+        match db.get(&self.destination) {
+            Some(Structure::List(_)) | None => {},
+            Some(_) => return Ok(Frame::Error(f.to_string())),
+        }
+
+        let element = match db.get_mut(&self.source) {
+            Some(Structure::List(list)) => {
+                if list.is_empty() {
+                    return Ok(Frame::Null);
+                }
+                match self.from {
+                    Direction::Left => list.pop_front().unwrap(),
+                    Direction::Right => list.pop_back().unwrap(),
+                }
+            },
+            Some(_) => return Ok(Frame::Error(f.to_string())),
+            None => return Ok(Frame::Null),
+        };
+
+        match db.get_mut(&self.destination) {
+            Some(Structure::List(list)) => {
+                match self.to {
+                    Direction::Left => list.push_front(element.clone()),
+                    Direction::Right => list.push_back(element.clone()),
+                }
+            },
+            _ => {
+                db.insert(self.destination.clone(), Structure::List(VecDeque::from([element.clone()])));
+            }
+        }
+        db.remove_if_empty(&self.source);
+
+        Ok(Frame::BulkString(element))
+    }
+
+    /**
+     * 撤销执行，把移动到目标列表的元素放回源列表
+     *
+     * @param db 数据库
+     * @param element 移动的元素
+     */
+    pub fn restore(&self, db: &mut Db, element: Vec<u8>) {
+        if let Some(Structure::List(list)) = db.get_mut(&self.destination) {
+            match self.to {
+                Direction::Left => list.pop_front(),
+                Direction::Right => list.pop_back(),
+            };
+        }
+        db.remove_if_empty(&self.destination);
+        unpop(db, &self.source, self.from, vec![element]);
+    }
+
+    /**
+     * 转换为命令帧
+     */
+    pub fn to_frame(&self) -> Frame {
+        Frame::Array(vec![
+            Frame::BulkString(b"LMOVE".to_vec()),
+            Frame::BulkString(self.source.clone()),
+            Frame::BulkString(self.destination.clone()),
+            Frame::BulkString(self.from.name().as_bytes().to_vec()),
+            Frame::BulkString(self.to.name().as_bytes().to_vec()),
+        ])
+    }
+}
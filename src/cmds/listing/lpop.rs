@@ -2,45 +2,59 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Lpop {
-    key: String,
+    key: Vec<u8>,
+    count: Option<usize>, // 弹出的元素数量，指定时返回数组
 }
 
 impl Lpop {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
-        if args.len() != 2 {
+        if args.len() != 2 && args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'lpop' command"));
         }
 
-        let key = args[1].to_string(); // 键
+        let key = args[1].clone(); // 键
+        let count = match frame.get_arg(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count >= 0 => Some(count as usize),
+                _ => return Err(Error::msg("ERR value is out of range, must be positive")),
+            },
+            None => None,
+        };
         
-        Ok(Lpop { key })
+        Ok(Lpop { key, count })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.get_mut(&self.key) {
+        let result = match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::List(list) => {
-                        if list.is_empty() {
-                            Ok(Frame::Null)
-                        } else {
-                            let value = list.remove(0); // 移除列表的第一个元素
-                            Ok(Frame::BulkString(value))
+                        match self.count {
+                            Some(count) => {
+                                let count = count.min(list.len());
+                                let values = list.drain(..count).collect::<Vec<_>>();
+                                Frame::Array(values.into_iter().map(Frame::BulkString).collect())
+                            },
+                            None if list.is_empty() => Frame::Null,
+                            None => Frame::BulkString(list.pop_front().unwrap()), // 移除列表的第一个元素
                         }
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
-                        Ok(Frame::Error(f.to_string()))
+                        return Ok(Frame::Error(f.to_string()));
                     }
                 }
             },
             None => {
-                Ok(Frame::Null)
+                // 指定数量时回复空数组
+                return Ok(if self.count.is_some() { Frame::NullArray } else { Frame::Null });
             }
-        }
+        };
+        db.remove_if_empty(&self.key);
+        Ok(result)
     }
-}
\ No newline at end of file
+}
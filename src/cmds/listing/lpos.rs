@@ -0,0 +1,103 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Lpos {
+    key: Vec<u8>,
+    element: Vec<u8>,
+    rank: i64, // 从第几个匹配开始返回，负数表示从尾部开始查找
+    count: Option<usize>, // 返回的匹配数量，0 表示全部
+    maxlen: usize, // 最多比较的元素数量，0 表示不限制
+}
+
+impl Lpos {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'lpos' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let element = frame.get_arg_bytes(2).unwrap(); // 元素
+
+        let mut rank = 1;
+        let mut count = None;
+        let mut maxlen = 0;
+        let mut idx = 3;
+        while idx < args.len() {
+            let value = match args.get(idx + 1) {
+                Some(value) => value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?,
+                None => return Err(Error::msg("ERR syntax error")),
+            };
+            match args[idx].to_uppercase().as_str() {
+                "RANK" => {
+                    if value == 0 {
+                        return Err(Error::msg("ERR RANK can't be zero: use 1 to start from the first match, 2 from the second ... or use negative to start from the last match"));
+                    }
+                    rank = value;
+                },
+                "COUNT" => {
+                    if value < 0 {
+                        return Err(Error::msg("ERR COUNT can't be negative"));
+                    }
+                    count = Some(value as usize);
+                },
+                "MAXLEN" => {
+                    if value < 0 {
+                        return Err(Error::msg("ERR MAXLEN can't be negative"));
+                    }
+                    maxlen = value as usize;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            idx += 2;
+        }
+
+        Ok(Lpos { key, element, rank, count, maxlen })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let list = match db.get(&self.key) {
+            Some(Structure::List(list)) => list,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                return Ok(if self.count.is_some() { Frame::Array(Vec::new()) } else { Frame::Null });
+            }
+        };
+
+        let limit = match self.count {
+            Some(0) => usize::MAX,
+            Some(count) => count,
+            None => 1,
+        };
+        let maxlen = if self.maxlen == 0 { list.len() } else { self.maxlen.min(list.len()) };
+        let mut skip = self.rank.unsigned_abs() as usize - 1;
+        let mut positions = Vec::new();
+
+        let indexes: Box<dyn Iterator<Item = usize>> = if self.rank > 0 {
+            Box::new(0..maxlen)
+        } else {
+            Box::new((list.len() - maxlen..list.len()).rev())
+        };
+        for index in indexes {
+            if list[index] != self.element {
+                continue;
+            }
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            positions.push(index as i64);
+            if positions.len() >= limit {
+                break;
+            }
+        }
+
+        match self.count {
+            Some(_) => Ok(Frame::Array(positions.into_iter().map(Frame::Integer).collect())),
+            None => Ok(positions.first().map_or(Frame::Null, |position| Frame::Integer(*position))),
+        }
+    }
+}
@@ -1,23 +1,25 @@
+use std::collections::VecDeque;
+
 use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Lpush {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl Lpush {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'lpush' command"));
         }
 
-        let key = args[1].to_string(); // 键
-        let values: Vec<String> = args.iter().skip(2).map(|v| v.to_string()).collect(); // 值
+        let key = args[1].clone(); // 键
+        let values: Vec<Vec<u8>> = args[2..].to_vec(); // 值
 
         Ok(Lpush { key, values })
     }
@@ -27,8 +29,8 @@ impl Lpush {
             Some(structure) => {
                 match structure {
                     Structure::List(list) => {
-                        for value in self.values.into_iter().rev() {
-                            list.insert(0, value); // 向引用 mut 中添加数据
+                        for value in self.values {
+                            list.push_front(value); // 向引用 mut 中添加数据
                         }
                         Ok(Frame::Integer(list.len() as i64))
                     },
@@ -39,12 +41,13 @@ impl Lpush {
                 }
             },
             None => {
-                let mut list = Vec::new(); 
-                for value in self.values.into_iter().rev() {
-                    list.insert(0, value); // 倒序遍历
+                let mut list = VecDeque::with_capacity(self.values.len());
+                for value in self.values {
+                    list.push_front(value); // 依次插入头部，最后一个值位于表头
                 }
-                db.insert(self.key.clone(), Structure::List(list.clone()));
-                Ok(Frame::Integer(list.len() as i64))
+                let len = list.len();
+                db.insert(self.key.clone(), Structure::List(list));
+                Ok(Frame::Integer(len as i64))
             }
         }
     }
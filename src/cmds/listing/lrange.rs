@@ -2,14 +2,14 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Lrange {
-    key: String,
+    key: Vec<u8>,
     start: i64,
     stop: i64,
 }
 
 impl Lrange {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         let start = frame.get_arg(2);
         let stop = frame.get_arg(3);
 
@@ -17,7 +17,7 @@ impl Lrange {
             return Err(Error::msg("ERR wrong number of arguments for 'lrange' command"));
         }
 
-        let final_key = key.unwrap().to_string(); // 键
+        let final_key = key.unwrap(); // 键
 
         let start = match start.unwrap().parse::<i64>() {
             Ok(n) => n,
@@ -53,9 +53,7 @@ impl Lrange {
                             return Ok(Frame::Array(vec![]));
                         }
 
-                        let result: Vec<Frame> = list.iter()
-                            .skip(start as usize)
-                            .take((stop - start + 1) as usize)
+                        let result: Vec<Frame> = list.range(start as usize..=stop as usize)
                             .map(|item| Frame::BulkString(item.clone()))
                             .collect();
 
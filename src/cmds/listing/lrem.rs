@@ -0,0 +1,58 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Lrem {
+    key: Vec<u8>,
+    count: i64, // 大于 0 从头部开始删除，小于 0 从尾部开始删除，等于 0 删除全部
+    element: Vec<u8>,
+}
+
+impl Lrem {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'lrem' command"));
+        }
+        let key = args[1].clone(); // 键
+        let count = frame.get_arg(2).unwrap().parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+        let element = args[3].clone(); // 要删除的元素
+        Ok(Lrem { key, count, element })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let removed = match db.get_mut(&self.key) {
+            Some(Structure::List(list)) => {
+                let limit = if self.count == 0 { usize::MAX } else { self.count.unsigned_abs() as usize };
+                let mut removed = 0;
+                if self.count >= 0 {
+                    list.retain(|item| {
+                        if removed < limit && *item == self.element {
+                            removed += 1;
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                } else {
+                    let mut index = list.len();
+                    while index > 0 && removed < limit {
+                        index -= 1;
+                        if list[index] == self.element {
+                            list.remove(index);
+                            removed += 1;
+                        }
+                    }
+                }
+                removed
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => 0,
+        };
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
@@ -3,9 +3,9 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Lset {
-    key: String,
+    key: Vec<u8>,
     index: isize, // 索引，支持负数索引
-    value: String, // 要设置的值
+    value: Vec<u8>, // 要设置的值
 }
 
 impl Lset {
@@ -18,9 +18,9 @@ impl Lset {
             return Err(Error::msg("ERR wrong number of arguments for 'lset' command"));
         }
 
-        let key = args[1].to_string(); // 键
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
         let index = args[2].parse::<isize>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?; // 索引
-        let value = args[3].to_string(); // 要设置的值
+        let value = frame.get_arg_bytes(3).unwrap(); // 要设置的值
 
         Ok(Lset { key, index, value })
     }
@@ -0,0 +1,46 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Ltrim {
+    key: Vec<u8>,
+    start: i64,
+    stop: i64,
+}
+
+impl Ltrim {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'ltrim' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let start = args[2].parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+        let stop = args[3].parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+        Ok(Ltrim { key, start, stop })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::List(list)) => {
+                let len = list.len() as i64;
+                let start = if self.start < 0 { (len + self.start).max(0) } else { self.start };
+                let stop = if self.stop < 0 { len + self.stop } else { self.stop.min(len - 1) };
+
+                if start > stop || start >= len {
+                    list.clear();
+                } else {
+                    list.truncate(stop as usize + 1);
+                    list.drain(..start as usize);
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {},
+        }
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Ok)
+    }
+}
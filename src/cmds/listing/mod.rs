@@ -7,4 +7,14 @@ pub mod lrange;
 pub mod lpop;
 pub mod rpop;
 pub mod llen;
-pub mod lset;
\ No newline at end of file
+pub mod lset;
+pub mod linsert;
+pub mod lrem;
+pub mod ltrim;
+pub mod lpos;
+pub mod lmove;
+pub mod rpoplpush;pub mod blocking;
+pub mod blpop;
+pub mod brpop;
+pub mod blmove;
+pub mod blmpop;
@@ -1,50 +1,60 @@
 use anyhow::Error;
-
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Rpop {
-    key: String,
+    key: Vec<u8>,
+    count: Option<usize>, // 弹出的元素数量，指定时返回数组
 }
 
 impl Rpop {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
-        if args.len() != 2 {
+        if args.len() != 2 && args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'rpop' command"));
         }
 
-        let key = args[1].to_string(); // 键
-
-        Ok(Rpop { key })
+        let key = args[1].clone(); // 键
+        let count = match frame.get_arg(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count >= 0 => Some(count as usize),
+                _ => return Err(Error::msg("ERR value is out of range, must be positive")),
+            },
+            None => None,
+        };
+        
+        Ok(Rpop { key, count })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.get_mut(&self.key) {
+        let result = match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::List(list) => {
-                        if list.is_empty() {
-                            Ok(Frame::Null)
-                        } else {
-                            let value = list.pop(); // 移除列表的最后一个元素
-                            match value {
-                                Some(val) => Ok(Frame::BulkString(val)),
-                                None => Ok(Frame::Null), // 理论上不会执行到这，因为前面已经判断过列表不为空
-                            }
+                        match self.count {
+                            Some(count) => {
+                                let count = count.min(list.len());
+                                let values = (0..count).filter_map(|_| list.pop_back()).collect::<Vec<_>>();
+                                Frame::Array(values.into_iter().map(Frame::BulkString).collect())
+                            },
+                            None if list.is_empty() => Frame::Null,
+                            None => Frame::BulkString(list.pop_back().unwrap()), // 移除列表的最后一个元素
                         }
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
-                        Ok(Frame::Error(f.to_string()))
+                        return Ok(Frame::Error(f.to_string()));
                     }
                 }
             },
             None => {
-                Ok(Frame::Null)
+                // 指定数量时回复空数组
+                return Ok(if self.count.is_some() { Frame::NullArray } else { Frame::Null });
             }
-        }
+        };
+        db.remove_if_empty(&self.key);
+        Ok(result)
     }
-}
\ No newline at end of file
+}
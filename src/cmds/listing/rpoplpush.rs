@@ -0,0 +1,24 @@
+use anyhow::Error;
+use crate::{cmds::listing::lmove::{Direction, Lmove}, store::db::Db, frame::Frame};
+
+pub struct Rpoplpush {
+    source: Vec<u8>,
+    destination: Vec<u8>,
+}
+
+impl Rpoplpush {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'rpoplpush' command"));
+        }
+        let source = args[1].clone(); // 源列表
+        let destination = args[2].clone(); // 目标列表
+        Ok(Rpoplpush { source, destination })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        Lmove::new(self.source, self.destination, Direction::Right, Direction::Left).apply(db)
+    }
+}
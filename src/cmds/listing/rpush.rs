@@ -1,20 +1,22 @@
+use std::collections::VecDeque;
+
 use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Rpush {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl Rpush {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'rpush' command"));
         }
-        let key = args[1].to_string(); // 键
-        let values: Vec<String> = args.iter().skip(2).map(|v| v.to_string()).collect(); // 值
+        let key = args[1].clone(); // 键
+        let values: Vec<Vec<u8>> = args[2..].to_vec(); // 值
         Ok(Rpush { key, values })
     }
 
@@ -24,7 +26,7 @@ impl Rpush {
                 match structure {
                     Structure::List(list) => {
                         for value in self.values {
-                            list.push(value); // 向引用 mut 中添加数据
+                            list.push_back(value); // 向引用 mut 中添加数据
                         }
                         Ok(Frame::Integer(list.len() as i64))
                     },
@@ -35,12 +37,10 @@ impl Rpush {
                 }
             },
             None => {
-                let mut list = Vec::new();
-                for value in self.values {
-                    list.push(value); // 正序遍历
-                }
-                db.insert(self.key.clone(), Structure::List(list.clone()));
-                Ok(Frame::Integer(list.len() as i64))
+                let list = VecDeque::from(self.values);
+                let len = list.len();
+                db.insert(self.key.clone(), Structure::List(list));
+                Ok(Frame::Integer(len as i64))
             }
         }
     }
@@ -2,19 +2,19 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Rpushx {
-    key: String,
-    values: Vec<String>,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
 }
 
 impl Rpushx {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'rpushx' command"));
         }
-        let key = args[1].to_string(); // 键
-        let values: Vec<String> = args.iter().skip(2).map(|v| v.to_string()).collect(); // 值
+        let key = args[1].clone(); // 键
+        let values: Vec<Vec<u8>> = args[2..].to_vec(); // 值
         Ok(Rpushx { key, values })
     }
 
@@ -24,7 +24,7 @@ impl Rpushx {
                 match structure {
                     Structure::List(list) => {
                         for value in self.values {
-                            list.push(value); // 向引用 mut 中添加数据
+                            list.push_back(value); // 向引用 mut 中添加数据
                         }
                         Ok(Frame::Integer(list.len() as i64))
                     },
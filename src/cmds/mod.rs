@@ -8,4 +8,9 @@ pub mod server;
 pub mod server_sync;
 pub mod string;
 pub mod set;
-pub mod transaction;
\ No newline at end of file
+pub mod transaction;
+pub mod bitmap;
+pub mod hyperloglog;
+pub mod geo;
+pub mod stream;
+pub mod vector;
\ No newline at end of file
@@ -20,7 +20,7 @@ impl Flushall {
             let (sender, _receiver) = oneshot::channel(); // 创建通道
             match target_sender.send(DatabaseMessage::Command {
                 command: Command::Flushdb(Flushdb {}),
-                sender: sender
+                sender
             }).await {
                 Ok(()) => {}
                 Err(e) => {
@@ -4,6 +4,12 @@ use crate::{store::db::Db, frame::Frame};
 
 pub struct Flushdb {}
 
+impl Default for Flushdb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Flushdb {
 
     pub fn new() -> Flushdb {
@@ -15,8 +21,7 @@ impl Flushdb {
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        db.expire_records.clear();
-        db.records.clear();
+        db.clear();
         Ok(Frame::Ok)
     }
 }
\ No newline at end of file
@@ -3,7 +3,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::{
     frame::Frame,
-    store::db::Db,
+    store::{blocking, db::Db},
 };
 
 pub struct Info {
@@ -25,24 +25,24 @@ impl Info {
 
     pub fn apply(self, db: &Db) -> Result<Frame, Error> {
         let info = self.generate_info(db);
-        Ok(Frame::BulkString(info))
+        Ok(Frame::VerbatimString("txt".to_string(), info))
     }
 
     fn generate_info(&self, db: &Db) -> String {
         let mut info = String::new();
         
         // Default sections to show
-        let show_all = self.section.is_none() || self.section.as_ref().map_or(false, |s| s == "all");
-        let show_default = self.section.is_none() || self.section.as_ref().map_or(true, |s| s == "default");
-        let show_server = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "server");
-        let show_clients = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "clients");
-        let show_memory = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "memory");
-        let show_persistence = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "persistence");
-        let show_stats = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "stats");
-        let show_replication = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "replication");
-        let show_cpu = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "cpu");
-        let show_commandstats = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "commandstats");
-        let show_keyspace = show_all || show_default || self.section.as_ref().map_or(false, |s| s == "keyspace");
+        let show_all = self.section.is_none() || self.section.as_ref().is_some_and(|s| s == "all");
+        let show_default = self.section.is_none() || self.section.as_ref().is_none_or(|s| s == "default");
+        let show_server = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "server");
+        let show_clients = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "clients");
+        let show_memory = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "memory");
+        let show_persistence = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "persistence");
+        let show_stats = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "stats");
+        let show_replication = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "replication");
+        let show_cpu = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "cpu");
+        let show_commandstats = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "commandstats");
+        let show_keyspace = show_all || show_default || self.section.as_ref().is_some_and(|s| s == "keyspace");
 
         // Server section
         if show_server {
@@ -78,7 +78,7 @@ impl Info {
             info.push_str("connected_clients:1\r\n");
             info.push_str("client_recent_max_input_buffer:0\r\n");
             info.push_str("client_recent_max_output_buffer:0\r\n");
-            info.push_str("blocked_clients:0\r\n");
+            info.push_str(&format!("blocked_clients:{}\r\n", blocking::blocked_clients()));
             info.push_str("tracking_clients:0\r\n");
             info.push_str("clients_in_timeout_table:0\r\n\r\n");
         }
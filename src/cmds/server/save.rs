@@ -25,11 +25,8 @@ impl Save {
                     eprintln!("Failed to write to socket; err = {:?}", e);
                 }
             };
-            match receiver.await {
-                Ok(snapshot) => {
-                    rdb_file.set_database(index, snapshot);
-                },
-                Err(_) => {}
+            if let Ok(snapshot) = receiver.await {
+                rdb_file.set_database(index, snapshot);
             };
 
             let (tx, rx) = oneshot::channel();
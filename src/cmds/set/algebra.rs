@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * 集合运算
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum Operation {
+    Inter,
+    Diff,
+}
+
+/**
+ * 计算多个集合的交集或差集，不存在的键视为空集合
+ *
+ * @param db 数据库
+ * @param keys 集合键，差集以第一个集合为基准
+ * @param operation 运算类型
+ * @return 任一键的类型不是集合时返回错误帧
+ */
+pub fn compute(db: &mut Db, keys: &[Vec<u8>], operation: Operation) -> Result<HashSet<Vec<u8>>, Frame> {
+    let mut sets = Vec::with_capacity(keys.len());
+    for key in keys {
+        match db.get(key) {
+            Some(Structure::Set(set)) => sets.push(Some(set.iter().cloned().collect::<HashSet<_>>())),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Err(Frame::Error(f.to_string()));
+            },
+            None => sets.push(None),
+        }
+    }
+
+    let mut sets = sets.into_iter();
+    let mut result = sets.next().flatten().unwrap_or_default();
+    for set in sets {
+        match (operation, set) {
+            (Operation::Inter, Some(set)) => result.retain(|member| set.contains(member)),
+            (Operation::Inter, None) => result.clear(),
+            (Operation::Diff, Some(set)) => result.retain(|member| !set.contains(member)),
+            (Operation::Diff, None) => {},
+        }
+    }
+    Ok(result)
+}
+
+/**
+ * 保存运算结果，结果为空时删除目标键
+ *
+ * @param db 数据库
+ * @param destination 目标键
+ * @param set 运算结果
+ * @return 结果集合的元素数量
+ */
+pub fn store(db: &mut Db, destination: Vec<u8>, set: HashSet<Vec<u8>>) -> Frame {
+    let len = set.len() as i64;
+    if set.is_empty() {
+        db.remove(&destination);
+    } else {
+        db.insert(destination, Structure::Set(set.into()));
+    }
+    Frame::Integer(len)
+}
+
+/**
+ * 集合转为响应帧
+ *
+ * @param set 集合
+ */
+pub fn to_frame(set: HashSet<Vec<u8>>) -> Frame {
+    Frame::Set(set.into_iter().map(Frame::BulkString).collect())
+}
@@ -6,4 +6,13 @@ pub mod scard;
 pub mod spop;
 pub mod srem;
 pub mod sunion;
-pub mod sinter;
\ No newline at end of file
+pub mod sinter;
+pub mod algebra;
+pub mod sdiff;
+pub mod sdiffstore;
+pub mod sinterstore;
+pub mod sintercard;
+pub mod smismember;
+pub mod smove;
+pub mod srandmember;
+pub mod sscan;
@@ -1,25 +1,23 @@
-use std::collections::HashSet;
-
 use anyhow::Error;
-use crate::{store::db::{Db, Structure}, frame::Frame};
+use crate::{store::{db::{Db, Structure}, set::Set}, frame::Frame};
 
 pub struct Sadd {
-    key: String,
-    members: Vec<String>,
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
 }
 
 impl Sadd {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'sadd' command"));
         }
 
-        let key = args[1].to_string(); // 键
-        let members: Vec<String> = args.iter().skip(2).map(|v| v.to_string()).collect(); // 成员
+        let key = args[1].clone(); // 键
+        let members: Vec<Vec<u8>> = args[2..].to_vec(); // 成员
 
         Ok(Sadd { key, members })
     }
@@ -44,14 +42,14 @@ impl Sadd {
                 }
             },
             None => {
-                let mut set = HashSet::new(); 
+                let mut set = Set::new();
                 let mut added_count = 0;
                 for member in self.members {
                     if set.insert(member) {
                         added_count += 1;
                     }
                 }
-                db.records.insert(self.key.clone(), Structure::Set(set));
+                db.insert(self.key.clone(), Structure::Set(set));
                 Ok(Frame::Integer(added_count as i64))
             }
         }
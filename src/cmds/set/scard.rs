@@ -2,20 +2,20 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Scard {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Scard {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() != 2 {
             return Err(Error::msg("ERR wrong number of arguments for 'scard' command"));
         }
 
-        let key = args[1].to_string(); // 键
+        let key = args[1].clone(); // 键
 
         Ok(Scard { key })
     }
@@ -0,0 +1,25 @@
+use anyhow::Error;
+use crate::{cmds::set::algebra::{self, Operation}, store::db::Db, frame::Frame};
+
+pub struct Sdiff {
+    keys: Vec<Vec<u8>>,
+}
+
+impl Sdiff {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'sdiff' command"));
+        }
+        let keys = args[1..].to_vec();
+        Ok(Sdiff { keys })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match algebra::compute(db, &self.keys, Operation::Diff) {
+            Ok(set) => Ok(algebra::to_frame(set)),
+            Err(f) => Ok(f),
+        }
+    }
+}
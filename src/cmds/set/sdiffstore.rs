@@ -0,0 +1,27 @@
+use anyhow::Error;
+use crate::{cmds::set::algebra::{self, Operation}, store::db::Db, frame::Frame};
+
+pub struct Sdiffstore {
+    destination: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+}
+
+impl Sdiffstore {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'sdiffstore' command"));
+        }
+        let destination = args[1].clone();
+        let keys = args[2..].to_vec();
+        Ok(Sdiffstore { destination, keys })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match algebra::compute(db, &self.keys, Operation::Diff) {
+            Ok(set) => Ok(algebra::store(db, self.destination, set)),
+            Err(f) => Ok(f),
+        }
+    }
+}
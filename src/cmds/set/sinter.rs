@@ -3,17 +3,17 @@ use crate::{store::db::{Db, Structure}, frame::Frame};
 use std::collections::HashSet;
 
 pub struct Sinter {
-    keys: Vec<String>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Sinter {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() < 2 {
             return Err(Error::msg("ERR wrong number of arguments for 'sinter' command"));
         }
-        let keys: Vec<String> = args[1..].iter().map(|arg| arg.to_string()).collect();
+        let keys: Vec<Vec<u8>> = args[1..].to_vec();
         Ok(Sinter { keys })
     }
 
@@ -26,13 +26,13 @@ impl Sinter {
             Some(structure) => {
                 match structure {
                     Structure::Set(first_set) => {       
-                        let mut intersection: HashSet<String> = first_set.clone();
+                        let mut intersection: HashSet<Vec<u8>> = first_set.iter().cloned().collect();
                         for key in iter {
                             match db.records.get(key) {
                                 Some(structure) => {
                                     match structure {
                                         Structure::Set(set) => {
-                                            intersection = intersection.intersection(set).cloned().collect();
+                                            intersection.retain(|member| set.contains(member));
                                         },
                                         _ => {
                                             let f = "ERR Operation against a key holding the wrong kind of value";
@@ -50,7 +50,7 @@ impl Sinter {
                         for member in intersection.iter() {
                             result.push(Frame::BulkString(member.clone()));
                         }
-                        Ok(Frame::Array(result))
+                        Ok(Frame::Set(result))
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
@@ -59,7 +59,7 @@ impl Sinter {
                 }
             },
             None => {
-                Ok(Frame::Array(Vec::new()))
+                Ok(Frame::Set(Vec::new()))
             }
         }
     }
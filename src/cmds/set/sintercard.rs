@@ -0,0 +1,57 @@
+use anyhow::Error;
+use crate::{cmds::set::algebra::{self, Operation}, store::db::Db, frame::Frame};
+
+pub struct Sintercard {
+    keys: Vec<Vec<u8>>,
+    limit: usize, // 0 表示不限制
+}
+
+impl Sintercard {
+
+    /**
+     * SINTERCARD numkeys key [key ...] [LIMIT limit]
+     */
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'sintercard' command"));
+        }
+        let numkeys = match args[1].parse::<i64>() {
+            Ok(n) if n > 0 => n as usize,
+            Ok(_) => return Err(Error::msg("ERR numkeys should be greater than 0")),
+            Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+        };
+        if numkeys > args.len() - 2 {
+            return Err(Error::msg("ERR Number of keys can't be greater than number of args"));
+        }
+        let keys = (2..2 + numkeys).map(|i| frame.get_arg_bytes(i).unwrap()).collect();
+
+        let mut limit = 0;
+        let mut index = 2 + numkeys;
+        while index < args.len() {
+            match args[index].to_uppercase().as_str() {
+                "LIMIT" if index + 1 < args.len() => {
+                    limit = match args[index + 1].parse::<i64>() {
+                        Ok(n) if n >= 0 => n as usize,
+                        Ok(_) => return Err(Error::msg("ERR LIMIT can't be negative")),
+                        Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+                    };
+                    index += 2;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+        }
+
+        Ok(Sintercard { keys, limit })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match algebra::compute(db, &self.keys, Operation::Inter) {
+            Ok(set) => {
+                let len = if self.limit == 0 { set.len() } else { set.len().min(self.limit) };
+                Ok(Frame::Integer(len as i64))
+            },
+            Err(f) => Ok(f),
+        }
+    }
+}
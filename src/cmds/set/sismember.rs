@@ -2,19 +2,19 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Sismember {
-    key: String,
-    member: String,
+    key: Vec<u8>,
+    member: Vec<u8>,
 }
 
 impl Sismember {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'sismember' command"));
         }
-        let key = args[1].to_string(); // 键
-        let member = args[2].to_string(); // 成员
+        let key = args[1].clone(); // 键
+        let member = args[2].clone(); // 成员
         Ok(Sismember { key, member })
     }
 
@@ -2,18 +2,18 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Smembers {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Smembers {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() != 2 {
             return Err(Error::msg("ERR wrong number of arguments for 'smembers' command"));
         }
 
-        let key = args[1].to_string(); // 键
+        let key = args[1].clone(); // 键
         Ok(Smembers { key })
     }
 
@@ -26,7 +26,7 @@ impl Smembers {
                         for member in set.iter() {
                             members.push(Frame::BulkString(member.clone()));
                         }
-                        Ok(Frame::Array(members))
+                        Ok(Frame::Set(members))
                     },
                     _ => {
                         let f = "ERR Operation against a key holding the wrong kind of value";
@@ -35,7 +35,7 @@ impl Smembers {
                 }
             },
             None => {
-                Ok(Frame::Array(Vec::new()))
+                Ok(Frame::Set(Vec::new()))
             }
         }
     }
@@ -0,0 +1,34 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Smismember {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl Smismember {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'smismember' command"));
+        }
+        let key = args[1].clone();
+        let members = args[2..].to_vec();
+        Ok(Smismember { key, members })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let result = match db.get(&self.key) {
+            Some(Structure::Set(set)) => {
+                self.members.iter().map(|member| Frame::Integer(set.contains(member) as i64)).collect()
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => self.members.iter().map(|_| Frame::Integer(0)).collect(),
+        };
+        Ok(Frame::Array(result))
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::Error;
+use crate::{store::{db::{Db, Structure}, set::Set}, frame::Frame};
+
+pub struct Smove {
+    source: Vec<u8>,
+    destination: Vec<u8>,
+    member: Vec<u8>,
+}
+
+impl Smove {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'smove' command"));
+        }
+        Ok(Smove {
+            source: args[1].clone(),
+            destination: args[2].clone(),
+            member: args[3].clone(),
+        })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let f = "ERR Operation against a key holding the wrong kind of value";
+
+        // 先检查目标集合的类型，避免移除后无法写入
+        match db.get(&self.destination) {
+            Some(Structure::Set(_)) | None => {},
+            Some(_) => return Ok(Frame::Error(f.to_string())),
+        }
+
+        let removed = match db.get_mut(&self.source) {
+            Some(Structure::Set(set)) => {
+                if self.source == self.destination {
+                    return Ok(Frame::Integer(set.contains(&self.member) as i64));
+                }
+                set.remove(&self.member)
+            },
+            Some(_) => return Ok(Frame::Error(f.to_string())),
+            None => false,
+        };
+        if !removed {
+            return Ok(Frame::Integer(0));
+        }
+        db.remove_if_empty(&self.source);
+
+        match db.get_mut(&self.destination) {
+            Some(Structure::Set(set)) => {
+                set.insert(self.member);
+            },
+            _ => {
+                db.insert(self.destination, Structure::Set(Set::from([self.member])));
+            }
+        }
+        Ok(Frame::Integer(1))
+    }
+}
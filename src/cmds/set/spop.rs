@@ -1,9 +1,9 @@
 use anyhow::Error;
-use crate::{store::db::{Db, Structure}, frame::Frame};
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::random};
 
 pub struct Spop {
-    key: String,
-    count: Option<usize>,
+    key: Vec<u8>,
+    count: Option<usize>, // 弹出的成员数量，指定时返回数组
 }
 
 impl Spop {
@@ -16,11 +16,11 @@ impl Spop {
             return Err(Error::msg("ERR wrong number of arguments for 'spop' command"));
         }
 
-        let key = args[1].to_string(); // 键
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
         let count = if args.len() == 3 {
-            match args[2].parse::<usize>() {
-                Ok(c) => Some(c),
-                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            match args[2].parse::<i64>() {
+                Ok(c) if c >= 0 => Some(c as usize),
+                _ => return Err(Error::msg("ERR value is out of range, must be positive")),
             }
         } else {
             None
@@ -29,40 +29,57 @@ impl Spop {
         Ok(Spop { key, count })
     }
 
+    /**
+     * 将命令帧改写为删除实际弹出的成员，避免重放时随机选出不同的成员。
+     * 弹出全部成员时 SREM 同样会删除空集合
+     *
+     * @param frame 客户端发送的命令帧
+     * @param reply 执行结果
+     */
+    pub fn resolve_frame(frame: Frame, reply: &Frame) -> Frame {
+        let members = match reply {
+            Frame::BulkString(member) => vec![member.clone()],
+            Frame::Array(members) => members.iter().filter_map(|member| match member {
+                Frame::BulkString(member) => Some(member.clone()),
+                _ => None,
+            }).collect(),
+            _ => Vec::new(),
+        };
+        let key = match frame.get_arg_bytes(1) {
+            Some(key) if !members.is_empty() => key,
+            _ => return frame,
+        };
+        let mut args = vec![b"SREM".to_vec(), key];
+        args.extend(members);
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.records.get_mut(&self.key) {
-            Some(structure) => {
-                match structure {
-                    Structure::Set(set) => {
-                        if set.is_empty() {
-                            Ok(Frame::Null)
-                        } else {
-                            let pop_count = self.count.unwrap_or(1);
-                            let mut popped_members = Vec::new();
-                            for _ in 0..pop_count {
-                                if let Some(member) = set.iter().next().cloned() {
-                                    set.remove(&member);
-                                    popped_members.push(Frame::BulkString(member));
-                                } else {
-                                    break;
-                                }
-                            }
-                            if pop_count == 1 {
-                                Ok(popped_members.pop().unwrap_or(Frame::Null))
-                            } else {
-                                Ok(Frame::Array(popped_members))
-                            }
-                        }
-                    },
-                    _ => {
-                        let f = "ERR Operation against a key holding the wrong kind of value";
-                        Ok(Frame::Error(f.to_string()))
-                    }
+        let result = match db.get_mut(&self.key) {
+            Some(Structure::Set(set)) => {
+                let count = self.count.unwrap_or(1);
+                let members = if count >= set.len() {
+                    set.take().into_iter().collect::<Vec<_>>()
+                } else {
+                    random::choose_multiple(set.iter(), set.len(), count).into_iter().cloned().collect::<Vec<_>>()
+                };
+                for member in &members {
+                    set.remove(member);
+                }
+                match self.count {
+                    Some(_) => Frame::Array(members.into_iter().map(Frame::BulkString).collect()),
+                    None => members.into_iter().next().map(Frame::BulkString).unwrap_or(Frame::Null),
                 }
             },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
             None => {
-                Ok(Frame::Null)
+                return Ok(if self.count.is_some() { Frame::Array(vec![]) } else { Frame::Null });
             }
-        }
+        };
+        db.remove_if_empty(&self.key);
+        Ok(result)
     }
-}
\ No newline at end of file
+}
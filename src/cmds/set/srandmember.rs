@@ -0,0 +1,59 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame, tools::random};
+
+pub struct Srandmember {
+    key: Vec<u8>,
+    count: Option<i64>, // 正数返回不重复的成员，负数允许重复
+}
+
+impl Srandmember {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 || args.len() > 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'srandmember' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let count = match args.get(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count > i64::MIN / 2 && count < i64::MAX / 2 => Some(count),
+                Ok(_) => return Err(Error::msg("ERR value is out of range")),
+                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            },
+            None => None,
+        };
+        Ok(Srandmember { key, count })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let set = match db.get(&self.key) {
+            Some(Structure::Set(set)) => set,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                return Ok(if self.count.is_some() { Frame::Array(vec![]) } else { Frame::Null });
+            }
+        };
+
+        match self.count {
+            None => {
+                let member = set.iter().nth(random::index(set.len())).unwrap();
+                Ok(Frame::BulkString(member.clone()))
+            },
+            Some(count) if count >= 0 => {
+                let members = random::choose_multiple(set.iter(), set.len(), count as usize);
+                Ok(Frame::Array(members.into_iter().map(|member| Frame::BulkString(member.clone())).collect()))
+            },
+            Some(count) => {
+                // 允许重复时先收集成员，避免每次抽取都遍历集合
+                let members = set.iter().collect::<Vec<_>>();
+                let result = (0..count.unsigned_abs())
+                    .map(|_| Frame::BulkString(members[random::index(members.len())].clone()))
+                    .collect();
+                Ok(Frame::Array(result))
+            }
+        }
+    }
+}
@@ -2,22 +2,22 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Srem {
-    key: String,
-    members: Vec<String>,
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
 }
 
 impl Srem {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
 
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'srem' command"));
         }
 
-        let key = args[1].to_string(); // 键
-        let members = args[2..].iter().map(|arg| arg.to_string()).collect(); // 要移除的成员
+        let key = args[1].clone(); // 键
+        let members = args[2..].to_vec(); // 要移除的成员
 
         Ok(Srem { key, members })
     }
@@ -0,0 +1,44 @@
+use anyhow::Error;
+
+use crate::{cmds::key::scan::{scan_reply, ScanArgs}, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * SSCAN key cursor [MATCH pattern] [COUNT count]
+ *
+ * 以游标分批遍历集合的成员
+ */
+pub struct Sscan {
+    key: Vec<u8>,
+    args: ScanArgs,
+}
+
+impl Sscan {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes_from_index(2);
+        let key = frame.get_arg_bytes(1);
+        if key.is_none() || args.is_empty() {
+            return Err(Error::msg("ERR wrong number of arguments for 'sscan' command"));
+        }
+        let key = key.unwrap(); // 键
+        Ok(Sscan { key, args: ScanArgs::parse(&args, false)? })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::Set(set)) => {
+                let (cursor, members) = set.scan(self.args.cursor, self.args.count);
+                let result = members.into_iter()
+                    .filter(|member| self.args.is_match(member))
+                    .map(|member| Frame::BulkString(member.to_vec()))
+                    .collect();
+                Ok(scan_reply(cursor, result))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(scan_reply(0, Vec::new())),
+        }
+    }
+}
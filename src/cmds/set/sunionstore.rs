@@ -4,22 +4,22 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Sunionstore {
-    destination: String,
-    keys: Vec<String>,
+    destination: Vec<u8>,
+    keys: Vec<Vec<u8>>,
 }
 
 impl Sunionstore {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
 
         // 至少需要三个参数（命令名、目标集合键、一个或多个源集合键）
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'sunionstore' command"));
         }
 
-        let destination = args[1].to_string();
-        let keys = args[2..].iter().map(|arg| arg.to_string()).collect();
+        let destination = args[1].clone();
+        let keys = args[2..].to_vec();
 
         Ok(Sunionstore { destination, keys })
     }
@@ -46,7 +46,8 @@ impl Sunionstore {
                 }
             }
         }
-        db.records.insert(destination, Structure::Set(result_set.clone()));
-        Ok(Frame::Integer(result_set.len() as i64))
+        let len = result_set.len() as i64;
+        db.insert(destination, Structure::Set(result_set.into()));
+        Ok(Frame::Integer(len))
     }
 }
\ No newline at end of file
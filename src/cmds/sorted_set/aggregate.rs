@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
+
+/**
+ * 有序集合运算
+ */
+#[derive(Clone, Copy, PartialEq)]
+pub enum Operation {
+    Union,
+    Inter,
+    Diff,
+}
+
+/**
+ * 成员出现在多个集合中时分数的聚合方式
+ */
+#[derive(Clone, Copy)]
+pub enum Aggregate {
+    Sum,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            // +inf 与 -inf 相加得到 NaN，与 Redis 一致按 0 处理
+            Aggregate::Sum => zero_if_nan(a + b),
+            Aggregate::Min => a.min(b),
+            Aggregate::Max => a.max(b),
+        }
+    }
+}
+
+fn zero_if_nan(value: f64) -> f64 {
+    if value.is_nan() { 0.0 } else { value }
+}
+
+/**
+ * ZUNION、ZINTER、ZDIFF 及其 STORE 形式的参数
+ *
+ * @param operation 运算类型
+ * @param keys 输入键，可以是有序集合或集合（集合成员的分数视为 1）
+ * @param weights 每个输入键的权重
+ * @param aggregate 聚合方式
+ * @param with_scores 是否返回分数
+ */
+pub struct Combination {
+    operation: Operation,
+    keys: Vec<Vec<u8>>,
+    weights: Vec<f64>,
+    aggregate: Aggregate,
+    with_scores: bool,
+}
+
+impl Combination {
+
+    /**
+     * 解析 numkeys key [key ...] [WEIGHTS weight ...] [AGGREGATE SUM|MIN|MAX] [WITHSCORES]
+     *
+     * @param args 从 numkeys 开始的参数
+     * @param command 命令名称（小写）
+     */
+    pub fn parse(args: &[Vec<u8>], command: &str) -> Result<Self, Error> {
+        let operation = if command.starts_with("zinter") {
+            Operation::Inter
+        } else if command.starts_with("zdiff") {
+            Operation::Diff
+        } else {
+            Operation::Union
+        };
+        let store = command.ends_with("store");
+
+        let numkeys = match String::from_utf8_lossy(&args[0]).parse::<i64>() {
+            Ok(n) if n > 0 => n as usize,
+            Ok(_) => return Err(Error::msg(format!("ERR at least 1 input key is needed for '{}' command", command))),
+            Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+        };
+        if numkeys > args.len() - 1 {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        let keys = args[1..=numkeys].to_vec();
+
+        let mut weights = vec![1.0; numkeys];
+        let mut aggregate = Aggregate::Sum;
+        let mut with_scores = false;
+        let mut index = 1 + numkeys;
+        while index < args.len() {
+            let option = String::from_utf8_lossy(&args[index]).to_uppercase();
+            match option.as_str() {
+                "WEIGHTS" if operation != Operation::Diff && index + numkeys < args.len() => {
+                    for (i, weight) in weights.iter_mut().enumerate() {
+                        *weight = String::from_utf8_lossy(&args[index + 1 + i]).parse::<f64>()
+                            .ok()
+                            .filter(|weight| !weight.is_nan())
+                            .ok_or_else(|| Error::msg("ERR weight value is not a float"))?;
+                    }
+                    index += numkeys;
+                },
+                "AGGREGATE" if operation != Operation::Diff && index + 1 < args.len() => {
+                    aggregate = match String::from_utf8_lossy(&args[index + 1]).to_uppercase().as_str() {
+                        "SUM" => Aggregate::Sum,
+                        "MIN" => Aggregate::Min,
+                        "MAX" => Aggregate::Max,
+                        _ => return Err(Error::msg("ERR syntax error")),
+                    };
+                    index += 1;
+                },
+                "WITHSCORES" if !store => with_scores = true,
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 1;
+        }
+
+        Ok(Combination { operation, keys, weights, aggregate, with_scores })
+    }
+
+    pub fn with_scores(&self) -> bool {
+        self.with_scores
+    }
+
+    /**
+     * 计算运算结果，不存在的键视为空集合
+     *
+     * @param db 数据库
+     * @return 任一键的类型不是有序集合或集合时返回错误帧
+     */
+    pub fn compute(&self, db: &mut Db) -> Result<SortedSet, Frame> {
+        // 先读取所有输入，保证类型错误总能被发现
+        let mut inputs = Vec::with_capacity(self.keys.len());
+        for (key, weight) in self.keys.iter().zip(&self.weights) {
+            let scores = match db.get(key) {
+                Some(Structure::SortedSet(set)) => {
+                    set.iter().map(|(member, score)| (member.to_vec(), zero_if_nan(score * weight))).collect()
+                },
+                Some(Structure::Set(set)) => {
+                    set.iter().map(|member| (member.clone(), zero_if_nan(*weight))).collect()
+                },
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Err(Frame::Error(f.to_string()));
+                },
+                None => HashMap::new(),
+            };
+            inputs.push(scores);
+        }
+
+        let mut inputs = inputs.into_iter();
+        let mut result: HashMap<Vec<u8>, f64> = inputs.next().unwrap_or_default();
+        for scores in inputs {
+            match self.operation {
+                Operation::Union => {
+                    for (member, score) in scores {
+                        result.entry(member)
+                            .and_modify(|current| *current = self.aggregate.apply(*current, score))
+                            .or_insert(score);
+                    }
+                },
+                Operation::Inter => {
+                    result.retain(|member, current| match scores.get(member) {
+                        Some(score) => {
+                            *current = self.aggregate.apply(*current, *score);
+                            true
+                        },
+                        None => false,
+                    });
+                },
+                Operation::Diff => result.retain(|member, _| !scores.contains_key(member)),
+            }
+        }
+        Ok(result.into_iter().collect())
+    }
+}
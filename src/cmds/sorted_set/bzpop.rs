@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use anyhow::Error;
+
+use crate::{cmds::{listing::blocking::parse_timeout, sorted_set::zpop::pop}, store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
+
+/**
+ * BZPOPMIN 与 BZPOPMAX
+ */
+pub struct Bzpop {
+    keys: Vec<Vec<u8>>,
+    timeout: Option<Duration>, // None 表示永久阻塞
+    max: bool, // 是否弹出分数最高的成员
+}
+
+impl Bzpop {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let keys = args[1..args.len() - 1].to_vec();
+        let timeout = parse_timeout(&String::from_utf8_lossy(&args[args.len() - 1]))?;
+        Ok(Bzpop { keys, timeout, max: command == "bzpopmax" })
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /**
+     * 以非阻塞方式执行，所有有序集合都为空时返回空数组
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        for key in &self.keys {
+            match pop(db, key, self.max, 1) {
+                Ok(mut items) if !items.is_empty() => {
+                    let (member, score) = items.remove(0);
+                    return Ok(Frame::Array(vec![
+                        Frame::BulkString(key.clone()),
+                        Frame::BulkString(member),
+                        Frame::Double(score),
+                    ]));
+                },
+                Ok(_) => continue,
+                Err(f) => return Ok(f),
+            }
+        }
+        Ok(Frame::NullArray)
+    }
+
+    /**
+     * 实际执行的命令，传播到 AOF 与从节点
+     *
+     * @param reply 执行结果
+     * @return 没有弹出成员时返回 None
+     */
+    pub fn propagation_frame(&self, reply: &Frame) -> Option<Frame> {
+        match reply {
+            Frame::Array(items) => match items.first() {
+                Some(Frame::BulkString(key)) => {
+                    let name: &[u8] = if self.max { b"ZPOPMAX" } else { b"ZPOPMIN" };
+                    Some(Frame::Array(vec![Frame::BulkString(name.to_vec()), Frame::BulkString(key.clone())]))
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /**
+     * 撤销执行，把弹出的成员放回有序集合
+     *
+     * @param db 数据库
+     * @param reply 执行结果
+     */
+    pub fn restore(&self, db: &mut Db, reply: Frame) {
+        let (key, member, score) = match reply {
+            Frame::Array(items) => match <[Frame; 3]>::try_from(items) {
+                Ok([Frame::BulkString(key), Frame::BulkString(member), Frame::Double(score)]) => (key, member, score),
+                _ => return,
+            },
+            _ => return,
+        };
+        if !matches!(db.get(&key), Some(Structure::SortedSet(_))) {
+            db.insert(key.clone(), Structure::SortedSet(SortedSet::new()));
+        }
+        if let Some(Structure::SortedSet(set)) = db.get_mut(&key) {
+            set.insert(member, score);
+        }
+    }
+}
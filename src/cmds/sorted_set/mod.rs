@@ -3,4 +3,19 @@ pub mod zadd;
 pub mod zrem;
 pub mod zscore;
 pub mod zcard;
-pub mod zrank;
\ No newline at end of file
+pub mod zrank;
+pub mod range;
+pub mod zrange;
+pub mod zrangestore;
+pub mod zrevrank;
+pub mod zincrby;
+pub mod zmscore;
+pub mod zrandmember;
+pub mod zlexcount;
+pub mod zremrange;
+pub mod zpop;
+pub mod bzpop;
+pub mod aggregate;
+pub mod zunion;
+pub mod zunionstore;
+pub mod zscan;
@@ -0,0 +1,201 @@
+use anyhow::Error;
+
+use crate::{frame::Frame, store::sorted_set::{LexBound, ScoreBound, SortedSet}};
+
+/**
+ * 区间类型
+ */
+pub enum Interval {
+    Rank(i64, i64),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+/**
+ * 有序集合的区间查询
+ *
+ * @param interval 区间，REV 时保存为升序的 (min, max)
+ * @param rev 是否按降序返回
+ * @param with_scores 是否返回分数
+ * @param offset LIMIT 偏移量
+ * @param count LIMIT 数量，负数时返回全部
+ */
+pub struct RangeQuery {
+    interval: Interval,
+    rev: bool,
+    with_scores: bool,
+    offset: i64,
+    count: i64,
+}
+
+/**
+ * 解析排名参数
+ *
+ * @param value 参数
+ */
+pub fn parse_rank(value: &[u8]) -> Result<i64, Error> {
+    String::from_utf8_lossy(value).parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))
+}
+
+/**
+ * 解析分数区间
+ *
+ * @param min 下边界
+ * @param max 上边界
+ */
+pub fn parse_score_range(min: &[u8], max: &[u8]) -> Result<(ScoreBound, ScoreBound), Error> {
+    let parse = |value: &[u8]| ScoreBound::parse(&String::from_utf8_lossy(value)).ok_or_else(|| Error::msg("ERR min or max is not a float"));
+    Ok((parse(min)?, parse(max)?))
+}
+
+/**
+ * 解析字典序区间
+ *
+ * @param min 下边界
+ * @param max 上边界
+ */
+pub fn parse_lex_range(min: &[u8], max: &[u8]) -> Result<(LexBound, LexBound), Error> {
+    let parse = |value: &[u8]| LexBound::parse(value).ok_or_else(|| Error::msg("ERR min or max not valid string range item"));
+    Ok((parse(min)?, parse(max)?))
+}
+
+impl Interval {
+
+    /**
+     * 区间在升序排列中对应的排名区间 [start, end)
+     *
+     * @param set 有序集合
+     * @param rev 排名区间是否按降序计算
+     */
+    pub fn ranks(&self, set: &SortedSet, rev: bool) -> (usize, usize) {
+        match self {
+            Interval::Rank(start, stop) => {
+                let len = set.len() as i64;
+                let start = if *start < 0 { (len + start).max(0) } else { *start };
+                let stop = if *stop < 0 { len + stop } else { (*stop).min(len - 1) };
+                if start > stop || start >= len {
+                    return (0, 0);
+                }
+                // 逆序时排名按降序计算，换算为升序排名
+                if rev {
+                    ((len - 1 - stop) as usize, (len - start) as usize)
+                } else {
+                    (start as usize, stop as usize + 1)
+                }
+            },
+            Interval::Score(min, max) => set.rank_range_by_score(*min, *max),
+            Interval::Lex(min, max) => set.rank_range_by_lex(min, max),
+        }
+    }
+}
+
+impl RangeQuery {
+
+    /**
+     * 解析 ZRANGE 系列命令的区间与选项
+     *
+     * @param args 从 start 开始的参数
+     * @param command 命令名称（小写）
+     */
+    pub fn parse(args: &[Vec<u8>], command: &str) -> Result<Self, Error> {
+        let syntax_error = || Error::msg("ERR syntax error");
+        let (mut by_score, mut by_lex, mut rev) = match command {
+            "zrevrange" => (false, false, true),
+            "zrangebyscore" => (true, false, false),
+            "zrevrangebyscore" => (true, false, true),
+            "zrangebylex" => (false, true, false),
+            "zrevrangebylex" => (false, true, true),
+            _ => (false, false, false),
+        };
+        // 只有统一语法的 ZRANGE 与 ZRANGESTORE 支持 BYSCORE、BYLEX 与 REV 选项
+        let unified = command == "zrange" || command == "zrangestore";
+
+        let mut limit = None;
+        let mut with_scores = false;
+        let mut index = 2;
+        while index < args.len() {
+            match String::from_utf8_lossy(&args[index]).to_uppercase().as_str() {
+                "BYSCORE" if unified => by_score = true,
+                "BYLEX" if unified => by_lex = true,
+                "REV" if unified => rev = true,
+                "WITHSCORES" if command != "zrangestore" && !command.ends_with("bylex") => with_scores = true,
+                "LIMIT" if command != "zrevrange" && index + 2 < args.len() => {
+                    limit = Some((parse_rank(&args[index + 1])?, parse_rank(&args[index + 2])?));
+                    index += 2;
+                },
+                _ => return Err(syntax_error()),
+            }
+            index += 1;
+        }
+
+        if by_score && by_lex {
+            return Err(syntax_error());
+        }
+        if limit.is_some() && !by_score && !by_lex {
+            return Err(Error::msg("ERR syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"));
+        }
+        if with_scores && by_lex {
+            return Err(Error::msg("ERR syntax error, WITHSCORES not supported in combination with BYLEX"));
+        }
+
+        // 按分数或字典序逆序查询时，参数顺序为 max min
+        let (min, max) = if rev && (by_score || by_lex) { (&args[1], &args[0]) } else { (&args[0], &args[1]) };
+        let interval = if by_score {
+            let (min, max) = parse_score_range(min, max)?;
+            Interval::Score(min, max)
+        } else if by_lex {
+            let (min, max) = parse_lex_range(min, max)?;
+            Interval::Lex(min, max)
+        } else {
+            Interval::Rank(parse_rank(min)?, parse_rank(max)?)
+        };
+        let (offset, count) = limit.unwrap_or((0, -1));
+        Ok(RangeQuery { interval, rev, with_scores, offset, count })
+    }
+
+    pub fn with_scores(&self) -> bool {
+        self.with_scores
+    }
+
+    /**
+     * 执行查询
+     *
+     * @param set 有序集合
+     * @return 按请求顺序排列的成员及其分数
+     */
+    pub fn collect(&self, set: &SortedSet) -> Vec<(Vec<u8>, f64)> {
+        let (start, end) = self.interval.ranks(set, self.rev);
+        if self.offset < 0 {
+            return Vec::new();
+        }
+        let offset = self.offset as usize;
+        let count = if self.count < 0 { usize::MAX } else { self.count as usize };
+        let take = (end - start).saturating_sub(offset).min(count);
+        if take == 0 {
+            return Vec::new();
+        }
+        let items = if self.rev {
+            set.iter_rev_from(end - 1 - offset).take(take).collect::<Vec<_>>()
+        } else {
+            set.iter_from(start + offset).take(take).collect::<Vec<_>>()
+        };
+        items.into_iter().map(|(member, score)| (member.to_vec(), score)).collect()
+    }
+}
+
+/**
+ * 将成员转换为回复，WITHSCORES 时成员与分数交替排列
+ *
+ * @param items 成员及其分数
+ * @param with_scores 是否返回分数
+ */
+pub fn to_frame(items: Vec<(Vec<u8>, f64)>, with_scores: bool) -> Frame {
+    let mut frames = Vec::with_capacity(if with_scores { items.len() * 2 } else { items.len() });
+    for (member, score) in items {
+        frames.push(Frame::BulkString(member));
+        if with_scores {
+            frames.push(Frame::Double(score));
+        }
+    }
+    Frame::Array(frames)
+}
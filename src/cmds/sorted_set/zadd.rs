@@ -1,65 +1,135 @@
-use std::collections::BTreeMap;
-
 use anyhow::Error;
 
-use crate::{store::db::{Db, Structure}, frame::Frame};
+use crate::{store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
 
+/**
+ * ZADD key [NX | XX] [GT | LT] [CH] [INCR] score member [score member ...]
+ *
+ * @param nx 只添加新成员，不更新已有成员
+ * @param xx 只更新已有成员，不添加新成员
+ * @param gt 只在新分数大于当前分数时更新
+ * @param lt 只在新分数小于当前分数时更新
+ * @param ch 返回新增与分数被修改的成员总数
+ * @param incr 以增量方式修改分数并返回新分数
+ */
 pub struct Zadd {
-    key: String,
-    members: Vec<(f64, String)>, // 成员及其分数
+    key: Vec<u8>,
+    members: Vec<(f64, Vec<u8>)>, // 成员及其分数
+    nx: bool,
+    xx: bool,
+    gt: bool,
+    lt: bool,
+    ch: bool,
+    incr: bool,
 }
 
 impl Zadd {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
         let args = frame.get_args();
-        if args.len() < 4 || args.len() % 2 != 0 {
+        let bytes = frame.get_args_bytes();
+        if args.len() < 4 {
             return Err(Error::msg("ERR wrong number of arguments for 'zadd' command"));
         }
-        
-        let key = args[1].to_string(); // 键
-        let mut members = Vec::new();
 
-        for chunk in args[2..].chunks(2) {
-            if chunk.len() != 2 {
-                return Err(Error::msg("ERR wrong number of arguments for 'zadd' command"));
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let (mut nx, mut xx, mut gt, mut lt, mut ch, mut incr) = (false, false, false, false, false, false);
+        let mut index = 2;
+        while index < args.len() {
+            match args[index].to_uppercase().as_str() {
+                "NX" => nx = true,
+                "XX" => xx = true,
+                "GT" => gt = true,
+                "LT" => lt = true,
+                "CH" => ch = true,
+                "INCR" => incr = true,
+                _ => break,
             }
-            let score = chunk[0].parse::<f64>().map_err(|_| Error::msg("ERR score is not a valid float"))?;
-            let member = chunk[1].to_string();
+            index += 1;
+        }
+
+        let pairs = args.len() - index;
+        if pairs == 0 || !pairs.is_multiple_of(2) {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        if nx && xx {
+            return Err(Error::msg("ERR XX and NX options at the same time are not compatible"));
+        }
+        if (gt && lt) || (nx && (gt || lt)) {
+            return Err(Error::msg("ERR GT, LT, and/or NX options at the same time are not compatible"));
+        }
+        if incr && pairs > 2 {
+            return Err(Error::msg("ERR INCR option supports a single increment-element pair"));
+        }
+
+        let mut members = Vec::new();
+        for (chunk, raw) in args[index..].chunks(2).zip(bytes[index..].chunks(2)) {
+            let score = chunk[0].parse::<f64>().ok().filter(|score| !score.is_nan()).ok_or_else(|| Error::msg("ERR value is not a valid float"))?;
+            let member = raw[1].clone();
             members.push((score, member));
         }
 
-        Ok(Zadd { key, members })
+        Ok(Zadd { key, members, nx, xx, gt, lt, ch, incr })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        let mut added_count = 0;
+        let set = match db.get_mut(&self.key) {
+            Some(Structure::SortedSet(set)) => set,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                // XX 模式下不会添加成员，无需创建键
+                if self.xx {
+                    return Ok(if self.incr { Frame::Null } else { Frame::Integer(0) });
+                }
+                db.insert(self.key.clone(), Structure::SortedSet(SortedSet::new()));
+                match db.get_mut(&self.key) {
+                    Some(Structure::SortedSet(set)) => set,
+                    _ => unreachable!(),
+                }
+            }
+        };
 
-        match db.records.get_mut(&self.key) {
-            Some(structure) => {
-                match structure {
-                    Structure::SortedSet(set) => {
-                        for (score, member) in self.members {
-                            if set.insert(member, score).is_none() {
-                                added_count += 1; // 成员新增成功
-                            }
-                        }
-                    },
-                    _ => {
-                        let f = "ERR Operation against a key holding the wrong kind of value";
+        let mut added_count = 0;
+        let mut updated_count = 0;
+        let mut result = None; // INCR 模式下的新分数
+        for (score, member) in self.members {
+            match set.score(&member) {
+                Some(current) => {
+                    if self.nx {
+                        continue;
+                    }
+                    let score = if self.incr { current + score } else { score };
+                    if score.is_nan() {
+                        let f = "ERR resulting score is not a number (NaN)";
                         return Ok(Frame::Error(f.to_string()));
                     }
-                }
-            },
-            None => { // 键不存在，创建一个新的有序集合并插入所有成员
-                let mut set = BTreeMap::new();
-                for (score, member) in self.members {
+                    if (self.gt && score <= current) || (self.lt && score >= current) {
+                        continue;
+                    }
+                    if score != current {
+                        set.insert(member, score);
+                        updated_count += 1;
+                    }
+                    result = Some(score);
+                },
+                None => {
+                    if self.xx {
+                        continue;
+                    }
                     set.insert(member, score);
                     added_count += 1; // 成员新增成功
+                    result = Some(score);
                 }
-                db.records.insert(self.key, Structure::SortedSet(set));
             }
         }
+        db.remove_if_empty(&self.key);
 
-        Ok(Frame::Integer(added_count as i64))
+        if self.incr {
+            return Ok(result.map_or(Frame::Null, Frame::Double));
+        }
+        let count = if self.ch { added_count + updated_count } else { added_count };
+        Ok(Frame::Integer(count as i64))
     }
-}
\ No newline at end of file
+}
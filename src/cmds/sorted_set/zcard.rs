@@ -3,21 +3,21 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Zcard {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Zcard {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() != 2 {
             return Err(Error::msg("ERR wrong number of arguments for 'zcard' command"));
         }
-        let key = args[1].to_string(); // 键
+        let key = args[1].clone(); // 键
         Ok(Zcard { key })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.records.get(&self.key) {
+        match db.get(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::SortedSet(set) => {
@@ -0,0 +1,45 @@
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
+
+pub struct Zincrby {
+    key: Vec<u8>,
+    increment: f64,
+    member: Vec<u8>,
+}
+
+impl Zincrby {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zincrby' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let increment = args[2].parse::<f64>().ok().filter(|increment| !increment.is_nan()).ok_or_else(|| Error::msg("ERR value is not a valid float"))?;
+        let member = frame.get_arg_bytes(3).unwrap(); // 成员
+        Ok(Zincrby { key, increment, member })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                let score = set.score(&self.member).unwrap_or(0.0) + self.increment;
+                if score.is_nan() {
+                    return Ok(Frame::Error("ERR resulting score is not a number (NaN)".to_string()));
+                }
+                set.insert(self.member, score);
+                Ok(Frame::Double(score))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => {
+                let mut set = SortedSet::new();
+                set.insert(self.member, self.increment);
+                db.insert(self.key, Structure::SortedSet(set));
+                Ok(Frame::Double(self.increment))
+            }
+        }
+    }
+}
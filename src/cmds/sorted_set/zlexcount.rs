@@ -0,0 +1,35 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::parse_lex_range, store::{db::{Db, Structure}, sorted_set::LexBound}, frame::Frame};
+
+pub struct Zlexcount {
+    key: Vec<u8>,
+    min: LexBound,
+    max: LexBound,
+}
+
+impl Zlexcount {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zlexcount' command"));
+        }
+        let key = args[1].clone(); // 键
+        let (min, max) = parse_lex_range(&args[2], &args[3])?;
+        Ok(Zlexcount { key, min, max })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                let (start, end) = set.rank_range_by_lex(&self.min, &self.max);
+                Ok(Frame::Integer((end - start) as i64))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
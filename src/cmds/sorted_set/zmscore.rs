@@ -0,0 +1,34 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Zmscore {
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
+}
+
+impl Zmscore {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zmscore' command"));
+        }
+        let key = args[1].clone(); // 键
+        let members = args[2..].to_vec(); // 成员
+        Ok(Zmscore { key, members })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let result = match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                self.members.iter().map(|member| set.score(member).map_or(Frame::Null, Frame::Double)).collect()
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => self.members.iter().map(|_| Frame::Null).collect(),
+        };
+        Ok(Frame::Array(result))
+    }
+}
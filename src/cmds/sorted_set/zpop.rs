@@ -0,0 +1,62 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::to_frame, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * ZPOPMIN 与 ZPOPMAX
+ */
+pub struct Zpop {
+    key: Vec<u8>,
+    count: Option<usize>,
+    max: bool, // 是否弹出分数最高的成员
+}
+
+impl Zpop {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 || args.len() > 3 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let count = match args.get(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count >= 0 => Some(count as usize),
+                _ => return Err(Error::msg("ERR value is out of range, must be positive")),
+            },
+            None => None,
+        };
+        Ok(Zpop { key, count, max: command == "zpopmax" })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match pop(db, &self.key, self.max, self.count.unwrap_or(1)) {
+            Ok(items) => Ok(to_frame(items, true)),
+            Err(f) => Ok(f),
+        }
+    }
+}
+
+/**
+ * 弹出分数最低或最高的成员，集合为空时删除键
+ *
+ * @param db 数据库
+ * @param key 键
+ * @param max 是否弹出分数最高的成员
+ * @param count 最多弹出的成员数量
+ */
+pub fn pop(db: &mut Db, key: &[u8], max: bool, count: usize) -> Result<Vec<(Vec<u8>, f64)>, Frame> {
+    let items = match db.get_mut(key) {
+        Some(Structure::SortedSet(set)) => {
+            let count = count.min(set.len());
+            (0..count).filter_map(|_| set.pop(max)).collect()
+        },
+        Some(_) => {
+            let f = "ERR Operation against a key holding the wrong kind of value";
+            return Err(Frame::Error(f.to_string()));
+        },
+        None => Vec::new(),
+    };
+    db.remove_if_empty(key);
+    Ok(items)
+}
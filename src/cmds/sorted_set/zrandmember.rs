@@ -0,0 +1,62 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::to_frame, store::db::{Db, Structure}, frame::Frame, tools::random};
+
+pub struct Zrandmember {
+    key: Vec<u8>,
+    count: Option<i64>, // 正数返回不重复的成员，负数允许重复
+    with_scores: bool,
+}
+
+impl Zrandmember {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 || args.len() > 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zrandmember' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let count = match args.get(2) {
+            Some(count) => match count.parse::<i64>() {
+                Ok(count) if count > i64::MIN / 2 && count < i64::MAX / 2 => Some(count),
+                Ok(_) => return Err(Error::msg("ERR value is out of range")),
+                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            },
+            None => None,
+        };
+        let with_scores = match args.get(3) {
+            Some(option) if option.eq_ignore_ascii_case("WITHSCORES") => true,
+            Some(_) => return Err(Error::msg("ERR syntax error")),
+            None => false,
+        };
+        Ok(Zrandmember { key, count, with_scores })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let set = match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => set,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                return Ok(if self.count.is_some() { Frame::Array(vec![]) } else { Frame::Null });
+            }
+        };
+
+        // 按排名随机抽取，每次定位的代价为 O(log n)
+        let indexes = match self.count {
+            None => {
+                let (member, _) = set.get_by_rank(random::index(set.len())).unwrap();
+                return Ok(Frame::BulkString(member.to_vec()));
+            },
+            Some(count) if count >= 0 => random::sample(set.len(), count as usize),
+            Some(count) => (0..count.unsigned_abs()).map(|_| random::index(set.len())).collect(),
+        };
+        let items = indexes.into_iter()
+            .filter_map(|index| set.get_by_rank(index))
+            .map(|(member, score)| (member.to_vec(), score))
+            .collect();
+        Ok(to_frame(items, self.with_scores))
+    }
+}
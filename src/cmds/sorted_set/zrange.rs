@@ -0,0 +1,35 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::{to_frame, RangeQuery}, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * ZRANGE 及其旧版变体 ZREVRANGE、ZRANGEBYSCORE、ZREVRANGEBYSCORE、ZRANGEBYLEX、ZREVRANGEBYLEX
+ */
+pub struct Zrange {
+    key: Vec<u8>,
+    query: RangeQuery,
+}
+
+impl Zrange {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = args[1].clone(); // 键
+        let query = RangeQuery::parse(&args[2..], command)?;
+        Ok(Zrange { key, query })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => Ok(to_frame(self.query.collect(set), self.query.with_scores())),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Array(vec![])),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::RangeQuery, store::{db::{Db, Structure}, sorted_set::SortedSet}, frame::Frame};
+
+pub struct Zrangestore {
+    destination: Vec<u8>,
+    source: Vec<u8>,
+    query: RangeQuery,
+}
+
+impl Zrangestore {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zrangestore' command"));
+        }
+        let destination = args[1].clone(); // 目标键
+        let source = args[2].clone(); // 源键
+        let query = RangeQuery::parse(&args[3..], "zrangestore")?;
+        Ok(Zrangestore { destination, source, query })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let items = match db.get(&self.source) {
+            Some(Structure::SortedSet(set)) => self.query.collect(set),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => Vec::new(),
+        };
+
+        // 结果为空时删除目标键
+        let count = items.len();
+        if count == 0 {
+            db.remove(&self.destination);
+        } else {
+            db.insert(self.destination, Structure::SortedSet(items.into_iter().collect::<SortedSet>()));
+        }
+        Ok(Frame::Integer(count as i64))
+    }
+}
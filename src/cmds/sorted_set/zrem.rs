@@ -2,23 +2,23 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Zrem {
-    key: String,
-    members: Vec<String>,
+    key: Vec<u8>,
+    members: Vec<Vec<u8>>,
 }
 
 impl Zrem {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'zrem' command"));
         }
-        let key = args[1].to_string(); // 键
-        let members = args[2..].iter().map(|arg| arg.to_string()).collect(); // 要移除的成员
+        let key = args[1].clone(); // 键
+        let members = args[2..].to_vec(); // 要移除的成员
         Ok(Zrem { key, members })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.records.get_mut(&self.key) {
+        match db.get_mut(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::SortedSet(set) => {
@@ -28,6 +28,7 @@ impl Zrem {
                                 removed_count += 1;
                             }
                         }
+                        db.remove_if_empty(&self.key);
                         Ok(Frame::Integer(removed_count as i64))
                     },
                     _ => {
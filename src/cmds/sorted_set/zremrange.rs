@@ -0,0 +1,50 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::range::{parse_lex_range, parse_rank, parse_score_range, Interval}, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * ZREMRANGEBYRANK、ZREMRANGEBYSCORE 与 ZREMRANGEBYLEX
+ */
+pub struct Zremrange {
+    key: Vec<u8>,
+    interval: Interval,
+}
+
+impl Zremrange {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 4 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = args[1].clone(); // 键
+        let interval = match command {
+            "zremrangebyscore" => {
+                let (min, max) = parse_score_range(&args[2], &args[3])?;
+                Interval::Score(min, max)
+            },
+            "zremrangebylex" => {
+                let (min, max) = parse_lex_range(&args[2], &args[3])?;
+                Interval::Lex(min, max)
+            },
+            _ => Interval::Rank(parse_rank(&args[2])?, parse_rank(&args[3])?),
+        };
+        Ok(Zremrange { key, interval })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let removed = match db.get_mut(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                let (start, end) = self.interval.ranks(set, false);
+                set.remove_range(start, end)
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => 0,
+        };
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Zrevrank {
+    key: Vec<u8>,
+    member: Vec<u8>,
+}
+
+impl Zrevrank {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'zrevrank' command"));
+        }
+        let key = args[1].clone(); // 键
+        let member = args[2].clone(); // 成员
+        Ok(Zrevrank { key, member })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::SortedSet(set)) => {
+                // 计算排名（从大到小），成员不存在时返回 nil
+                match set.rev_rank(&self.member) {
+                    Some(rank) => Ok(Frame::Integer(rank as i64)),
+                    None => Ok(Frame::Null),
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Null),
+        }
+    }
+}
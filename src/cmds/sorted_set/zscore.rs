@@ -3,28 +3,28 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Zscore {
-    key: String,
-    member: String,
+    key: Vec<u8>,
+    member: Vec<u8>,
 }
 
 impl Zscore {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'zscore' command"));
         }
-        let key = args[1].to_string(); // 键
-        let member = args[2].to_string(); // 成员
+        let key = args[1].clone(); // 键
+        let member = args[2].clone(); // 成员
         Ok(Zscore { key, member })
     }
 
     pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
-        match db.records.get(&self.key) {
+        match db.get(&self.key) {
             Some(structure) => {
                 match structure {
                     Structure::SortedSet(set) => {
-                        if let Some(score) = set.get(&self.member) {
-                            Ok(Frame::BulkString(score.to_string()))
+                        if let Some(score) = set.score(&self.member) {
+                            Ok(Frame::Double(score))
                         } else {
                             Ok(Frame::Null)
                         }
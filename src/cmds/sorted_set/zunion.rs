@@ -0,0 +1,32 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::{aggregate::Combination, range::to_frame}, store::db::Db, frame::Frame};
+
+/**
+ * ZUNION、ZINTER 与 ZDIFF
+ */
+pub struct Zunion {
+    combination: Combination,
+}
+
+impl Zunion {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let combination = Combination::parse(&args[1..], command)?;
+        Ok(Zunion { combination })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match self.combination.compute(db) {
+            Ok(set) => {
+                let items = set.iter().map(|(member, score)| (member.to_vec(), score)).collect();
+                Ok(to_frame(items, self.combination.with_scores()))
+            },
+            Err(f) => Ok(f),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Error;
+
+use crate::{cmds::sorted_set::aggregate::Combination, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * ZUNIONSTORE、ZINTERSTORE 与 ZDIFFSTORE
+ */
+pub struct Zunionstore {
+    destination: Vec<u8>,
+    combination: Combination,
+}
+
+impl Zunionstore {
+
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let destination = args[1].clone(); // 目标键
+        let combination = Combination::parse(&args[2..], command)?;
+        Ok(Zunionstore { destination, combination })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match self.combination.compute(db) {
+            Ok(set) => {
+                // 结果为空时删除目标键
+                let len = set.len() as i64;
+                if set.is_empty() {
+                    db.remove(&self.destination);
+                } else {
+                    db.insert(self.destination, Structure::SortedSet(set));
+                }
+                Ok(Frame::Integer(len))
+            },
+            Err(f) => Ok(f),
+        }
+    }
+}
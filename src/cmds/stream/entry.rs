@@ -0,0 +1,64 @@
+use anyhow::Error;
+
+use crate::{frame::Frame, store::stream::{Fields, StreamId}};
+
+pub const INVALID_ID: &str = "ERR Invalid stream ID specified as stream command argument";
+
+/**
+ * 解析完整或省略序号的消息 ID
+ *
+ * @param id ID 参数
+ * @param default_seq 省略序号时使用的序号
+ */
+pub fn parse_id(id: &str, default_seq: u64) -> Result<StreamId, Error> {
+    StreamId::parse(id, default_seq).ok_or_else(|| Error::msg(INVALID_ID))
+}
+
+/**
+ * 解析区间一端的 ID
+ *
+ * @param id ID 参数
+ * @param is_start 是否为区间起点
+ * @return 不包含的端点已是极值时区间为空，返回 None
+ */
+pub fn parse_bound(id: &str, is_start: bool) -> Result<Option<StreamId>, Error> {
+    match id {
+        "-" => Ok(Some(StreamId::MIN)),
+        "+" => Ok(Some(StreamId::MAX)),
+        _ => {
+            let default_seq = if is_start { 0 } else { u64::MAX };
+            match id.strip_prefix('(') {
+                Some(id) => {
+                    let id = parse_id(id, default_seq)?;
+                    Ok(if is_start { id.next() } else { id.prev() })
+                },
+                None => parse_id(id, default_seq).map(Some),
+            }
+        }
+    }
+}
+
+/**
+ * 单条消息的回复：[ID, [字段, 值, ...]]
+ *
+ * @param id 消息 ID
+ * @param fields 字段与值
+ */
+pub fn entry_to_frame(id: &StreamId, fields: &Fields) -> Frame {
+    let fields = fields.iter().flat_map(|(field, value)| [Frame::BulkString(field.clone()), Frame::BulkString(value.clone())]).collect();
+    Frame::Array(vec![id_to_frame(id), Frame::Array(fields)])
+}
+
+pub fn id_to_frame(id: &StreamId) -> Frame {
+    Frame::BulkString(id.to_string().into_bytes())
+}
+
+/**
+ * 键或消费者组不存在时的错误信息
+ *
+ * @param key 键
+ * @param group 组名
+ */
+pub fn no_such_group(key: &[u8], group: &[u8]) -> String {
+    format!("NOGROUP No such key '{}' or consumer group '{}'", String::from_utf8_lossy(key), String::from_utf8_lossy(group))
+}
@@ -0,0 +1,15 @@
+pub mod entry;
+pub mod trim;
+pub mod xadd;
+pub mod xrange;
+pub mod xread;
+pub mod xlen;
+pub mod xdel;
+pub mod xtrim;
+pub mod xgroup;
+pub mod xreadgroup;
+pub mod xack;
+pub mod xpending;
+pub mod xclaim;
+pub mod xautoclaim;
+pub mod xinfo;
@@ -0,0 +1,95 @@
+use anyhow::Error;
+
+use crate::{cmds::stream::entry::parse_id, store::stream::{Stream, TrimStrategy}};
+
+// 近似裁剪时默认最多删除的消息数量
+const DEFAULT_LIMIT: usize = 10000;
+
+/**
+ * XADD 与 XTRIM 的裁剪条件：MAXLEN | MINID [= | ~] threshold [LIMIT count]
+ *
+ * 近似裁剪（~）同样精确地删除消息，但每次最多删除 limit 条，避免一次删除过多阻塞数据库
+ *
+ * @param limit 最多删除的数量，usize::MAX 表示不限制
+ */
+#[derive(Clone, Copy)]
+pub struct Trim {
+    strategy: TrimStrategy,
+    limit: usize,
+}
+
+impl Trim {
+
+    /**
+     * 从指定位置解析裁剪条件
+     *
+     * @param args 命令参数
+     * @param index MAXLEN 或 MINID 所在的位置
+     * @return (裁剪条件, 裁剪条件之后的位置)
+     */
+    pub fn parse(args: &[String], index: usize) -> Result<(Self, usize), Error> {
+        let syntax_error = || Error::msg("ERR syntax error");
+        let kind = args[index].to_uppercase();
+        let mut index = index + 1;
+        let mut approximate = false;
+        match args.get(index).map(|arg| arg.as_str()) {
+            Some("~") => {
+                approximate = true;
+                index += 1;
+            },
+            Some("=") => index += 1,
+            _ => {},
+        }
+
+        let threshold = args.get(index).ok_or_else(syntax_error)?;
+        let strategy = match kind.as_str() {
+            "MAXLEN" => match threshold.parse::<i64>() {
+                Ok(max_len) if max_len >= 0 => TrimStrategy::MaxLen(max_len as u64),
+                Ok(_) => return Err(Error::msg("ERR The MAXLEN argument must be >= 0.")),
+                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            },
+            _ => TrimStrategy::MinId(parse_id(threshold, 0)?),
+        };
+        index += 1;
+
+        let mut limit = if approximate { DEFAULT_LIMIT } else { usize::MAX };
+        if args.get(index).is_some_and(|arg| arg.eq_ignore_ascii_case("LIMIT")) {
+            if !approximate {
+                return Err(Error::msg("ERR syntax error, LIMIT cannot be used without the special ~ option"));
+            }
+            limit = match args.get(index + 1).ok_or_else(syntax_error)?.parse::<i64>() {
+                Ok(0) => usize::MAX,
+                Ok(limit) if limit > 0 => limit as usize,
+                Ok(_) => return Err(Error::msg("ERR The LIMIT argument must be >= 0.")),
+                Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+            };
+            index += 2;
+        }
+        Ok((Trim { strategy, limit }, index))
+    }
+
+    /**
+     * 裁剪流
+     *
+     * @return 删除的消息数量
+     */
+    pub fn apply(&self, stream: &mut Stream) -> usize {
+        stream.trim(self.strategy, self.limit)
+    }
+
+    /**
+     * 转换为用于传播的参数
+     */
+    pub fn to_args(&self) -> Vec<Vec<u8>> {
+        let mut args = match self.strategy {
+            TrimStrategy::MaxLen(max_len) => vec![b"MAXLEN".to_vec(), b"=".to_vec(), max_len.to_string().into_bytes()],
+            TrimStrategy::MinId(min_id) => vec![b"MINID".to_vec(), b"=".to_vec(), min_id.to_string().into_bytes()],
+        };
+        if self.limit != usize::MAX {
+            args[1] = b"~".to_vec();
+            args.push(b"LIMIT".to_vec());
+            args.push(self.limit.to_string().into_bytes());
+        }
+        args
+    }
+}
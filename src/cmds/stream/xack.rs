@@ -0,0 +1,44 @@
+use anyhow::Error;
+
+use crate::{cmds::stream::entry::parse_id, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+/**
+ * XACK key group id [id ...]
+ *
+ * 确认消息，将其从消费者组的待确认列表中移除
+ */
+pub struct Xack {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    ids: Vec<StreamId>,
+}
+
+impl Xack {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xack' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let group = frame.get_arg_bytes(2).unwrap(); // 组名
+        let ids = args[3..].iter().map(|id| parse_id(id, 0)).collect::<Result<Vec<_>, _>>()?; // 消息 ID
+        Ok(Xack { key, group, ids })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => match stream.group_mut(&self.group) {
+                Some(group) => {
+                    let acked = self.ids.iter().filter(|id| group.ack(id)).count();
+                    Ok(Frame::Integer(acked as i64))
+                },
+                None => Ok(Frame::Integer(0)),
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
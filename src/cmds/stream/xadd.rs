@@ -0,0 +1,168 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Error;
+
+use crate::{cmds::stream::{entry::parse_id, trim::Trim}, store::{db::{Db, Structure}, stream::{Fields, Stream, StreamId}}, frame::Frame};
+
+/**
+ * 新消息的 ID
+ */
+#[derive(Clone, Copy)]
+enum NewId {
+    Auto, // *
+    AutoSeq(u64), // ms-*
+    Explicit(StreamId), // ms-seq
+}
+
+/**
+ * XADD key [NOMKSTREAM] [MAXLEN | MINID [= | ~] threshold [LIMIT count]] * | id field value [field value ...]
+ *
+ * @param nomkstream 流不存在时不创建
+ */
+pub struct Xadd {
+    key: Vec<u8>,
+    id: NewId,
+    fields: Fields,
+    trim: Option<Trim>,
+    nomkstream: bool,
+}
+
+impl Xadd {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 5 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xadd' command"));
+        }
+        let key = bytes[1].clone(); // 键
+
+        let mut nomkstream = false;
+        let mut trim = None;
+        let mut index = 2;
+        while index < args.len() {
+            match args[index].to_uppercase().as_str() {
+                "NOMKSTREAM" => {
+                    nomkstream = true;
+                    index += 1;
+                },
+                "MAXLEN" | "MINID" => {
+                    let (parsed, next) = Trim::parse(&args, index)?;
+                    trim = Some(parsed);
+                    index = next;
+                },
+                _ => break,
+            }
+        }
+
+        let id = match args.get(index).ok_or_else(|| Error::msg("ERR syntax error"))?.as_str() {
+            "*" => NewId::Auto,
+            id => match id.strip_suffix("-*") {
+                Some(ms) => NewId::AutoSeq(ms.parse().map_err(|_| Error::msg("ERR Invalid stream ID specified as stream command argument"))?),
+                None => {
+                    let id = parse_id(id, 0)?;
+                    if id == StreamId::MIN {
+                        return Err(Error::msg("ERR The ID specified in XADD must be greater than 0-0"));
+                    }
+                    NewId::Explicit(id)
+                }
+            }
+        };
+        index += 1;
+
+        let values = &bytes[index..];
+        if values.is_empty() || !values.len().is_multiple_of(2) {
+            return Err(Error::msg("ERR wrong number of arguments for 'xadd' command"));
+        }
+        let fields = values.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect();
+        Ok(Xadd { key, id, fields, trim, nomkstream })
+    }
+
+    /**
+     * 转换为用于传播的命令帧，ID 由 resolve_frame 根据执行结果补全
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![b"XADD".to_vec(), self.key.clone()];
+        if self.nomkstream {
+            args.push(b"NOMKSTREAM".to_vec());
+        }
+        if let Some(trim) = &self.trim {
+            args.extend(trim.to_args());
+        }
+        args.push(match self.id {
+            NewId::Auto => b"*".to_vec(),
+            NewId::AutoSeq(ms) => format!("{}-*", ms).into_bytes(),
+            NewId::Explicit(id) => id.to_string().into_bytes(),
+        });
+        for (field, value) in &self.fields {
+            args.push(field.clone());
+            args.push(value.clone());
+        }
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    /**
+     * 将 to_frame 生成的命令帧中的 ID 改写为实际生成的 ID，保证重放 AOF 与从节点得到相同的消息
+     *
+     * @param frame to_frame 生成的命令帧
+     * @param reply 执行结果
+     */
+    pub fn resolve_frame(frame: Frame, reply: &Frame) -> Frame {
+        let id = match reply {
+            Frame::BulkString(id) => id.clone(),
+            _ => return frame,
+        };
+        let mut args = frame.get_args_bytes();
+        let mut index = 2;
+        if args[index].eq_ignore_ascii_case(b"NOMKSTREAM") {
+            index += 1;
+        }
+        if args[index].eq_ignore_ascii_case(b"MAXLEN") || args[index].eq_ignore_ascii_case(b"MINID") {
+            index += 3;
+            if args[index].eq_ignore_ascii_case(b"LIMIT") {
+                index += 2;
+            }
+        }
+        args[index] = id;
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let stream = match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                if self.nomkstream {
+                    return Ok(Frame::Null);
+                }
+                db.insert(self.key.clone(), Structure::Stream(Stream::new()));
+                match db.get_mut(&self.key) {
+                    Some(Structure::Stream(stream)) => stream,
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+        let id = match self.id {
+            NewId::Auto => stream.next_id(now, None),
+            NewId::AutoSeq(ms) => stream.next_id(now, Some(ms)),
+            NewId::Explicit(id) => Some(id),
+        };
+        let id = match id {
+            Some(id) => id,
+            None => return Ok(Frame::Error("ERR The stream has exhausted the last possible ID, unable to add more items".to_string())),
+        };
+        if id <= stream.last_id() {
+            return Ok(Frame::Error("ERR The ID specified in XADD is equal or smaller than the target stream top item".to_string()));
+        }
+
+        stream.append(id, self.fields);
+        if let Some(trim) = &self.trim {
+            trim.apply(stream);
+        }
+        Ok(Frame::BulkString(id.to_string().into_bytes()))
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::{entry::{id_to_frame, no_such_group, parse_bound}, xclaim::{claimed_ids, claimed_to_frame}}}, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+// 默认认领的数量
+const DEFAULT_COUNT: usize = 100;
+
+// 每次最多检查 count 的多少倍条待确认消息
+const ATTEMPTS_FACTOR: usize = 10;
+
+/**
+ * XAUTOCLAIM key group consumer min-idle-time start [COUNT count] [JUSTID]
+ *
+ * 从 start 开始扫描待确认列表，认领空闲时间不小于 min-idle-time 的消息，
+ * 回复 [下一次扫描的起点, 认领的消息, 已被删除的消息 ID]，起点为 0-0 表示扫描完成
+ *
+ * @param start 扫描的起点，None 表示没有可扫描的消息
+ */
+pub struct Xautoclaim {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    min_idle: u64,
+    start: Option<StreamId>,
+    count: usize,
+    justid: bool,
+}
+
+impl Xautoclaim {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 6 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xautoclaim' command"));
+        }
+        let key = bytes[1].clone(); // 键
+        let group = bytes[2].clone(); // 组名
+        let consumer = bytes[3].clone(); // 消费者名称
+        let min_idle = args[4].parse::<i64>().map_err(|_| Error::msg("ERR Invalid min-idle-time argument for XAUTOCLAIM"))?.max(0) as u64;
+        let start = parse_bound(&args[5], true)?;
+
+        let mut count = DEFAULT_COUNT;
+        let mut justid = false;
+        let mut index = 6;
+        while index < args.len() {
+            match (args[index].to_uppercase().as_str(), args.get(index + 1)) {
+                ("JUSTID", _) => justid = true,
+                ("COUNT", Some(value)) => {
+                    count = match value.parse::<i64>() {
+                        Ok(value) if value > 0 => value as usize,
+                        _ => return Err(Error::msg("ERR COUNT must be > 0")),
+                    };
+                    index += 1;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 1;
+        }
+        Ok(Xautoclaim { key, group, consumer, min_idle, start, count, justid })
+    }
+
+    /**
+     * 改写为认领相同消息的 XCLAIM，已被删除的消息一并传入以便从待确认列表中移除
+     *
+     * @param frame 客户端发送的命令帧
+     * @param reply 执行结果
+     */
+    pub fn resolve_frame(frame: Frame, reply: &Frame) -> Frame {
+        let (claimed, deleted) = match reply {
+            Frame::Array(parts) if parts.len() == 3 => (&parts[1], &parts[2]),
+            _ => return frame,
+        };
+        let mut ids = claimed_ids(claimed);
+        ids.extend(claimed_ids(deleted));
+        if ids.is_empty() {
+            return frame;
+        }
+        let args = frame.get_args_bytes();
+        let justid = args.iter().skip(6).any(|arg| arg.eq_ignore_ascii_case(b"JUSTID"));
+        let mut claim = vec![b"XCLAIM".to_vec(), args[1].clone(), args[2].clone(), args[3].clone(), b"0".to_vec()];
+        claim.extend(ids);
+        if justid {
+            claim.push(b"JUSTID".to_vec());
+        }
+        Frame::Array(claim.into_iter().map(Frame::BulkString).collect())
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let stream = match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Error(no_such_group(&self.key, &self.group))),
+        };
+        let group = match stream.group(&self.group) {
+            Some(group) => group,
+            None => return Ok(Frame::Error(no_such_group(&self.key, &self.group))),
+        };
+
+        // 多取一条作为下一次扫描的起点
+        let attempts = self.count.saturating_mul(ATTEMPTS_FACTOR);
+        let candidates = match self.start {
+            Some(start) => group.pending.range(start..)
+                .take(attempts.saturating_add(1))
+                .map(|(id, entry)| (*id, entry.delivery_time, entry.delivery_count, stream.get(id).is_some()))
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+
+        let now = now_millis();
+        let group = stream.group_mut(&self.group).unwrap();
+        group.touch_consumer(&self.consumer, now);
+        let mut claimed = Vec::new();
+        let mut deleted = Vec::new();
+        let mut examined = 0;
+        for (id, delivery_time, delivery_count, exists) in candidates.iter().take(attempts) {
+            if claimed.len() == self.count {
+                break;
+            }
+            examined += 1;
+            if !exists {
+                // 消息已被删除，从待确认列表中移除
+                group.ack(id);
+                deleted.push(*id);
+                continue;
+            }
+            if now.saturating_sub(*delivery_time) < self.min_idle {
+                continue;
+            }
+            let delivery_count = if self.justid { *delivery_count } else { delivery_count + 1 };
+            group.assign(*id, &self.consumer, now, delivery_count);
+            claimed.push(*id);
+        }
+        if !claimed.is_empty() {
+            if let Some(consumer) = group.consumers.get_mut(&self.consumer) {
+                consumer.active_time = Some(now);
+            }
+        }
+
+        let cursor = candidates.get(examined).map(|(id, ..)| *id).unwrap_or(StreamId::MIN);
+        Ok(Frame::Array(vec![
+            id_to_frame(&cursor),
+            claimed_to_frame(stream, &claimed, self.justid),
+            Frame::Array(deleted.iter().map(id_to_frame).collect()),
+        ]))
+    }
+}
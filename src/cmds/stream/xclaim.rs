@@ -0,0 +1,247 @@
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::entry::{entry_to_frame, id_to_frame, no_such_group, parse_id}}, store::{db::{Db, Structure}, stream::{Stream, StreamId}}, frame::Frame};
+
+/**
+ * 认领后的投递时间
+ */
+#[derive(Clone, Copy)]
+enum DeliveryTime {
+    Idle(u64), // 距今的毫秒数
+    Time(u64), // 毫秒时间戳
+}
+
+/**
+ * XCLAIM 的可选参数
+ *
+ * @param delivery_time 认领后的投递时间，None 表示当前时间
+ * @param retry_count 认领后的投递次数，None 表示累加一次
+ * @param force 消息不在待确认列表中但仍存在于流中时也创建待确认记录
+ * @param justid 只返回 ID，且不累加投递次数
+ * @param last_id 推进组的最后投递 ID
+ */
+#[derive(Default)]
+struct ClaimOptions {
+    delivery_time: Option<DeliveryTime>,
+    retry_count: Option<u64>,
+    force: bool,
+    justid: bool,
+    last_id: Option<StreamId>,
+}
+
+/**
+ * 认领结果的回复，JUSTID 时只返回 ID
+ *
+ * @param stream 流
+ * @param ids 认领成功的消息 ID
+ * @param justid 是否只返回 ID
+ */
+pub fn claimed_to_frame(stream: &Stream, ids: &[StreamId], justid: bool) -> Frame {
+    let frames = ids.iter().filter_map(|id| match justid {
+        true => Some(id_to_frame(id)),
+        false => stream.get(id).map(|fields| entry_to_frame(id, fields)),
+    }).collect();
+    Frame::Array(frames)
+}
+
+/**
+ * 从认领结果的回复中取出消息 ID
+ */
+pub fn claimed_ids(reply: &Frame) -> Vec<Vec<u8>> {
+    match reply {
+        Frame::Array(frames) => frames.iter().filter_map(|frame| match frame {
+            Frame::BulkString(id) => Some(id.clone()),
+            Frame::Array(entry) => match entry.first() {
+                Some(Frame::BulkString(id)) => Some(id.clone()),
+                _ => None,
+            },
+            _ => None,
+        }).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/**
+ * XCLAIM key group consumer min-idle-time id [id ...] [IDLE ms] [TIME unix-time-milliseconds] [RETRYCOUNT count] [FORCE] [JUSTID] [LASTID lastid]
+ *
+ * 将空闲时间不小于 min-idle-time 的待确认消息转移给指定的消费者
+ *
+ * @param options 可选参数，单独分配以减小命令的大小
+ */
+pub struct Xclaim {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    min_idle: u64,
+    ids: Vec<StreamId>,
+    options: Box<ClaimOptions>,
+}
+
+impl Xclaim {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 6 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xclaim' command"));
+        }
+        let key = bytes[1].clone(); // 键
+        let group = bytes[2].clone(); // 组名
+        let consumer = bytes[3].clone(); // 消费者名称
+        let min_idle = args[4].parse::<i64>().map_err(|_| Error::msg("ERR Invalid min-idle-time argument for XCLAIM"))?.max(0) as u64;
+
+        // ID 之后的第一个无法解析为 ID 的参数开始是可选参数
+        let mut index = 5;
+        let mut ids = Vec::new();
+        while let Some(id) = args.get(index).and_then(|id| StreamId::parse(id, 0)) {
+            ids.push(id);
+            index += 1;
+        }
+
+        let mut options = ClaimOptions::default();
+        while index < args.len() {
+            let option = args[index].to_uppercase();
+            match (option.as_str(), args.get(index + 1)) {
+                ("FORCE", _) => options.force = true,
+                ("JUSTID", _) => options.justid = true,
+                ("IDLE", Some(value)) => {
+                    let idle = value.parse::<i64>().map_err(|_| Error::msg("ERR Invalid IDLE option argument for XCLAIM"))?;
+                    options.delivery_time = Some(DeliveryTime::Idle(idle.max(0) as u64));
+                    index += 1;
+                },
+                ("TIME", Some(value)) => {
+                    let time = value.parse::<i64>().map_err(|_| Error::msg("ERR Invalid TIME option argument for XCLAIM"))?;
+                    options.delivery_time = Some(DeliveryTime::Time(time.max(0) as u64));
+                    index += 1;
+                },
+                ("RETRYCOUNT", Some(value)) => {
+                    let count = value.parse::<i64>().map_err(|_| Error::msg("ERR Invalid RETRYCOUNT option argument for XCLAIM"))?;
+                    options.retry_count = Some(count.max(0) as u64);
+                    index += 1;
+                },
+                ("LASTID", Some(value)) => {
+                    options.last_id = Some(parse_id(value, 0)?);
+                    index += 1;
+                },
+                _ => return Err(Error::msg(format!("ERR Unrecognized XCLAIM option '{}'", args[index]))),
+            }
+            index += 1;
+        }
+        Ok(Xclaim { key, group, consumer, min_idle, ids, options: Box::new(options) })
+    }
+
+    /**
+     * 转换为用于传播的命令帧，相对的 IDLE 改写为 TIME 时间戳
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![
+            b"XCLAIM".to_vec(),
+            self.key.clone(),
+            self.group.clone(),
+            self.consumer.clone(),
+            self.min_idle.to_string().into_bytes(),
+        ];
+        args.extend(self.ids.iter().map(|id| id.to_string().into_bytes()));
+        match self.options.delivery_time {
+            Some(DeliveryTime::Idle(idle)) => args.extend([b"TIME".to_vec(), now_millis().saturating_sub(idle).to_string().into_bytes()]),
+            Some(DeliveryTime::Time(time)) => args.extend([b"TIME".to_vec(), time.to_string().into_bytes()]),
+            None => {},
+        }
+        if let Some(count) = self.options.retry_count {
+            args.extend([b"RETRYCOUNT".to_vec(), count.to_string().into_bytes()]);
+        }
+        if self.options.force {
+            args.push(b"FORCE".to_vec());
+        }
+        if self.options.justid {
+            args.push(b"JUSTID".to_vec());
+        }
+        if let Some(last_id) = self.options.last_id {
+            args.extend([b"LASTID".to_vec(), last_id.to_string().into_bytes()]);
+        }
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    /**
+     * 将命令帧改写为只认领实际认领成功的消息且不限制空闲时间，
+     * 避免重放时因投递时间不同而得到不同的结果
+     *
+     * @param frame to_frame 生成的命令帧
+     * @param reply 执行结果
+     */
+    pub fn resolve_frame(frame: Frame, reply: &Frame) -> Frame {
+        let ids = claimed_ids(reply);
+        if ids.is_empty() {
+            return frame;
+        }
+        let mut args = frame.get_args_bytes();
+        // 保留 ID 之后的可选参数
+        let options = args.iter().skip(5).position(|arg| StreamId::parse(&String::from_utf8_lossy(arg), 0).is_none());
+        let options = options.map(|position| args.split_off(5 + position)).unwrap_or_default();
+        args.truncate(4);
+        args.push(b"0".to_vec());
+        args.extend(ids);
+        args.extend(options);
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let stream = match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Error(no_such_group(&self.key, &self.group))),
+        };
+
+        let now = now_millis();
+        let delivery_time = match self.options.delivery_time {
+            Some(DeliveryTime::Idle(idle)) => now.saturating_sub(idle),
+            // 未来的时间按当前时间处理
+            Some(DeliveryTime::Time(time)) => time.min(now),
+            None => now,
+        };
+        let exists = self.ids.iter().map(|id| stream.get(id).is_some()).collect::<Vec<_>>();
+        let group = match stream.group_mut(&self.group) {
+            Some(group) => group,
+            None => return Ok(Frame::Error(no_such_group(&self.key, &self.group))),
+        };
+        if let Some(last_id) = self.options.last_id {
+            group.last_delivered_id = group.last_delivered_id.max(last_id);
+        }
+        group.touch_consumer(&self.consumer, now);
+
+        let mut claimed = Vec::new();
+        for (id, exists) in self.ids.iter().zip(exists) {
+            let delivery_count = match group.pending.get(id).map(|entry| (entry.delivery_time, entry.delivery_count)) {
+                Some((time, count)) => {
+                    if now.saturating_sub(time) < self.min_idle {
+                        continue;
+                    }
+                    if !exists {
+                        // 消息已被删除，不再需要确认
+                        group.ack(id);
+                        continue;
+                    }
+                    count
+                },
+                // 从未投递过的消息没有空闲时间的限制
+                None if self.options.force && exists => 0,
+                None => continue,
+            };
+            let delivery_count = match self.options.retry_count {
+                Some(count) => count,
+                None if self.options.justid => delivery_count,
+                None => delivery_count + 1,
+            };
+            group.assign(*id, &self.consumer, delivery_time, delivery_count);
+            claimed.push(*id);
+        }
+        if !claimed.is_empty() {
+            if let Some(consumer) = group.consumers.get_mut(&self.consumer) {
+                consumer.active_time = Some(now);
+            }
+        }
+        Ok(claimed_to_frame(stream, &claimed, self.options.justid))
+    }
+}
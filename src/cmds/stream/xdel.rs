@@ -0,0 +1,35 @@
+use anyhow::Error;
+
+use crate::{cmds::stream::entry::parse_id, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+pub struct Xdel {
+    key: Vec<u8>,
+    ids: Vec<StreamId>,
+}
+
+impl Xdel {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xdel' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let ids = args[2..].iter().map(|id| parse_id(id, 0)).collect::<Result<Vec<_>, _>>()?; // 消息 ID
+        Ok(Xdel { key, ids })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => {
+                // 删除所有消息后流本身仍然保留
+                let removed = self.ids.iter().filter(|id| stream.remove(id)).count();
+                Ok(Frame::Integer(removed as i64))
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::entry::parse_id}, store::{db::{Db, Structure}, stream::{ConsumerGroup, Stream, StreamId}}, frame::Frame};
+
+enum Action {
+    Create { id: Option<StreamId>, mkstream: bool, entries_read: Option<u64> },
+    SetId { id: Option<StreamId>, entries_read: Option<u64> },
+    Destroy,
+    CreateConsumer(Vec<u8>),
+    DelConsumer(Vec<u8>),
+}
+
+/**
+ * 解析组的起始 ID，$ 表示流当前最后的 ID，返回 None
+ */
+fn parse_group_id(id: &str) -> Result<Option<StreamId>, Error> {
+    match id {
+        "$" => Ok(None),
+        id => parse_id(id, 0).map(Some),
+    }
+}
+
+/**
+ * 解析 ENTRIESREAD 选项，-1 表示未知
+ */
+fn parse_entries_read(args: &[String], index: usize) -> Result<Option<u64>, Error> {
+    if args.len() != index + 2 || !args[index].eq_ignore_ascii_case("ENTRIESREAD") {
+        return Err(Error::msg("ERR syntax error"));
+    }
+    match args[index + 1].parse::<i64>() {
+        Ok(-1) => Ok(None),
+        Ok(value) if value >= 0 => Ok(Some(value as u64)),
+        Ok(_) => Err(Error::msg("ERR value for ENTRIESREAD must be positive or -1")),
+        Err(_) => Err(Error::msg("ERR value is not an integer or out of range")),
+    }
+}
+
+/**
+ * XGROUP CREATE key group id | $ [MKSTREAM] [ENTRIESREAD entries-read]
+ * XGROUP SETID key group id | $ [ENTRIESREAD entries-read]
+ * XGROUP DESTROY key group
+ * XGROUP CREATECONSUMER key group consumer
+ * XGROUP DELCONSUMER key group consumer
+ */
+pub struct Xgroup {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    action: Action,
+}
+
+impl Xgroup {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xgroup' command"));
+        }
+        let subcommand = args[1].to_uppercase();
+        let arity_error = || Error::msg(format!("ERR wrong number of arguments for 'xgroup|{}' command", subcommand.to_lowercase()));
+        let action = match subcommand.as_str() {
+            "CREATE" => {
+                if args.len() < 5 {
+                    return Err(arity_error());
+                }
+                let id = parse_group_id(&args[4])?;
+                let mut mkstream = false;
+                let mut index = 5;
+                if args.get(index).is_some_and(|arg| arg.eq_ignore_ascii_case("MKSTREAM")) {
+                    mkstream = true;
+                    index += 1;
+                }
+                let entries_read = if index < args.len() { parse_entries_read(&args, index)? } else { None };
+                Action::Create { id, mkstream, entries_read }
+            },
+            "SETID" => {
+                if args.len() < 5 {
+                    return Err(arity_error());
+                }
+                let id = parse_group_id(&args[4])?;
+                let entries_read = if args.len() > 5 { parse_entries_read(&args, 5)? } else { None };
+                Action::SetId { id, entries_read }
+            },
+            "DESTROY" => {
+                if args.len() != 4 {
+                    return Err(arity_error());
+                }
+                Action::Destroy
+            },
+            "CREATECONSUMER" | "DELCONSUMER" => {
+                if args.len() != 5 {
+                    return Err(arity_error());
+                }
+                let consumer = bytes[4].clone(); // 消费者名称
+                if subcommand == "CREATECONSUMER" { Action::CreateConsumer(consumer) } else { Action::DelConsumer(consumer) }
+            },
+            _ => return Err(Error::msg(format!("ERR unknown subcommand '{}'. Try XGROUP HELP.", args[1]))),
+        };
+        let key = bytes[2].clone(); // 键
+        let group = bytes[3].clone(); // 组名
+        Ok(Xgroup { key, group, action })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        if let Action::Create { mkstream: true, .. } = self.action {
+            if db.get(&self.key).is_none() {
+                db.insert(self.key.clone(), Structure::Stream(Stream::new()));
+            }
+        }
+        let stream = match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                let f = "ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.";
+                return Ok(Frame::Error(f.to_string()));
+            }
+        };
+
+        let no_group = || Frame::Error(format!(
+            "NOGROUP No such consumer group '{}' for key name '{}'",
+            String::from_utf8_lossy(&self.group),
+            String::from_utf8_lossy(&self.key),
+        ));
+        match self.action {
+            Action::Create { id, entries_read, .. } => {
+                let id = id.unwrap_or(stream.last_id());
+                if !stream.create_group(&self.group, ConsumerGroup::new(id, entries_read)) {
+                    return Ok(Frame::Error("BUSYGROUP Consumer Group name already exists".to_string()));
+                }
+                Ok(Frame::Ok)
+            },
+            Action::SetId { id, entries_read } => {
+                let id = id.unwrap_or(stream.last_id());
+                match stream.group_mut(&self.group) {
+                    Some(group) => {
+                        group.last_delivered_id = id;
+                        group.entries_read = entries_read;
+                        Ok(Frame::Ok)
+                    },
+                    None => Ok(no_group()),
+                }
+            },
+            Action::Destroy => Ok(Frame::Integer(stream.remove_group(&self.group) as i64)),
+            Action::CreateConsumer(consumer) => match stream.group_mut(&self.group) {
+                Some(group) => Ok(Frame::Integer(group.create_consumer(&consumer, now_millis()) as i64)),
+                None => Ok(no_group()),
+            },
+            Action::DelConsumer(consumer) => match stream.group_mut(&self.group) {
+                Some(group) => Ok(Frame::Integer(group.remove_consumer(&consumer).unwrap_or(0) as i64)),
+                None => Ok(no_group()),
+            },
+        }
+    }
+}
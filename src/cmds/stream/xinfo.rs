@@ -0,0 +1,190 @@
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::entry::{entry_to_frame, id_to_frame}}, store::{db::{Db, Structure}, stream::{ConsumerGroup, Stream}}, frame::Frame};
+
+// FULL 模式下默认返回的消息数量
+const DEFAULT_FULL_COUNT: usize = 10;
+
+enum Action {
+    Stream { full: bool, count: usize },
+    Groups,
+    Consumers(Vec<u8>),
+}
+
+fn field(name: &str, value: Frame) -> (Frame, Frame) {
+    (Frame::BulkString(name.as_bytes().to_vec()), value)
+}
+
+fn optional_integer(value: Option<u64>) -> Frame {
+    value.map(|value| Frame::Integer(value as i64)).unwrap_or(Frame::Null)
+}
+
+/**
+ * XINFO STREAM key [FULL [COUNT count]]
+ * XINFO GROUPS key
+ * XINFO CONSUMERS key group
+ *
+ * 查看流、消费者组与消费者的状态
+ */
+pub struct Xinfo {
+    key: Vec<u8>,
+    action: Action,
+}
+
+impl Xinfo {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xinfo' command"));
+        }
+        let subcommand = args[1].to_uppercase();
+        let arity_error = || Error::msg(format!("ERR wrong number of arguments for 'xinfo|{}' command", subcommand.to_lowercase()));
+        let action = match subcommand.as_str() {
+            "STREAM" => {
+                if args.len() < 3 {
+                    return Err(arity_error());
+                }
+                let mut full = false;
+                let mut count = DEFAULT_FULL_COUNT;
+                match &args[3..] {
+                    [] => {},
+                    [option] if option.eq_ignore_ascii_case("FULL") => full = true,
+                    [option, name, value] if option.eq_ignore_ascii_case("FULL") && name.eq_ignore_ascii_case("COUNT") => {
+                        full = true;
+                        let value = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+                        // COUNT 0 表示返回所有消息
+                        count = if value > 0 { value as usize } else { usize::MAX };
+                    },
+                    _ => return Err(Error::msg("ERR syntax error")),
+                }
+                Action::Stream { full, count }
+            },
+            "GROUPS" => {
+                if args.len() != 3 {
+                    return Err(arity_error());
+                }
+                Action::Groups
+            },
+            "CONSUMERS" => {
+                if args.len() != 4 {
+                    return Err(arity_error());
+                }
+                Action::Consumers(bytes[3].clone())
+            },
+            _ => return Err(Error::msg(format!("ERR unknown subcommand '{}'. Try XINFO HELP.", args[1]))),
+        };
+        let key = bytes[2].clone(); // 键
+        Ok(Xinfo { key, action })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let stream = match db.get(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Error("ERR no such key".to_string())),
+        };
+
+        let now = now_millis();
+        match self.action {
+            Action::Stream { full, count } => Ok(Self::stream_info(stream, full, count)),
+            Action::Groups => {
+                let groups = stream.groups().iter().map(|(name, group)| Frame::Map(vec![
+                    field("name", Frame::BulkString(name.clone())),
+                    field("consumers", Frame::Integer(group.consumers.len() as i64)),
+                    field("pending", Frame::Integer(group.pending.len() as i64)),
+                    field("last-delivered-id", id_to_frame(&group.last_delivered_id)),
+                    field("entries-read", optional_integer(group.entries_read)),
+                    field("lag", optional_integer(stream.lag(group))),
+                ])).collect();
+                Ok(Frame::Array(groups))
+            },
+            Action::Consumers(name) => {
+                let group = match stream.group(&name) {
+                    Some(group) => group,
+                    None => {
+                        let f = format!(
+                            "NOGROUP No such consumer group '{}' for key name '{}'",
+                            String::from_utf8_lossy(&name),
+                            String::from_utf8_lossy(&self.key),
+                        );
+                        return Ok(Frame::Error(f));
+                    }
+                };
+                let consumers = group.consumers.iter().map(|(name, consumer)| Frame::Map(vec![
+                    field("name", Frame::BulkString(name.clone())),
+                    field("pending", Frame::Integer(consumer.pending.len() as i64)),
+                    field("idle", Frame::Integer(now.saturating_sub(consumer.seen_time) as i64)),
+                    field("inactive", consumer.active_time.map(|time| Frame::Integer(now.saturating_sub(time) as i64)).unwrap_or(Frame::Integer(-1))),
+                ])).collect();
+                Ok(Frame::Array(consumers))
+            },
+        }
+    }
+
+    /**
+     * XINFO STREAM 的回复，FULL 模式下包含消息、消费者组及其待确认列表的详情
+     *
+     * @param stream 流
+     * @param full 是否为 FULL 模式
+     * @param count FULL 模式下消息与待确认列表最多返回的数量
+     */
+    fn stream_info(stream: &Stream, full: bool, count: usize) -> Frame {
+        let first_id = stream.first_entry().map(|(id, _)| *id).unwrap_or_default();
+        let mut fields = vec![
+            field("length", Frame::Integer(stream.len() as i64)),
+            field("last-generated-id", id_to_frame(&stream.last_id())),
+            field("max-deleted-entry-id", id_to_frame(&stream.max_deleted_id())),
+            field("entries-added", Frame::Integer(stream.entries_added() as i64)),
+            field("recorded-first-entry-id", id_to_frame(&first_id)),
+        ];
+        if !full {
+            let entry = |entry: Option<_>| entry.map(|(id, fields)| entry_to_frame(id, fields)).unwrap_or(Frame::Null);
+            fields.push(field("groups", Frame::Integer(stream.groups().len() as i64)));
+            fields.push(field("first-entry", entry(stream.first_entry())));
+            fields.push(field("last-entry", entry(stream.last_entry())));
+            return Frame::Map(fields);
+        }
+
+        let entries = stream.range(first_id, stream.last_id()).take(count).map(|(id, fields)| entry_to_frame(id, fields)).collect();
+        fields.push(field("entries", Frame::Array(entries)));
+        let groups = stream.groups().iter().map(|(name, group)| Self::group_info(stream, name, group, count)).collect();
+        fields.push(field("groups", Frame::Array(groups)));
+        Frame::Map(fields)
+    }
+
+    fn group_info(stream: &Stream, name: &[u8], group: &ConsumerGroup, count: usize) -> Frame {
+        let pending = group.pending.iter().take(count).map(|(id, entry)| Frame::Array(vec![
+            id_to_frame(id),
+            Frame::BulkString(entry.consumer.clone()),
+            Frame::Integer(entry.delivery_time as i64),
+            Frame::Integer(entry.delivery_count as i64),
+        ])).collect();
+        let consumers = group.consumers.iter().map(|(name, consumer)| {
+            let pending = consumer.pending.iter().take(count).filter_map(|id| group.pending.get(id).map(|entry| Frame::Array(vec![
+                id_to_frame(id),
+                Frame::Integer(entry.delivery_time as i64),
+                Frame::Integer(entry.delivery_count as i64),
+            ]))).collect();
+            Frame::Map(vec![
+                field("name", Frame::BulkString(name.clone())),
+                field("seen-time", Frame::Integer(consumer.seen_time as i64)),
+                field("active-time", consumer.active_time.map(|time| Frame::Integer(time as i64)).unwrap_or(Frame::Integer(-1))),
+                field("pel-count", Frame::Integer(consumer.pending.len() as i64)),
+                field("pending", Frame::Array(pending)),
+            ])
+        }).collect();
+        Frame::Map(vec![
+            field("name", Frame::BulkString(name.to_vec())),
+            field("last-delivered-id", id_to_frame(&group.last_delivered_id)),
+            field("entries-read", optional_integer(group.entries_read)),
+            field("lag", optional_integer(stream.lag(group))),
+            field("pel-count", Frame::Integer(group.pending.len() as i64)),
+            field("pending", Frame::Array(pending)),
+            field("consumers", Frame::Array(consumers)),
+        ])
+    }
+}
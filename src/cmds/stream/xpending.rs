@@ -0,0 +1,117 @@
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::entry::{id_to_frame, no_such_group, parse_bound}}, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+/**
+ * 扩展形式的查询条件
+ *
+ * @param idle 最小空闲毫秒数
+ * @param start 起始 ID（包含），大于 end 时区间为空
+ * @param end 结束 ID（包含）
+ * @param count 最多返回的数量
+ * @param consumer 只返回该消费者的消息
+ */
+struct PendingRange {
+    idle: u64,
+    start: StreamId,
+    end: StreamId,
+    count: usize,
+    consumer: Option<Vec<u8>>,
+}
+
+/**
+ * XPENDING key group [[IDLE min-idle-time] start end count [consumer]]
+ *
+ * 不带区间时返回待确认消息的概要，否则返回区间内每条待确认消息的详情
+ */
+pub struct Xpending {
+    key: Vec<u8>,
+    group: Vec<u8>,
+    range: Option<PendingRange>,
+}
+
+impl Xpending {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xpending' command"));
+        }
+        let key = bytes[1].clone(); // 键
+        let group = bytes[2].clone(); // 组名
+        if args.len() == 3 {
+            return Ok(Xpending { key, group, range: None });
+        }
+
+        let mut idle = 0;
+        let mut index = 3;
+        if args[index].eq_ignore_ascii_case("IDLE") {
+            let value = args.get(index + 1).ok_or_else(|| Error::msg("ERR syntax error"))?;
+            idle = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?.max(0) as u64;
+            index += 2;
+        }
+        if args.len() < index + 3 || args.len() > index + 4 {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        let (start, end) = match (parse_bound(&args[index], true)?, parse_bound(&args[index + 1], false)?) {
+            (Some(start), Some(end)) => (start, end),
+            _ => (StreamId::MAX, StreamId::MIN),
+        };
+        let count = args[index + 2].parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?.max(0) as usize;
+        let consumer = bytes.get(index + 3).cloned();
+        Ok(Xpending { key, group, range: Some(PendingRange { idle, start, end, count, consumer }) })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let group = match db.get(&self.key) {
+            Some(Structure::Stream(stream)) => stream.group(&self.group),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => None,
+        };
+        let group = match group {
+            Some(group) => group,
+            None => return Ok(Frame::Error(no_such_group(&self.key, &self.group))),
+        };
+
+        let range = match self.range {
+            Some(range) => range,
+            None => {
+                // 概要：数量、最小 ID、最大 ID 与每个消费者的待确认数量
+                let (first, last) = match (group.pending.first_key_value(), group.pending.last_key_value()) {
+                    (Some((first, _)), Some((last, _))) => (first, last),
+                    _ => return Ok(Frame::Array(vec![Frame::Integer(0), Frame::Null, Frame::Null, Frame::Null])),
+                };
+                let consumers = group.consumers.iter()
+                    .filter(|(_, consumer)| !consumer.pending.is_empty())
+                    .map(|(name, consumer)| Frame::Array(vec![
+                        Frame::BulkString(name.clone()),
+                        Frame::BulkString(consumer.pending.len().to_string().into_bytes()),
+                    ]))
+                    .collect();
+                return Ok(Frame::Array(vec![
+                    Frame::Integer(group.pending.len() as i64),
+                    id_to_frame(first),
+                    id_to_frame(last),
+                    Frame::Array(consumers),
+                ]));
+            }
+        };
+
+        let now = now_millis();
+        let entries = (range.start <= range.end).then(|| group.pending.range(range.start..=range.end)).into_iter().flatten()
+            .filter(|(_, entry)| range.consumer.as_ref().is_none_or(|consumer| *consumer == entry.consumer))
+            .filter(|(_, entry)| now.saturating_sub(entry.delivery_time) >= range.idle)
+            .take(range.count)
+            .map(|(id, entry)| Frame::Array(vec![
+                id_to_frame(id),
+                Frame::BulkString(entry.consumer.clone()),
+                Frame::Integer(now.saturating_sub(entry.delivery_time) as i64),
+                Frame::Integer(entry.delivery_count as i64),
+            ]))
+            .collect();
+        Ok(Frame::Array(entries))
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::Error;
+
+use crate::{cmds::stream::entry::{entry_to_frame, parse_bound}, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+/**
+ * XRANGE key start end [COUNT count] 与 XREVRANGE key end start [COUNT count]
+ *
+ * 区间两端支持 - 与 +、省略序号的 ID 以及表示不包含的 ( 前缀
+ *
+ * @param start 起始 ID（包含），大于 end 时区间为空
+ * @param end 结束 ID（包含）
+ * @param count 最多返回的数量，None 表示不限制
+ * @param rev 是否按 ID 倒序返回
+ */
+pub struct Xrange {
+    key: Vec<u8>,
+    start: StreamId,
+    end: StreamId,
+    count: Option<usize>,
+    rev: bool,
+}
+
+impl Xrange {
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 && args.len() != 6 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let rev = command == "xrevrange";
+        let (start, end) = if rev { (&args[3], &args[2]) } else { (&args[2], &args[3]) };
+        let (start, end) = match (parse_bound(start, true)?, parse_bound(end, false)?) {
+            (Some(start), Some(end)) => (start, end),
+            _ => (StreamId::MAX, StreamId::MIN),
+        };
+
+        let mut count = None;
+        if args.len() == 6 {
+            if !args[4].eq_ignore_ascii_case("COUNT") {
+                return Err(Error::msg("ERR syntax error"));
+            }
+            let value = args[5].parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+            count = Some(value.max(0) as usize);
+        }
+        Ok(Xrange { key, start, end, count, rev })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let stream = match db.get(&self.key) {
+            Some(Structure::Stream(stream)) => stream,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(Vec::new())),
+        };
+
+        let count = self.count.unwrap_or(usize::MAX);
+        let entries = stream.range(self.start, self.end);
+        let frames = if self.rev {
+            entries.rev().take(count).map(|(id, fields)| entry_to_frame(id, fields)).collect()
+        } else {
+            entries.take(count).map(|(id, fields)| entry_to_frame(id, fields)).collect()
+        };
+        Ok(Frame::Array(frames))
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use anyhow::Error;
+
+use crate::{cmds::stream::entry::{entry_to_frame, parse_id}, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+/**
+ * 读取位置
+ */
+#[derive(Clone, Copy)]
+enum ReadId {
+    Last, // $，只读取阻塞之后添加的消息
+    After(StreamId), // 读取 ID 大于该值的消息
+}
+
+/**
+ * XREAD [COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]
+ *
+ * @param count 每个流最多返回的数量，None 表示不限制
+ * @param block 是否为阻塞读取
+ * @param timeout 阻塞的超时时间，None 表示永久阻塞
+ */
+pub struct Xread {
+    keys: Vec<Vec<u8>>,
+    ids: Vec<ReadId>,
+    count: Option<usize>,
+    block: bool,
+    timeout: Option<Duration>,
+}
+
+impl Xread {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xread' command"));
+        }
+
+        let mut count = None;
+        let mut block = false;
+        let mut timeout = None;
+        let mut index = 1;
+        loop {
+            let option = args.get(index).ok_or_else(|| Error::msg("ERR syntax error"))?.to_uppercase();
+            match (option.as_str(), args.get(index + 1)) {
+                ("STREAMS", _) => break,
+                ("COUNT", Some(value)) => {
+                    let value = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+                    // 与 Redis 一致，非正数表示不限制
+                    count = (value > 0).then_some(value as usize);
+                },
+                ("BLOCK", Some(value)) => {
+                    let milliseconds = value.parse::<i64>().map_err(|_| Error::msg("ERR timeout is not an integer or out of range"))?;
+                    if milliseconds < 0 {
+                        return Err(Error::msg("ERR timeout is negative"));
+                    }
+                    block = true;
+                    timeout = (milliseconds > 0).then(|| Duration::from_millis(milliseconds as u64));
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 2;
+        }
+
+        let streams = &bytes[index + 1..];
+        if streams.is_empty() || !streams.len().is_multiple_of(2) {
+            return Err(Error::msg("ERR Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified."));
+        }
+        let (keys, ids) = streams.split_at(streams.len() / 2);
+        let ids = ids.iter().map(|id| match String::from_utf8_lossy(id).as_ref() {
+            "$" => Ok(ReadId::Last),
+            id => parse_id(id, 0).map(ReadId::After),
+        }).collect::<Result<Vec<_>, _>>()?;
+        Ok(Xread { keys: keys.to_vec(), ids, count, block, timeout })
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.block
+    }
+
+    /**
+     * 阻塞前将 $ 替换为各个流当前最后的 ID
+     *
+     * @param db 数据库
+     */
+    pub fn resolve_last_ids(&mut self, db: &mut Db) {
+        for (key, id) in self.keys.iter().zip(self.ids.iter_mut()) {
+            if let ReadId::Last = id {
+                *id = match db.get(key) {
+                    Some(Structure::Stream(stream)) => ReadId::After(stream.last_id()),
+                    _ => ReadId::After(StreamId::MIN),
+                };
+            }
+        }
+    }
+
+    /**
+     * 以非阻塞方式执行，所有流都没有新消息时返回空数组
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        let mut result = Vec::new();
+        for (key, id) in self.keys.iter().zip(&self.ids) {
+            let stream = match db.get(key) {
+                Some(Structure::Stream(stream)) => stream,
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Ok(Frame::Error(f.to_string()));
+                },
+                None => continue,
+            };
+            let start = match id {
+                ReadId::After(id) => id.next(),
+                ReadId::Last => None,
+            };
+            let entries = match start {
+                Some(start) => stream.range(start, StreamId::MAX)
+                    .take(self.count.unwrap_or(usize::MAX))
+                    .map(|(id, fields)| entry_to_frame(id, fields))
+                    .collect::<Vec<_>>(),
+                None => Vec::new(),
+            };
+            if !entries.is_empty() {
+                result.push(Frame::Array(vec![Frame::BulkString(key.clone()), Frame::Array(entries)]));
+            }
+        }
+        if result.is_empty() {
+            return Ok(Frame::NullArray);
+        }
+        Ok(Frame::Array(result))
+    }
+}
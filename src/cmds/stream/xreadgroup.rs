@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use anyhow::Error;
+
+use crate::{cmds::{hash::hexpire::now_millis, stream::entry::{entry_to_frame, id_to_frame, parse_id}}, store::{db::{Db, Structure}, stream::StreamId}, frame::Frame};
+
+/**
+ * XREADGROUP GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK] STREAMS key [key ...] id [id ...]
+ *
+ * ID 为 > 时读取组内尚未投递的新消息，否则读取该消费者 ID 之后的待确认消息
+ *
+ * @param ids 每个流的读取位置，None 表示 >，使用定长切片以减小命令的大小
+ * @param count 每个流最多返回的数量，usize::MAX 表示不限制
+ * @param block 是否为阻塞读取
+ * @param timeout 阻塞的超时时间，None 表示永久阻塞
+ * @param noack 新消息不加入待确认列表
+ */
+pub struct Xreadgroup {
+    group: Vec<u8>,
+    consumer: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+    ids: Box<[Option<StreamId>]>,
+    count: usize,
+    timeout: Option<Duration>,
+    block: bool,
+    noack: bool,
+}
+
+impl Xreadgroup {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        let bytes = frame.get_args_bytes();
+        if args.len() < 7 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xreadgroup' command"));
+        }
+        if !args[1].eq_ignore_ascii_case("GROUP") {
+            return Err(Error::msg("ERR Missing 'GROUP' in 'XREADGROUP'"));
+        }
+        let group = bytes[2].clone(); // 组名
+        let consumer = bytes[3].clone(); // 消费者名称
+
+        let mut count = usize::MAX;
+        let mut block = false;
+        let mut timeout = None;
+        let mut noack = false;
+        let mut index = 4;
+        loop {
+            let option = args.get(index).ok_or_else(|| Error::msg("ERR syntax error"))?.to_uppercase();
+            match (option.as_str(), args.get(index + 1)) {
+                ("STREAMS", _) => break,
+                ("NOACK", _) => {
+                    noack = true;
+                    index += 1;
+                    continue;
+                },
+                ("COUNT", Some(value)) => {
+                    let value = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+                    // 与 Redis 一致，非正数表示不限制
+                    count = if value > 0 { value as usize } else { usize::MAX };
+                },
+                ("BLOCK", Some(value)) => {
+                    let milliseconds = value.parse::<i64>().map_err(|_| Error::msg("ERR timeout is not an integer or out of range"))?;
+                    if milliseconds < 0 {
+                        return Err(Error::msg("ERR timeout is negative"));
+                    }
+                    block = true;
+                    timeout = (milliseconds > 0).then(|| Duration::from_millis(milliseconds as u64));
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 2;
+        }
+
+        let streams = &bytes[index + 1..];
+        if streams.is_empty() || !streams.len().is_multiple_of(2) {
+            return Err(Error::msg("ERR Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified."));
+        }
+        let (keys, ids) = streams.split_at(streams.len() / 2);
+        let ids = ids.iter().map(|id| match String::from_utf8_lossy(id).as_ref() {
+            ">" => Ok(None),
+            "$" => Err(Error::msg("ERR The $ ID is meaningless in the context of XREADGROUP: you want to read the history of this consumer by specifying a proper ID, or use the > ID to get new messages. The $ ID would just return an empty result set.")),
+            id => parse_id(id, 0).map(Some),
+        }).collect::<Result<Box<[_]>, _>>()?;
+        Ok(Xreadgroup { group, consumer, keys: keys.to_vec(), ids, count, timeout, block, noack })
+    }
+
+    pub fn keys(&self) -> &[Vec<u8>] {
+        &self.keys
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.block
+    }
+
+    /**
+     * 以非阻塞方式执行，所有流都没有新消息时返回空数组
+     *
+     * 读取历史消息的流总是出现在结果中，因此不会阻塞
+     *
+     * @param db 数据库
+     */
+    pub fn apply(&self, db: &mut Db) -> Result<Frame, Error> {
+        // 先检查所有的流与组，避免只读取了一部分
+        for key in &self.keys {
+            match db.get(key) {
+                Some(Structure::Stream(stream)) if stream.group(&self.group).is_some() => {},
+                Some(Structure::Stream(_)) | None => {
+                    let f = format!(
+                        "NOGROUP No such key '{}' or consumer group '{}' in XREADGROUP with GROUP option",
+                        String::from_utf8_lossy(key),
+                        String::from_utf8_lossy(&self.group),
+                    );
+                    return Ok(Frame::Error(f));
+                },
+                Some(_) => {
+                    let f = "ERR Operation against a key holding the wrong kind of value";
+                    return Ok(Frame::Error(f.to_string()));
+                },
+            }
+        }
+
+        let now = now_millis();
+        let mut result = Vec::new();
+        for (key, id) in self.keys.iter().zip(self.ids.iter()) {
+            let stream = match db.get_mut(key) {
+                Some(Structure::Stream(stream)) => stream,
+                _ => continue,
+            };
+            if let Some(group) = stream.group_mut(&self.group) {
+                group.touch_consumer(&self.consumer, now);
+            }
+            let entries = match id {
+                None => stream.read_group(&self.group, &self.consumer, self.count, self.noack, now)
+                    .iter()
+                    .map(|(id, fields)| entry_to_frame(id, fields))
+                    .collect::<Vec<_>>(),
+                Some(id) => {
+                    // 读取消费者的待确认消息，重新投递并累加投递次数
+                    let ids = match (stream.group(&self.group), id.next()) {
+                        (Some(group), Some(start)) => match group.consumers.get(&self.consumer) {
+                            Some(consumer) => consumer.pending.range(start..).take(self.count).copied().collect::<Vec<_>>(),
+                            None => Vec::new(),
+                        },
+                        _ => Vec::new(),
+                    };
+                    let frames = ids.iter().map(|id| match stream.get(id) {
+                        Some(fields) => entry_to_frame(id, fields),
+                        // 已被删除的消息只返回 ID
+                        None => Frame::Array(vec![id_to_frame(id), Frame::Null]),
+                    }).collect::<Vec<_>>();
+                    if let Some(group) = stream.group_mut(&self.group) {
+                        for id in &ids {
+                            if let Some(entry) = group.pending.get_mut(id) {
+                                entry.delivery_time = now;
+                                entry.delivery_count += 1;
+                            }
+                        }
+                    }
+                    result.push(Frame::Array(vec![Frame::BulkString(key.clone()), Frame::Array(frames)]));
+                    continue;
+                }
+            };
+            if !entries.is_empty() {
+                result.push(Frame::Array(vec![Frame::BulkString(key.clone()), Frame::Array(entries)]));
+            }
+        }
+        if result.is_empty() {
+            return Ok(Frame::NullArray);
+        }
+        Ok(Frame::Array(result))
+    }
+
+    /**
+     * 实际执行的修改，传播到 AOF 与从节点
+     *
+     * 与 Redis 一致，投递的每条消息改写为带投递时间与投递次数的 XCLAIM，
+     * 读取新消息的流再以 XGROUP SETID 推进组的最后投递 ID，保证重放时得到相同的投递状态
+     *
+     * @param db 数据库
+     * @param reply 执行结果
+     */
+    pub fn propagation_frames(&self, db: &mut Db, reply: &Frame) -> Vec<Frame> {
+        let streams = match reply {
+            Frame::Array(streams) => streams,
+            _ => return Vec::new(),
+        };
+        let mut frames = Vec::new();
+        for item in streams {
+            let (key, entries) = match item {
+                Frame::Array(item) => match item.as_slice() {
+                    [Frame::BulkString(key), Frame::Array(entries)] => (key, entries),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let is_new = match self.keys.iter().position(|other| other == key) {
+                Some(index) => self.ids[index].is_none(),
+                None => continue,
+            };
+            let group = match db.get(key) {
+                Some(Structure::Stream(stream)) => match stream.group(&self.group) {
+                    Some(group) => group,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            for entry in entries {
+                // 已被删除的消息只有 ID，没有需要传播的投递状态
+                let id = match entry {
+                    Frame::Array(entry) => match entry.as_slice() {
+                        [Frame::BulkString(id), Frame::Array(_)] => StreamId::parse(&String::from_utf8_lossy(id), 0),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                // NOACK 读取的新消息不在待确认列表中
+                let (id, pending) = match id.and_then(|id| group.pending.get(&id).map(|pending| (id, pending))) {
+                    Some(pending) => pending,
+                    None => continue,
+                };
+                let args = [
+                    b"XCLAIM".to_vec(),
+                    key.clone(),
+                    self.group.clone(),
+                    self.consumer.clone(),
+                    b"0".to_vec(),
+                    id.to_string().into_bytes(),
+                    b"TIME".to_vec(),
+                    pending.delivery_time.to_string().into_bytes(),
+                    b"RETRYCOUNT".to_vec(),
+                    pending.delivery_count.to_string().into_bytes(),
+                    b"FORCE".to_vec(),
+                    b"JUSTID".to_vec(),
+                ];
+                frames.push(Frame::Array(args.into_iter().map(Frame::BulkString).collect()));
+            }
+            if is_new {
+                let entries_read = group.entries_read.map(|read| read as i64).unwrap_or(-1);
+                let args = [
+                    b"XGROUP".to_vec(),
+                    b"SETID".to_vec(),
+                    key.clone(),
+                    self.group.clone(),
+                    group.last_delivered_id.to_string().into_bytes(),
+                    b"ENTRIESREAD".to_vec(),
+                    entries_read.to_string().into_bytes(),
+                ];
+                frames.push(Frame::Array(args.into_iter().map(Frame::BulkString).collect()));
+            }
+        }
+        frames
+    }
+}
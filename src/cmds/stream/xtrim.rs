@@ -0,0 +1,40 @@
+use anyhow::Error;
+
+use crate::{cmds::stream::trim::Trim, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * XTRIM key MAXLEN | MINID [= | ~] threshold [LIMIT count]
+ */
+pub struct Xtrim {
+    key: Vec<u8>,
+    trim: Trim,
+}
+
+impl Xtrim {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'xtrim' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        if !args[2].eq_ignore_ascii_case("MAXLEN") && !args[2].eq_ignore_ascii_case("MINID") {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        let (trim, index) = Trim::parse(&args, 2)?;
+        if index != args.len() {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        Ok(Xtrim { key, trim })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get_mut(&self.key) {
+            Some(Structure::Stream(stream)) => Ok(Frame::Integer(self.trim.apply(stream) as i64)),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Integer(0)),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Decrby {
-    key: String,
+    key: Vec<u8>,
     decrement: i64,
 }
 
@@ -12,7 +12,7 @@ impl Decrby {
         if args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'decrby' command"));
         }
-        let key = args[1].to_string(); // 键
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
         let decrement = args[2].parse::<i64>().map_err(|_| {
             Error::msg("ERR value is not an integer or out of range")
         })?;
@@ -24,13 +24,13 @@ impl Decrby {
             Some(structure) => {
                 match structure {
                     Structure::String(str) => {
-                        match str.parse::<i64>() {
-                            Ok(mut num) => {
+                        match std::str::from_utf8(str).ok().and_then(|s| s.parse::<i64>().ok()) {
+                            Some(mut num) => {
                                 num -= self.decrement;
-                                *str = num.to_string();
+                                *str = num.to_string().into_bytes();
                                 Ok(Frame::Integer(num))
                             },
-                            Err(_) => {
+                            None => {
                                 let f = "ERR value is not an integer or out of range";
                                 Ok(Frame::Error(f.to_string()))
                             }
@@ -44,7 +44,7 @@ impl Decrby {
             },
             None => {
                 let new_value = -self.decrement;
-                db.insert(self.key.clone(), Structure::String(new_value.to_string()));
+                db.insert(self.key.clone(), Structure::String(new_value.to_string().into_bytes()));
                 Ok(Frame::Integer(new_value))
             }
         }
@@ -0,0 +1,35 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Getdel {
+    key: Vec<u8>,
+}
+
+impl Getdel {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'getdel' command"));
+        }
+        let key = args[1].clone(); // 键
+        Ok(Getdel { key })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::String(_)) => {
+                match db.remove(&self.key) {
+                    Some(Structure::String(value)) => Ok(Frame::BulkString(value)),
+                    _ => Ok(Frame::Null),
+                }
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Null),
+        }
+    }
+}
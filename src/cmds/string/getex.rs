@@ -0,0 +1,54 @@
+use anyhow::Error;
+
+use crate::{cmds::string::set::Expiration, store::db::{Db, Structure}, frame::Frame};
+
+pub struct Getex {
+    key: Vec<u8>,
+    expiration: Option<Expiration>,
+}
+
+impl Getex {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() < 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'getex' command"));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+
+        let expiration = match args.len() {
+            2 => None,
+            3 if args[2].eq_ignore_ascii_case("PERSIST") => Some(Expiration::Persist),
+            4 => Some(Expiration::parse(&args[2].to_uppercase(), args.get(3), "getex")?),
+            _ => return Err(Error::msg("ERR syntax error")),
+        };
+
+        Ok(Getex { key, expiration })
+    }
+
+    /**
+     * 转换为用于传播的命令帧，相对的过期时间改写为 PXAT 时间戳
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![b"GETEX".to_vec(), self.key.clone()];
+        if let Some(expiration) = &self.expiration {
+            args.extend(expiration.to_args());
+        }
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let value = match db.get(&self.key) {
+            Some(Structure::String(value)) => value.clone(),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Null),
+        };
+        if let Some(expiration) = self.expiration {
+            expiration.apply(db, &self.key);
+        }
+        Ok(Frame::BulkString(value))
+    }
+}
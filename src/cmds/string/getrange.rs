@@ -6,14 +6,14 @@ use crate::{
 };
 
 pub struct GetRange {
-    key: String,
+    key: Vec<u8>,
     start: i64,
     end: i64,
 }
 
 impl GetRange {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
         let start = frame.get_arg(2);
         let end = frame.get_arg(3);
 
@@ -23,9 +23,9 @@ impl GetRange {
             ));
         }
 
-        let final_key = key.unwrap().to_string();
-        let final_start = start.unwrap().to_string();
-        let final_end = end.unwrap().to_string();
+        let final_key = key.unwrap();
+        let final_start = start.unwrap();
+        let final_end = end.unwrap();
 
         let start_int = match final_start.parse::<i64>() {
             Ok(n) => n,
@@ -56,34 +56,18 @@ impl GetRange {
 
         let len = value.len() as i64;
         let normalize = |index: i64| {
-            if index < 0 {
-                (len + index).max(0)
-            } else {
-                index
-            }
-            .min(len) as usize
+            if index < 0 { (len + index).max(0) } else { index }
         };
 
         let start = normalize(self.start);
-        let end = normalize(self.end);
+        let end = normalize(self.end).min(len - 1);
         
         // 处理无效范围
-        if start > end {
-            return Ok(Frame::BulkString("".into()));
+        if len == 0 || start > end {
+            return Ok(Frame::BulkString(Vec::new()));
         }
 
-        // 安全截取字符串 (考虑 UTF-8 边界)
-        let substring = match value.char_indices().nth(start) {
-            Some((start_idx, _)) => {
-                let remainder = &value[start_idx..];
-                match remainder.char_indices().nth(end - start) {
-                    Some((end_idx, ch)) => &remainder[..end_idx + ch.len_utf8()],
-                    None => remainder,
-                }
-            }
-            None => "",
-        };
-
-        Ok(Frame::BulkString(substring.into()))
+        let substring = value[start as usize..=end as usize].to_vec();
+        Ok(Frame::BulkString(substring))
     }
 }
\ No newline at end of file
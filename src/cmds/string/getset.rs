@@ -2,20 +2,20 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct GetSet {
-    key: String,
-    value: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
 }
 
 impl GetSet {
     /// 从 Frame 解析出 GetSet 命令
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
         // 确保参数数量正确（key + value）
-        if frame.get_args().len() < 3 {
+        if frame.get_args_bytes().len() < 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'getset' command"));
         }
 
-        let key = frame.get_arg(1).ok_or(Error::msg("ERR missing key"))?.to_string();
-        let value = frame.get_arg(2).ok_or(Error::msg("ERR missing value"))?.to_string();
+        let key = frame.get_arg_bytes(1).ok_or(Error::msg("ERR missing key"))?;
+        let value = frame.get_arg_bytes(2).ok_or(Error::msg("ERR missing value"))?;
 
         Ok(GetSet { key, value })
     }
@@ -33,7 +33,8 @@ impl GetSet {
         // 插入新值（覆盖旧值）
         db.insert(self.key.clone(), Structure::String(self.value.clone()));
 
-        // TODO 是否移除过期时间
+        // 覆盖后移除过期时间
+        db.persist(&self.key);
 
         // 返回结果：旧值或 nil
         match old_value {
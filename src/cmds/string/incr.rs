@@ -2,17 +2,17 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Incr {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Incr {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let args = frame.get_args();
+        let args = frame.get_args_bytes();
         if args.len() != 2 {
             return Err(Error::msg("ERR wrong number of arguments for 'incr' command"));
         }
-        let key = args[1].to_string(); // 键
+        let key = args[1].clone(); // 键
         Ok(Incr { key })
     }
 
@@ -21,13 +21,13 @@ impl Incr {
             Some(structure) => {
                 match structure {
                     Structure::String(str) => {
-                        match str.parse::<i64>() {
-                            Ok(mut num) => {
+                        match std::str::from_utf8(str).ok().and_then(|s| s.parse::<i64>().ok()) {
+                            Some(mut num) => {
                                 num += 1;
-                                *str = num.to_string();
+                                *str = num.to_string().into_bytes();
                                 Ok(Frame::Integer(num))
                             },
-                            Err(_) => {
+                            None => {
                                 let f = "ERR value is not an integer or out of range";
                                 Ok(Frame::Error(f.to_string()))
                             }
@@ -40,7 +40,7 @@ impl Incr {
                 }
             },
             None => {
-                db.insert(self.key.clone(), Structure::String("1".to_string()));
+                db.insert(self.key.clone(), Structure::String(b"1".to_vec()));
                 Ok(Frame::Integer(1))
             }
         }
@@ -2,7 +2,7 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Incrby {
-    key: String,
+    key: Vec<u8>,
     increment: i64,
 }
 
@@ -13,7 +13,7 @@ impl Incrby {
         if args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'incrby' command"));
         }
-        let key = args[1].to_string(); // 键
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
         let increment = args[2].parse::<i64>().map_err(|_| {
             Error::msg("ERR value is not an integer or out of range")
         })?;
@@ -25,13 +25,13 @@ impl Incrby {
             Some(structure) => {
                 match structure {
                     Structure::String(str) => {
-                        match str.parse::<i64>() {
-                            Ok(mut num) => {
+                        match std::str::from_utf8(str).ok().and_then(|s| s.parse::<i64>().ok()) {
+                            Some(mut num) => {
                                 num += self.increment;
-                                *str = num.to_string();
+                                *str = num.to_string().into_bytes();
                                 Ok(Frame::Integer(num))
                             },
-                            Err(_) => {
+                            None => {
                                 let f = "ERR value is not an integer or out of range";
                                 Ok(Frame::Error(f.to_string()))
                             }
@@ -45,7 +45,7 @@ impl Incrby {
             },
             None => {
                 let new_value = self.increment;
-                db.insert(self.key.clone(), Structure::String(new_value.to_string()));
+                db.insert(self.key.clone(), Structure::String(new_value.to_string().into_bytes()));
                 Ok(Frame::Integer(new_value))
             }
         }
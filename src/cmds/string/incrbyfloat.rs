@@ -2,7 +2,7 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct IncrbyFloat {
-    key: String,
+    key: Vec<u8>,
     increment: f64,
 }
 
@@ -12,7 +12,7 @@ impl IncrbyFloat {
         if args.len() != 3 {
             return Err(Error::msg("ERR wrong number of arguments for 'incrbyfloat' command"));
         }
-        let key = args[1].to_string();
+        let key = frame.get_arg_bytes(1).unwrap();
         let increment = args[2].parse::<f64>().map_err(|_| {
             Error::msg("ERR value is not a valid float")
         })?;
@@ -20,7 +20,7 @@ impl IncrbyFloat {
     }
 
     // 改进的浮点数格式化函数
-    fn format_float(value: f64) -> String {
+    pub fn format_float(value: f64) -> String {
         // 处理特殊值
         if value.is_nan() {
             return "nan".to_string();
@@ -60,14 +60,14 @@ impl IncrbyFloat {
             Some(structure) => {
                 match structure {
                     Structure::String(str_value) => {
-                        match str_value.parse::<f64>() {
-                            Ok(current) => {
+                        match std::str::from_utf8(str_value).ok().and_then(|s| s.parse::<f64>().ok()) {
+                            Some(current) => {
                                 let new_value = current + self.increment;
-                                let formatted = Self::format_float(new_value);
+                                let formatted = Self::format_float(new_value).into_bytes();
                                 *str_value = formatted.clone();
-                                Ok(Frame::BulkString(formatted.into()))
+                                Ok(Frame::BulkString(formatted))
                             },
-                            Err(_) => {
+                            None => {
                                 let e = "ERR value is not a valid float";
                                 Ok(Frame::Error(e.to_string()))
                             }
@@ -80,9 +80,9 @@ impl IncrbyFloat {
                 }
             },
             None => {
-                let formatted = Self::format_float(self.increment);
+                let formatted = Self::format_float(self.increment).into_bytes();
                 db.insert(self.key.clone(), Structure::String(formatted.clone()));
-                Ok(Frame::BulkString(formatted.into()))
+                Ok(Frame::BulkString(formatted))
             }
         }
     }
@@ -10,4 +10,9 @@ pub mod incr;
 pub mod incrby;
 pub mod incrbyfloat;
 pub mod decrby;
-pub mod decr;
\ No newline at end of file
+pub mod decr;
+pub mod setnx;
+pub mod setex;
+pub mod getex;
+pub mod getdel;
+pub mod msetnx;
\ No newline at end of file
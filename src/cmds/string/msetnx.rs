@@ -0,0 +1,35 @@
+use anyhow::Error;
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Msetnx {
+    key_vals: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Msetnx {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+
+        let args = frame.get_args_bytes_from_index(1);
+
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(Error::msg("ERR wrong number of arguments for 'msetnx' command"));
+        }
+
+        let key_vals = args.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect();
+
+        Ok(Msetnx { key_vals })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        // 只要有一个键存在，就不执行任何写入
+        for (key, _) in &self.key_vals {
+            if db.get(key).is_some() {
+                return Ok(Frame::Integer(0));
+            }
+        }
+        for (key, val) in self.key_vals {
+            db.insert(key, Structure::String(val));
+        }
+        Ok(Frame::Integer(1))
+    }
+}
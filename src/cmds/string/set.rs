@@ -1,19 +1,101 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use anyhow::Error;
 
-use crate::{store::db::{Db, Structure}, frame::Frame};
+use crate::{cmds::hash::hexpire::now_millis, store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * 过期选项
+ */
+pub enum Expiration {
+    Milliseconds(u64), // EX、PX：距离现在多少毫秒后过期
+    UnixMilliseconds(u64), // EXAT、PXAT：过期的 Unix 时间戳【毫秒】
+    KeepTtl, // KEEPTTL：保留原有的过期时间
+    Persist, // PERSIST：移除过期时间
+}
+
+impl Expiration {
+
+    /**
+     * 解析 EX、PX、EXAT、PXAT 选项
+     *
+     * @param option 选项名称（大写）
+     * @param value 选项值
+     * @param command 命令名称，用于错误信息
+     */
+    pub fn parse(option: &str, value: Option<&String>, command: &str) -> Result<Self, Error> {
+        let value = value.ok_or_else(|| Error::msg("ERR syntax error"))?;
+        let value = value.parse::<i64>().map_err(|_| Error::msg("ERR value is not an integer or out of range"))?;
+        let invalid = || Error::msg(format!("ERR invalid expire time in '{}' command", command));
+        if value <= 0 {
+            return Err(invalid());
+        }
+        let value = value as u64;
+        match option {
+            "EX" => value.checked_mul(1000).map(Expiration::Milliseconds).ok_or_else(invalid),
+            "PX" => Ok(Expiration::Milliseconds(value)),
+            "EXAT" => value.checked_mul(1000).map(Expiration::UnixMilliseconds).ok_or_else(invalid),
+            "PXAT" => Ok(Expiration::UnixMilliseconds(value)),
+            _ => Err(Error::msg("ERR syntax error")),
+        }
+    }
+
+    /**
+     * 转换为用于传播的参数，相对的过期时间改写为 PXAT 时间戳，避免重放时重新计时
+     */
+    pub fn to_args(&self) -> Vec<Vec<u8>> {
+        match self {
+            Expiration::Milliseconds(ttl) => vec![b"PXAT".to_vec(), now_millis().saturating_add(*ttl).to_string().into_bytes()],
+            Expiration::UnixMilliseconds(timestamp) => vec![b"PXAT".to_vec(), timestamp.to_string().into_bytes()],
+            Expiration::KeepTtl => vec![b"KEEPTTL".to_vec()],
+            Expiration::Persist => vec![b"PERSIST".to_vec()],
+        }
+    }
+
+    /**
+     * 将过期选项应用到键上
+     *
+     * @param db 数据库
+     * @param key 键名
+     */
+    pub fn apply(&self, db: &mut Db, key: &[u8]) {
+        match self {
+            Expiration::Milliseconds(ttl) => db.expire(key.to_vec(), *ttl),
+            Expiration::UnixMilliseconds(timestamp) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_millis() as u64;
+                db.expire(key.to_vec(), timestamp.saturating_sub(now));
+            },
+            Expiration::KeepTtl => {},
+            Expiration::Persist => {
+                db.persist(key);
+            },
+        }
+    }
+}
+
+/**
+ * 写入条件
+ */
+#[derive(PartialEq)]
+pub enum Condition {
+    Nx, // 仅当键不存在时写入
+    Xx, // 仅当键存在时写入
+}
 
 pub struct Set {
-    pub key: String,
-    pub val: String,
-    pub ttl: Option<u64>
+    pub key: Vec<u8>,
+    pub val: Vec<u8>,
+    pub expiration: Expiration,
+    pub condition: Option<Condition>,
+    pub get: bool, // 是否返回旧值
 }
 
 impl Set {
 
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error>{
 
-        let key = frame.get_arg(1);
-        let val = frame.get_arg(2);
+        let key = frame.get_arg_bytes(1);
+        let val = frame.get_arg_bytes(2);
 
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'set' command"));
@@ -23,47 +105,101 @@ impl Set {
             return Err(Error::msg("ERR wrong number of arguments for 'set' command"));
         }
 
-        let fianl_key = key.unwrap().to_string(); // 键
-        let final_val = val.unwrap().to_string(); // 值
+        let fianl_key = key.unwrap(); // 键
+        let final_val = val.unwrap(); // 值
 
         let args = frame.get_args();
 
-        // 检测 EX 和 PX 是否存在
-        let mut ttl: Option<u64> = None; // 默认 ttl 为 0
-        for (idx, item) in args.iter().enumerate() {
-            if idx > 2 { // 从第三个参数开始检查，因为前两个是 key 和 val
-                match item.as_str() {
-                    "EX" | "PX" => {
-                        if let Some(ttl_value) = args.get(idx + 1) {
-                            ttl = match item.as_str() {
-                                "EX" => Some(ttl_value.parse::<u64>()? * 1000), // EX 秒
-                                "PX" => Some(ttl_value.parse::<u64>()?), // PX 毫秒
-                                _ => None,
-                            };
-                            break; // 找到 ttl 后退出循环
-                        }
-                    },
-                    _ => continue,
-                }
+        let mut expiration: Option<Expiration> = None;
+        let mut condition: Option<Condition> = None;
+        let mut get = false;
+        let mut idx = 3; // 从第三个参数开始检查，因为前两个是 key 和 val
+        while idx < args.len() {
+            let option = args[idx].to_uppercase();
+            match option.as_str() {
+                "NX" | "XX" if condition.is_none() => {
+                    condition = Some(if option == "NX" { Condition::Nx } else { Condition::Xx });
+                },
+                "GET" if !get => get = true,
+                "KEEPTTL" if expiration.is_none() => expiration = Some(Expiration::KeepTtl),
+                "EX" | "PX" | "EXAT" | "PXAT" if expiration.is_none() => {
+                    expiration = Some(Expiration::parse(&option, args.get(idx + 1), "set")?);
+                    idx += 1;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
             }
+            idx += 1;
         }
         
         Ok(Set { 
             key: fianl_key, 
             val: final_val,
-            ttl: ttl
+            expiration: expiration.unwrap_or(Expiration::Persist),
+            condition,
+            get
         })
     }
 
-    pub fn new(key: String, val: String, ttl: Option<u64>) -> Self {
-        Set { key, val, ttl }
+    pub fn new(key: Vec<u8>, val: Vec<u8>, ttl: Option<u64>) -> Self {
+        let expiration = match ttl {
+            Some(ttl) => Expiration::Milliseconds(ttl),
+            None => Expiration::Persist,
+        };
+        Set { key, val, expiration, condition: None, get: false }
+    }
+
+    /**
+     * 转换为用于传播的命令帧，相对的过期时间改写为时间戳
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![b"SET".to_vec(), self.key.clone(), self.val.clone()];
+        match self.condition {
+            Some(Condition::Nx) => args.push(b"NX".to_vec()),
+            Some(Condition::Xx) => args.push(b"XX".to_vec()),
+            None => {},
+        }
+        if self.get {
+            args.push(b"GET".to_vec());
+        }
+        // 不带过期选项的 SET 本身就会移除过期时间
+        if !matches!(self.expiration, Expiration::Persist) {
+            args.extend(self.expiration.to_args());
+        }
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
     }
 
     pub fn apply(self,db: &mut Db) -> Result<Frame, Error> {
+        let (exists, old_value) = match db.get(&self.key) {
+            Some(Structure::String(value)) => (true, Some(value.clone())),
+            Some(_) if self.get => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            Some(_) => (true, None),
+            None => (false, None),
+        };
+
+        let reply = |value: Option<Vec<u8>>| match value {
+            Some(value) => Frame::BulkString(value),
+            None => Frame::Null,
+        };
+
+        let skip = match self.condition {
+            Some(Condition::Nx) => exists,
+            Some(Condition::Xx) => !exists,
+            None => false,
+        };
+        if skip {
+            return Ok(if self.get { reply(old_value) } else { Frame::Null });
+        }
+
         db.insert(self.key.clone(), Structure::String(self.val));
-        if let Some(ttl) = self.ttl {
-            db.expire(self.key.clone(), ttl);
+        self.expiration.apply(db, &self.key);
+
+        if self.get {
+            Ok(reply(old_value))
+        } else {
+            Ok(Frame::Ok)
         }
-        Ok(Frame::Ok)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,45 @@
+use anyhow::Error;
+
+use crate::{cmds::string::set::Expiration, store::db::{Db, Structure}, frame::Frame};
+
+pub struct Setex {
+    key: Vec<u8>,
+    val: Vec<u8>,
+    expiration: Expiration,
+}
+
+impl Setex {
+
+    /**
+     * 解析 SETEX、PSETEX 命令
+     *
+     * @param frame 命令帧
+     * @param command 命令名称（小写），setex 以秒为单位，psetex 以毫秒为单位
+     */
+    pub fn parse_from_frame(frame: Frame, command: &str) -> Result<Self, Error> {
+        let args = frame.get_args();
+        if args.len() != 4 {
+            return Err(Error::msg(format!("ERR wrong number of arguments for '{}' command", command)));
+        }
+        let key = frame.get_arg_bytes(1).unwrap(); // 键
+        let option = if command == "setex" { "EX" } else { "PX" };
+        let expiration = Expiration::parse(option, args.get(2), command)?; // 过期时间
+        let val = frame.get_arg_bytes(3).unwrap(); // 值
+        Ok(Setex { key, val, expiration })
+    }
+
+    /**
+     * 转换为用于传播的命令帧，改写为带 PXAT 时间戳的 SET
+     */
+    pub fn to_frame(&self) -> Frame {
+        let mut args = vec![b"SET".to_vec(), self.key.clone(), self.val.clone()];
+        args.extend(self.expiration.to_args());
+        Frame::Array(args.into_iter().map(Frame::BulkString).collect())
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        db.insert(self.key.clone(), Structure::String(self.val));
+        self.expiration.apply(db, &self.key);
+        Ok(Frame::Ok)
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Setnx {
+    key: Vec<u8>,
+    val: Vec<u8>,
+}
+
+impl Setnx {
+
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'setnx' command"));
+        }
+        let key = args[1].clone(); // 键
+        let val = args[2].clone(); // 值
+        Ok(Setnx { key, val })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        if db.get(&self.key).is_some() {
+            return Ok(Frame::Integer(0));
+        }
+        db.insert(self.key, Structure::String(self.val));
+        Ok(Frame::Integer(1))
+    }
+}
@@ -2,19 +2,19 @@ use anyhow::Error;
 use crate::{store::db::{Db, Structure}, frame::Frame};
 
 pub struct Strlen {
-    key: String,
+    key: Vec<u8>,
 }
 
 impl Strlen {
     
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let key = frame.get_arg(1);
+        let key = frame.get_arg_bytes(1);
 
         if key.is_none() {
             return Err(Error::msg("ERR wrong number of arguments for 'strlen' command"));
         }
 
-        let final_key = key.unwrap().to_string();
+        let final_key = key.unwrap();
 
         Ok(Strlen {
             key: final_key,
@@ -0,0 +1,47 @@
+use anyhow::Error;
+
+/**
+ * 解析向量参数，支持两种格式：
+ *
+ * FP32 blob：小端序 32 位浮点数组成的二进制串
+ * VALUES num v1 v2 ...：逐个给出的分量
+ *
+ * @param args 命令参数
+ * @param index 向量参数的起始位置
+ * @return (向量, 向量参数之后的位置)
+ */
+pub fn parse_vector(args: &[Vec<u8>], index: usize) -> Result<(Vec<f32>, usize), Error> {
+    let syntax_error = || Error::msg("ERR syntax error");
+    let invalid = || Error::msg("ERR invalid vector specification");
+    let format = args.get(index).ok_or_else(syntax_error)?;
+    let (vector, next) = match String::from_utf8_lossy(format).to_uppercase().as_str() {
+        "FP32" => {
+            let blob = args.get(index + 1).ok_or_else(syntax_error)?;
+            if blob.is_empty() || blob.len() % 4 != 0 {
+                return Err(invalid());
+            }
+            let vector = blob.chunks_exact(4).map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])).collect::<Vec<_>>();
+            (vector, index + 2)
+        },
+        "VALUES" => {
+            let count = args.get(index + 1)
+                .and_then(|count| String::from_utf8_lossy(count).parse::<usize>().ok())
+                .filter(|count| *count > 0)
+                .ok_or_else(invalid)?;
+            let values = args.get(index + 2..index + 2 + count).ok_or_else(invalid)?;
+            let vector = values.iter()
+                .map(|value| String::from_utf8_lossy(value).parse::<f32>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+            (vector, index + 2 + count)
+        },
+        _ => return Err(syntax_error()),
+    };
+    if vector.iter().any(|value| !value.is_finite()) {
+        return Err(invalid());
+    }
+    Ok((vector, next))
+}
+
+pub fn dimension_mismatch(got: usize, expected: usize) -> String {
+    format!("ERR Vector dimension mismatch - got {} but set has {}", got, expected)
+}
@@ -0,0 +1,9 @@
+pub mod input;
+pub mod vadd;
+pub mod vrem;
+pub mod vemb;
+pub mod vcard;
+pub mod vdim;
+pub mod vsim;
+pub mod vindex;
+pub mod vinfo;
\ No newline at end of file
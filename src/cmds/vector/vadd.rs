@@ -0,0 +1,53 @@
+use anyhow::Error;
+
+use crate::{cmds::vector::input::{dimension_mismatch, parse_vector}, store::{db::{Db, Structure}, vector::Vector}, frame::Frame};
+
+/**
+ * VADD key (FP32 blob | VALUES num value [value ...]) element
+ *
+ * 添加或替换元素的向量，集合的维度由第一个向量决定
+ */
+pub struct Vadd {
+    key: Vec<u8>,
+    vector: Vec<f32>,
+    element: Vec<u8>,
+}
+
+impl Vadd {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vadd' command"));
+        }
+        let key = args[1].clone(); // 键
+        let (vector, index) = parse_vector(&args, 2)?; // 向量
+        if index + 1 != args.len() {
+            return Err(Error::msg("ERR syntax error"));
+        }
+        let element = args[index].clone(); // 元素
+        Ok(Vadd { key, vector, element })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let collection = match db.get_mut(&self.key) {
+            Some(Structure::VectorCollection(collection)) => collection,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => {
+                db.insert(self.key.clone(), Structure::VectorCollection(Vector::new(self.vector.len())));
+                match db.get_mut(&self.key) {
+                    Some(Structure::VectorCollection(collection)) => collection,
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        if collection.dimension != self.vector.len() {
+            return Ok(Frame::Error(dimension_mismatch(self.vector.len(), collection.dimension)));
+        }
+        let added = collection.insert(self.element, self.vector);
+        Ok(Frame::Integer(added as i64))
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Vdim {
+    key: Vec<u8>,
+}
+
+impl Vdim {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vdim' command"));
+        }
+        let key = args[1].clone(); // 键
+        Ok(Vdim { key })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::VectorCollection(collection)) => Ok(Frame::Integer(collection.dimension as i64)),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Error("ERR key does not exist".to_string())),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Vemb {
+    key: Vec<u8>,
+    element: Vec<u8>,
+}
+
+impl Vemb {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vemb' command"));
+        }
+        let key = args[1].clone(); // 键
+        let element = args[2].clone(); // 元素
+        Ok(Vemb { key, element })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        match db.get(&self.key) {
+            Some(Structure::VectorCollection(collection)) => match collection.get(&self.element) {
+                Some(vector) => Ok(Frame::Array(vector.iter().map(|value| Frame::Double(*value as f64)).collect())),
+                None => Ok(Frame::Null),
+            },
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                Ok(Frame::Error(f.to_string()))
+            },
+            None => Ok(Frame::Null),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Error;
+
+use crate::{store::{db::{Db, Structure}, hnsw::Hnsw, vector::Metric}, frame::Frame};
+
+// 默认的索引参数
+const DEFAULT_M: usize = 16;
+const DEFAULT_EF_CONSTRUCTION: usize = 200;
+const DEFAULT_EF_SEARCH: usize = 100;
+
+// 参数上限
+const MAX_M: usize = 1024;
+pub const MAX_EF: usize = 1_000_000;
+
+/**
+ * 解析候选集大小
+ *
+ * @param value 参数值
+ */
+pub fn parse_ef(value: &[u8]) -> Result<usize, Error> {
+    match String::from_utf8_lossy(value).parse::<usize>() {
+        Ok(ef) if (1..=MAX_EF).contains(&ef) => Ok(ef),
+        _ => Err(Error::msg(format!("ERR EF must be between 1 and {}", MAX_EF))),
+    }
+}
+
+enum Action {
+    Create { m: usize, ef_construction: usize, ef_search: usize, metric: Metric },
+    Drop,
+}
+
+/**
+ * VINDEX key CREATE [M m] [EFCONSTRUCTION ef] [EFSEARCH ef] [METRIC COSINE | L2 | IP]
+ * VINDEX key DROP
+ *
+ * 为向量集合创建（或按新参数重建）HNSW 索引，或删除索引
+ */
+pub struct Vindex {
+    key: Vec<u8>,
+    action: Action,
+}
+
+impl Vindex {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vindex' command"));
+        }
+        let key = args[1].clone(); // 键
+        let subcommand = String::from_utf8_lossy(&args[2]).to_uppercase();
+        let action = match subcommand.as_str() {
+            "CREATE" => {
+                let mut m = DEFAULT_M;
+                let mut ef_construction = DEFAULT_EF_CONSTRUCTION;
+                let mut ef_search = DEFAULT_EF_SEARCH;
+                let mut metric = Metric::Cosine;
+                let mut index = 3;
+                while index < args.len() {
+                    let option = String::from_utf8_lossy(&args[index]).to_uppercase();
+                    let value = args.get(index + 1).ok_or_else(|| Error::msg("ERR syntax error"))?;
+                    match option.as_str() {
+                        "M" => {
+                            m = match String::from_utf8_lossy(value).parse::<usize>() {
+                                Ok(m) if (2..=MAX_M).contains(&m) => m,
+                                _ => return Err(Error::msg(format!("ERR M must be between 2 and {}", MAX_M))),
+                            };
+                        },
+                        "EFCONSTRUCTION" => ef_construction = parse_ef(value)?,
+                        "EFSEARCH" => ef_search = parse_ef(value)?,
+                        "METRIC" => {
+                            metric = Metric::parse(&String::from_utf8_lossy(value)).ok_or_else(|| Error::msg("ERR unsupported metric. please use COSINE, L2, IP"))?;
+                        },
+                        _ => return Err(Error::msg("ERR syntax error")),
+                    }
+                    index += 2;
+                }
+                Action::Create { m, ef_construction, ef_search, metric }
+            },
+            "DROP" if args.len() == 3 => Action::Drop,
+            "DROP" => return Err(Error::msg("ERR syntax error")),
+            _ => return Err(Error::msg(format!("ERR unknown subcommand '{}'", subcommand))),
+        };
+        Ok(Vindex { key, action })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let collection = match db.get_mut(&self.key) {
+            Some(Structure::VectorCollection(collection)) => collection,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Error("ERR key does not exist".to_string())),
+        };
+        match self.action {
+            Action::Create { m, ef_construction, ef_search, metric } => {
+                collection.create_index(Hnsw::new(m, ef_construction, ef_search, metric));
+                Ok(Frame::Ok)
+            },
+            Action::Drop => Ok(Frame::Integer(collection.drop_index() as i64)),
+        }
+    }
+}
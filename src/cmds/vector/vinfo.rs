@@ -0,0 +1,52 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+/**
+ * VINFO key
+ *
+ * 返回向量集合的维度、元素数量及索引参数
+ */
+pub struct Vinfo {
+    key: Vec<u8>,
+}
+
+impl Vinfo {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 2 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vinfo' command"));
+        }
+        let key = args[1].clone(); // 键
+        Ok(Vinfo { key })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let collection = match db.get(&self.key) {
+            Some(Structure::VectorCollection(collection)) => collection,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Null),
+        };
+
+        let field = |name: &str| Frame::BulkString(name.as_bytes().to_vec());
+        let mut frames = vec![
+            field("dimension"), Frame::Integer(collection.dimension as i64),
+            field("size"), Frame::Integer(collection.len() as i64),
+        ];
+        match collection.index() {
+            Some(index) => frames.extend([
+                field("index"), field("hnsw"),
+                field("metric"), field(index.metric().name()),
+                field("m"), Frame::Integer(index.m() as i64),
+                field("ef-construction"), Frame::Integer(index.ef_construction() as i64),
+                field("ef-search"), Frame::Integer(index.ef_search() as i64),
+                field("max-level"), Frame::Integer(index.max_level().map_or(-1, |level| level as i64)),
+            ]),
+            None => frames.extend([field("index"), field("none")]),
+        }
+        Ok(Frame::Array(frames))
+    }
+}
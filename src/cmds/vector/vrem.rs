@@ -0,0 +1,34 @@
+use anyhow::Error;
+
+use crate::{store::db::{Db, Structure}, frame::Frame};
+
+pub struct Vrem {
+    key: Vec<u8>,
+    element: Vec<u8>,
+}
+
+impl Vrem {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() != 3 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vrem' command"));
+        }
+        let key = args[1].clone(); // 键
+        let element = args[2].clone(); // 元素
+        Ok(Vrem { key, element })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let removed = match db.get_mut(&self.key) {
+            Some(Structure::VectorCollection(collection)) => collection.remove(&self.element),
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => false,
+        };
+        // 删除最后一个元素后同时删除键
+        db.remove_if_empty(&self.key);
+        Ok(Frame::Integer(removed as i64))
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Error;
+
+use crate::{cmds::vector::{input::{dimension_mismatch, parse_vector}, vindex::parse_ef}, store::{db::{Db, Structure}, vector::Metric}, frame::Frame};
+
+/**
+ * 查询目标
+ */
+enum Query {
+    Element(Vec<u8>),
+    Vector(Vec<f32>),
+}
+
+/**
+ * VSIM key (ELE element | FP32 blob | VALUES num value [value ...]) [WITHSCORES] [COUNT count] [METRIC COSINE | L2 | IP] [EF ef] [TRUTH]
+ *
+ * 返回与查询目标最相似的元素，余弦相似度与内积按分数降序，欧氏距离按分数升序。
+ * 集合建有索引且度量方式一致时使用索引近似查询，否则逐个比较
+ *
+ * @param count 最多返回的数量，默认为 10
+ * @param metric 度量方式，默认与索引一致，没有索引时为余弦相似度
+ * @param ef 索引查询的候选集大小，0 表示使用索引的默认值
+ * @param truth 不使用索引，返回精确结果
+ */
+pub struct Vsim {
+    key: Vec<u8>,
+    query: Query,
+    count: usize,
+    metric: Option<Metric>,
+    ef: usize,
+    truth: bool,
+    with_scores: bool,
+}
+
+impl Vsim {
+    pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
+        let args = frame.get_args_bytes();
+        if args.len() < 4 {
+            return Err(Error::msg("ERR wrong number of arguments for 'vsim' command"));
+        }
+        let key = args[1].clone(); // 键
+        let (query, mut index) = if String::from_utf8_lossy(&args[2]).eq_ignore_ascii_case("ELE") {
+            (Query::Element(args[3].clone()), 4)
+        } else {
+            let (vector, index) = parse_vector(&args, 2)?;
+            (Query::Vector(vector), index)
+        };
+
+        let mut count = 10;
+        let mut metric = None;
+        let mut ef = 0;
+        let mut truth = false;
+        let mut with_scores = false;
+        while index < args.len() {
+            let option = String::from_utf8_lossy(&args[index]).to_uppercase();
+            match (option.as_str(), args.get(index + 1)) {
+                ("WITHSCORES", _) => with_scores = true,
+                ("TRUTH", _) => truth = true,
+                ("EF", Some(value)) => {
+                    ef = parse_ef(value)?;
+                    index += 1;
+                },
+                ("COUNT", Some(value)) => {
+                    count = match String::from_utf8_lossy(value).parse::<i64>() {
+                        Ok(count) if count > 0 => count as usize,
+                        Ok(_) => return Err(Error::msg("ERR COUNT must be > 0")),
+                        Err(_) => return Err(Error::msg("ERR value is not an integer or out of range")),
+                    };
+                    index += 1;
+                },
+                ("METRIC", Some(value)) => {
+                    metric = Some(Metric::parse(&String::from_utf8_lossy(value)).ok_or_else(|| Error::msg("ERR unsupported metric. please use COSINE, L2, IP"))?);
+                    index += 1;
+                },
+                _ => return Err(Error::msg("ERR syntax error")),
+            }
+            index += 1;
+        }
+
+        Ok(Vsim { key, query, count, metric, ef, truth, with_scores })
+    }
+
+    pub fn apply(self, db: &mut Db) -> Result<Frame, Error> {
+        let collection = match db.get(&self.key) {
+            Some(Structure::VectorCollection(collection)) => collection,
+            Some(_) => {
+                let f = "ERR Operation against a key holding the wrong kind of value";
+                return Ok(Frame::Error(f.to_string()));
+            },
+            None => return Ok(Frame::Array(Vec::new())),
+        };
+
+        let query = match &self.query {
+            Query::Element(element) => match collection.get(element) {
+                Some(vector) => vector,
+                None => return Ok(Frame::Error("ERR element not found in set".to_string())),
+            },
+            Query::Vector(vector) => {
+                if vector.len() != collection.dimension {
+                    return Ok(Frame::Error(dimension_mismatch(vector.len(), collection.dimension)));
+                }
+                vector
+            },
+        };
+
+        let metric = self.metric.or(collection.index().map(|index| index.metric())).unwrap_or(Metric::Cosine);
+        let approximate = if self.truth { None } else { collection.approximate_search(query, metric, self.count, self.ef) };
+        let results = approximate.unwrap_or_else(|| collection.search(query, metric, self.count));
+
+        let mut frames = Vec::new();
+        for (element, score) in results {
+            frames.push(Frame::BulkString(element.to_vec()));
+            if self.with_scores {
+                frames.push(Frame::Double(score as f64));
+            }
+        }
+        Ok(Frame::Array(frames))
+    }
+}
@@ -2,21 +2,46 @@ use anyhow::Error;
 
 use crate::{
     cmds::{
-        connect::{auth::Auth, client::Client, echo::Echo, ping::Ping, select::Select}, hash::{
+        bitmap::{
+            bitcount::Bitcount, bitfield::Bitfield, bitop::Bitop, bitpos::Bitpos, getbit::Getbit, setbit::Setbit,
+        }, connect::{auth::Auth, client::Client, echo::Echo, hello::Hello, ping::Ping, select::Select}, hash::{
             hdel::Hdel, hexists::Hexists, hget::Hget, hgetall::Hgetall, hkeys::Hkeys, hlen::Hlen,
             hmget::Hmget, hmset::Hmset, hset::Hset, hsetnx::Hsetnx, hstrlen::Hstrlen, hvals::Hvals,
+            hincrby::Hincrby, hincrbyfloat::HincrbyFloat, hrandfield::Hrandfield,
+            hexpire::Hexpire, httl::Httl, hpersist::Hpersist, hgetex::Hgetex, hscan::Hscan,
+        }, geo::{
+            geoadd::Geoadd, geopos::Geopos, geodist::Geodist, geohash::Geohash,
+            geosearch::Geosearch, geosearchstore::Geosearchstore,
+        }, stream::{
+            xadd::Xadd, xrange::Xrange, xread::Xread, xlen::Xlen, xdel::Xdel, xtrim::Xtrim,
+            xgroup::Xgroup, xreadgroup::Xreadgroup, xack::Xack, xpending::Xpending,
+            xclaim::Xclaim, xautoclaim::Xautoclaim, xinfo::Xinfo,
+        }, vector::{
+            vadd::Vadd, vrem::Vrem, vemb::Vemb, vcard::Vcard, vdim::Vdim, vsim::Vsim,
+            vindex::Vindex, vinfo::Vinfo,
+        }, hyperloglog::{
+            pfadd::Pfadd, pfcount::Pfcount, pfmerge::Pfmerge,
         }, key::{
-            del::Del, exists::Exists, expire::Expire, expireat::ExpireAt, keys::Keys, persist::Persist, pexpire::Pexpire, pexpireat::PexpireAt, pttl::Pttl, randomkey::RandomKey, rename::Rename, renamenx::Renamenx, r#move::Move, ttl::Ttl, r#type::Type
+            del::Del, exists::Exists, expire::Expire, expireat::ExpireAt, keys::Keys, persist::Persist, pexpire::Pexpire, pexpireat::PexpireAt, pttl::Pttl, randomkey::RandomKey, rename::Rename, renamenx::Renamenx, r#move::Move, ttl::Ttl, r#type::Type, scan::Scan
         }, listing::{
             lindex::Lindex, llen::Llen, lpop::Lpop, lpush::Lpush, lpushx::Lpushx, lrange::Lrange,
-            lset::Lset, rpop::Rpop, rpush::Rpush, rpushx::Rpushx,
+            lset::Lset, rpop::Rpop, rpush::Rpush, rpushx::Rpushx, linsert::Linsert, lrem::Lrem,
+            ltrim::Ltrim, lpos::Lpos, lmove::Lmove, rpoplpush::Rpoplpush,
+            blpop::Blpop, brpop::Brpop, blmove::Blmove, blmpop::Blmpop,
         }, server::{bgsave::Bgsave, dbsize::Dbsize, flushall::Flushall, flushdb::Flushdb, info::Info, save::Save}, server_sync::{psync::Psync, replconf::Replconf}, set::{
             sadd::Sadd, scard::Scard, sinter::Sinter, sismember::Sismember, smembers::Smembers,
             spop::Spop, srem::Srem, sunion::Sunion, sunionstore::Sunionstore,
+            sdiff::Sdiff, sdiffstore::Sdiffstore, sinterstore::Sinterstore, sintercard::Sintercard,
+            smismember::Smismember, smove::Smove, srandmember::Srandmember, sscan::Sscan,
         }, sorted_set::{
             zadd::Zadd, zcard::Zcard, zcount::Zcount, zrank::Zrank, zrem::Zrem, zscore::Zscore,
+            zrange::Zrange, zrangestore::Zrangestore, zrevrank::Zrevrank, zincrby::Zincrby,
+            zmscore::Zmscore, zrandmember::Zrandmember, zlexcount::Zlexcount, zremrange::Zremrange,
+            zpop::Zpop, bzpop::Bzpop, zunion::Zunion, zunionstore::Zunionstore,
+            zscan::Zscan,
         }, string::{
-            append::Append, decr::Decr, decrby::Decrby, get::Get, getrange::GetRange, getset::GetSet, incr::Incr, incrby::Incrby, incrbyfloat::IncrbyFloat, mget::Mget, mset::Mset, set::Set, strlen::Strlen
+            append::Append, decr::Decr, decrby::Decrby, get::Get, getrange::GetRange, getset::GetSet, incr::Incr, incrby::Incrby, incrbyfloat::IncrbyFloat, mget::Mget, mset::Mset, set::Set, strlen::Strlen,
+            setnx::Setnx, setex::Setex, getex::Getex, getdel::Getdel, msetnx::Msetnx
         }, transaction::{
             multi::Multi, exec::Exec, discard::Discard
         }, unknown::Unknown
@@ -33,8 +58,10 @@ pub enum Command {
     Expire(Expire),
     Del(Del),
     Keys(Keys),
+    Scan(Scan),
     Flushdb(Flushdb),
     Get(Get),
+    Hello(Hello),
     GetRange(GetRange),
     Ping(Ping),
     Pttl(Pttl),
@@ -46,10 +73,32 @@ pub enum Command {
     Mget(Mget),
     Strlen(Strlen),
     Sunionstore(Sunionstore),
+    Sdiff(Sdiff),
+    Sdiffstore(Sdiffstore),
+    Sinterstore(Sinterstore),
+    Sintercard(Sintercard),
+    Smismember(Smismember),
+    Smove(Smove),
+    Srandmember(Srandmember),
+    Sscan(Sscan),
     Renamenx(Renamenx),
     Rename(Rename),
     Exists(Exists),
     Hset(Hset),
+    Hincrby(Hincrby),
+    HincrbyFloat(HincrbyFloat),
+    Hrandfield(Hrandfield),
+    Hexpire(Hexpire),
+    Hpexpire(Hexpire),
+    Hexpireat(Hexpire),
+    Hpexpireat(Hexpire),
+    Httl(Httl),
+    Hpttl(Httl),
+    Hexpiretime(Httl),
+    Hpexpiretime(Httl),
+    Hpersist(Hpersist),
+    Hgetex(Hgetex),
+    Hscan(Hscan),
     Hget(Hget),
     Type(Type),
     Hmset(Hmset),
@@ -90,6 +139,60 @@ pub enum Command {
     Zcard(Zcard),
     Zrank(Zrank),
     Zrem(Zrem),
+    Zrange(Zrange),
+    Zrevrange(Zrange),
+    Zrangebyscore(Zrange),
+    Zrevrangebyscore(Zrange),
+    Zrangebylex(Zrange),
+    Zrevrangebylex(Zrange),
+    Zrangestore(Zrangestore),
+    Zrevrank(Zrevrank),
+    Zincrby(Zincrby),
+    Zmscore(Zmscore),
+    Zrandmember(Zrandmember),
+    Zlexcount(Zlexcount),
+    Zremrangebyrank(Zremrange),
+    Zremrangebyscore(Zremrange),
+    Zremrangebylex(Zremrange),
+    Zpopmin(Zpop),
+    Zpopmax(Zpop),
+    Bzpopmin(Bzpop),
+    Bzpopmax(Bzpop),
+    Zunion(Zunion),
+    Zinter(Zunion),
+    Zdiff(Zunion),
+    Zunionstore(Zunionstore),
+    Zinterstore(Zunionstore),
+    Zdiffstore(Zunionstore),
+    Zscan(Zscan),
+    Geoadd(Geoadd),
+    Geopos(Geopos),
+    Geodist(Geodist),
+    Geohash(Geohash),
+    Geosearch(Geosearch),
+    Geosearchstore(Geosearchstore),
+    Xadd(Xadd),
+    Xrange(Xrange),
+    Xrevrange(Xrange),
+    Xread(Xread),
+    Xlen(Xlen),
+    Xdel(Xdel),
+    Xtrim(Xtrim),
+    Xgroup(Xgroup),
+    Xreadgroup(Xreadgroup),
+    Xack(Xack),
+    Xpending(Xpending),
+    Xclaim(Xclaim),
+    Xautoclaim(Xautoclaim),
+    Xinfo(Xinfo),
+    Vadd(Vadd),
+    Vrem(Vrem),
+    Vemb(Vemb),
+    Vcard(Vcard),
+    Vdim(Vdim),
+    Vsim(Vsim),
+    Vindex(Vindex),
+    Vinfo(Vinfo),
     Incrby(Incrby),
     Decrby(Decrby),
     Echo(Echo),
@@ -105,6 +208,31 @@ pub enum Command {
     GetSet(GetSet),
     Info(Info),
     Move(Move),
+    Setnx(Setnx),
+    Setex(Setex),
+    Psetex(Setex),
+    Getex(Getex),
+    Getdel(Getdel),
+    Msetnx(Msetnx),
+    Setbit(Setbit),
+    Getbit(Getbit),
+    Bitcount(Bitcount),
+    Bitpos(Bitpos),
+    Bitop(Bitop),
+    Bitfield(Bitfield),
+    Pfadd(Pfadd),
+    Pfcount(Pfcount),
+    Pfmerge(Pfmerge),
+    Linsert(Linsert),
+    Lrem(Lrem),
+    Ltrim(Ltrim),
+    Lpos(Lpos),
+    Lmove(Lmove),
+    Rpoplpush(Rpoplpush),
+    Blpop(Blpop),
+    Brpop(Brpop),
+    Blmove(Blmove),
+    Blmpop(Blmpop),
     // 事务命令
     Multi(Multi),
     Exec(Exec),
@@ -113,9 +241,14 @@ pub enum Command {
 
 impl Command {
     pub fn parse_from_frame(frame: Frame) -> Result<Self, Error> {
-        let command_name = frame.get_arg(0).unwrap();
+        // 空数组、空值以及非数组的帧都不是合法的命令
+        let command_name = match frame.get_arg(0) {
+            Some(name) => name,
+            None => return Err(Error::msg("ERR Protocol error: expected a non-empty array of bulk strings")),
+        };
         let command = match command_name.to_uppercase().as_str() {
             "AUTH" => Command::Auth(Auth::parse_from_frame(frame)?),
+            "HELLO" => Command::Hello(Hello::parse_from_frame(frame)?),
             "DEL" => Command::Del(Del::parse_from_frame(frame)?),
             "EXPIRE" => Command::Expire(Expire::parse_from_frame(frame)?),
             "FLUSHALL" => Command::Flushall(Flushall::parse_from_frame(frame)?),
@@ -137,12 +270,27 @@ impl Command {
             "APPEND" => Command::Append(Append::parse_from_frame(frame)?),
             "DBSIZE" => Command::Dbsize(Dbsize::parse_from_frame(frame)?),
             "HSET" => Command::Hset(Hset::parse_from_frame(frame)?),
+            "HINCRBY" => Command::Hincrby(Hincrby::parse_from_frame(frame)?),
+            "HINCRBYFLOAT" => Command::HincrbyFloat(HincrbyFloat::parse_from_frame(frame)?),
+            "HRANDFIELD" => Command::Hrandfield(Hrandfield::parse_from_frame(frame)?),
+            "HEXPIRE" => Command::Hexpire(Hexpire::parse_from_frame(frame, "hexpire")?),
+            "HPEXPIRE" => Command::Hpexpire(Hexpire::parse_from_frame(frame, "hpexpire")?),
+            "HEXPIREAT" => Command::Hexpireat(Hexpire::parse_from_frame(frame, "hexpireat")?),
+            "HPEXPIREAT" => Command::Hpexpireat(Hexpire::parse_from_frame(frame, "hpexpireat")?),
+            "HTTL" => Command::Httl(Httl::parse_from_frame(frame, "httl")?),
+            "HPTTL" => Command::Hpttl(Httl::parse_from_frame(frame, "hpttl")?),
+            "HEXPIRETIME" => Command::Hexpiretime(Httl::parse_from_frame(frame, "hexpiretime")?),
+            "HPEXPIRETIME" => Command::Hpexpiretime(Httl::parse_from_frame(frame, "hpexpiretime")?),
+            "HPERSIST" => Command::Hpersist(Hpersist::parse_from_frame(frame)?),
+            "HGETEX" => Command::Hgetex(Hgetex::parse_from_frame(frame)?),
+            "HSCAN" => Command::Hscan(Hscan::parse_from_frame(frame)?),
             "HGET" => Command::Hget(Hget::parse_from_frame(frame)?),
             "HMSET" => Command::Hmset(Hmset::parse_from_frame(frame)?),
             "HDEL" => Command::Hdel(Hdel::parse_from_frame(frame)?),
             "HEXISTS" => Command::Hexists(Hexists::parse_from_frame(frame)?),
             "HSTRLEN" => Command::Hstrlen(Hstrlen::parse_from_frame(frame)?),
             "KEYS" => Command::Keys(Keys::parse_from_frame(frame)?),
+            "SCAN" => Command::Scan(Scan::parse_from_frame(frame)?),
             "HMGET" => Command::Hmget(Hmget::parse_from_frame(frame)?),
             "HLEN" => Command::Hlen(Hlen::parse_from_frame(frame)?),
             "HGETALL" => Command::Hgetall(Hgetall::parse_from_frame(frame)?),
@@ -161,6 +309,14 @@ impl Command {
             "RENAMENX" => Command::Renamenx(Renamenx::parse_from_frame(frame)?),
             "EXPIREAT" => Command::ExpireAt(ExpireAt::parse_from_frame(frame)?),
             "SUNIONSTORE" => Command::Sunionstore(Sunionstore::parse_from_frame(frame)?),
+            "SDIFF" => Command::Sdiff(Sdiff::parse_from_frame(frame)?),
+            "SDIFFSTORE" => Command::Sdiffstore(Sdiffstore::parse_from_frame(frame)?),
+            "SINTERSTORE" => Command::Sinterstore(Sinterstore::parse_from_frame(frame)?),
+            "SINTERCARD" => Command::Sintercard(Sintercard::parse_from_frame(frame)?),
+            "SMISMEMBER" => Command::Smismember(Smismember::parse_from_frame(frame)?),
+            "SMOVE" => Command::Smove(Smove::parse_from_frame(frame)?),
+            "SRANDMEMBER" => Command::Srandmember(Srandmember::parse_from_frame(frame)?),
+            "SSCAN" => Command::Sscan(Sscan::parse_from_frame(frame)?),
             "SISMEMBER" => Command::Sismember(Sismember::parse_from_frame(frame)?),
             "SMEMBERS" => Command::Smembers(Smembers::parse_from_frame(frame)?),
             "SPOP" => Command::Spop(Spop::parse_from_frame(frame)?),
@@ -178,6 +334,60 @@ impl Command {
             "ZREM" => Command::Zrem(Zrem::parse_from_frame(frame)?),
             "SINTER" => Command::Sinter(Sinter::parse_from_frame(frame)?),
             "ZRANK" => Command::Zrank(Zrank::parse_from_frame(frame)?),
+            "ZRANGE" => Command::Zrange(Zrange::parse_from_frame(frame, "zrange")?),
+            "ZREVRANGE" => Command::Zrevrange(Zrange::parse_from_frame(frame, "zrevrange")?),
+            "ZRANGEBYSCORE" => Command::Zrangebyscore(Zrange::parse_from_frame(frame, "zrangebyscore")?),
+            "ZREVRANGEBYSCORE" => Command::Zrevrangebyscore(Zrange::parse_from_frame(frame, "zrevrangebyscore")?),
+            "ZRANGEBYLEX" => Command::Zrangebylex(Zrange::parse_from_frame(frame, "zrangebylex")?),
+            "ZREVRANGEBYLEX" => Command::Zrevrangebylex(Zrange::parse_from_frame(frame, "zrevrangebylex")?),
+            "ZRANGESTORE" => Command::Zrangestore(Zrangestore::parse_from_frame(frame)?),
+            "ZREVRANK" => Command::Zrevrank(Zrevrank::parse_from_frame(frame)?),
+            "ZINCRBY" => Command::Zincrby(Zincrby::parse_from_frame(frame)?),
+            "ZMSCORE" => Command::Zmscore(Zmscore::parse_from_frame(frame)?),
+            "ZRANDMEMBER" => Command::Zrandmember(Zrandmember::parse_from_frame(frame)?),
+            "ZLEXCOUNT" => Command::Zlexcount(Zlexcount::parse_from_frame(frame)?),
+            "ZREMRANGEBYRANK" => Command::Zremrangebyrank(Zremrange::parse_from_frame(frame, "zremrangebyrank")?),
+            "ZREMRANGEBYSCORE" => Command::Zremrangebyscore(Zremrange::parse_from_frame(frame, "zremrangebyscore")?),
+            "ZREMRANGEBYLEX" => Command::Zremrangebylex(Zremrange::parse_from_frame(frame, "zremrangebylex")?),
+            "ZPOPMIN" => Command::Zpopmin(Zpop::parse_from_frame(frame, "zpopmin")?),
+            "ZPOPMAX" => Command::Zpopmax(Zpop::parse_from_frame(frame, "zpopmax")?),
+            "BZPOPMIN" => Command::Bzpopmin(Bzpop::parse_from_frame(frame, "bzpopmin")?),
+            "BZPOPMAX" => Command::Bzpopmax(Bzpop::parse_from_frame(frame, "bzpopmax")?),
+            "ZUNION" => Command::Zunion(Zunion::parse_from_frame(frame, "zunion")?),
+            "ZINTER" => Command::Zinter(Zunion::parse_from_frame(frame, "zinter")?),
+            "ZDIFF" => Command::Zdiff(Zunion::parse_from_frame(frame, "zdiff")?),
+            "ZUNIONSTORE" => Command::Zunionstore(Zunionstore::parse_from_frame(frame, "zunionstore")?),
+            "ZINTERSTORE" => Command::Zinterstore(Zunionstore::parse_from_frame(frame, "zinterstore")?),
+            "ZDIFFSTORE" => Command::Zdiffstore(Zunionstore::parse_from_frame(frame, "zdiffstore")?),
+            "ZSCAN" => Command::Zscan(Zscan::parse_from_frame(frame)?),
+            "GEOADD" => Command::Geoadd(Geoadd::parse_from_frame(frame)?),
+            "GEOPOS" => Command::Geopos(Geopos::parse_from_frame(frame)?),
+            "GEODIST" => Command::Geodist(Geodist::parse_from_frame(frame)?),
+            "GEOHASH" => Command::Geohash(Geohash::parse_from_frame(frame)?),
+            "GEOSEARCH" => Command::Geosearch(Geosearch::parse_from_frame(frame)?),
+            "GEOSEARCHSTORE" => Command::Geosearchstore(Geosearchstore::parse_from_frame(frame)?),
+            "XADD" => Command::Xadd(Xadd::parse_from_frame(frame)?),
+            "XRANGE" => Command::Xrange(Xrange::parse_from_frame(frame, "xrange")?),
+            "XREVRANGE" => Command::Xrevrange(Xrange::parse_from_frame(frame, "xrevrange")?),
+            "XREAD" => Command::Xread(Xread::parse_from_frame(frame)?),
+            "XLEN" => Command::Xlen(Xlen::parse_from_frame(frame)?),
+            "XDEL" => Command::Xdel(Xdel::parse_from_frame(frame)?),
+            "XTRIM" => Command::Xtrim(Xtrim::parse_from_frame(frame)?),
+            "XGROUP" => Command::Xgroup(Xgroup::parse_from_frame(frame)?),
+            "XREADGROUP" => Command::Xreadgroup(Xreadgroup::parse_from_frame(frame)?),
+            "XACK" => Command::Xack(Xack::parse_from_frame(frame)?),
+            "XPENDING" => Command::Xpending(Xpending::parse_from_frame(frame)?),
+            "XCLAIM" => Command::Xclaim(Xclaim::parse_from_frame(frame)?),
+            "XAUTOCLAIM" => Command::Xautoclaim(Xautoclaim::parse_from_frame(frame)?),
+            "XINFO" => Command::Xinfo(Xinfo::parse_from_frame(frame)?),
+            "VADD" => Command::Vadd(Vadd::parse_from_frame(frame)?),
+            "VREM" => Command::Vrem(Vrem::parse_from_frame(frame)?),
+            "VEMB" => Command::Vemb(Vemb::parse_from_frame(frame)?),
+            "VCARD" => Command::Vcard(Vcard::parse_from_frame(frame)?),
+            "VDIM" => Command::Vdim(Vdim::parse_from_frame(frame)?),
+            "VSIM" => Command::Vsim(Vsim::parse_from_frame(frame)?),
+            "VINDEX" => Command::Vindex(Vindex::parse_from_frame(frame)?),
+            "VINFO" => Command::Vinfo(Vinfo::parse_from_frame(frame)?),
             "INCRBY" => Command::Incrby(Incrby::parse_from_frame(frame)?),
             "INCRBYFLOAT" => Command::IncrbyFloat(IncrbyFloat::parse_from_frame(frame)?),
             "DECRBY" => Command::Decrby(Decrby::parse_from_frame(frame)?),
@@ -191,6 +401,31 @@ impl Command {
             "CLIENT" => Command::Client(Client::parse_from_frame(frame)?),
             "INFO" => Command::Info(Info::parse_from_frame(frame)?),
             "MOVE" => Command::Move(Move::parse_from_frame(frame)?),
+            "SETNX" => Command::Setnx(Setnx::parse_from_frame(frame)?),
+            "SETEX" => Command::Setex(Setex::parse_from_frame(frame, "setex")?),
+            "PSETEX" => Command::Psetex(Setex::parse_from_frame(frame, "psetex")?),
+            "GETEX" => Command::Getex(Getex::parse_from_frame(frame)?),
+            "GETDEL" => Command::Getdel(Getdel::parse_from_frame(frame)?),
+            "MSETNX" => Command::Msetnx(Msetnx::parse_from_frame(frame)?),
+            "SETBIT" => Command::Setbit(Setbit::parse_from_frame(frame)?),
+            "GETBIT" => Command::Getbit(Getbit::parse_from_frame(frame)?),
+            "BITCOUNT" => Command::Bitcount(Bitcount::parse_from_frame(frame)?),
+            "BITPOS" => Command::Bitpos(Bitpos::parse_from_frame(frame)?),
+            "BITOP" => Command::Bitop(Bitop::parse_from_frame(frame)?),
+            "BITFIELD" => Command::Bitfield(Bitfield::parse_from_frame(frame)?),
+            "PFADD" => Command::Pfadd(Pfadd::parse_from_frame(frame)?),
+            "PFCOUNT" => Command::Pfcount(Pfcount::parse_from_frame(frame)?),
+            "PFMERGE" => Command::Pfmerge(Pfmerge::parse_from_frame(frame)?),
+            "LINSERT" => Command::Linsert(Linsert::parse_from_frame(frame)?),
+            "LREM" => Command::Lrem(Lrem::parse_from_frame(frame)?),
+            "LTRIM" => Command::Ltrim(Ltrim::parse_from_frame(frame)?),
+            "LPOS" => Command::Lpos(Lpos::parse_from_frame(frame)?),
+            "LMOVE" => Command::Lmove(Lmove::parse_from_frame(frame)?),
+            "RPOPLPUSH" => Command::Rpoplpush(Rpoplpush::parse_from_frame(frame)?),
+            "BLPOP" => Command::Blpop(Blpop::parse_from_frame(frame)?),
+            "BRPOP" => Command::Brpop(Brpop::parse_from_frame(frame)?),
+            "BLMOVE" => Command::Blmove(Blmove::parse_from_frame(frame)?),
+            "BLMPOP" => Command::Blmpop(Blmpop::parse_from_frame(frame)?),
             "MULTI" => Command::Multi(Multi::parse_from_frame(frame)?),
             "EXEC" => Command::Exec(Exec::parse_from_frame(frame)?),
             "DISCARD" => Command::Discard(Discard::parse_from_frame(frame)?),
@@ -199,8 +434,62 @@ impl Command {
         Ok(command)
     }
 
-    pub fn propagate_aof_if_needed(&self) -> bool {
+    /**
+     * 传播到 AOF 与从节点的命令帧，相对的过期时间、字段过期时间与认领时间改写为时间戳
+     *
+     * @param frame 客户端发送的命令帧
+     */
+    pub fn propagation_frame(&self, frame: Frame) -> Frame {
         match self {
+            Command::Hexpire(hexpire) |
+            Command::Hpexpire(hexpire) |
+            Command::Hexpireat(hexpire) |
+            Command::Hpexpireat(hexpire) => hexpire.to_frame(),
+            Command::Hgetex(hgetex) => hgetex.to_frame(),
+            Command::Set(set) => set.to_frame(),
+            Command::Setex(setex) |
+            Command::Psetex(setex) => setex.to_frame(),
+            Command::Getex(getex) => getex.to_frame(),
+            Command::Xadd(xadd) => xadd.to_frame(),
+            Command::Xclaim(xclaim) => xclaim.to_frame(),
+            _ => frame,
+        }
+    }
+
+    /**
+     * 根据执行结果补全传播帧，XADD 自动生成的 ID 改写为实际的 ID，
+     * XCLAIM 与 XAUTOCLAIM 改写为只认领实际认领成功的消息，SPOP 改写为删除实际弹出的成员
+     *
+     * @param frame propagation_frame 返回的命令帧
+     * @param reply 执行结果
+     */
+    pub fn resolve_propagation_frame(frame: Frame, reply: &Frame) -> Frame {
+        match frame.get_arg(0) {
+            Some(name) if name.eq_ignore_ascii_case("XADD") => Xadd::resolve_frame(frame, reply),
+            Some(name) if name.eq_ignore_ascii_case("XCLAIM") => Xclaim::resolve_frame(frame, reply),
+            Some(name) if name.eq_ignore_ascii_case("XAUTOCLAIM") => Xautoclaim::resolve_frame(frame, reply),
+            Some(name) if name.eq_ignore_ascii_case("SPOP") => Spop::resolve_frame(frame, reply),
+            _ => frame,
+        }
+    }
+
+    /**
+     * 是否传播实际执行的命令而不是客户端发送的命令，实际执行的命令由数据库在执行时给出
+     */
+    pub fn propagates_effects(&self) -> bool {
+        matches!(self,
+            Command::Blpop(_) |
+            Command::Brpop(_) |
+            Command::Blmove(_) |
+            Command::Blmpop(_) |
+            Command::Bzpopmin(_) |
+            Command::Bzpopmax(_) |
+            Command::Xreadgroup(_)
+        )
+    }
+
+    pub fn propagate_aof_if_needed(&self) -> bool {
+        matches!(self,
             Command::Del(_) |
             Command::Expire(_) |
             Command::ExpireAt(_) |
@@ -218,11 +507,39 @@ impl Command {
             Command::IncrbyFloat(_) |
             Command::Mset(_) |
             Command::Set(_) | 
+            Command::Setnx(_) |
+            Command::Setex(_) |
+            Command::Psetex(_) |
+            Command::Getex(_) |
+            Command::Getdel(_) |
+            Command::Msetnx(_) |
+            Command::Setbit(_) |
+            Command::Bitop(_) |
+            Command::Bitfield(_) |
+            Command::Pfadd(_) |
+            Command::Pfmerge(_) |
+            Command::Linsert(_) |
+            Command::Lrem(_) |
+            Command::Ltrim(_) |
+            Command::Lmove(_) |
+            Command::Rpoplpush(_) |
+            Command::Blpop(_) |
+            Command::Brpop(_) |
+            Command::Blmove(_) |
+            Command::Blmpop(_) |
             Command::Flushall(_) |
             Command::Flushdb(_) |
             Command::Hdel(_) |
             Command::Hmset(_) |
             Command::Hset(_) |
+            Command::Hincrby(_) |
+            Command::HincrbyFloat(_) |
+            Command::Hexpire(_) |
+            Command::Hpexpire(_) |
+            Command::Hexpireat(_) |
+            Command::Hpexpireat(_) |
+            Command::Hpersist(_) |
+            Command::Hgetex(_) |
             Command::Hsetnx(_) |
             Command::Lpop(_) |
             Command::Lpush(_) |
@@ -236,10 +553,37 @@ impl Command {
             Command::Spop(_) |
             Command::Srem(_) |
             Command::Sunionstore(_) |
+            Command::Sdiffstore(_) |
+            Command::Sinterstore(_) |
+            Command::Smove(_) |
             Command::Zadd(_) |
             Command::Zrem(_) |
-            Command::Move(_) => true,
-            _ => false,
-        }
+            Command::Zrangestore(_) |
+            Command::Zincrby(_) |
+            Command::Zremrangebyrank(_) |
+            Command::Zremrangebyscore(_) |
+            Command::Zremrangebylex(_) |
+            Command::Zpopmin(_) |
+            Command::Zpopmax(_) |
+            Command::Bzpopmin(_) |
+            Command::Bzpopmax(_) |
+            Command::Zunionstore(_) |
+            Command::Zinterstore(_) |
+            Command::Zdiffstore(_) |
+            Command::Geoadd(_) |
+            Command::Geosearchstore(_) |
+            Command::Xadd(_) |
+            Command::Xdel(_) |
+            Command::Xtrim(_) |
+            Command::Xgroup(_) |
+            Command::Xreadgroup(_) |
+            Command::Xack(_) |
+            Command::Xclaim(_) |
+            Command::Xautoclaim(_) |
+            Command::Vadd(_) |
+            Command::Vrem(_) |
+            Command::Vindex(_) |
+            Command::Move(_)
+        )
     }
 }
\ No newline at end of file
@@ -1,6 +1,91 @@
+use std::fmt;
+
 use crate::persistence::rdb_file::RdbFile;
 use anyhow::Error;
 
+// 单行（类型与长度声明）的最大长度
+const MAX_LINE_LEN: usize = 64 * 1024;
+
+// 批量字符串的最大长度（512MB）
+const MAX_BULK_LEN: i64 = 512 * 1024 * 1024;
+
+// 数组的最大元素数量
+const MAX_MULTIBULK_LEN: i64 = i32::MAX as i64;
+
+// 聚合类型的最大嵌套层数，避免恶意数据耗尽栈空间
+const MAX_NESTING_DEPTH: usize = 128;
+
+/**
+ * 协议错误
+ *
+ * 客户端发送了不符合协议的数据，连接在回复错误后关闭
+ */
+#[derive(Debug)]
+pub struct ProtocolError {
+    message: String,
+}
+
+impl ProtocolError {
+
+    /**
+     * 创建协议错误
+     *
+     * @param message 错误描述
+     */
+    pub fn error(message: impl Into<String>) -> Error {
+        Error::new(ProtocolError { message: message.into() })
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ERR Protocol error: {}", self.message)
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+// 带长度声明的二进制内容（长度为 -1 时为 None）及下一个值的开始位置
+type DecodedBlob<'a> = (Option<&'a [u8]>, usize);
+
+/*
+ * 协议版本
+ *
+ * 客户端通过 HELLO 命令协商，默认为 RESP2
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Protocol {
+    #[default]
+    Resp2,
+    Resp3,
+}
+
+impl Protocol {
+
+    /**
+     * 通过版本号创建协议
+     *
+     * @param version 版本号
+     */
+    pub fn from_version(version: i64) -> Option<Protocol> {
+        match version {
+            2 => Some(Protocol::Resp2),
+            3 => Some(Protocol::Resp3),
+            _ => None,
+        }
+    }
+
+    /**
+     * 获取协议版本号
+     */
+    pub fn version(&self) -> i64 {
+        match self {
+            Protocol::Resp2 => 2,
+            Protocol::Resp3 => 3,
+        }
+    }
+}
+
 /*
  * 命令帧枚举
  */
@@ -11,376 +96,671 @@ pub enum Frame {
     RDBFile(Vec<u8>),
     SimpleString(String),
     Array(Vec<Frame>),
-    BulkString(String),
+    BulkString(Vec<u8>),
     Error(String),
-    Null
+    Null,
+    NullArray,
+    // RESP3
+    Map(Vec<(Frame, Frame)>),
+    Set(Vec<Frame>),
+    Double(f64),
+    Boolean(bool),
+    BigNumber(String),
+    VerbatimString(String, String),
+    Push(Vec<Frame>),
+    Attribute(Vec<(Frame, Frame)>),
+}
+
+/*
+ * 将 frame 转化为字符串
+ */
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Frame::Ok => write!(f, "OK"),
+            Frame::Integer(i) => write!(f, "{}", i),
+            Frame::RDBFile(data) => write!(f, "[RDBFile {} bytes]", data.len()),
+            Frame::SimpleString(s) => write!(f, "{}", s),
+            Frame::BulkString(s) => write!(f, "{}", String::from_utf8_lossy(s)),
+            Frame::Error(e) => write!(f, "{}", e),
+            Frame::Null | Frame::NullArray => Ok(()),
+            Frame::Double(d) => write!(f, "{}", Frame::format_double(*d)),
+            Frame::Boolean(b) => write!(f, "{}", if *b { 1 } else { 0 }),
+            Frame::BigNumber(n) => write!(f, "{}", n),
+            Frame::VerbatimString(_, text) => write!(f, "{}", text),
+            Frame::Array(arr) | Frame::Set(arr) | Frame::Push(arr) => {
+                let items: Vec<String> = arr.iter().map(|item| item.to_string()).collect();
+                write!(f, "{}", items.join(" ").trim_end())
+            },
+            Frame::Map(pairs) | Frame::Attribute(pairs) => {
+                let items: Vec<String> = pairs.iter().map(|(key, value)| format!("{} {}", key, value)).collect();
+                write!(f, "{}", items.join(" ").trim_end())
+            },
+        }
+    }
 }
 
 impl Frame {
 
     /**
-     * 将 frame 转化为字符串
+     * 将 frame 转换为 bytes【RESP2】
      * 
      * @param self 本身
      */
-    pub fn to_string(&self) -> String {
-        match self {
-            Frame::Ok => String::from("OK"),
-            Frame::Integer(i) => i.to_string(),
-            Frame::RDBFile(data) => format!("[RDBFile {} bytes]", data.len()),
-            Frame::SimpleString(s) => s.clone(),
-            Frame::BulkString(s) => s.clone(),
-            Frame::Error(e) => e.clone(),
-            Frame::Null => String::new(),
-            Frame::Array(arr) => {
-                let mut result = String::new();
-                for item in arr {
-                    result.push_str(&item.to_string());
-                    result.push(' ');
-                }
-                result.trim_end().to_string()
-            },
-        }
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.as_bytes_with(Protocol::Resp2)
     }
 
     /**
-     * 将 frame 转换为 bytes
-     * 
+     * 按指定协议将 frame 转换为 bytes
+     *
+     * RESP2 下，RESP3 专有类型会降级为等价的 RESP2 类型：
+     * Map 展开为键值交替的数组，Set、Push 转为数组，Double、BigNumber、
+     * VerbatimString 转为批量字符串，Boolean 转为整数，Attribute 被忽略。
+     *
      * @param self 本身
+     * @param protocol 协议版本
      */
-    pub fn as_bytes(&self) -> Vec<u8> {
+    pub fn as_bytes_with(&self, protocol: Protocol) -> Vec<u8> {
+        let resp3 = protocol == Protocol::Resp3;
         match self {
             Frame::Ok => b"+OK\r\n".to_vec(),
             Frame::Integer(i) => format!(":{}\r\n", i).into_bytes(),
             Frame::SimpleString(s) => format!("+{}\r\n", s).into_bytes(),
             Frame::Error(e) => format!("-{}\r\n", e).into_bytes(),
-            Frame::Null => b"$-1\r\n".to_vec(),
+            Frame::Null => {
+                if resp3 { b"_\r\n".to_vec() } else { b"$-1\r\n".to_vec() }
+            },
+            Frame::NullArray => {
+                if resp3 { b"_\r\n".to_vec() } else { b"*-1\r\n".to_vec() }
+            },
             Frame::RDBFile(data) => {
-                let mut bytes = format!("~{}\r\n", data.len()).into_bytes();
+                let mut bytes = format!("${}\r\n", data.len()).into_bytes();
                 bytes.extend(data);
-                bytes.extend(b"\r\n");
                 bytes
             },
-            Frame::Array(arr) => {
-                let mut bytes = format!("*{}\r\n", arr.len()).into_bytes();
-                for item in arr {
-                    bytes.extend(item.as_bytes());
+            Frame::Array(arr) => Frame::aggregate_bytes(b'*', arr, protocol),
+            Frame::Set(arr) => Frame::aggregate_bytes(if resp3 { b'~' } else { b'*' }, arr, protocol),
+            Frame::Push(arr) => Frame::aggregate_bytes(if resp3 { b'>' } else { b'*' }, arr, protocol),
+            Frame::Map(pairs) => {
+                let mut bytes = if resp3 {
+                    format!("%{}\r\n", pairs.len()).into_bytes()
+                } else {
+                    format!("*{}\r\n", pairs.len() * 2).into_bytes()
+                };
+                for (key, value) in pairs {
+                    bytes.extend(key.as_bytes_with(protocol));
+                    bytes.extend(value.as_bytes_with(protocol));
                 }
                 bytes
             },
-            Frame::BulkString(s) => {
-                let mut bytes = format!("${}\r\n", s.len()).into_bytes();
-                bytes.extend(s.as_bytes());
-                bytes.extend(b"\r\n");
+            Frame::Attribute(pairs) => {
+                if !resp3 {
+                    return Vec::new();
+                }
+                let mut bytes = format!("|{}\r\n", pairs.len()).into_bytes();
+                for (key, value) in pairs {
+                    bytes.extend(key.as_bytes_with(protocol));
+                    bytes.extend(value.as_bytes_with(protocol));
+                }
                 bytes
             },
+            Frame::BulkString(s) => Frame::bulk_bytes(s),
+            Frame::Double(d) => {
+                if resp3 {
+                    format!(",{}\r\n", Frame::format_double(*d)).into_bytes()
+                } else {
+                    Frame::bulk_bytes(Frame::format_double(*d).as_bytes())
+                }
+            },
+            Frame::Boolean(b) => {
+                match (resp3, b) {
+                    (true, true) => b"#t\r\n".to_vec(),
+                    (true, false) => b"#f\r\n".to_vec(),
+                    (false, true) => b":1\r\n".to_vec(),
+                    (false, false) => b":0\r\n".to_vec(),
+                }
+            },
+            Frame::BigNumber(n) => {
+                if resp3 { format!("({}\r\n", n).into_bytes() } else { Frame::bulk_bytes(n.as_bytes()) }
+            },
+            Frame::VerbatimString(format, text) => {
+                if resp3 {
+                    let mut bytes = format!("={}\r\n", text.len() + 4).into_bytes();
+                    bytes.extend(format.as_bytes());
+                    bytes.push(b':');
+                    bytes.extend(text.as_bytes());
+                    bytes.extend(b"\r\n");
+                    bytes
+                } else {
+                    Frame::bulk_bytes(text.as_bytes())
+                }
+            },
         }
     }
-    
+
     /**
-     * 通过解析 bytes 创建命令帧
+     * 批量字符串编码
      *
-     * @param bytes 二进制
+     * @param s 内容
      */
-    pub fn parse_from_bytes(bytes: &[u8]) -> Result<Frame, Error> {
-        match bytes[0] {
-            b'+' => Frame::parse_simple_string(bytes),
-            b'~' => Frame::parse_rdb_file(bytes),
-            b'*' => Frame::parse_array(bytes),
-            _ => Err(Error::msg("Unknown frame type")),
-        }
+    fn bulk_bytes(s: &[u8]) -> Vec<u8> {
+        let mut bytes = format!("${}\r\n", s.len()).into_bytes();
+        bytes.extend(s);
+        bytes.extend(b"\r\n");
+        bytes
     }
 
     /**
-     * 解析粘连的多个命令帧
+     * 聚合类型编码（Array、Set、Push）
      *
-     * @param bytes 二进制数据
+     * @param prefix 类型前缀
+     * @param items 元素
+     * @param protocol 协议版本
      */
-    pub fn parse_multiple_frames(bytes: &[u8]) -> Result<Vec<Frame>, Error> {
-        let mut frames = Vec::new();
-        let mut position = 0;
-        
-        while position < bytes.len() {
-            // 查找下一个完整的命令帧
-            if let Some(frame_end) = Frame::find_frame_end(&bytes[position..]) {
-                let frame_bytes = &bytes[position..position + frame_end];
-                let frame = Frame::parse_from_bytes(frame_bytes)?;
-                frames.push(frame);
-                position += frame_end;
-            } else {
-                // 如果找不到完整的帧结束位置，将剩余的数据作为最后一个帧处理
-                let frame_bytes = &bytes[position..];
-                let frame = Frame::parse_from_bytes(frame_bytes)?;
-                frames.push(frame);
-                break;
-            }
+    fn aggregate_bytes(prefix: u8, items: &[Frame], protocol: Protocol) -> Vec<u8> {
+        let mut bytes = vec![prefix];
+        bytes.extend(format!("{}\r\n", items.len()).into_bytes());
+        for item in items {
+            bytes.extend(item.as_bytes_with(protocol));
         }
-        
-        Ok(frames)
+        bytes
     }
-    
+
     /**
-     * 查找单个命令帧的结束位置
+     * 浮点数格式化，与 Redis 保持一致（inf、-inf、nan）
      *
-     * @param bytes 二进制数据
+     * @param d 浮点数
      */
-    fn find_frame_end(bytes: &[u8]) -> Option<usize> {
-       
-        if bytes.is_empty() {
-            return None;
-        }
-        
-        match bytes[0] {
-            b'*' => {
-                // 数组类型的帧
-                // 首先找到数组长度行的结束位置
-                let mut line_end = None;
-                for i in 1..bytes.len().min(20) { // 限制搜索范围，防止过长的第一行
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        line_end = Some(i + 2);
-                        break;
-                    }
-                }
-                
-                let line_end = line_end?;
-                if line_end >= bytes.len() {
-                    return None;
-                }
-                
-                // 解析数组长度
-                let line = std::str::from_utf8(&bytes[1..line_end - 2]).ok()?;
-                let array_len: usize = line.parse().ok()?;
-                
-                // 计算数组元素的结束位置
-                let mut current_pos = line_end;
-                for _ in 0..array_len {
-                    if current_pos >= bytes.len() {
-                        return None;
-                    }
-                    
-                    // 查找当前元素的结束位置
-                    if let Some(element_end) = Frame::find_element_end(&bytes[current_pos..]) {
-                        current_pos += element_end;
-                    } else {
-                        return None;
-                    }
-                }
-                
-                Some(current_pos)
-            },
-            b'+' | b'-' | b':' => {
-                // 简单字符串、错误、整数类型
-                for i in 1..bytes.len() {
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        return Some(i + 2);
-                    }
-                }
-                None
-            },
-            b'$' => {
-                // 批量字符串类型
-                // 找到长度行的结束
-                let mut line_end = None;
-                for i in 1..bytes.len().min(20) {
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        line_end = Some(i + 2);
-                        break;
-                    }
-                }
-                
-                let line_end = line_end?;
-                if line_end >= bytes.len() {
-                    return None;
-                }
-                
-                // 解析字符串长度
-                let line = std::str::from_utf8(&bytes[1..line_end - 2]).ok()?;
-                if line == "-1" {
-                    // NULL批量字符串
-                    return Some(line_end);
-                }
-                
-                let str_len: usize = line.parse().ok()?;
-                
-                // 字符串内容 + \r\n
-                if line_end + str_len + 2 <= bytes.len() {
-                    Some(line_end + str_len + 2)
-                } else {
-                    None
-                }
-            },
-            b'~' => {
-                // RDB文件类型
-                let mut len_end = None;
-                for i in 1..bytes.len().min(20) {
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        len_end = Some(i + 2);
-                        break;
-                    }
-                }
-                
-                let len_end = len_end?;
-                if len_end >= bytes.len() {
-                    return None;
-                }
-                
-                let len_str = std::str::from_utf8(&bytes[1..len_end - 2]).ok()?;
-                let data_len: usize = len_str.parse().ok()?;
-                
-                if len_end + data_len + 2 <= bytes.len() {
-                    Some(len_end + data_len + 2)
-                } else {
-                    None
-                }
-            },
-            _ => None,
+    pub fn format_double(d: f64) -> String {
+        if d.is_nan() {
+            "nan".to_string()
+        } else if d.is_infinite() {
+            if d > 0.0 { "inf".to_string() } else { "-inf".to_string() }
+        } else {
+            d.to_string()
         }
     }
     
     /**
-     * 查找元素的结束位置
+     * 增量解析：从缓冲区头部解析出一个完整的命令帧
      *
-     * @param bytes 二进制数据
+     * 支持 as_bytes 能够产生的全部 RESP2、RESP3 类型（包括嵌套聚合类型），
+     * 其他内容按内联命令解析。数据不完整时返回 Ok(None)，由调用方继续读取后重试；
+     * 数据格式错误时返回协议错误，而不是 panic。
+     *
+     * @param bytes 缓冲区数据
+     * @return 命令帧及其占用的字节数
      */
-    fn find_element_end(bytes: &[u8]) -> Option<usize> {
-        if bytes.is_empty() {
-            return None;
-        }
-        
-        match bytes[0] {
-            b'*' => Frame::find_frame_end(bytes),
-            b'+' | b'-' | b':' => {
-                for i in 1..bytes.len() {
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        return Some(i + 2);
-                    }
-                }
-                None
-            },
-            b'$' => {
-                // 找到长度行的结束
-                let mut line_end = None;
-                for i in 1..bytes.len().min(20) {
-                    if bytes[i] == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        line_end = Some(i + 2);
-                        break;
-                    }
-                }
-                
-                let line_end = line_end?;
-                if line_end >= bytes.len() {
-                    return None;
-                }
-                
-                // 解析字符串长度
-                let line = std::str::from_utf8(&bytes[1..line_end - 2]).ok()?;
-                if line == "-1" {
-                    // NULL批量字符串
-                    return Some(line_end);
-                }
-                
-                let str_len: usize = line.parse().ok()?;
-                
-                // 字符串内容 + \r\n
-                if line_end + str_len + 2 <= bytes.len() {
-                    Some(line_end + str_len + 2)
-                } else {
-                    None
-                }
-            },
-            _ => None,
+    pub fn decode(bytes: &[u8]) -> Result<Option<(Frame, usize)>, Error> {
+        match bytes.first() {
+            None => Ok(None),
+            Some(prefix) if Frame::is_type_prefix(*prefix) => Frame::decode_value(bytes, 0, 0),
+            Some(_) => Frame::decode_inline(bytes),
         }
     }
 
     /**
-     * 简单字符串
+     * 是否为 RESP 类型前缀
      *
-     * @param bytes 二进制
+     * @param prefix 首字节
      */
-    fn parse_simple_string(bytes: &[u8]) -> Result<Frame, Error> {
-        let end = bytes.iter().position(|&x| x == b'\r').unwrap();
-        let content = String::from_utf8(bytes[1..end].to_vec())?;
-        Ok(Frame::SimpleString(content))
+    fn is_type_prefix(prefix: u8) -> bool {
+        matches!(prefix, b'+' | b'-' | b':' | b'$' | b'*' | b'_' | b',' | b'#' | b'(' | b'=' | b'%' | b'~' | b'>' | b'|')
     }
 
     /**
-     * 正确解析 RDB 文件帧
-     * 
-     * @param bytes 二进制
+     * 增量解析指定位置的单个值
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     * @param depth 当前的嵌套层数
      */
-    fn parse_rdb_file(bytes: &[u8]) -> Result<Frame, Error> {
-
-        let mut len_end = None;
-        for (i, &byte) in bytes.iter().enumerate() {
-            if byte == b'\r' {
-                len_end = Some(i);
-                break;
-            }
+    fn decode_value(bytes: &[u8], start: usize, depth: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let prefix = match bytes.get(start) {
+            Some(prefix) => *prefix,
+            None => return Ok(None),
+        };
+        match prefix {
+            b'$' => return Frame::decode_bulk_string(bytes, start),
+            b'=' => return Frame::decode_verbatim_string(bytes, start),
+            b'*' | b'~' | b'>' => return Frame::decode_aggregate(bytes, start, depth + 1),
+            b'%' | b'|' => return Frame::decode_pairs(bytes, start, depth + 1),
+            _ => {}
         }
 
-        let len_end = match len_end {
-            Some(pos) => pos,
-            None => return Err(Error::msg("Invalid RDB format: missing CR")),
+        let (line, next) = match Frame::decode_line(bytes, start)? {
+            Some(line) => line,
+            None => return Ok(None),
         };
+        let content = &line[1..];
+        let text = || String::from_utf8_lossy(content).to_string();
+        let frame = match prefix {
+            b'+' => Frame::SimpleString(text()),
+            b'-' => Frame::Error(text()),
+            b':' => Frame::Integer(Frame::parse_length(content).map_err(|_| ProtocolError::error("invalid integer"))?),
+            b'_' => Frame::Null,
+            b',' => Frame::Double(text().parse::<f64>().map_err(|_| ProtocolError::error("invalid double"))?),
+            b'#' => match content {
+                b"t" => Frame::Boolean(true),
+                b"f" => Frame::Boolean(false),
+                _ => return Err(ProtocolError::error("invalid boolean")),
+            },
+            b'(' => Frame::BigNumber(text()),
+            other => return Err(ProtocolError::error(format!("unexpected type '{}'", other as char))),
+        };
+        Ok(Some((frame, next)))
+    }
 
-        let len_bytes = &bytes[1..len_end];
-        let len_str = match std::str::from_utf8(len_bytes) {
-            Ok(s) => s,
-            Err(e) => return Err(Error::msg(format!("Invalid UTF-8: {}", e))),
+    /**
+     * 增量解析聚合类型（Array、Set、Push），元素可以是任意类型
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     * @param depth 嵌套层数
+     */
+    fn decode_aggregate(bytes: &[u8], start: usize, depth: usize) -> Result<Option<(Frame, usize)>, Error> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(ProtocolError::error("too deeply nested"));
+        }
+        let (prefix, count, mut position) = match Frame::decode_aggregate_header(bytes, start)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let count = match count {
+            Some(count) => count,
+            None => return Ok(Some((Frame::NullArray, position))),
         };
 
-        let data_len = match len_str.parse::<usize>() {
-            Ok(n) => n,
-            Err(e) => return Err(Error::msg(format!("Invalid RDB length: {} ({})", len_str, e))),
+        let mut frames = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            match Frame::decode_value(bytes, position, depth)? {
+                Some((frame, next)) => {
+                    frames.push(frame);
+                    position = next;
+                },
+                None => return Ok(None),
+            }
+        }
+        Ok(Some((Frame::aggregate(prefix, frames), position)))
+    }
+
+    /**
+     * 增量解析聚合类型（Array、Set、Push）的头部
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     * @return (类型前缀, 元素数量, 第一个元素的开始位置)，数量为 None 表示空数组（*-1）
+     */
+    pub fn decode_aggregate_header(bytes: &[u8], start: usize) -> Result<Option<(u8, Option<usize>, usize)>, Error> {
+        let (header, next) = match Frame::decode_line(bytes, start)? {
+            Some(line) => line,
+            None => return Ok(None),
         };
+        let count = Frame::decode_length(&header[1..], "multibulk length", MAX_MULTIBULK_LEN)?;
+        Ok(Some((header[0], usize::try_from(count).ok(), next)))
+    }
 
-        let data_start = len_end + 2;
-        let data_end = data_start + data_len;
+    /**
+     * 增量解析顶层聚合类型中的一个元素
+     *
+     * @param bytes 缓冲区数据，从元素的开始位置起
+     */
+    pub fn decode_element(bytes: &[u8]) -> Result<Option<(Frame, usize)>, Error> {
+        Frame::decode_value(bytes, 0, 1)
+    }
 
-        if bytes.len() < data_end + 2 {
-            return Err(Error::msg(format!("RDB data incomplete: expected {} bytes, got {}", data_end + 2, bytes.len())));
+    /**
+     * 按类型前缀构造聚合类型
+     *
+     * @param prefix 类型前缀
+     * @param frames 元素
+     */
+    pub fn aggregate(prefix: u8, frames: Vec<Frame>) -> Frame {
+        match prefix {
+            b'~' => Frame::Set(frames),
+            b'>' => Frame::Push(frames),
+            _ => Frame::Array(frames),
         }
+    }
 
-        if bytes[data_end] != b'\r' || bytes[data_end + 1] != b'\n' {
-            return Err(Error::msg("Invalid RDB terminator"));
+    /**
+     * 增量解析键值对类型（Map、Attribute）
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     * @param depth 嵌套层数
+     */
+    fn decode_pairs(bytes: &[u8], start: usize, depth: usize) -> Result<Option<(Frame, usize)>, Error> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(ProtocolError::error("too deeply nested"));
+        }
+        let (header, mut position) = match Frame::decode_line(bytes, start)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let count = Frame::decode_length(&header[1..], "map length", MAX_MULTIBULK_LEN)?;
+        if count < 0 {
+            return Err(ProtocolError::error("invalid map length"));
         }
 
-        let mut data = Vec::with_capacity(data_len);
-        for &byte in &bytes[data_start..data_end] {
-            data.push(byte);
+        let mut pairs = Vec::with_capacity((count as usize).min(1024));
+        for _ in 0..count {
+            let (key, next) = match Frame::decode_value(bytes, position, depth)? {
+                Some(key) => key,
+                None => return Ok(None),
+            };
+            let (value, next) = match Frame::decode_value(bytes, next, depth)? {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+            pairs.push((key, value));
+            position = next;
         }
 
-        Ok(Frame::RDBFile(data))
+        let frame = match header[0] {
+            b'|' => Frame::Attribute(pairs),
+            _ => Frame::Map(pairs),
+        };
+        Ok(Some((frame, position)))
+    }
+
+    /**
+     * 增量解析批量字符串，按声明的长度读取
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     */
+    fn decode_bulk_string(bytes: &[u8], start: usize) -> Result<Option<(Frame, usize)>, Error> {
+        match Frame::decode_blob(bytes, start)? {
+            Some((Some(content), next)) => Ok(Some((Frame::BulkString(content.to_vec()), next))),
+            Some((None, next)) => Ok(Some((Frame::Null, next))),
+            None => Ok(None),
+        }
     }
 
+    /**
+     * 增量解析逐字字符串（格式为 fmt:text）
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     */
+    fn decode_verbatim_string(bytes: &[u8], start: usize) -> Result<Option<(Frame, usize)>, Error> {
+        match Frame::decode_blob(bytes, start)? {
+            Some((Some(content), next)) => {
+                if content.len() < 4 || content[3] != b':' {
+                    return Err(ProtocolError::error("invalid verbatim string"));
+                }
+                let format = String::from_utf8_lossy(&content[..3]).to_string();
+                let text = String::from_utf8_lossy(&content[4..]).to_string();
+                Ok(Some((Frame::VerbatimString(format, text), next)))
+            },
+            Some((None, _)) => Err(ProtocolError::error("invalid verbatim string")),
+            None => Ok(None),
+        }
+    }
 
     /**
-     * 数组字符串
+     * 增量读取带长度声明的二进制内容（以 \r\n 结尾）
      *
-     * @param bytes 二进制
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     * @return 内容（长度为 -1 时为 None）及下一个值的开始位置
      */
-    fn  parse_array(bytes: &[u8]) -> Result<Frame, Error> {
-        let mut frames = Vec::new();
+    fn decode_blob(bytes: &[u8], start: usize) -> Result<Option<DecodedBlob<'_>>, Error> {
+        let (header, next) = match Frame::decode_line(bytes, start)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let len = Frame::decode_length(&header[1..], "bulk length", MAX_BULK_LEN)?;
+        if len < 0 {
+            return Ok(Some((None, next)));
+        }
+        let end = next + len as usize;
+        if bytes.len() < end + 2 {
+            return Ok(None);
+        }
+        if &bytes[end..end + 2] != b"\r\n" {
+            return Err(ProtocolError::error("bulk string is not terminated by CRLF"));
+        }
+        Ok(Some((Some(&bytes[next..end]), end + 2)))
+    }
+
+    /**
+     * 增量解析 RDB 文件帧
+     *
+     * 与 Redis 一致，RDB 文件以 $<len>\r\n<payload> 的形式传输，末尾不带 \r\n，
+     * 因此只能在复制握手阶段由调用方显式解析
+     *
+     * @param bytes 缓冲区数据
+     */
+    pub fn decode_rdb_file(bytes: &[u8]) -> Result<Option<(Frame, usize)>, Error> {
+        let (header, next) = match Frame::decode_line(bytes, 0)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        if header[0] != b'$' {
+            return Err(ProtocolError::error("expected RDB file"));
+        }
+        let len = Frame::decode_length(&header[1..], "rdb length", i64::MAX)?;
+        if len < 0 {
+            return Err(ProtocolError::error("invalid rdb length"));
+        }
+        let end = next + len as usize;
+        if bytes.len() < end {
+            return Ok(None);
+        }
+        Ok(Some((Frame::RDBFile(bytes[next..end].to_vec()), end)))
+    }
+
+    /**
+     * 增量解析内联命令（telnet、nc 等客户端直接发送的文本命令）
+     *
+     * 以 \n 作为命令结束符，可选的 \r 会被忽略，空行会被跳过
+     *
+     * @param bytes 缓冲区数据
+     */
+    fn decode_inline(bytes: &[u8]) -> Result<Option<(Frame, usize)>, Error> {
+        // 跳过的空行计入占用的字节数
         let mut start = 0;
+        loop {
+            let rest = &bytes[start..];
+            let end = match rest.iter().position(|&b| b == b'\n') {
+                Some(end) => end,
+                None if rest.len() > MAX_LINE_LEN => return Err(ProtocolError::error("too big inline request")),
+                None => return Ok(None),
+            };
+            if end > MAX_LINE_LEN {
+                return Err(ProtocolError::error("too big inline request"));
+            }
 
-        for (i, &item) in bytes.iter().enumerate() {
+            let line = rest[..end].strip_suffix(b"\r").unwrap_or(&rest[..end]);
+            let args = Frame::split_inline_args(line)?;
+            if !args.is_empty() {
+                let frames = args.into_iter().map(Frame::BulkString).collect();
+                return Ok(Some((Frame::Array(frames), start + end + 1)));
+            }
+            start += end + 1;
+            match bytes.get(start) {
+                None => return Ok(None),
+                // 空行之后是 RESP 格式的命令
+                Some(prefix) if Frame::is_type_prefix(*prefix) => {
+                    return match Frame::decode_value(bytes, start, 0)? {
+                        Some((frame, next)) => Ok(Some((frame, next))),
+                        None => Ok(None),
+                    };
+                },
+                Some(_) => {},
+            }
+        }
+    }
 
-            if item == b'\r' && i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
+    /**
+     * 按 Redis 规则拆分内联命令参数
+     *
+     * 支持双引号（\n、\r、\t、\b、\a、\\、\"、\xHH 转义）与单引号（\' 转义），
+     * 引号闭合后必须紧跟空白字符或行尾
+     *
+     * @param line 命令行内容
+     */
+    fn split_inline_args(line: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+        let unbalanced = || ProtocolError::error("unbalanced quotes in request");
+        let mut args = Vec::new();
+        let mut i = 0;
 
-                let part = match std::str::from_utf8(&bytes[start..i]) {
-                    Ok(v) => v,
-                    Err(_) => return Err(Error::msg("Invalid UTF-8 sequence")),
-                };
+        loop {
+            while i < line.len() && line[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if i >= line.len() {
+                return Ok(args);
+            }
 
-                if !((part.starts_with('*') && part.len()!= 1) || part.starts_with('$')) {
-                    frames.push(Frame::BulkString(part.to_string()));
+            let mut current = Vec::new();
+            let mut in_double = false;
+            let mut in_single = false;
+            loop {
+                if in_double {
+                    match line.get(i) {
+                        None => return Err(unbalanced()),
+                        Some(b'\\') if i + 3 < line.len() && line[i + 1] == b'x'
+                            && line[i + 2].is_ascii_hexdigit() && line[i + 3].is_ascii_hexdigit() => {
+                            let hex = std::str::from_utf8(&line[i + 2..i + 4]).unwrap_or_default();
+                            current.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                            i += 3;
+                        },
+                        Some(b'\\') if i + 1 < line.len() => {
+                            i += 1;
+                            current.push(match line[i] {
+                                b'n' => b'\n',
+                                b'r' => b'\r',
+                                b't' => b'\t',
+                                b'b' => 0x08,
+                                b'a' => 0x07,
+                                other => other,
+                            });
+                        },
+                        Some(b'"') => {
+                            if line.get(i + 1).is_some_and(|b| !b.is_ascii_whitespace()) {
+                                return Err(unbalanced());
+                            }
+                            in_double = false;
+                        },
+                        Some(&b) => current.push(b),
+                    }
+                } else if in_single {
+                    match line.get(i) {
+                        None => return Err(unbalanced()),
+                        Some(b'\\') if line.get(i + 1) == Some(&b'\'') => {
+                            i += 1;
+                            current.push(b'\'');
+                        },
+                        Some(b'\'') => {
+                            if line.get(i + 1).is_some_and(|b| !b.is_ascii_whitespace()) {
+                                return Err(unbalanced());
+                            }
+                            in_single = false;
+                        },
+                        Some(&b) => current.push(b),
+                    }
+                } else {
+                    match line.get(i) {
+                        None => break,
+                        Some(b) if b.is_ascii_whitespace() => break,
+                        Some(b'"') => in_double = true,
+                        Some(b'\'') => in_single = true,
+                        Some(&b) => current.push(b),
+                    }
                 }
+                i += 1;
+            }
+            args.push(current);
+        }
+    }
+
+    /**
+     * 增量读取一行（包含类型前缀，不含 \r\n）
+     *
+     * 找不到 \r\n 时返回 Ok(None)；超过最大行长度时返回协议错误
+     *
+     * @param bytes 缓冲区数据
+     * @param start 开始位置
+     */
+    fn decode_line(bytes: &[u8], start: usize) -> Result<Option<(&[u8], usize)>, Error> {
+        let rest = bytes.get(start..).unwrap_or_default();
+        match rest.windows(2).position(|window| window == b"\r\n") {
+            Some(end) => Ok(Some((&rest[..end], start + end + 2))),
+            None if rest.len() > MAX_LINE_LEN => Err(ProtocolError::error("too big line")),
+            None => Ok(None),
+        }
+    }
+
+    /**
+     * 解析长度声明并校验范围
+     *
+     * @param bytes 长度内容
+     * @param name 长度名称，用于错误信息
+     * @param max 允许的最大长度
+     */
+    fn decode_length(bytes: &[u8], name: &str, max: i64) -> Result<i64, Error> {
+        match Frame::parse_length(bytes) {
+            Ok(len) if len >= -1 && len <= max => Ok(len),
+            _ => Err(ProtocolError::error(format!("invalid {}", name))),
+        }
+    }
+
+    /**
+     * 通过解析 bytes 创建命令帧
+     *
+     * @param bytes 二进制
+     */
+    pub fn parse_from_bytes(bytes: &[u8]) -> Result<Frame, Error> {
+        if bytes.is_empty() {
+            return Err(Error::msg("Empty frame"));
+        }
+        match Frame::decode(bytes)? {
+            Some((frame, _)) => Ok(frame),
+            None if !Frame::is_type_prefix(bytes[0]) => {
+                // 没有换行符的内联命令
+                let args = Frame::split_inline_args(bytes)?;
+                Ok(Frame::Array(args.into_iter().map(Frame::BulkString).collect()))
+            },
+            None => Err(Error::msg("Incomplete frame")),
+        }
+    }
 
-                start = i + 2;
+    /**
+     * 解析粘连的多个命令帧
+     *
+     * @param bytes 二进制数据
+     */
+    pub fn parse_multiple_frames(bytes: &[u8]) -> Result<Vec<Frame>, Error> {
+        let mut frames = Vec::new();
+        let mut position = 0;
+
+        while position < bytes.len() {
+            match Frame::decode(&bytes[position..])? {
+                Some((frame, len)) => {
+                    frames.push(frame);
+                    position += len;
+                },
+                None => return Err(Error::msg("Incomplete frame")),
             }
         }
 
-        Ok(Frame::Array(frames))
+        Ok(frames)
+    }
+
+    /**
+     * 解析长度声明
+     *
+     * @param bytes 二进制
+     */
+    fn parse_length(bytes: &[u8]) -> Result<i64, Error> {
+        std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|s| s.parse::<i64>().ok())
+            .ok_or_else(|| Error::msg("Invalid length"))
     }
 
     /**
@@ -401,6 +781,56 @@ impl Frame {
         }
     }
 
+    /**
+     * 获取指定索引的内容【二进制】
+     *
+     * @param index 索引
+     */
+    pub fn get_arg_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        match self {
+            Frame::Array(array) => array.get(index).map(|frame| frame.as_arg_bytes()),
+            _ => None,
+        }
+    }
+
+    /**
+     * 获取命令帧中的所有参数【二进制】
+     *
+     * @param self 本身
+     */
+    pub fn get_args_bytes(&self) -> Vec<Vec<u8>> {
+        self.get_args_bytes_from_index(0)
+    }
+
+    /**
+     * 获取从指定索引开始的内容集合【二进制】
+     *
+     * @param self 本身
+     * @param start_index 开始索引
+     */
+    pub fn get_args_bytes_from_index(&self, start_index: usize) -> Vec<Vec<u8>> {
+        match self {
+            Frame::Array(array) => {
+                if start_index < array.len() {
+                    array[start_index..].iter().map(|frame| frame.as_arg_bytes()).collect()
+                } else {
+                    Vec::new()
+                }
+            },
+            _ => Vec::new()
+        }
+    }
+
+    /**
+     * 参数内容：批量字符串保留原始字节，其他类型取字符串形式
+     */
+    fn as_arg_bytes(&self) -> Vec<u8> {
+        match self {
+            Frame::BulkString(bytes) => bytes.clone(),
+            other => other.to_string().into_bytes(),
+        }
+    }
+
     /**
      * 获取命令帧中的所有参数
      * 
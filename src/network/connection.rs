@@ -1,47 +1,63 @@
 // src/network/connection.rs
 use anyhow::Error;
-use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpStream};
+use tokio::{io::AsyncWriteExt, net::TcpStream};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::{frame::{Frame, ProtocolError}, network::frame_decoder::FrameDecoder};
+
 #[derive(Clone)]
 pub struct Connection {
     stream: Arc<Mutex<TcpStream>>,
+    decoder: Arc<Mutex<FrameDecoder>>,
 }
 
 impl Connection {
-    pub fn new(stream: TcpStream) -> Self {
+    /**
+     * 创建连接
+     *
+     * @param stream 客户端连接
+     * @param query_buffer_limit 查询缓冲区的上限（字节）
+     */
+    pub fn new(stream: TcpStream, query_buffer_limit: usize) -> Self {
         Connection {
             stream: Arc::new(Mutex::new(stream)),
+            decoder: Arc::new(Mutex::new(FrameDecoder::with_limit(query_buffer_limit))),
         }
     }
 
-    pub async fn read_bytes(&self) -> Result<Vec<u8>, Error> {
+    /**
+     * 读取客户端发送的命令帧，不完整的帧保留在连接的读缓冲区中
+     *
+     * @return 对端正常关闭时返回 None；协议错误时回复错误信息后返回 Err
+     */
+    pub async fn read_frames(&self) -> Result<Option<Vec<Frame>>, Error> {
+        let mut decoder = self.decoder.lock().await;
         let mut stream = self.stream.lock().await;
-        let mut bytes: Vec<u8> = Vec::new();
-        let mut temp_bytes: [u8; 1024] = [0; 1024]; 
-        
-        loop {
-            let n = match stream.read(&mut temp_bytes).await {
-                Ok(n) => n,
-                Err(e) => {
-                    return Err(Error::msg(format!("Failed to read from stream: {:?}", e)));
-                }
-            };
-
-            if n == 0 {
-                if bytes.is_empty() {
-                    return Err(Error::msg("Connection closed by peer"));
-                } else {
-                    break;
+        match decoder.read_frames(&mut *stream).await {
+            Ok(frames) => Ok(frames),
+            Err(e) => {
+                if let Some(error) = e.downcast_ref::<ProtocolError>() {
+                    let _ = stream.write_all(&Frame::Error(error.to_string()).as_bytes()).await;
                 }
+                Err(e)
             }
-            bytes.extend_from_slice(&temp_bytes[..n]);
-            if n < temp_bytes.len() {
-                break;
-            }
         }
-        Ok(bytes)
+    }
+
+    /**
+     * 等待对端关闭连接
+     *
+     * 对端发送的新数据会被读入读缓冲区，留给后续的 read_frames 处理，
+     * 因此流水线中排在后面的命令不会妨碍检测连接关闭。
+     * 读缓冲区超过上限时回复协议错误，并视为连接已关闭
+     */
+    pub async fn closed(&self) {
+        let mut decoder = self.decoder.lock().await;
+        let mut stream = self.stream.lock().await;
+        if let Err(e) = decoder.wait_closed(&mut *stream).await {
+            let _ = stream.write_all(&Frame::Error(e.to_string()).as_bytes()).await;
+        }
     }
 
     pub async fn write_bytes(&self, bytes: Vec<u8>) {
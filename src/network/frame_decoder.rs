@@ -0,0 +1,220 @@
+use anyhow::Error;
+use bytes::{Buf, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::frame::{Frame, ProtocolError};
+
+/**
+ * 尚未接收完整的顶层聚合类型
+ *
+ * 已解析的元素从缓冲区中移出，后续读取只解析新到达的元素，
+ * 避免元素很多的命令在分多次到达时被反复从头解析
+ *
+ * @param prefix 类型前缀
+ * @param remaining 还需解析的元素数量
+ * @param frames 已解析的元素
+ * @param size 已解析部分占用的字节数，计入查询缓冲区的大小
+ */
+struct PartialAggregate {
+    prefix: u8,
+    remaining: usize,
+    frames: Vec<Frame>,
+    size: usize,
+}
+
+/**
+ * 有状态的命令帧解码器
+ *
+ * 每个连接持有一个读缓冲区，不完整的帧会保留在缓冲区中，
+ * 直到后续读取补齐数据后再解析，从而正确处理拆包与粘包。
+ *
+ * @param buffer 读缓冲区
+ * @param partial 正在解析的顶层聚合类型
+ * @param limit 查询缓冲区的上限，超过时报告协议错误
+ */
+pub struct FrameDecoder {
+    buffer: BytesMut,
+    partial: Option<PartialAggregate>,
+    limit: usize,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameDecoder {
+
+    /**
+     * 创建不限制缓冲区大小的解码器，用于读取主节点的数据
+     */
+    pub fn new() -> Self {
+        Self::with_limit(usize::MAX)
+    }
+
+    /**
+     * 创建限制缓冲区大小的解码器，用于读取客户端的命令
+     *
+     * @param limit 查询缓冲区的上限（字节）
+     */
+    pub fn with_limit(limit: usize) -> Self {
+        FrameDecoder {
+            buffer: BytesMut::with_capacity(4 * 1024),
+            partial: None,
+            limit,
+        }
+    }
+
+    /**
+     * 从缓冲区中取出一个完整的命令帧
+     *
+     * 顶层的聚合类型逐个元素解析，已解析的元素保存在 partial 中
+     *
+     * @return 数据不完整时返回 None
+     */
+    pub fn next_frame(&mut self) -> Result<Option<Frame>, Error> {
+        if self.partial.is_none() {
+            if !matches!(self.buffer.first(), Some(b'*' | b'~' | b'>')) {
+                return match Frame::decode(&self.buffer)? {
+                    Some((frame, len)) => {
+                        self.buffer.advance(len);
+                        Ok(Some(frame))
+                    },
+                    None => Ok(None),
+                };
+            }
+            let (prefix, count, len) = match Frame::decode_aggregate_header(&self.buffer, 0)? {
+                Some(header) => header,
+                None => return Ok(None),
+            };
+            self.buffer.advance(len);
+            let count = match count {
+                Some(count) => count,
+                None => return Ok(Some(Frame::NullArray)),
+            };
+            self.partial = Some(PartialAggregate { prefix, remaining: count, frames: Vec::with_capacity(count.min(1024)), size: len });
+        }
+
+        if let Some(partial) = &mut self.partial {
+            while partial.remaining > 0 {
+                match Frame::decode_element(&self.buffer)? {
+                    Some((frame, len)) => {
+                        self.buffer.advance(len);
+                        partial.frames.push(frame);
+                        partial.remaining -= 1;
+                        partial.size += len;
+                    },
+                    None => return Ok(None),
+                }
+            }
+        }
+        Ok(self.partial.take().map(|partial| Frame::aggregate(partial.prefix, partial.frames)))
+    }
+
+    /**
+     * 检查查询缓冲区（包括已解析但未完整的命令）是否超过上限
+     */
+    fn check_limit(&self) -> Result<(), Error> {
+        let size = self.buffer.len() + self.partial.as_ref().map_or(0, |partial| partial.size);
+        if size > self.limit {
+            return Err(ProtocolError::error("query buffer limit exceeded"));
+        }
+        Ok(())
+    }
+
+    /**
+     * 读取至少一个完整的命令帧，并返回缓冲区中所有已完整的命令帧
+     *
+     * 解析出错时先返回错误之前的命令帧，错误的数据留在缓冲区中，由下一次读取报告
+     *
+     * @param stream 数据源
+     * @return 对端正常关闭时返回 None
+     */
+    pub async fn read_frames<R: AsyncRead + Unpin>(&mut self, stream: &mut R) -> Result<Option<Vec<Frame>>, Error> {
+        loop {
+            let mut frames = Vec::new();
+            loop {
+                match self.next_frame() {
+                    Ok(Some(frame)) => frames.push(frame),
+                    Ok(None) => break,
+                    Err(_) if !frames.is_empty() => break,
+                    Err(e) => return Err(e),
+                }
+            }
+            if !frames.is_empty() {
+                return Ok(Some(frames));
+            }
+
+            if stream.read_buf(&mut self.buffer).await? == 0 {
+                if self.buffer.is_empty() && self.partial.is_none() {
+                    return Ok(None);
+                }
+                return Err(Error::msg("Connection reset by peer"));
+            }
+            self.check_limit()?;
+        }
+    }
+
+    /**
+     * 等待数据源关闭，期间收到的数据保留在缓冲区中，留给后续的读取处理
+     *
+     * @param stream 数据源
+     * @return 缓冲区超过上限时返回协议错误
+     */
+    pub async fn wait_closed<R: AsyncRead + Unpin>(&mut self, stream: &mut R) -> Result<(), Error> {
+        while let Ok(n) = stream.read_buf(&mut self.buffer).await {
+            if n == 0 {
+                return Ok(());
+            }
+            self.check_limit()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * 读取一个完整的命令帧
+     *
+     * @param stream 数据源
+     * @return 对端正常关闭时返回 None
+     */
+    pub async fn read_frame<R: AsyncRead + Unpin>(&mut self, stream: &mut R) -> Result<Option<Frame>, Error> {
+        self.read_with(stream, Frame::decode).await
+    }
+
+    /**
+     * 读取复制握手阶段主节点发送的 RDB 文件
+     *
+     * @param stream 数据源
+     * @return 对端正常关闭时返回 None
+     */
+    pub async fn read_rdb_file<R: AsyncRead + Unpin>(&mut self, stream: &mut R) -> Result<Option<Frame>, Error> {
+        self.read_with(stream, Frame::decode_rdb_file).await
+    }
+
+    /**
+     * 使用指定的解析函数读取一个完整的帧
+     *
+     * @param stream 数据源
+     * @param decode 解析函数
+     */
+    async fn read_with<R, F>(&mut self, stream: &mut R, decode: F) -> Result<Option<Frame>, Error>
+    where
+        R: AsyncRead + Unpin,
+        F: Fn(&[u8]) -> Result<Option<(Frame, usize)>, Error>,
+    {
+        loop {
+            if let Some((frame, len)) = decode(&self.buffer)? {
+                self.buffer.advance(len);
+                return Ok(Some(frame));
+            }
+
+            if stream.read_buf(&mut self.buffer).await? == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return Err(Error::msg("Connection reset by peer"));
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use tokio::sync::mpsc::Sender;
-use crate::{frame::Frame, network::{connection::Connection, session_role::SessionRole}, store::db::DatabaseMessage};
+use crate::{frame::{Frame, Protocol}, network::{connection::Connection, session_role::SessionRole}, store::db::DatabaseMessage};
 
 static SESSION_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -13,6 +13,8 @@ pub struct Session {
     pub connection: Connection,
     current_db: usize,
     role: SessionRole,
+    protocol: Protocol,
+    name: Option<String>,
     in_transaction: bool,
     transaction_frames: Vec<Frame>
 }
@@ -28,6 +30,8 @@ impl Session {
             current_db,
             connection,
             role: SessionRole::Other,
+            protocol: Protocol::Resp2,
+            name: None,
             in_transaction: false,
             transaction_frames: Vec::new()
         }
@@ -69,6 +73,22 @@ impl Session {
         &self.role
     }
 
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+        self.protocol = protocol;
+    }
+
+    pub fn get_protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    pub fn get_name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
     // 事务相关方法
     pub fn start_transaction(&mut self) {
         self.in_transaction = true;
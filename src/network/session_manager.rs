@@ -7,6 +7,12 @@ pub struct SessionManager {
     sessions: DashMap<usize, Session>
 }
 
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SessionManager {
 
     // 创建实例
@@ -1,18 +1,13 @@
 /// 会话角色标志
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SessionRole {
     /// 从节点
     Slave,
     /// 其他角色（可根据需要扩展）
+    #[default]
     Other,
 }
 
-impl Default for SessionRole {
-    fn default() -> Self {
-        SessionRole::Other
-    }
-}
-
 impl SessionRole {
     
     /// 检查是否为从节点
@@ -36,19 +36,27 @@ impl AofFile {
         let content = tokio::fs::read(&self.file_path).await?;
         let mut frames = Vec::new();
         let mut start = 0;
-        let separator = b"\r\n\r\n";
-
-        // 遍历内容查找分隔符
-        while let Some(pos) =&content[start..].windows(separator.len()).position(|window| window == separator) {
-            let end = start + pos;
-            let frame_data = &content[start..end + separator.len() / 2];
-            if !frame_data.is_empty() {
-                if let Ok(frame) = Frame::parse_from_bytes(frame_data) {
+
+        // 按帧声明的长度逐个读取，命令之间以 \r\n 分隔
+        while start < content.len() {
+            if content[start..].starts_with(b"\r\n") {
+                start += 2;
+                continue;
+            }
+            match Frame::decode(&content[start..]) {
+                Ok(Some((frame, len))) => {
                     frames.push(frame);
+                    start += len;
+                },
+                Ok(None) => {
+                    log::warn!("Truncated AOF file, ignoring {} trailing bytes", content.len() - start);
+                    break;
+                },
+                Err(e) => {
+                    log::error!("Corrupted AOF file at offset {}: {}", start, e);
+                    break;
                 }
             }
-            // 跳过分隔符（4字节）
-            start = end + separator.len();
         }
         Ok(frames)
     }
@@ -83,8 +91,8 @@ impl AofFile {
             if idx != current_db_index {
 
                 let select_frame = Frame::Array(vec![
-                    Frame::BulkString("SELECT".to_string()),
-                    Frame::BulkString(idx.to_string()),
+                    Frame::BulkString(b"SELECT".to_vec()),
+                    Frame::BulkString(idx.to_string().into_bytes()),
                 ]);
 
                 if let Err(e) = file.write_all(&select_frame.as_bytes()).await {
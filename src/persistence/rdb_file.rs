@@ -3,7 +3,7 @@ use std::{collections::HashMap, fs::{self, File}, io::Write, path::PathBuf, time
 use anyhow::Error;
 use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
 
-use crate::store::db::DatabaseSnapshot;
+use crate::store::db::{DatabaseSnapshot, Structure};
 
 /// Rudis 数据库快照文件 (RDB) 的表示
 ///
@@ -17,6 +17,43 @@ pub struct RdbFile {
     path: PathBuf,
 }
 
+/// 文件头，用于区分当前格式与不含文件头的旧格式
+const MAGIC: &[u8] = b"RUDIS0002";
+
+/// 旧版本的数据库快照（不含哈希字段过期时间），仅用于加载旧文件
+#[derive(Decode)]
+struct LegacyDatabaseSnapshot {
+    expire_records: HashMap<Vec<u8>, SystemTime>,
+    records: HashMap<Vec<u8>, Structure>,
+}
+
+/// 旧版本的 RDB 文件
+#[derive(Decode)]
+struct LegacyRdbFile {
+    databases: HashMap<usize, LegacyDatabaseSnapshot>,
+    last_save_time: SystemTime,
+    last_save_changes: u64,
+    path: PathBuf,
+}
+
+impl From<LegacyRdbFile> for RdbFile {
+    fn from(legacy: LegacyRdbFile) -> Self {
+        let databases = legacy.databases.into_iter().map(|(id, snapshot)| {
+            (id, DatabaseSnapshot {
+                expire_records: snapshot.expire_records,
+                records: snapshot.records,
+                field_expire_records: HashMap::new(),
+            })
+        }).collect();
+        Self {
+            databases,
+            last_save_time: legacy.last_save_time,
+            last_save_changes: legacy.last_save_changes,
+            path: legacy.path,
+        }
+    }
+}
+
 impl RdbFile {
 
     /// 创建新的空 RDB 文件对象
@@ -66,8 +103,16 @@ impl RdbFile {
     /// - `Err(Error)`: 反序列化失败时返回错误
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
         let config = config::standard();
-        let (rdb_file, _) = decode_from_slice(bytes, config)?;
-        Ok(rdb_file)
+        match bytes.strip_prefix(MAGIC) {
+            Some(payload) => {
+                let (rdb_file, _) = decode_from_slice(payload, config)?;
+                Ok(rdb_file)
+            },
+            None => {
+                let (legacy, _) = decode_from_slice::<LegacyRdbFile, _>(bytes, config)?;
+                Ok(legacy.into())
+            }
+        }
     }
 
     /// 将当前对象序列化为字节向量
@@ -77,7 +122,9 @@ impl RdbFile {
     /// - `Err(Error)`: 序列化失败时返回错误
     pub fn serialize(&self) -> Result<Vec<u8>, Error> {
         let config = config::standard();
-        Ok(encode_to_vec(self, config)?)
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(encode_to_vec(self, config)?);
+        Ok(bytes)
     }
 
     /// 获取指定数据库的快照
@@ -89,7 +136,7 @@ impl RdbFile {
     /// - 存在: 返回数据库快照的克隆
     /// - 不存在: 返回默认空快照
     pub fn get_database(&self, id: usize) -> DatabaseSnapshot {
-        self.databases.get(&id).cloned().unwrap_or_else(|| DatabaseSnapshot::default())
+        self.databases.get(&id).cloned().unwrap_or_else(DatabaseSnapshot::default)
     }
 
     /// 设置/更新数据库快照
@@ -117,8 +164,7 @@ impl RdbFile {
             fs::create_dir_all(parent)?;
         }
         let mut file = File::create(&self.path)?;
-        let config = config::standard();
-        let serialized = encode_to_vec(&*self, config)?;
+        let serialized = self.serialize()?;
         file.write_all(&serialized)?;
         Ok(())
     }
@@ -136,8 +182,7 @@ impl RdbFile {
     pub fn load(&mut self) -> Result<(), Error> {
         if self.path.exists() {
             let data = fs::read(&self.path)?;
-            let config = config::standard();
-            let (deserialized, _) = decode_from_slice::<RdbFile, _>(&data, config)?;
+            let deserialized = Self::from_bytes(&data)?;
             self.last_save_changes = deserialized.last_save_changes;
             self.last_save_time = deserialized.last_save_time;
             self.databases = deserialized.databases;
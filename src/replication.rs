@@ -2,9 +2,10 @@ use std::{sync::Arc};
 
 use anyhow::{Error, Result};
 use tokio::net::TcpStream;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 use crate::store::db::{DatabaseMessage};
 use crate::store::db_manager::DatabaseManager;
+use crate::network::frame_decoder::FrameDecoder;
 use crate::{args::Args, frame::Frame};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -20,7 +21,8 @@ pub struct ReplicationManager {
     pub state: ReplicationState,
     pub db_manager: Arc<DatabaseManager>,
     pub stream: Option<TcpStream>,
-    pub args: Arc<Args>
+    pub args: Arc<Args>,
+    decoder: FrameDecoder
 }
 
 impl ReplicationManager {
@@ -29,9 +31,10 @@ impl ReplicationManager {
         
         Self {
             state: ReplicationState::Disconnected,
-            db_manager: db_manager,
+            db_manager,
             stream: None,
-            args
+            args,
+            decoder: FrameDecoder::new()
         }
     }
     
@@ -45,6 +48,7 @@ impl ReplicationManager {
                 match TcpStream::connect(addr).await {
                     Ok(mut _stream) => {
                         self.stream = Some(_stream);
+                        self.decoder = FrameDecoder::new();
                         self.ping().await?;
                         self.replconf().await?;
                         self.psync().await?; 
@@ -72,13 +76,11 @@ impl ReplicationManager {
     async fn ping(&mut self) -> Result<()> {
 
         let stream = self.stream.as_mut().unwrap();
-        let frame = Frame::Array(vec![Frame::BulkString("PING".to_string())]);
+        let frame = Frame::Array(vec![Frame::BulkString(b"PING".to_vec())]);
         stream.write_all(&frame.as_bytes()).await?;
         
         // 等待 PING 响应
-        let mut buffer = [0; 1024];
-        let n = stream.read(&mut buffer).await?;
-        let response = Frame::parse_from_bytes(&buffer[..n]).unwrap();
+        let response = self.read_frame().await?;
         if let Frame::SimpleString(s) = response {
             if s == "PONG" {
                 return Ok(());
@@ -105,17 +107,15 @@ impl ReplicationManager {
         let ip_address_str = String::from("IP-ADDRESS");
         
         let replconf_frame = Frame::Array(vec![
-            Frame::BulkString(replconf_str),
-            Frame::BulkString(listening_port_str),
-            Frame::BulkString(port),
-            Frame::BulkString(ip_address_str),
-            Frame::BulkString(bind),
+            Frame::BulkString(replconf_str.into_bytes()),
+            Frame::BulkString(listening_port_str.into_bytes()),
+            Frame::BulkString(port.into_bytes()),
+            Frame::BulkString(ip_address_str.into_bytes()),
+            Frame::BulkString(bind.into_bytes()),
         ]);
 
         stream.write_all(&replconf_frame.as_bytes()).await?;
-        let mut buffer = [0; 1024];
-        let n = stream.read(&mut buffer).await?;
-        let response = Frame::parse_from_bytes(&buffer[..n]).unwrap();
+        let response = self.read_frame().await?;
         if let Frame::SimpleString(s) = response {
             if s == "OK" {
                 return Ok(());
@@ -132,7 +132,7 @@ impl ReplicationManager {
      */
     async fn psync(&mut self) -> Result<()> {
         let stream = self.stream.as_mut().unwrap();
-        let psync_frame = Frame::Array(vec![Frame::BulkString("PSYNC".to_string())]);
+        let psync_frame = Frame::Array(vec![Frame::BulkString(b"PSYNC".to_vec())]);
         stream.write_all(&psync_frame.as_bytes()).await?;
         self.state = ReplicationState::WaitPsync;
         Ok(())
@@ -144,11 +144,12 @@ impl ReplicationManager {
      * @param self
      */
     async fn rdb_file_receiver(&mut self) -> Result<()> {
-        let mut buffer = [0; 1024];
-        let stream: &mut TcpStream = self.stream.as_mut().unwrap();
-        let n = stream.read(&mut buffer).await?;
-        let frame = Frame::parse_from_bytes(&buffer[..n]).unwrap();
-        let rdb_file = frame.to_rdb_file().unwrap();
+        let stream = self.stream.as_mut().ok_or_else(|| Error::msg("Not connected to master"))?;
+        let frame = match self.decoder.read_rdb_file(stream).await? {
+            Some(frame) => frame,
+            None => return Err(Error::msg("Master connection closed")),
+        };
+        let rdb_file = frame.to_rdb_file()?;
         let senders = self.db_manager.get_senders();
         for (db_index, target_sender) in senders.iter().enumerate() {
             match target_sender.send(DatabaseMessage::Restore(rdb_file.get_database(db_index))).await {
@@ -161,59 +162,67 @@ impl ReplicationManager {
         Ok(())
     }
 
+    /**
+     * 读取主节点发送的一个完整命令帧
+     * 
+     * @param self
+     */
+    async fn read_frame(&mut self) -> Result<Frame> {
+        let stream = self.stream.as_mut().ok_or_else(|| Error::msg("Not connected to master"))?;
+        match self.decoder.read_frame(stream).await? {
+            Some(Frame::Error(e)) => Err(Error::msg(e)),
+            Some(frame) => Ok(frame),
+            None => Err(Error::msg("Master connection closed")),
+        }
+    }
+
     /**
      * 接收 COMMAND 传播
      * 
      * @param self
      */
     async fn cmd_receiver(&mut self) -> Result<()> {
-        let stream = self.stream.as_mut().unwrap();
-        let mut buffer = [0; 4096];
         let mut current_db_index = 0;
         
         log::info!("Connected to master, waiting for commands...");
         
         loop {
-            let n = stream.read(&mut buffer).await?;
-            if n == 0 {
-                self.state = ReplicationState::Disconnected;
-                log::warn!("Master connection closed");
-                break;
-            }
+            let stream = self.stream.as_mut().unwrap();
+            let frame = match self.decoder.read_frame(stream).await? {
+                Some(frame) => frame,
+                None => {
+                    self.state = ReplicationState::Disconnected;
+                    log::warn!("Master connection closed");
+                    break;
+                }
+            };
             
-            match Frame::parse_from_bytes(&buffer[..n]) {
-                Ok(frame) => {
-                    match crate::command::Command::parse_from_frame(frame) {
-                        Ok(command) => {
-                            if let crate::command::Command::Select(ref select_cmd) = command {
-                                current_db_index = select_cmd.get_db_index();
-                                continue;
-                            }
-                            
-                            let db_sender = self.db_manager.get_sender(current_db_index);
-                            let (sender, receiver) = tokio::sync::oneshot::channel();
-                            let message = DatabaseMessage::Command { sender, command };
-                            match db_sender.send(message).await {
-                                Ok(()) => {
-                                    match receiver.await {
-                                        Ok(_result) => {}
-                                        Err(e) => {
-                                            log::error!("Failed to receive command result: {}", e);
-                                        }
-                                    }
-                                }
+            match crate::command::Command::parse_from_frame(frame) {
+                Ok(command) => {
+                    if let crate::command::Command::Select(ref select_cmd) = command {
+                        current_db_index = select_cmd.get_db_index();
+                        continue;
+                    }
+                    
+                    let db_sender = self.db_manager.get_sender(current_db_index);
+                    let (sender, receiver) = tokio::sync::oneshot::channel();
+                    let message = DatabaseMessage::Command { sender, command };
+                    match db_sender.send(message).await {
+                        Ok(()) => {
+                            match receiver.await {
+                                Ok(_result) => {}
                                 Err(e) => {
-                                    log::error!("Failed to send command to database: {}", e);
+                                    log::error!("Failed to receive command result: {}", e);
                                 }
                             }
                         }
                         Err(e) => {
-                            log::error!("Failed to parse master node command: {}", e);
+                            log::error!("Failed to send command to database: {}", e);
                         }
                     }
                 }
                 Err(e) => {
-                    log::error!("Failed to parse frame from master: {}", e);
+                    log::error!("Failed to parse master node command: {}", e);
                 }
             }
         }
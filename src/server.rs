@@ -15,12 +15,13 @@ use crate::network::session::Session;
 use crate::network::session_manager::SessionManager;
 use crate::network::session_role::SessionRole;
 use crate::persistence::aof_file::AofFile;
+use crate::store::blocking::BlockingCommand;
 use crate::store::db::DatabaseMessage;
 use crate::store::db_manager::DatabaseManager;
 use crate::network::connection::Connection;
 use crate::replication::ReplicationManager;
 use crate::command::Command;
-use crate::frame::Frame;
+use crate::frame::{Frame, Protocol};
 
 pub struct Server {
     args: Arc<Args>,
@@ -56,7 +57,7 @@ impl Server {
     pub async fn start(&mut self) {
 
         if let Some(af) = &mut self.aof_file {
-            if let Err(_) = Self::replay_aof_file(af, self.db_manager.clone()).await {
+            if Self::replay_aof_file(af, self.db_manager.clone()).await.is_err() {
                 log::info!("Failed to load AOF file");
             }
         }
@@ -149,6 +150,7 @@ impl Server {
 pub struct Handler {
     session: Session,
     aof_sender: Option<Sender<(usize, Frame)>>,
+    propagation: Vec<Frame>, // 当前命令执行后需要额外传播的命令
     session_manager: Arc<SessionManager>,
     db_manager: Arc<DatabaseManager>,
     args: Arc<Args>
@@ -174,13 +176,14 @@ impl Handler {
         let args_ref = args.as_ref();
         let certification = args_ref.requirepass.is_none();
         let sender = db_manager.as_ref().get_sender(0);
-        let connection = Connection::new(stream);
+        let connection = Connection::new(stream, args_ref.client_query_buffer_limit);
         let session = Session::new(certification, sender, connection);
         session_manager.create_session(session.clone());
 
         Handler {
             session,
             aof_sender,
+            propagation: Vec::new(),
             session_manager,
             db_manager,
             args,
@@ -200,7 +203,7 @@ impl Handler {
                 self.session.set_certification(true);
                 return Ok(())
             } 
-            return Err(Error::msg("ERR invalid password"));
+            Err(Error::msg("ERR invalid password"))
         } else {
             Ok(())
         }
@@ -232,31 +235,43 @@ impl Handler {
         self.session_manager.create_session(self.session.clone());
     }
 
+    /**
+     * 设置会话协议版本并同步到 SessionManager
+     *
+     * @param protocol 协议版本
+     */
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+        self.session.set_protocol(protocol);
+        self.session_manager.create_session(self.session.clone());
+    }
+
+    /**
+     * 设置客户端名称并同步到 SessionManager
+     *
+     * @param name 客户端名称
+     */
+    pub fn set_client_name(&mut self, name: Option<String>) {
+        self.session.set_name(name);
+        self.session_manager.create_session(self.session.clone());
+    }
+
     /// Handling client connections
     pub async fn handle(&mut self) {
         loop {
-            log::debug!("Waiting for bytes");
-            let bytes = match self.session.connection.read_bytes().await {
-                Ok(bytes) => bytes,
-                Err(_e) => {
+            log::debug!("Waiting for frames");
+            let frames = match self.session.connection.read_frames().await {
+                Ok(Some(frames)) => frames,
+                Ok(None) => {
                     self.session_manager.remove_session(self.session.get_id());
                     return;
-                }
-            };
-            
-            // 解析可能的多个粘连命令帧
-            let frames = match Frame::parse_multiple_frames(bytes.as_slice()) {
-                Ok(frames) => frames,
+                },
                 Err(e) => {
-                    log::error!("Failed to parse multiple frames: {:?}", e);
-                    let frame = Frame::Error(format!("Failed to parse frames: {:?}", e));
-                    self.session.connection.write_bytes(frame.as_bytes()).await;
-                    continue;
+                    log::debug!("Closing connection: {}", e);
+                    self.session_manager.remove_session(self.session.get_id());
+                    return;
                 }
             };
-            
-            log::debug!("Received bytes: {:?}", String::from_utf8_lossy(bytes.as_slice()));
-            
+
             for frame in frames {
                 log::debug!("Received frame: {}", frame.to_string());
                 let frame_copy = frame.clone();
@@ -279,31 +294,28 @@ impl Handler {
                 };
                 
                 match command {
-                    Command::Auth(_) => {},
+                    Command::Auth(_) | Command::Hello(_) => {},
                     _ => { 
-                        if self.args.requirepass.is_some() {
-                            if self.session.get_certification() == false {
+                        if self.args.requirepass.is_some()
+                            && !self.session.get_certification() {
                                 let frame = Frame::Error("NOAUTH Authentication required.".to_string());
                                 self.session.connection.write_bytes(frame.as_bytes()).await;
                                 continue;
-                            }
-                        } 
+                            } 
                     },
                 };
 
                 let is_psync_command = matches!(command, Command::Psync(_));
-                let should_propagate = command.propagate_aof_if_needed();
+                let should_propagate = command.propagate_aof_if_needed() && !command.propagates_effects();
+                let propagate_frame = command.propagation_frame(frame_copy);
                 let result = self.apply_command(command).await;
 
                 match result {
                     Ok(frame) => {
                         if should_propagate {
-                            if let Some(ref aof_sender) = self.aof_sender {
-                                let _ = aof_sender.send((self.session.get_current_db(), frame_copy.clone())).await;
-                            }
-                            self.propagate_to_slaves(frame_copy.clone()).await;
+                            self.propagate(Command::resolve_propagation_frame(propagate_frame, &frame)).await;
                         }
-                        self.session.connection.write_bytes(frame.as_bytes()).await;
+                        self.session.connection.write_bytes(frame.as_bytes_with(self.session.get_protocol())).await;
                         if is_psync_command {
                             return;
                         }
@@ -312,6 +324,10 @@ impl Handler {
                         println!("Failed to receive; err = {:?}", e);
                     }
                 }
+                // 阻塞命令以及被当前命令唤醒的阻塞命令实际执行的命令
+                for propagate_frame in std::mem::take(&mut self.propagation) {
+                    self.propagate(propagate_frame).await;
+                }
             }
         }
     }
@@ -320,6 +336,7 @@ impl Handler {
     async fn apply_command(&mut self, command: Command) -> Result<Frame, Error> {
         match command {
             Command::Auth(auth) => auth.apply(self),
+            Command::Hello(hello) => hello.apply(self),
             Command::Client(client) => client.apply(),
             Command::Replconf(replconf) => replconf.apply(self),
             Command::Save(save) => save.apply(self.db_manager.clone(), self.args.clone()).await,
@@ -334,7 +351,10 @@ impl Handler {
             Command::Unknown(unknown) => unknown.apply(),
             Command::Ping(ping) => ping.apply(),
             Command::Echo(echo) => echo.apply(),
-            _ => self.apply_db_command(command).await,
+            _ => match BlockingCommand::from_command(command) {
+                Ok(command) => self.apply_blocking_command(command).await,
+                Err(command) => self.apply_db_command(command).await,
+            },
         }
     }
 
@@ -366,6 +386,7 @@ impl Handler {
                     // 为了避免递归（实际不会有）
                     let result = match command {
                         Command::Auth(auth) => auth.apply(self),
+                        Command::Hello(hello) => hello.apply(self),
                         Command::Client(client) => client.apply(),
                         Command::Replconf(replconf) => replconf.apply(self),
                         Command::Save(save) => save.apply(self.db_manager.clone(), self.args.clone()).await,
@@ -391,20 +412,50 @@ impl Handler {
     }
 
     /// 执行数据库命令
-    async fn apply_db_command(&self, command: Command) -> Result<Frame, Error> {
+    async fn apply_db_command(&mut self, command: Command) -> Result<Frame, Error> {
         let (sender, receiver) = oneshot::channel();
-        let message = DatabaseMessage::Command { sender, command };
+        let message = DatabaseMessage::Client { sender, command };
         let db_sender = self.session.get_sender();
         if let Err(e) = db_sender.send(message).await {
             return Ok(Frame::Error(format!("Channel closed: {:?}", e)));
         }
         let result = match receiver.await {
-            Ok(f) => f,
+            Ok(reply) => {
+                self.propagation.extend(reply.propagation);
+                reply.frame
+            },
             Err(e) => Frame::Error(format!("{:?}", e))
         };
         Ok(result)
     }
 
+    /// 执行阻塞命令，等待期间客户端断开时放弃等待
+    async fn apply_blocking_command(&mut self, command: BlockingCommand) -> Result<Frame, Error> {
+        let (sender, receiver) = oneshot::channel();
+        let message = DatabaseMessage::Blocking { sender, command };
+        let db_sender = self.session.get_sender();
+        if let Err(e) = db_sender.send(message).await {
+            return Ok(Frame::Error(format!("Channel closed: {:?}", e)));
+        }
+        let reply = tokio::select! {
+            result = receiver => match result {
+                Ok(reply) => reply,
+                Err(e) => return Ok(Frame::Error(format!("{:?}", e)))
+            },
+            _ = self.session.connection.closed() => return Err(Error::msg("Connection closed while blocked")),
+        };
+        self.propagation.extend(reply.propagation);
+        Ok(reply.frame)
+    }
+
+    /// 传播到 AOF 与从节点
+    async fn propagate(&self, frame: Frame) {
+        if let Some(ref aof_sender) = self.aof_sender {
+            let _ = aof_sender.send((self.session.get_current_db(), frame.clone())).await;
+        }
+        self.propagate_to_slaves(frame).await;
+    }
+
     /// 传播主节点命令
     async fn propagate_to_slaves(&self, frame: Frame) {
         let slave_sessions = self.session_manager.get_slave_sessions();
@@ -417,7 +468,7 @@ impl Handler {
             let frame_clone = frame.clone();
             let db = current_db;
             
-            slave_session.connection.write_bytes(Frame::Array(vec![Frame::BulkString("SELECT".to_string()),Frame::BulkString(db.to_string())]).as_bytes()).await;
+            slave_session.connection.write_bytes(Frame::Array(vec![Frame::BulkString(b"SELECT".to_vec()),Frame::BulkString(db.to_string().into_bytes())]).as_bytes()).await;
             slave_session.connection.write_bytes(frame_clone.as_bytes()).await;
         }
     }
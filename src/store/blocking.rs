@@ -0,0 +1,336 @@
+use std::{
+    collections::{HashMap, VecDeque}, sync::atomic::{AtomicU64, Ordering}, time::Duration
+};
+
+use anyhow::Error;
+use tokio::{sync::oneshot, time::Instant};
+
+use crate::{
+    cmds::{listing::{blmove::Blmove, blmpop::Blmpop, blpop::Blpop, brpop::Brpop}, sorted_set::bzpop::Bzpop, stream::{xread::Xread, xreadgroup::Xreadgroup}},
+    command::Command,
+    frame::Frame,
+    store::db::{Db, Reply},
+};
+
+/**
+ * 所有数据库中被阻塞的客户端数量
+ */
+static BLOCKED_CLIENTS: AtomicU64 = AtomicU64::new(0);
+
+pub fn blocked_clients() -> u64 {
+    BLOCKED_CLIENTS.load(Ordering::Relaxed)
+}
+
+/**
+ * 阻塞命令
+ */
+pub enum BlockingCommand {
+    Blpop(Blpop),
+    Brpop(Brpop),
+    Blmove(Blmove),
+    Blmpop(Blmpop),
+    Bzpopmin(Bzpop),
+    Bzpopmax(Bzpop),
+    Xread(Xread),
+    Xreadgroup(Xreadgroup),
+}
+
+impl BlockingCommand {
+
+    /**
+     * 从命令中提取阻塞命令
+     *
+     * @param command 命令
+     * @return 非阻塞命令原样返回
+     */
+    pub fn from_command(command: Command) -> Result<Self, Command> {
+        match command {
+            Command::Blpop(blpop) => Ok(BlockingCommand::Blpop(blpop)),
+            Command::Brpop(brpop) => Ok(BlockingCommand::Brpop(brpop)),
+            Command::Blmove(blmove) => Ok(BlockingCommand::Blmove(blmove)),
+            Command::Blmpop(blmpop) => Ok(BlockingCommand::Blmpop(blmpop)),
+            Command::Bzpopmin(bzpop) => Ok(BlockingCommand::Bzpopmin(bzpop)),
+            Command::Bzpopmax(bzpop) => Ok(BlockingCommand::Bzpopmax(bzpop)),
+            Command::Xread(xread) if xread.is_blocking() => Ok(BlockingCommand::Xread(xread)),
+            Command::Xreadgroup(xreadgroup) if xreadgroup.is_blocking() => Ok(BlockingCommand::Xreadgroup(xreadgroup)),
+            command => Err(command),
+        }
+    }
+
+    fn keys(&self) -> &[Vec<u8>] {
+        match self {
+            BlockingCommand::Blpop(blpop) => blpop.keys(),
+            BlockingCommand::Brpop(brpop) => brpop.keys(),
+            BlockingCommand::Blmove(blmove) => blmove.keys(),
+            BlockingCommand::Blmpop(blmpop) => blmpop.keys(),
+            BlockingCommand::Bzpopmin(bzpop) | BlockingCommand::Bzpopmax(bzpop) => bzpop.keys(),
+            BlockingCommand::Xread(xread) => xread.keys(),
+            BlockingCommand::Xreadgroup(xreadgroup) => xreadgroup.keys(),
+        }
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        match self {
+            BlockingCommand::Blpop(blpop) => blpop.timeout(),
+            BlockingCommand::Brpop(brpop) => brpop.timeout(),
+            BlockingCommand::Blmove(blmove) => blmove.timeout(),
+            BlockingCommand::Blmpop(blmpop) => blmpop.timeout(),
+            BlockingCommand::Bzpopmin(bzpop) | BlockingCommand::Bzpopmax(bzpop) => bzpop.timeout(),
+            BlockingCommand::Xread(xread) => xread.timeout(),
+            BlockingCommand::Xreadgroup(xreadgroup) => xreadgroup.timeout(),
+        }
+    }
+
+    /**
+     * 是否会消费元素，消费元素的命令按阻塞的先后顺序服务，
+     * 排在前面的客户端没有得到元素时，后面的客户端也不会得到。
+     * XREADGROUP 的客户端可能属于不同的消费者组，需要逐个尝试
+     */
+    fn consumes(&self) -> bool {
+        !matches!(self, BlockingCommand::Xread(_) | BlockingCommand::Xreadgroup(_))
+    }
+
+    /**
+     * 阻塞前的准备工作
+     *
+     * @param db 数据库
+     */
+    fn prepare(&mut self, db: &mut Db) {
+        if let BlockingCommand::Xread(xread) = self {
+            xread.resolve_last_ids(db);
+        }
+    }
+
+    /**
+     * 实际执行的命令，传播到 AOF 与从节点
+     *
+     * @param db 数据库
+     * @param frame 执行结果
+     */
+    fn propagation_frames(&self, db: &mut Db, frame: &Frame) -> Vec<Frame> {
+        match self {
+            BlockingCommand::Blpop(blpop) => blpop.propagation_frame(frame).into_iter().collect(),
+            BlockingCommand::Brpop(brpop) => brpop.propagation_frame(frame).into_iter().collect(),
+            BlockingCommand::Blmove(blmove) => blmove.propagation_frame(frame).into_iter().collect(),
+            BlockingCommand::Blmpop(blmpop) => blmpop.propagation_frame(frame).into_iter().collect(),
+            BlockingCommand::Bzpopmin(bzpop) | BlockingCommand::Bzpopmax(bzpop) => bzpop.propagation_frame(frame).into_iter().collect(),
+            BlockingCommand::Xreadgroup(xreadgroup) => xreadgroup.propagation_frames(db, frame),
+            // XREAD 不修改数据
+            BlockingCommand::Xread(_) => Vec::new(),
+        }
+    }
+
+    /**
+     * 撤销没能送达客户端的执行结果，把弹出的元素放回原处
+     *
+     * @param db 数据库
+     * @param frame 执行结果
+     */
+    fn restore(&self, db: &mut Db, frame: Frame) {
+        match self {
+            BlockingCommand::Blpop(blpop) => blpop.restore(db, frame),
+            BlockingCommand::Brpop(brpop) => brpop.restore(db, frame),
+            BlockingCommand::Blmove(blmove) => blmove.restore(db, frame),
+            BlockingCommand::Blmpop(blmpop) => blmpop.restore(db, frame),
+            BlockingCommand::Bzpopmin(bzpop) | BlockingCommand::Bzpopmax(bzpop) => bzpop.restore(db, frame),
+            // XREAD 不消费消息，XREADGROUP 投递的消息留在待处理列表中，可以通过 XCLAIM 重新认领
+            BlockingCommand::Xread(_) | BlockingCommand::Xreadgroup(_) => {},
+        }
+    }
+
+    /**
+     * 尝试执行命令
+     *
+     * @param db 数据库
+     * @return 没有可弹出的元素时返回 None
+     */
+    fn try_apply(&self, db: &mut Db) -> Result<Option<Frame>, Error> {
+        let frame = match self {
+            BlockingCommand::Blpop(blpop) => blpop.apply(db)?,
+            BlockingCommand::Brpop(brpop) => brpop.apply(db)?,
+            BlockingCommand::Blmove(blmove) => blmove.apply(db)?,
+            BlockingCommand::Blmpop(blmpop) => blmpop.apply(db)?,
+            BlockingCommand::Bzpopmin(bzpop) | BlockingCommand::Bzpopmax(bzpop) => bzpop.apply(db)?,
+            BlockingCommand::Xread(xread) => xread.apply(db)?,
+            BlockingCommand::Xreadgroup(xreadgroup) => xreadgroup.apply(db)?,
+        };
+        match frame {
+            Frame::Null | Frame::NullArray => Ok(None),
+            frame => Ok(Some(frame)),
+        }
+    }
+}
+
+/**
+ * 等待中的客户端
+ *
+ * @param sender 回复通道
+ * @param command 阻塞命令
+ * @param deadline 超时时刻，None 表示永久阻塞
+ */
+struct Waiter {
+    sender: oneshot::Sender<Reply>,
+    command: BlockingCommand,
+    deadline: Option<Instant>,
+}
+
+/**
+ * 阻塞队列
+ *
+ * 每个键维护一个先进先出的等待队列，同一个客户端可以同时等待多个键。
+ */
+#[derive(Default)]
+pub struct BlockingQueue {
+    next_id: u64,
+    waiters: HashMap<u64, Waiter>,
+    keys: HashMap<Vec<u8>, VecDeque<u64>>,
+}
+
+impl BlockingQueue {
+
+    pub fn is_empty(&self) -> bool {
+        self.waiters.is_empty()
+    }
+
+    /**
+     * 执行阻塞命令，没有可用元素时加入等待队列
+     *
+     * @param db 数据库
+     * @param sender 回复通道，立即执行时回复中附带实际执行的命令
+     * @param command 阻塞命令
+     */
+    pub fn block(db: &mut Db, sender: oneshot::Sender<Reply>, mut command: BlockingCommand) -> Result<(), Error> {
+        command.prepare(db);
+        if let Some(frame) = command.try_apply(db)? {
+            let propagation = command.propagation_frames(db, &frame);
+            if let Err(reply) = sender.send(Reply { frame, propagation }) {
+                command.restore(db, reply.frame);
+            }
+            return Ok(());
+        }
+        let deadline = command.timeout().map(|timeout| Instant::now() + timeout);
+        let queue = &mut db.blocking;
+        let id = queue.next_id;
+        queue.next_id += 1;
+        for key in command.keys() {
+            let ids = queue.keys.entry(key.clone()).or_default();
+            if !ids.contains(&id) {
+                ids.push_back(id);
+            }
+        }
+        queue.waiters.insert(id, Waiter { sender, command, deadline });
+        BLOCKED_CLIENTS.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /**
+     * 唤醒等待已就绪键的客户端，按阻塞的先后顺序服务
+     *
+     * @param db 数据库
+     * @return 被唤醒的客户端实际执行的命令，需要在触发唤醒的命令之后传播
+     */
+    pub fn serve(db: &mut Db) -> Result<Vec<Frame>, Error> {
+        let mut propagation = Vec::new();
+        loop {
+            let keys = db.blocking.keys.keys().cloned().collect::<Vec<_>>();
+            let mut served = false;
+            for key in keys {
+                if db.get(&key).is_none() {
+                    continue;
+                }
+                let ids = db.blocking.keys.get(&key).cloned().unwrap_or_default();
+                for id in ids {
+                    // 可能已经通过其他键得到服务
+                    let waiter = match db.blocking.remove(id) {
+                        Some(waiter) => waiter,
+                        None => continue,
+                    };
+                    if waiter.sender.is_closed() {
+                        continue;
+                    }
+                    match waiter.command.try_apply(db)? {
+                        Some(frame) => {
+                            let effects = waiter.command.propagation_frames(db, &frame);
+                            match waiter.sender.send(Reply { frame, propagation: Vec::new() }) {
+                                Ok(()) => {
+                                    propagation.extend(effects);
+                                    served = true;
+                                },
+                                // 客户端在执行期间断开，撤销执行，元素留给后面的客户端
+                                Err(reply) => waiter.command.restore(db, reply.frame),
+                            }
+                        },
+                        None => {
+                            // 仍然没有可用元素，恢复到原来的位置
+                            let consumes = waiter.command.consumes();
+                            db.blocking.restore(id, waiter);
+                            if consumes {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            // BLMOVE 可能让其他键变为就绪，直到没有客户端被唤醒为止
+            if !served {
+                return Ok(propagation);
+            }
+        }
+    }
+
+    /**
+     * 最近的超时时刻
+     */
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.waiters.values().filter_map(|waiter| waiter.deadline).min()
+    }
+
+    /**
+     * 超时的客户端回复空数组（与 Redis 一致），已断开的客户端直接移除
+     *
+     * @param now 当前时刻
+     */
+    pub fn expire(&mut self, now: Instant) {
+        let expired = self.waiters.iter()
+            .filter(|(_, waiter)| waiter.sender.is_closed() || waiter.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in expired {
+            if let Some(waiter) = self.remove(id) {
+                let _ = waiter.sender.send(Reply { frame: Frame::NullArray, propagation: Vec::new() });
+            }
+        }
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Waiter> {
+        let waiter = self.waiters.remove(&id)?;
+        for key in waiter.command.keys() {
+            if let Some(ids) = self.keys.get_mut(key) {
+                ids.retain(|other| *other != id);
+                if ids.is_empty() {
+                    self.keys.remove(key);
+                }
+            }
+        }
+        BLOCKED_CLIENTS.fetch_sub(1, Ordering::Relaxed);
+        Some(waiter)
+    }
+
+    fn restore(&mut self, id: u64, waiter: Waiter) {
+        for key in waiter.command.keys() {
+            let ids = self.keys.entry(key.clone()).or_default();
+            let position = ids.partition_point(|other| *other < id);
+            if ids.get(position) != Some(&id) {
+                ids.insert(position, id);
+            }
+        }
+        self.waiters.insert(id, waiter);
+        BLOCKED_CLIENTS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for BlockingQueue {
+    fn drop(&mut self) {
+        BLOCKED_CLIENTS.fetch_sub(self.waiters.len() as u64, Ordering::Relaxed);
+    }
+}
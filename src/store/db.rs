@@ -1,22 +1,34 @@
 use std::{
-    collections::{BTreeMap, HashMap, HashSet}, sync::{atomic::{AtomicU64, Ordering}}
+    collections::{HashMap, VecDeque}, sync::{atomic::{AtomicU64, Ordering}}
 };
 
 use anyhow::Error;
 use bincode::{Decode, Encode};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::sync::{
+use tokio::{sync::{
     mpsc::{channel, Receiver, Sender},
     oneshot,
-};
+}, time::Instant};
 
-use crate::{command::Command, frame::Frame, tools::pattern};
+use crate::{command::Command, frame::Frame, store::{blocking::{BlockingCommand, BlockingQueue}, dict::Dict, hash::Hash, set::Set, sorted_set::SortedSet, stream::Stream, vector::Vector}, tools::pattern::Pattern};
 
 // 数据库快照数据结构
 #[derive(Clone, Encode, Decode)]
 pub struct DatabaseSnapshot {
-    pub expire_records: HashMap<String, SystemTime>,
-    pub records: HashMap<String, Structure>,
+    pub expire_records: HashMap<Vec<u8>, SystemTime>,
+    pub records: HashMap<Vec<u8>, Structure>,
+    pub field_expire_records: HashMap<Vec<u8>, HashMap<Vec<u8>, SystemTime>>,
+}
+
+/**
+ * 客户端命令的回复
+ *
+ * @param frame 回复的内容
+ * @param propagation 需要在命令之后传播的命令，如因此被唤醒的阻塞命令实际执行的命令
+ */
+pub struct Reply {
+    pub frame: Frame,
+    pub propagation: Vec<Frame>,
 }
 
 /**
@@ -28,6 +40,8 @@ pub struct DatabaseSnapshot {
 pub enum DatabaseMessage {
     Changes(oneshot::Sender<u64>),
     Command { sender: oneshot::Sender<Frame>, command: Command},
+    Client { sender: oneshot::Sender<Reply>, command: Command },
+    Blocking { sender: oneshot::Sender<Reply>, command: BlockingCommand },
     Snapshot(oneshot::Sender<DatabaseSnapshot>),
     Restore(DatabaseSnapshot),
     CleanExpired, 
@@ -39,6 +53,7 @@ impl Default for DatabaseSnapshot {
         Self {
             expire_records: HashMap::with_capacity(100000),
             records: HashMap::with_capacity(100000),
+            field_expire_records: HashMap::new(),
         }
     }
 }
@@ -47,19 +62,13 @@ impl Default for DatabaseSnapshot {
 
 #[derive(Clone, Encode, Decode)]
 pub enum Structure {
-    String(String),
-    Hash(HashMap<String, String>),
-    SortedSet(BTreeMap<String, f64>),
+    String(Vec<u8>),
+    Hash(Hash),
+    SortedSet(SortedSet),
     VectorCollection(Vector),
-    Set(HashSet<String>),
-    List(Vec<String>)
-}
-
-#[derive(Clone, Encode, Decode)]
-pub struct Vector {
-    pub dimension: usize,
-    pub vectors: HashMap<String, Vec<f32>>,
-    pub norms: HashMap<String, f32>,
+    Set(Set),
+    List(VecDeque<Vec<u8>>),
+    Stream(Stream),
 }
 
 /**
@@ -74,9 +83,11 @@ pub struct Vector {
 pub struct Db {
     receiver: Receiver<DatabaseMessage>,
     pub sender: Sender<DatabaseMessage>,
-    pub expire_records: HashMap<String, SystemTime>,
-    pub records: HashMap<String, Structure>,
+    pub expire_records: HashMap<Vec<u8>, SystemTime>,
+    pub records: Dict<Vec<u8>, Structure>,
+    pub field_expire_records: HashMap<Vec<u8>, HashMap<Vec<u8>, SystemTime>>, // 哈希字段的过期时间
     pub changes: AtomicU64,
+    pub blocking: BlockingQueue,
 }
 
 impl Db {
@@ -90,12 +101,15 @@ impl Db {
 
         let (sender, receiver) = channel(1024);
         let expire_records = snapshot.expire_records;
-        let records = snapshot.records;
+        let records = snapshot.records.into();
+        let field_expire_records = snapshot.field_expire_records;
 
         Db {
             records,
             expire_records,
+            field_expire_records,
             changes: AtomicU64::new(0),
+            blocking: BlockingQueue::default(),
             receiver,
             sender,
         }
@@ -110,33 +124,57 @@ impl Db {
      */
     pub async fn run(&mut self) {
         loop {
-            match self.receiver.recv().await {
+            let deadline = self.blocking.next_deadline();
+            let message = tokio::select! {
+                message = self.receiver.recv() => message,
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    self.blocking.expire(Instant::now());
+                    continue;
+                }
+            };
+            match message {
                 Some(DatabaseMessage::Command { sender, command }) => {
-                    match self.handle_command(command) {
-                        Ok(f) => {
-                            let _ = sender.send(f);
+                    match self.execute(command) {
+                        Ok(reply) => {
+                            let _ = sender.send(reply.frame);
                         },
                         Err(e) => eprintln!("Error applying command: {:?}", e),
                     }
                 },
+                Some(DatabaseMessage::Client { sender, command }) => {
+                    match self.execute(command) {
+                        Ok(reply) => {
+                            let _ = sender.send(reply);
+                        },
+                        Err(e) => eprintln!("Error applying command: {:?}", e),
+                    }
+                },
+                Some(DatabaseMessage::Blocking { sender, command }) => {
+                    if let Err(e) = BlockingQueue::block(self, sender, command) {
+                        eprintln!("Error applying command: {:?}", e);
+                    }
+                },
                 Some(DatabaseMessage::CleanExpired) => {
                     self.clean_expired_keys();
+                    self.blocking.expire(Instant::now());
                 },
                 Some(DatabaseMessage::Changes(sender)) => {
                     let count = self.changes.load(Ordering::Relaxed);
                     let _ = sender.send(count);
                 },
                 Some(DatabaseMessage::Restore(snapshot)) => {
-                    self.records = snapshot.records;
+                    self.records = snapshot.records.into();
                     self.expire_records = snapshot.expire_records;
+                    self.field_expire_records = snapshot.field_expire_records;
                 },
                 Some(DatabaseMessage::ResetChanges) => {
                     self.changes.store(0, Ordering::Relaxed);
                 },
                 Some(DatabaseMessage::Snapshot(sender)) => {
                     let snapshot = DatabaseSnapshot {
-                        records: self.records.clone(),
+                        records: self.records.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
                         expire_records: self.expire_records.clone(),
+                        field_expire_records: self.field_expire_records.clone(),
                     };
                     let _ = sender.send(snapshot);
                 },
@@ -145,6 +183,34 @@ impl Db {
         }
     }
 
+    /**
+     * 执行命令，写命令执行后唤醒等待已就绪键的客户端
+     *
+     * @param command 命令
+     */
+    fn execute(&mut self, command: Command) -> Result<Reply, Error> {
+        let is_write = command.propagate_aof_if_needed();
+        let (result, mut propagation) = match command {
+            // XREADGROUP 传播投递的状态而不是命令本身
+            Command::Xreadgroup(xreadgroup) => {
+                let result = xreadgroup.apply(self);
+                let propagation = match &result {
+                    Ok(frame) => xreadgroup.propagation_frames(self, frame),
+                    Err(_) => Vec::new(),
+                };
+                (result, propagation)
+            },
+            command => (self.handle_command(command), Vec::new()),
+        };
+        if is_write && !self.blocking.is_empty() {
+            match BlockingQueue::serve(self) {
+                Ok(frames) => propagation.extend(frames),
+                Err(e) => eprintln!("Error serving blocked clients: {:?}", e),
+            }
+        }
+        result.map(|frame| Reply { frame, propagation })
+    }
+
     fn handle_command(&mut self, command: Command) -> Result<Frame, Error> {
         match command {
             Command::Set(set) => set.apply(self),
@@ -173,9 +239,24 @@ impl Db {
             Command::Hmget(hmget) => hmget.apply(self),
             Command::Hmset(hmset) => hmset.apply(self),
             Command::Hset(hset) => hset.apply(self),
+            Command::Hincrby(hincrby) => hincrby.apply(self),
+            Command::HincrbyFloat(hincrbyfloat) => hincrbyfloat.apply(self),
+            Command::Hrandfield(hrandfield) => hrandfield.apply(self),
+            Command::Hexpire(hexpire) => hexpire.apply(self),
+            Command::Hpexpire(hpexpire) => hpexpire.apply(self),
+            Command::Hexpireat(hexpireat) => hexpireat.apply(self),
+            Command::Hpexpireat(hpexpireat) => hpexpireat.apply(self),
+            Command::Httl(httl) => httl.apply(self),
+            Command::Hpttl(hpttl) => hpttl.apply(self),
+            Command::Hexpiretime(hexpiretime) => hexpiretime.apply(self),
+            Command::Hpexpiretime(hpexpiretime) => hpexpiretime.apply(self),
+            Command::Hpersist(hpersist) => hpersist.apply(self),
+            Command::Hgetex(hgetex) => hgetex.apply(self),
+            Command::Hscan(hscan) => hscan.apply(self),
             Command::Hget(hget) => hget.apply(self),
             Command::Hdel(hdel) => hdel.apply(self),
             Command::Keys(keys) => keys.apply(self),
+            Command::Scan(scan) => scan.apply(self),
             Command::Hlen(hlen) => hlen.apply(self),
             Command::Hkeys(hkeys) => hkeys.apply(self),
             Command::Hvals(hvals) => hvals.apply(self),
@@ -192,6 +273,14 @@ impl Db {
             Command::Sinter(sinter) => sinter.apply(self),
             Command::Sismember(sismember) => sismember.apply(self),
             Command::Sunionstore(sunionstore) => sunionstore.apply(self),
+            Command::Sdiff(sdiff) => sdiff.apply(self),
+            Command::Sdiffstore(sdiffstore) => sdiffstore.apply(self),
+            Command::Sinterstore(sinterstore) => sinterstore.apply(self),
+            Command::Sintercard(sintercard) => sintercard.apply(self),
+            Command::Smismember(smismember) => smismember.apply(self),
+            Command::Smove(smove) => smove.apply(self),
+            Command::Srandmember(srandmember) => srandmember.apply(self),
+            Command::Sscan(sscan) => sscan.apply(self),
             Command::Smembers(smembers) => smembers.apply(self),
             Command::Sunion(sunion) => sunion.apply(self),
             Command::Rpushx(rpushx) => rpushx.apply(self),
@@ -203,6 +292,60 @@ impl Db {
             Command::Zadd(zadd) => zadd.apply(self),
             Command::Zcount(zcount) => zcount.apply(self),
             Command::Zscore(zscore) => zscore.apply(self),
+            Command::Zrange(zrange) => zrange.apply(self),
+            Command::Zrevrange(zrevrange) => zrevrange.apply(self),
+            Command::Zrangebyscore(zrangebyscore) => zrangebyscore.apply(self),
+            Command::Zrevrangebyscore(zrevrangebyscore) => zrevrangebyscore.apply(self),
+            Command::Zrangebylex(zrangebylex) => zrangebylex.apply(self),
+            Command::Zrevrangebylex(zrevrangebylex) => zrevrangebylex.apply(self),
+            Command::Zrangestore(zrangestore) => zrangestore.apply(self),
+            Command::Zrevrank(zrevrank) => zrevrank.apply(self),
+            Command::Zincrby(zincrby) => zincrby.apply(self),
+            Command::Zmscore(zmscore) => zmscore.apply(self),
+            Command::Zrandmember(zrandmember) => zrandmember.apply(self),
+            Command::Zlexcount(zlexcount) => zlexcount.apply(self),
+            Command::Zremrangebyrank(zremrangebyrank) => zremrangebyrank.apply(self),
+            Command::Zremrangebyscore(zremrangebyscore) => zremrangebyscore.apply(self),
+            Command::Zremrangebylex(zremrangebylex) => zremrangebylex.apply(self),
+            Command::Zpopmin(zpopmin) => zpopmin.apply(self),
+            Command::Zpopmax(zpopmax) => zpopmax.apply(self),
+            Command::Bzpopmin(bzpopmin) => bzpopmin.apply(self),
+            Command::Bzpopmax(bzpopmax) => bzpopmax.apply(self),
+            Command::Zunion(zunion) => zunion.apply(self),
+            Command::Zinter(zinter) => zinter.apply(self),
+            Command::Zdiff(zdiff) => zdiff.apply(self),
+            Command::Zunionstore(zunionstore) => zunionstore.apply(self),
+            Command::Zinterstore(zinterstore) => zinterstore.apply(self),
+            Command::Zdiffstore(zdiffstore) => zdiffstore.apply(self),
+            Command::Zscan(zscan) => zscan.apply(self),
+            Command::Geoadd(geoadd) => geoadd.apply(self),
+            Command::Geopos(geopos) => geopos.apply(self),
+            Command::Geodist(geodist) => geodist.apply(self),
+            Command::Geohash(geohash) => geohash.apply(self),
+            Command::Geosearch(geosearch) => geosearch.apply(self),
+            Command::Geosearchstore(geosearchstore) => geosearchstore.apply(self),
+            Command::Xadd(xadd) => xadd.apply(self),
+            Command::Xrange(xrange) => xrange.apply(self),
+            Command::Xrevrange(xrevrange) => xrevrange.apply(self),
+            Command::Xread(xread) => xread.apply(self),
+            Command::Xlen(xlen) => xlen.apply(self),
+            Command::Xdel(xdel) => xdel.apply(self),
+            Command::Xtrim(xtrim) => xtrim.apply(self),
+            Command::Xgroup(xgroup) => xgroup.apply(self),
+            Command::Xreadgroup(xreadgroup) => xreadgroup.apply(self),
+            Command::Xack(xack) => xack.apply(self),
+            Command::Xpending(xpending) => xpending.apply(self),
+            Command::Xclaim(xclaim) => xclaim.apply(self),
+            Command::Xautoclaim(xautoclaim) => xautoclaim.apply(self),
+            Command::Xinfo(xinfo) => xinfo.apply(self),
+            Command::Vadd(vadd) => vadd.apply(self),
+            Command::Vrem(vrem) => vrem.apply(self),
+            Command::Vemb(vemb) => vemb.apply(self),
+            Command::Vcard(vcard) => vcard.apply(self),
+            Command::Vdim(vdim) => vdim.apply(self),
+            Command::Vsim(vsim) => vsim.apply(self),
+            Command::Vindex(vindex) => vindex.apply(self),
+            Command::Vinfo(vinfo) => vinfo.apply(self),
             Command::Zcard(zcard) => zcard.apply(self),
             Command::Zrank(zrank) => zrank.apply(self),
             Command::Zrem(zrem) => zrem.apply(self),
@@ -214,6 +357,31 @@ impl Db {
             Command::Lrange(lrange) => lrange.apply(self),
             Command::GetSet(getset) => getset.apply(self),
             Command::Info(info) => info.apply(self),
+            Command::Setnx(setnx) => setnx.apply(self),
+            Command::Setex(setex) => setex.apply(self),
+            Command::Psetex(psetex) => psetex.apply(self),
+            Command::Getex(getex) => getex.apply(self),
+            Command::Getdel(getdel) => getdel.apply(self),
+            Command::Msetnx(msetnx) => msetnx.apply(self),
+            Command::Setbit(setbit) => setbit.apply(self),
+            Command::Getbit(getbit) => getbit.apply(self),
+            Command::Bitcount(bitcount) => bitcount.apply(self),
+            Command::Bitpos(bitpos) => bitpos.apply(self),
+            Command::Bitop(bitop) => bitop.apply(self),
+            Command::Bitfield(bitfield) => bitfield.apply(self),
+            Command::Pfadd(pfadd) => pfadd.apply(self),
+            Command::Pfcount(pfcount) => pfcount.apply(self),
+            Command::Pfmerge(pfmerge) => pfmerge.apply(self),
+            Command::Linsert(linsert) => linsert.apply(self),
+            Command::Lrem(lrem) => lrem.apply(self),
+            Command::Ltrim(ltrim) => ltrim.apply(self),
+            Command::Lpos(lpos) => lpos.apply(self),
+            Command::Lmove(lmove) => lmove.apply(self),
+            Command::Rpoplpush(rpoplpush) => rpoplpush.apply(self),
+            Command::Blpop(blpop) => blpop.apply(self),
+            Command::Brpop(brpop) => brpop.apply(self),
+            Command::Blmove(blmove) => blmove.apply(self),
+            Command::Blmpop(blmpop) => blmpop.apply(self),
             _ => Err(Error::msg("Unknown command")),
         }
     }
@@ -224,17 +392,28 @@ impl Db {
      * @param key 键名
      * @param value 值
      */
-    pub fn insert(&mut self, key: String, value: Structure) {
+    pub fn insert(&mut self, key: Vec<u8>, value: Structure) {
         self.changes.fetch_add(1, Ordering::Relaxed);
+        self.field_expire_records.remove(&key);
         self.records.insert(key, value);
     }
 
+    /**
+     * 清空数据库，包括键与哈希字段的过期时间
+     */
+    pub fn clear(&mut self) {
+        self.changes.fetch_add(1, Ordering::Relaxed);
+        self.expire_records.clear();
+        self.field_expire_records.clear();
+        self.records.clear();
+    }
+
     /**
      * 获取键值
      *
      * @param key 键名
      */
-    pub fn get(&mut self, key: &str) -> Option<&Structure> {
+    pub fn get(&mut self, key: &[u8]) -> Option<&Structure> {
         self.expire_if_needed(key);
         self.records.get(key)
     }
@@ -244,7 +423,7 @@ impl Db {
      *
      * @param key 键名
      */
-    pub fn get_mut(&mut self, key: &str) -> Option<&mut Structure> {
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut Structure> {
         self.expire_if_needed(key);
         self.records.get_mut(key)
     }
@@ -255,27 +434,57 @@ impl Db {
      * @param key 键名
      * @param ttl 距离现在多少【毫秒】后过期
      */
-    pub fn expire(&mut self, key: String, ttl: u64) {
+    pub fn expire(&mut self, key: Vec<u8>, ttl: u64) {
         let expire_time = SystemTime::now() + std::time::Duration::from_millis(ttl);
         self.expire_records.insert(key, expire_time);
     }
 
+    /**
+     * 移除过期时间
+     *
+     * @param key 键名
+     * @return 如果键设置了过期时间，返回 true
+     */
+    pub fn persist(&mut self, key: &[u8]) -> bool {
+        self.expire_records.remove(key).is_some()
+    }
+
     /**
      * 删除键值
      *
      * @param key 键名
      * @return 如果删除成功，返回被删除的值；如果删除失败，返回 None
      */
-    pub fn remove(&mut self, key: &str) -> Option<Structure> {
+    pub fn remove(&mut self, key: &[u8]) -> Option<Structure> {
         if self.records.contains_key(key) {
             self.changes.fetch_add(1, Ordering::Relaxed);
             self.expire_records.remove(key);
+            self.field_expire_records.remove(key);
             self.records.remove(key)
         } else {
             None
         }
     }
 
+    /**
+     * 删除空的集合类型键（列表、哈希、集合、有序集合）
+     *
+     * @param key 键名
+     */
+    pub fn remove_if_empty(&mut self, key: &[u8]) {
+        let empty = match self.records.get(key) {
+            Some(Structure::List(list)) => list.is_empty(),
+            Some(Structure::Hash(hash)) => hash.is_empty(),
+            Some(Structure::Set(set)) => set.is_empty(),
+            Some(Structure::SortedSet(set)) => set.is_empty(),
+            Some(Structure::VectorCollection(vector)) => vector.is_empty(),
+            _ => false,
+        };
+        if empty {
+            self.remove(key);
+        }
+    }
+
     /**
      * 清理过期键
      */
@@ -295,6 +504,12 @@ impl Db {
         for key in expired_keys {
             self.remove(&key); // 调用 remove 方法
         }
+
+        // 删除过期的哈希字段
+        let keys = self.field_expire_records.keys().cloned().collect::<Vec<_>>();
+        for key in keys {
+            self.expire_fields_if_needed(&key);
+        }
     }
 
     /**
@@ -302,21 +517,96 @@ impl Db {
      *
      * @param key 键名
      */
-    pub fn expire_if_needed(&mut self, key: &str) {
+    pub fn expire_if_needed(&mut self, key: &[u8]) {
         if let Some(expire_time) = self.expire_records.get(key) {
             if SystemTime::now() > *expire_time {
                 self.remove(key);
+                return;
             }
         }
+        if self.field_expire_records.contains_key(key) {
+            self.expire_fields_if_needed(key);
+        }
+    }
+
+    /**
+     * 哈希字段过期检测，字段全部过期后删除键
+     *
+     * @param key 键名
+     */
+    fn expire_fields_if_needed(&mut self, key: &[u8]) {
+        let Some(fields) = self.field_expire_records.get_mut(key) else {
+            return;
+        };
+        let now = SystemTime::now();
+        let expired = fields.iter()
+            .filter(|(_, expire_time)| now >= **expire_time)
+            .map(|(field, _)| field.clone())
+            .collect::<Vec<_>>();
+        if expired.is_empty() {
+            return;
+        }
+        for field in &expired {
+            fields.remove(field);
+        }
+        if fields.is_empty() {
+            self.field_expire_records.remove(key);
+        }
+        if let Some(Structure::Hash(hash)) = self.records.get_mut(key) {
+            for field in &expired {
+                hash.remove(field);
+            }
+            self.changes.fetch_add(1, Ordering::Relaxed);
+        }
+        self.remove_if_empty(key);
+    }
+
+    /**
+     * 设置哈希字段的过期时间
+     *
+     * @param key 键名
+     * @param field 字段
+     * @param expire_time 过期时刻
+     */
+    pub fn expire_field(&mut self, key: &[u8], field: &[u8], expire_time: SystemTime) {
+        self.field_expire_records.entry(key.to_vec()).or_default().insert(field.to_vec(), expire_time);
+    }
+
+    /**
+     * 获取哈希字段的过期时刻
+     *
+     * @param key 键名
+     * @param field 字段
+     */
+    pub fn field_expire_time(&self, key: &[u8], field: &[u8]) -> Option<SystemTime> {
+        self.field_expire_records.get(key).and_then(|fields| fields.get(field)).copied()
+    }
+
+    /**
+     * 移除哈希字段的过期时间
+     *
+     * @param key 键名
+     * @param field 字段
+     * @return 如果字段设置了过期时间，返回 true
+     */
+    pub fn persist_field(&mut self, key: &[u8], field: &[u8]) -> bool {
+        let Some(fields) = self.field_expire_records.get_mut(key) else {
+            return false;
+        };
+        let removed = fields.remove(field).is_some();
+        if fields.is_empty() {
+            self.field_expire_records.remove(key);
+        }
+        removed
     }
 
     /**
      * 获取过期毫秒数
      *
      * @param key 键名
-     * @return 过期毫秒数，如果键不存在则返回 -2，如果键已过期则返回 -1
+     * @return 过期毫秒数，如果键不存在则返回 -2，如果键未设置过期则返回 -1
      */
-    pub fn ttl_millis(&mut self, key: &str) -> i64 {
+    pub fn ttl_millis(&mut self, key: &[u8]) -> i64 {
         if let Some(expire_time) = self.expire_records.get(key) {
             let now = SystemTime::now();
             if now >= *expire_time {
@@ -331,7 +621,7 @@ impl Db {
                 }
             }
         } else if self.records.contains_key(key) {
-            -2
+            -1
         } else {
             -2
         }
@@ -343,7 +633,7 @@ impl Db {
      * @param key 键名
      * @return 如果键存在返回 true，否则返回 false
      */
-    pub fn exists(&self, key: &str) -> bool {
+    pub fn exists(&self, key: &[u8]) -> bool {
         self.records.contains_key(key)
     }
 
@@ -353,8 +643,28 @@ impl Db {
      * @param pattern 模式
      * @return 符合模式的所有键的列表
      */
-    pub fn keys(&self, pattern: &str) -> Vec<String> {
-        self.records.keys().filter(|key| pattern::is_match(key, pattern)).cloned().collect()
+    pub fn keys(&self, pattern: &Pattern) -> Vec<Vec<u8>> {
+        if pattern.matches_all() {
+            return self.records.keys().cloned().collect();
+        }
+        self.records.keys().filter(|key| pattern.is_match(key)).cloned().collect()
+    }
+
+    /**
+     * 从游标开始遍历一部分键，已过期的键会被删除而不返回
+     *
+     * @param cursor 游标，0 表示开始遍历
+     * @param count 期望返回的数量
+     * @return (下一次的游标, 键)，游标为 0 表示遍历完成
+     */
+    pub fn scan(&mut self, cursor: u64, count: usize) -> (u64, Vec<Vec<u8>>) {
+        let (cursor, keys) = self.records.scan(cursor, count);
+        let mut keys = keys.into_iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.retain(|key| {
+            self.expire_if_needed(key);
+            self.records.contains_key(key)
+        });
+        (cursor, keys)
     }
 
     /**
@@ -362,8 +672,8 @@ impl Db {
      * 
      * @param 
      */
-    pub fn random_key(&self) -> Option<String> {
-        let keys: Vec<String> = self.records.keys().cloned().collect();
+    pub fn random_key(&self) -> Option<Vec<u8>> {
+        let keys: Vec<Vec<u8>> = self.records.keys().cloned().collect();
         if keys.is_empty() {
             return None;
         }
@@ -0,0 +1,289 @@
+use std::{borrow::Borrow, collections::{hash_map::RandomState, HashMap}, hash::{BuildHasher, Hash}};
+
+use bincode::{de::{BorrowDecoder, Decoder}, enc::Encoder, error::{DecodeError, EncodeError}, BorrowDecode, Decode, Encode};
+
+// 桶数量的最小值
+const MIN_BUCKETS: usize = 4;
+
+/**
+ * 支持 SCAN 游标的哈希表
+ *
+ * 采用与 Redis dict 相同的链式结构：元素所在的桶只由哈希值的低位决定，桶的数量总是 2 的幂。
+ * 游标是下一个要访问的桶，按反转二进制位的顺序递增，因此遍历期间扩容或缩容，
+ * 一直存在的元素仍然至少会被返回一次。遍历直接读取桶中的元素，不需要额外的索引。
+ *
+ * @param buckets 桶，长度为 0 或 2 的幂
+ * @param len 元素数量
+ * @param hasher 哈希函数，每个哈希表使用随机的密钥
+ */
+#[derive(Clone)]
+pub struct Dict<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+    len: usize,
+    hasher: RandomState,
+}
+
+impl<K, V> Default for Dict<K, V> {
+    fn default() -> Self {
+        Dict { buckets: Vec::new(), len: 0, hasher: RandomState::new() }
+    }
+}
+
+impl<K, V> Dict<K, V> {
+
+    pub fn new() -> Self {
+        Dict::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets = Vec::new();
+        self.len = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets.iter().flatten().map(|(key, value)| (key, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.buckets.iter_mut().flatten().map(|(key, value)| (&*key, value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /**
+     * 从游标开始遍历一部分元素
+     *
+     * 每次返回整个桶中的元素，因此返回的数量可能略大于 count；
+     * 连续遇到大量空桶时提前返回，返回的数量可能小于 count
+     *
+     * @param cursor 游标，0 表示开始遍历
+     * @param count 期望返回的数量
+     * @return (下一次的游标, 元素)，游标为 0 表示遍历完成
+     */
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&K, &V)>) {
+        if self.buckets.is_empty() {
+            return (0, Vec::new());
+        }
+        let count = count.max(1);
+        let mask = (self.buckets.len() - 1) as u64;
+        let mut cursor = cursor;
+        let mut empty_visits = count * 10;
+        let mut result = Vec::with_capacity(count);
+        loop {
+            let bucket = &self.buckets[(cursor & mask) as usize];
+            result.extend(bucket.iter().map(|(key, value)| (key, value)));
+            // 将游标的高位置 1 后反转，加 1 再反转回来，得到下一个桶
+            cursor = (cursor | !mask).reverse_bits().wrapping_add(1).reverse_bits();
+            if cursor == 0 || result.len() >= count {
+                break;
+            }
+            if bucket.is_empty() {
+                empty_visits -= 1;
+                if empty_visits == 0 {
+                    break;
+                }
+            }
+        }
+        (cursor, result)
+    }
+}
+
+impl<K: Hash + Eq, V> Dict<K, V> {
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut dict = Dict::new();
+        if capacity > 0 {
+            dict.buckets = Self::empty_buckets(capacity.next_power_of_two().max(MIN_BUCKETS));
+        }
+        dict
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let bucket = self.buckets.get(self.bucket(key)?)?;
+        bucket.iter().find(|(k, _)| k.borrow() == key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = self.bucket(key)?;
+        self.buckets[index].iter_mut().find(|(k, _)| k.borrow() == key).map(|(_, value)| value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get(key).is_some()
+    }
+
+    /**
+     * 插入元素
+     *
+     * @param key 键
+     * @param value 值
+     * @return 键原有的值
+     */
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(current) = self.get_mut(&key) {
+            return Some(std::mem::replace(current, value));
+        }
+        // 平均每个桶超过一个元素时扩容
+        if self.len >= self.buckets.len() {
+            self.resize((self.len + 1).next_power_of_two().max(MIN_BUCKETS));
+        }
+        let index = self.bucket(&key).unwrap_or_default();
+        let bucket = &mut self.buckets[index];
+        bucket.reserve_exact(1);
+        bucket.push((key, value));
+        self.len += 1;
+        None
+    }
+
+    /**
+     * 删除元素
+     *
+     * @param key 键
+     * @return 键原有的值
+     */
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let index = self.bucket(key)?;
+        let bucket = &mut self.buckets[index];
+        let position = bucket.iter().position(|(k, _)| k.borrow() == key)?;
+        let (_, value) = bucket.swap_remove(position);
+        if bucket.is_empty() {
+            *bucket = Vec::new();
+        }
+        self.len -= 1;
+        // 使用率低于 1/8 时缩容，删除所有元素后释放桶
+        if self.len == 0 {
+            self.buckets = Vec::new();
+        } else if self.buckets.len() > MIN_BUCKETS && self.len * 8 < self.buckets.len() {
+            self.resize(self.len.next_power_of_two().max(MIN_BUCKETS));
+        }
+        Some(value)
+    }
+
+    /**
+     * 保留满足条件的元素
+     *
+     * @param keep 判断是否保留的函数
+     */
+    pub fn retain(&mut self, mut keep: impl FnMut(&K, &mut V) -> bool) {
+        for bucket in &mut self.buckets {
+            bucket.retain_mut(|(key, value)| keep(key, value));
+        }
+        self.len = self.buckets.iter().map(Vec::len).sum();
+        if self.len == 0 {
+            self.buckets = Vec::new();
+        } else if self.buckets.len() > MIN_BUCKETS && self.len * 8 < self.buckets.len() {
+            self.resize(self.len.next_power_of_two().max(MIN_BUCKETS));
+        }
+    }
+
+    fn bucket<Q>(&self, key: &Q) -> Option<usize> where Q: Hash + ?Sized {
+        if self.buckets.is_empty() {
+            return None;
+        }
+        Some(self.hasher.hash_one(key) as usize & (self.buckets.len() - 1))
+    }
+
+    fn empty_buckets(size: usize) -> Vec<Vec<(K, V)>> {
+        let mut buckets = Vec::with_capacity(size);
+        buckets.resize_with(size, Vec::new);
+        buckets
+    }
+
+    /**
+     * 调整桶的数量，并将所有元素移动到新的桶中
+     *
+     * @param size 新的桶数量，必须是 2 的幂
+     */
+    fn resize(&mut self, size: usize) {
+        let buckets = std::mem::replace(&mut self.buckets, Self::empty_buckets(size));
+        for (key, value) in buckets.into_iter().flatten() {
+            let index = self.hasher.hash_one(&key) as usize & (size - 1);
+            let bucket = &mut self.buckets[index];
+            bucket.reserve_exact(1);
+            bucket.push((key, value));
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for Dict<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut dict = Dict::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for Dict<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> From<HashMap<K, V>> for Dict<K, V> {
+    fn from(map: HashMap<K, V>) -> Self {
+        let mut dict = Dict::with_capacity(map.len());
+        dict.extend(map);
+        dict
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize> From<[(K, V); N]> for Dict<K, V> {
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl<K: Hash + Eq, V> From<Dict<K, V>> for HashMap<K, V> {
+    fn from(dict: Dict<K, V>) -> Self {
+        dict.into_iter().collect()
+    }
+}
+
+impl<K, V> IntoIterator for Dict<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::iter::Flatten<std::vec::IntoIter<Vec<(K, V)>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buckets.into_iter().flatten()
+    }
+}
+
+// 序列化格式与 HashMap 相同，兼容旧版本的快照文件
+impl<K: Encode, V: Encode> Encode for Dict<K, V> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.len as u64).encode(encoder)?;
+        for (key, value) in self.iter() {
+            key.encode(encoder)?;
+            value.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Decode + Hash + Eq, V: Decode> Decode for Dict<K, V> {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(HashMap::decode(decoder)?.into())
+    }
+}
+
+impl<'de, K: BorrowDecode<'de> + Hash + Eq, V: BorrowDecode<'de>> BorrowDecode<'de> for Dict<K, V> {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(HashMap::borrow_decode(decoder)?.into())
+    }
+}
@@ -0,0 +1,8 @@
+use crate::store::dict::Dict;
+
+/**
+ * 哈希
+ *
+ * 字段到值的映射，HSCAN 直接按桶遍历
+ */
+pub type Hash = Dict<Vec<u8>, Vec<u8>>;
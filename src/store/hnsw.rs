@@ -0,0 +1,385 @@
+use std::{cmp::{Ordering, Reverse}, collections::{BinaryHeap, HashMap, HashSet}};
+
+use bincode::{Decode, Encode};
+
+use crate::{store::vector::{dot, norm, Metric}, tools::random};
+
+// 节点层数上限
+const MAX_LEVEL: usize = 16;
+
+/**
+ * 候选节点，按距离排序
+ */
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/**
+ * 图中的节点
+ *
+ * @param element 元素名称，向量本身保存在所属的向量集合中
+ * @param neighbors 每一层的邻居，层数为 neighbors.len() - 1
+ */
+#[derive(Clone, Encode, Decode)]
+struct Node {
+    element: Vec<u8>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+/**
+ * 查询向量及其模长
+ */
+#[derive(Clone, Copy)]
+struct Query<'a> {
+    vector: &'a [f32],
+    norm: f32,
+}
+
+/**
+ * 向量集合的数据，索引只保存图结构，计算距离时从这里读取向量
+ */
+#[derive(Clone, Copy)]
+pub struct Space<'a> {
+    pub vectors: &'a HashMap<Vec<u8>, Vec<f32>>,
+    pub norms: &'a HashMap<Vec<u8>, f32>,
+}
+
+impl<'a> Space<'a> {
+
+    fn query(&self, element: &[u8]) -> Query<'a> {
+        let vector = self.vectors[element].as_slice();
+        let norm = self.norms.get(element).copied().unwrap_or_else(|| norm(vector));
+        Query { vector, norm }
+    }
+}
+
+/**
+ * HNSW（Hierarchical Navigable Small World）近似最近邻索引
+ *
+ * 节点保存在数组中，删除后空出的位置会被复用。删除节点时只修复它的出边邻居，
+ * 其余指向它的边在搜索时跳过。
+ *
+ * @param m 每层保留的邻居数量，第 0 层为 2 * m
+ * @param ef_construction 插入时的候选集大小
+ * @param ef_search 查询时默认的候选集大小
+ * @param metric 构建索引使用的度量方式
+ * @param entry 入口节点，位于最高层
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct Hnsw {
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+    metric: Metric,
+    nodes: Vec<Option<Node>>,
+    free: Vec<usize>,
+    ids: HashMap<Vec<u8>, usize>,
+    entry: Option<usize>,
+}
+
+impl Hnsw {
+
+    pub fn new(m: usize, ef_construction: usize, ef_search: usize, metric: Metric) -> Self {
+        Hnsw { m, ef_construction, ef_search, metric, nodes: Vec::new(), free: Vec::new(), ids: HashMap::new(), entry: None }
+    }
+
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    pub fn ef_construction(&self) -> usize {
+        self.ef_construction
+    }
+
+    pub fn ef_search(&self) -> usize {
+        self.ef_search
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /**
+     * 最高层的层号，空索引返回 None
+     */
+    pub fn max_level(&self) -> Option<usize> {
+        self.entry.map(|entry| self.level(entry))
+    }
+
+    fn node(&self, id: usize) -> &Node {
+        self.nodes[id].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut Node {
+        self.nodes[id].as_mut().unwrap()
+    }
+
+    fn level(&self, id: usize) -> usize {
+        self.node(id).neighbors.len() - 1
+    }
+
+    /**
+     * 每层允许的最大邻居数量
+     */
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 { self.m * 2 } else { self.m }
+    }
+
+    /**
+     * 按 1 / ln(m) 的指数分布随机生成节点层数
+     */
+    fn random_level(&self) -> usize {
+        let uniform = (random::next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        let level = -(1.0 - uniform).ln() / (self.m as f64).ln();
+        (level as usize).min(MAX_LEVEL)
+    }
+
+    /**
+     * 查询向量到节点的距离，越小越相似
+     */
+    fn distance(&self, space: &Space, query: Query, id: usize) -> f32 {
+        let element = &self.node(id).element;
+        let vector = &space.vectors[element];
+        match self.metric {
+            Metric::Cosine => {
+                let norm = space.norms.get(element).copied().unwrap_or_else(|| norm(vector));
+                if norm == 0.0 || query.norm == 0.0 { 1.0 } else { 1.0 - dot(query.vector, vector) / (query.norm * norm) }
+            },
+            Metric::L2 => query.vector.iter().zip(vector).map(|(x, y)| (x - y) * (x - y)).sum(),
+            Metric::Ip => -dot(query.vector, vector),
+        }
+    }
+
+    /**
+     * 在指定层上搜索距离查询向量最近的 ef 个节点
+     *
+     * @param entries 入口节点
+     * @return 按距离升序排列的节点
+     */
+    fn search_layer(&self, space: &Space, query: Query, entries: Vec<Candidate>, ef: usize, layer: usize) -> Vec<Candidate> {
+        let mut visited = entries.iter().map(|candidate| candidate.id).collect::<HashSet<_>>();
+        let mut candidates = entries.iter().copied().map(Reverse).collect::<BinaryHeap<_>>();
+        let mut results = entries.into_iter().collect::<BinaryHeap<_>>();
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(candidate)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|farthest| candidate.distance > farthest.distance) {
+                break;
+            }
+            for &neighbor in &self.node(candidate.id).neighbors[layer] {
+                // 跳过已删除或被复用为较低层数的节点
+                let valid = self.nodes[neighbor].as_ref().is_some_and(|node| node.neighbors.len() > layer);
+                if !valid || !visited.insert(neighbor) {
+                    continue;
+                }
+                let distance = self.distance(space, query, neighbor);
+                if results.len() < ef || results.peek().is_some_and(|farthest| distance < farthest.distance) {
+                    let candidate = Candidate { distance, id: neighbor };
+                    candidates.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    /**
+     * 启发式选择邻居：只保留比已选邻居更靠近基准点的候选，使邻居分布在不同方向，
+     * 不足时再用被跳过的候选补齐
+     *
+     * @param candidates 按到基准点距离升序排列的候选
+     * @param max 最多选择的数量
+     */
+    fn select_neighbors(&self, space: &Space, candidates: Vec<Candidate>, max: usize) -> Vec<usize> {
+        let mut selected: Vec<Candidate> = Vec::with_capacity(max);
+        let mut pruned = Vec::new();
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let query = space.query(&self.node(candidate.id).element);
+            if selected.iter().all(|chosen| self.distance(space, query, chosen.id) > candidate.distance) {
+                selected.push(candidate);
+            } else {
+                pruned.push(candidate);
+            }
+        }
+        let remaining = max - selected.len();
+        selected.extend(pruned.into_iter().take(remaining));
+        selected.into_iter().map(|candidate| candidate.id).collect()
+    }
+
+    /**
+     * 以节点自身为基准重新选择某一层的邻居
+     *
+     * @param id 节点
+     * @param candidates 候选节点
+     */
+    fn reconnect(&mut self, space: &Space, id: usize, candidates: impl IntoIterator<Item = usize>, layer: usize) {
+        let query = space.query(&self.node(id).element);
+        let mut candidates = candidates.into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter(|&candidate| candidate != id && self.nodes[candidate].as_ref().is_some_and(|node| node.neighbors.len() > layer))
+            .map(|candidate| Candidate { distance: self.distance(space, query, candidate), id: candidate })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        let neighbors = self.select_neighbors(space, candidates, self.max_links(layer));
+        self.node_mut(id).neighbors[layer] = neighbors;
+    }
+
+    /**
+     * 从入口节点逐层贪心下降到指定层
+     */
+    fn descend(&self, space: &Space, query: Query, entry: usize, target: usize) -> Vec<Candidate> {
+        let mut entries = vec![Candidate { distance: self.distance(space, query, entry), id: entry }];
+        for layer in (target + 1..=self.level(entry)).rev() {
+            entries = self.search_layer(space, query, entries, 1, layer);
+        }
+        entries
+    }
+
+    /**
+     * 插入元素，已存在时按新的向量重新插入
+     *
+     * @param element 元素名称，向量必须已经保存在 space 中
+     */
+    pub fn insert(&mut self, space: &Space, element: &[u8]) {
+        if self.ids.contains_key(element) {
+            self.remove(space, element);
+        }
+
+        let level = self.random_level();
+        let node = Node { element: element.to_vec(), neighbors: vec![Vec::new(); level + 1] };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            },
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.ids.insert(element.to_vec(), id);
+
+        let entry = match self.entry {
+            Some(entry) => entry,
+            None => {
+                self.entry = Some(id);
+                return;
+            }
+        };
+
+        let query = space.query(element);
+        let top = self.level(entry);
+        let mut entries = self.descend(space, query, entry, level);
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(space, query, entries, self.ef_construction, layer);
+            let neighbors = self.select_neighbors(space, candidates.clone(), self.m);
+            let max_links = self.max_links(layer);
+            for &neighbor in &neighbors {
+                let links = &mut self.node_mut(neighbor).neighbors[layer];
+                links.push(id);
+                // 邻居的边数超出上限时重新选择
+                if links.len() > max_links {
+                    let links = links.clone();
+                    self.reconnect(space, neighbor, links, layer);
+                }
+            }
+            self.node_mut(id).neighbors[layer] = neighbors;
+            entries = candidates;
+        }
+        if level > top {
+            self.entry = Some(id);
+        }
+    }
+
+    /**
+     * 删除元素，并用被删除节点的邻居修复受影响节点的连接
+     *
+     * @param element 元素名称
+     * @return 元素是否存在
+     */
+    pub fn remove(&mut self, space: &Space, element: &[u8]) -> bool {
+        let id = match self.ids.remove(element) {
+            Some(id) => id,
+            None => return false,
+        };
+        let node = self.nodes[id].take().unwrap();
+        self.free.push(id);
+
+        for (layer, neighbors) in node.neighbors.iter().enumerate() {
+            for &neighbor in neighbors {
+                let valid = self.nodes[neighbor].as_ref().is_some_and(|node| node.neighbors.len() > layer);
+                if !valid {
+                    continue;
+                }
+                let links = &self.node(neighbor).neighbors[layer];
+                if !links.contains(&id) {
+                    continue;
+                }
+                let candidates = links.iter().copied().filter(|&link| link != id).chain(neighbors.iter().copied()).collect::<Vec<_>>();
+                self.reconnect(space, neighbor, candidates, layer);
+            }
+        }
+
+        if self.entry == Some(id) {
+            // 优先从同一层的邻居中选出新的入口，否则选出层数最高的节点
+            let top = node.neighbors.len() - 1;
+            self.entry = node.neighbors[top].iter().copied()
+                .find(|&neighbor| self.nodes[neighbor].as_ref().is_some_and(|node| node.neighbors.len() > top))
+                .or_else(|| self.ids.values().copied().max_by_key(|&id| self.level(id)));
+        }
+        true
+    }
+
+    /**
+     * 查询最相似的 count 个元素
+     *
+     * @param query 查询向量
+     * @param count 返回数量
+     * @param ef 候选集大小，越大结果越准确，不小于 count
+     * @return 按相似程度排列的元素
+     */
+    pub fn search<'s>(&'s self, space: &Space, query: &[f32], count: usize, ef: usize) -> Vec<&'s [u8]> {
+        let entry = match self.entry {
+            Some(entry) => entry,
+            None => return Vec::new(),
+        };
+        let query = Query { vector: query, norm: norm(query) };
+        let entries = self.descend(space, query, entry, 0);
+        let mut results = self.search_layer(space, query, entries, ef.max(count), 0);
+        results.truncate(count);
+        results.into_iter().map(|candidate| self.node(candidate.id).element.as_slice()).collect()
+    }
+}
@@ -0,0 +1,356 @@
+use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+
+// 寄存器索引位数，共 2^14 = 16384 个寄存器，标准误差约 1.04 / sqrt(16384) = 0.81%
+const HLL_P: u32 = 14;
+
+// 寄存器数量
+const HLL_REGISTERS: usize = 1 << HLL_P;
+
+// 哈希值中用于计算前导零的位数
+const HLL_Q: u32 = 64 - HLL_P;
+
+// 每个寄存器占用的位数
+const HLL_BITS: usize = 6;
+
+// 稠密编码占用的字节数（额外一个字节用于跨字节读写）
+const HLL_DENSE_SIZE: usize = HLL_REGISTERS * HLL_BITS / 8 + 1;
+
+// 稀疏编码的最大条目数，超过后转换为稠密编码
+const HLL_SPARSE_MAX_ENTRIES: usize = 1000;
+
+// 稀疏编码能够保存的最大寄存器值，超过后转换为稠密编码
+const HLL_SPARSE_MAX_VALUE: u8 = 32;
+
+// 与 Redis 一致的哈希种子
+const HLL_HASH_SEED: u64 = 0xadc83b19;
+
+// 保存为字符串值时的头部
+const HLL_MAGIC: &[u8] = b"HYLL";
+
+/**
+ * HyperLogLog 基数估计
+ *
+ * 基数较小时使用稀疏编码（只保存非零寄存器），
+ * 超过阈值后转换为 6 位紧凑存储的稠密编码（约 12KB）。
+ * 与 Redis 一致，HyperLogLog 以字符串值保存，PF 系列命令在读写时编码与解码
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct HyperLogLog {
+    encoding: Encoding,
+}
+
+#[derive(Clone, Encode, Decode)]
+enum Encoding {
+    Sparse(Vec<(u16, u8)>), // 按寄存器索引排序的非零寄存器
+    Dense(Vec<u8>),
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+
+    pub fn new() -> Self {
+        HyperLogLog { encoding: Encoding::Sparse(Vec::new()) }
+    }
+
+    /**
+     * 编码为字符串值
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = HLL_MAGIC.to_vec();
+        bytes.extend(encode_to_vec(self, config::standard()).expect("HyperLogLog encoding failed"));
+        bytes
+    }
+
+    /**
+     * 从字符串值解码，字符串被 SETRANGE 等命令修改过时可能不再有效
+     *
+     * @param bytes 字符串值
+     * @return 不是有效的 HyperLogLog 时返回 None
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.strip_prefix(HLL_MAGIC)?;
+        let (hll, len) = decode_from_slice::<HyperLogLog, _>(bytes, config::standard()).ok()?;
+        if len != bytes.len() || !hll.is_valid() {
+            return None;
+        }
+        Some(hll)
+    }
+
+    /**
+     * 寄存器的索引与取值是否都在有效范围内
+     */
+    fn is_valid(&self) -> bool {
+        match &self.encoding {
+            Encoding::Sparse(entries) => {
+                entries.len() <= HLL_SPARSE_MAX_ENTRIES
+                    && entries.windows(2).all(|pair| pair[0].0 < pair[1].0)
+                    && entries.iter().all(|(index, value)| (*index as usize) < HLL_REGISTERS && (1..=HLL_SPARSE_MAX_VALUE).contains(value))
+            },
+            Encoding::Dense(dense) => {
+                dense.len() == HLL_DENSE_SIZE
+                    && (0..HLL_REGISTERS).all(|index| dense_get(dense, index) as u32 <= HLL_Q + 1)
+            },
+        }
+    }
+
+    /**
+     * 是否为稀疏编码
+     */
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.encoding, Encoding::Sparse(_))
+    }
+
+    /**
+     * 添加元素
+     *
+     * @param element 元素
+     * @return 如果有寄存器被更新，返回 true
+     */
+    pub fn add(&mut self, element: &[u8]) -> bool {
+        let hash = murmurhash64a(element, HLL_HASH_SEED);
+        let index = (hash & (HLL_REGISTERS as u64 - 1)) as usize;
+        let bits = (hash >> HLL_P) | (1 << HLL_Q);
+        let count = bits.trailing_zeros() as u8 + 1;
+        self.update_register(index, count)
+    }
+
+    /**
+     * 合并另一个 HyperLogLog（逐个寄存器取最大值）
+     *
+     * @param other 另一个 HyperLogLog
+     */
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        match &other.encoding {
+            Encoding::Sparse(entries) => {
+                for (index, value) in entries {
+                    self.update_register(*index as usize, *value);
+                }
+            },
+            Encoding::Dense(_) => {
+                for (index, value) in other.registers().into_iter().enumerate() {
+                    if value > 0 {
+                        self.update_register(index, value);
+                    }
+                }
+            },
+        }
+    }
+
+    /**
+     * 估计基数
+     */
+    pub fn count(&self) -> u64 {
+        HyperLogLog::estimate(&self.registers())
+    }
+
+    /**
+     * 估计多个 HyperLogLog 并集的基数
+     *
+     * @param hlls HyperLogLog 列表
+     */
+    pub fn count_union(hlls: &[&HyperLogLog]) -> u64 {
+        let mut registers = vec![0u8; HLL_REGISTERS];
+        for hll in hlls {
+            for (index, value) in hll.registers().into_iter().enumerate() {
+                registers[index] = registers[index].max(value);
+            }
+        }
+        HyperLogLog::estimate(&registers)
+    }
+
+    /**
+     * 获取全部寄存器的值
+     */
+    fn registers(&self) -> Vec<u8> {
+        match &self.encoding {
+            Encoding::Sparse(entries) => {
+                let mut registers = vec![0u8; HLL_REGISTERS];
+                for (index, value) in entries {
+                    registers[*index as usize] = *value;
+                }
+                registers
+            },
+            Encoding::Dense(dense) => (0..HLL_REGISTERS).map(|index| dense_get(dense, index)).collect(),
+        }
+    }
+
+    /**
+     * 当新值大于寄存器当前值时更新寄存器，必要时转换为稠密编码
+     *
+     * @param index 寄存器索引
+     * @param value 新值
+     */
+    fn update_register(&mut self, index: usize, value: u8) -> bool {
+        if let Encoding::Sparse(entries) = &mut self.encoding {
+            match entries.binary_search_by_key(&(index as u16), |(i, _)| *i) {
+                Ok(pos) => {
+                    if entries[pos].1 >= value {
+                        return false;
+                    }
+                    if value <= HLL_SPARSE_MAX_VALUE {
+                        entries[pos].1 = value;
+                        return true;
+                    }
+                },
+                Err(pos) => {
+                    if value <= HLL_SPARSE_MAX_VALUE && entries.len() < HLL_SPARSE_MAX_ENTRIES {
+                        entries.insert(pos, (index as u16, value));
+                        return true;
+                    }
+                },
+            }
+            self.promote();
+        }
+
+        match &mut self.encoding {
+            Encoding::Dense(dense) => {
+                if dense_get(dense, index) >= value {
+                    return false;
+                }
+                dense_set(dense, index, value);
+                true
+            },
+            Encoding::Sparse(_) => unreachable!(),
+        }
+    }
+
+    /**
+     * 稀疏编码转换为稠密编码
+     */
+    fn promote(&mut self) {
+        if let Encoding::Sparse(entries) = &self.encoding {
+            let mut dense = vec![0u8; HLL_DENSE_SIZE];
+            for (index, value) in entries {
+                dense_set(&mut dense, *index as usize, *value);
+            }
+            self.encoding = Encoding::Dense(dense);
+        }
+    }
+
+    /**
+     * 基数估计（Otmar Ertl 改进的估计算法，与 Redis 一致）
+     *
+     * @param registers 全部寄存器的值
+     */
+    fn estimate(registers: &[u8]) -> u64 {
+        let m = HLL_REGISTERS as f64;
+        let mut histogram = [0u32; HLL_Q as usize + 2];
+        for value in registers {
+            histogram[*value as usize] += 1;
+        }
+
+        let mut z = m * tau((m - histogram[HLL_Q as usize + 1] as f64) / m);
+        for j in (1..=HLL_Q as usize).rev() {
+            z += histogram[j] as f64;
+            z *= 0.5;
+        }
+        z += m * sigma(histogram[0] as f64 / m);
+
+        let alpha_inf = 0.5 / std::f64::consts::LN_2;
+        (alpha_inf * m * m / z).round() as u64
+    }
+}
+
+/**
+ * 读取稠密编码中的寄存器
+ *
+ * @param dense 稠密编码
+ * @param index 寄存器索引
+ */
+fn dense_get(dense: &[u8], index: usize) -> u8 {
+    let byte = index * HLL_BITS / 8;
+    let fb = (index * HLL_BITS) & 7;
+    let value = (dense[byte] as u16 >> fb) | ((dense[byte + 1] as u16) << (8 - fb));
+    (value & 63) as u8
+}
+
+/**
+ * 写入稠密编码中的寄存器
+ *
+ * @param dense 稠密编码
+ * @param index 寄存器索引
+ * @param value 值
+ */
+fn dense_set(dense: &mut [u8], index: usize, value: u8) {
+    let byte = index * HLL_BITS / 8;
+    let fb = (index * HLL_BITS) & 7;
+    let word = (dense[byte] as u16) | ((dense[byte + 1] as u16) << 8);
+    let word = (word & !(63u16 << fb)) | ((value as u16 & 63) << fb);
+    dense[byte] = word as u8;
+    dense[byte + 1] = (word >> 8) as u8;
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if previous == z {
+            return z;
+        }
+    }
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if previous == z {
+            return z / 3.0;
+        }
+    }
+}
+
+/**
+ * MurmurHash64A（与 Redis 一致）
+ *
+ * @param key 数据
+ * @param seed 种子
+ */
+fn murmurhash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let chunks = key.chunks_exact(8);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
@@ -1,2 +1,10 @@
 pub mod db;
-pub mod db_manager;
\ No newline at end of file
+pub mod db_manager;
+pub mod hyperloglog;pub mod blocking;
+pub mod sorted_set;
+pub mod vector;
+pub mod hnsw;
+pub mod stream;
+pub mod dict;
+pub mod hash;
+pub mod set;
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use bincode::{de::Decoder, enc::Encoder, error::{DecodeError, EncodeError}, Decode, Encode};
+
+use crate::store::dict::Dict;
+
+/**
+ * 集合
+ *
+ * 成员的哈希集合，值为空的 Dict，SSCAN 直接按桶遍历
+ */
+#[derive(Clone, Default)]
+pub struct Set {
+    members: Dict<Vec<u8>, ()>,
+}
+
+impl Set {
+
+    pub fn new() -> Self {
+        Set::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn contains(&self, member: &[u8]) -> bool {
+        self.members.contains_key(member)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Vec<u8>> {
+        self.members.keys()
+    }
+
+    /**
+     * 添加成员
+     *
+     * @param member 成员
+     * @return 成员原本不存在时返回 true
+     */
+    pub fn insert(&mut self, member: Vec<u8>) -> bool {
+        self.members.insert(member, ()).is_none()
+    }
+
+    /**
+     * 删除成员
+     *
+     * @param member 成员
+     * @return 成员存在时返回 true
+     */
+    pub fn remove(&mut self, member: &[u8]) -> bool {
+        self.members.remove(member).is_some()
+    }
+
+    /**
+     * 取出所有成员，集合变为空
+     */
+    pub fn take(&mut self) -> Set {
+        std::mem::take(self)
+    }
+
+    /**
+     * 从游标开始遍历一部分成员
+     *
+     * @param cursor 游标，0 表示开始遍历
+     * @param count 期望返回的数量
+     * @return (下一次的游标, 成员)，游标为 0 表示遍历完成
+     */
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<&[u8]>) {
+        let (cursor, members) = self.members.scan(cursor, count);
+        (cursor, members.into_iter().map(|(member, _)| member.as_slice()).collect())
+    }
+}
+
+impl IntoIterator for Set {
+    type Item = Vec<u8>;
+    type IntoIter = std::iter::Map<<Dict<Vec<u8>, ()> as IntoIterator>::IntoIter, fn((Vec<u8>, ())) -> Vec<u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter().map(|(member, _)| member)
+    }
+}
+
+impl From<HashSet<Vec<u8>>> for Set {
+    fn from(members: HashSet<Vec<u8>>) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<[Vec<u8>; N]> for Set {
+    fn from(members: [Vec<u8>; N]) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl FromIterator<Vec<u8>> for Set {
+    fn from_iter<T: IntoIterator<Item = Vec<u8>>>(iter: T) -> Self {
+        Set { members: iter.into_iter().map(|member| (member, ())).collect() }
+    }
+}
+
+// 序列化为成员的集合，与旧版本的 HashSet 格式保持兼容
+impl Encode for Set {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.members.encode(encoder)
+    }
+}
+
+impl Decode for Set {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(HashSet::decode(decoder)?.into())
+    }
+}
+
+bincode::impl_borrow_decode!(Set);
@@ -0,0 +1,606 @@
+use std::cmp::Ordering;
+
+use bincode::{de::Decoder, enc::Encoder, error::{DecodeError, EncodeError}, Decode, Encode};
+
+use crate::{store::dict::Dict, tools::random};
+
+// 跳表的最大层数与晋升概率（与 Redis 一致）
+const MAX_LEVEL: usize = 32;
+const PROMOTE_THRESHOLD: u64 = u64::MAX / 4;
+
+/**
+ * 分数区间的边界
+ */
+#[derive(Clone, Copy)]
+pub enum ScoreBound {
+    Inclusive(f64),
+    Exclusive(f64), // 以 ( 开头的边界
+}
+
+impl ScoreBound {
+
+    /**
+     * 解析分数边界，支持 ( 前缀与 -inf、+inf
+     *
+     * @param value 参数
+     */
+    pub fn parse(value: &str) -> Option<Self> {
+        let (exclusive, number) = match value.strip_prefix('(') {
+            Some(number) => (true, number),
+            None => (false, value),
+        };
+        let score = number.parse::<f64>().ok().filter(|score| !score.is_nan())?;
+        Some(if exclusive { ScoreBound::Exclusive(score) } else { ScoreBound::Inclusive(score) })
+    }
+
+    /**
+     * 分数是否位于下边界之下
+     */
+    fn below_min(&self, score: f64) -> bool {
+        match *self {
+            ScoreBound::Inclusive(min) => score < min,
+            ScoreBound::Exclusive(min) => score <= min,
+        }
+    }
+
+    /**
+     * 分数是否未超过上边界
+     */
+    fn within_max(&self, score: f64) -> bool {
+        match *self {
+            ScoreBound::Inclusive(max) => score <= max,
+            ScoreBound::Exclusive(max) => score < max,
+        }
+    }
+}
+
+/**
+ * 字典序区间的边界
+ */
+#[derive(Clone)]
+pub enum LexBound {
+    Inclusive(Box<[u8]>), // 以 [ 开头的边界
+    Exclusive(Box<[u8]>), // 以 ( 开头的边界
+    Min,                // -
+    Max,                // +
+}
+
+impl LexBound {
+
+    /**
+     * 解析字典序边界
+     *
+     * @param value 参数
+     */
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        match value.first() {
+            Some(b'[') => Some(LexBound::Inclusive(value[1..].into())),
+            Some(b'(') => Some(LexBound::Exclusive(value[1..].into())),
+            Some(b'-') if value.len() == 1 => Some(LexBound::Min),
+            Some(b'+') if value.len() == 1 => Some(LexBound::Max),
+            _ => None,
+        }
+    }
+
+    /**
+     * 成员是否位于下边界之下
+     */
+    fn below_min(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Inclusive(min) => member < &**min,
+            LexBound::Exclusive(min) => member <= &**min,
+            LexBound::Min => false,
+            LexBound::Max => true,
+        }
+    }
+
+    /**
+     * 成员是否未超过上边界
+     */
+    fn within_max(&self, member: &[u8]) -> bool {
+        match self {
+            LexBound::Inclusive(max) => member <= &**max,
+            LexBound::Exclusive(max) => member < &**max,
+            LexBound::Min => false,
+            LexBound::Max => true,
+        }
+    }
+}
+
+/**
+ * 比较 (分数, 成员)，分数相同时按成员的字典序排列
+ */
+fn compare(score: f64, member: &[u8], other_score: f64, other_member: &[u8]) -> Ordering {
+    score.partial_cmp(&other_score).unwrap_or(Ordering::Equal).then_with(|| member.cmp(other_member))
+}
+
+/**
+ * 层级链接
+ *
+ * @param next 下一个节点
+ * @param span 到下一个节点跨越的元素数量，用于计算排名
+ */
+#[derive(Clone, Copy, Default)]
+struct Link {
+    next: Option<usize>,
+    span: usize,
+}
+
+#[derive(Clone)]
+struct Node {
+    member: Vec<u8>,
+    score: f64,
+    levels: Vec<Link>,
+    backward: Option<usize>,
+}
+
+/**
+ * 带跨度的跳表，节点保存在数组中，以下标互相引用
+ */
+#[derive(Clone)]
+struct SkipList {
+    head: Vec<Link>,
+    nodes: Vec<Option<Node>>,
+    free: Vec<usize>,
+    tail: Option<usize>,
+    level: usize,
+    len: usize,
+}
+
+impl SkipList {
+
+    fn new() -> Self {
+        SkipList {
+            head: vec![Link::default(); MAX_LEVEL],
+            nodes: Vec::new(),
+            free: Vec::new(),
+            tail: None,
+            level: 1,
+            len: 0,
+        }
+    }
+
+    fn node(&self, id: usize) -> &Node {
+        self.nodes[id].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut Node {
+        self.nodes[id].as_mut().unwrap()
+    }
+
+    /**
+     * 位置的层级链接，None 表示表头
+     */
+    fn link(&self, position: Option<usize>, level: usize) -> Link {
+        match position {
+            Some(id) => self.node(id).levels[level],
+            None => self.head[level],
+        }
+    }
+
+    fn link_mut(&mut self, position: Option<usize>, level: usize) -> &mut Link {
+        match position {
+            Some(id) => &mut self.node_mut(id).levels[level],
+            None => &mut self.head[level],
+        }
+    }
+
+    fn random_level() -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && random::next_u64() < PROMOTE_THRESHOLD {
+            level += 1;
+        }
+        level
+    }
+
+    /**
+     * 查找每一层中最后一个小于 (score, member) 的位置及其排名
+     */
+    fn find_update(&self, score: f64, member: &[u8]) -> ([Option<usize>; MAX_LEVEL], [usize; MAX_LEVEL]) {
+        let mut update = [None; MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut position = None;
+        for i in (0..self.level).rev() {
+            rank[i] = if i == self.level - 1 { 0 } else { rank[i + 1] };
+            loop {
+                let link = self.link(position, i);
+                match link.next {
+                    Some(next) if compare(self.node(next).score, &self.node(next).member, score, member) == Ordering::Less => {
+                        rank[i] += link.span;
+                        position = Some(next);
+                    },
+                    _ => break,
+                }
+            }
+            update[i] = position;
+        }
+        (update, rank)
+    }
+
+    /**
+     * 插入元素，调用方保证元素不存在
+     */
+    fn insert(&mut self, score: f64, member: Vec<u8>) {
+        let (mut update, mut rank) = self.find_update(score, &member);
+        let level = Self::random_level();
+        if level > self.level {
+            for i in self.level..level {
+                rank[i] = 0;
+                update[i] = None;
+                self.head[i] = Link { next: None, span: self.len };
+            }
+            self.level = level;
+        }
+
+        let node = Node { member, score, levels: vec![Link::default(); level], backward: update[0] };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            },
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+
+        for i in 0..level {
+            let previous = self.link(update[i], i);
+            self.node_mut(id).levels[i] = Link { next: previous.next, span: previous.span - (rank[0] - rank[i]) };
+            *self.link_mut(update[i], i) = Link { next: Some(id), span: rank[0] - rank[i] + 1 };
+        }
+        for (i, &position) in update.iter().enumerate().take(self.level).skip(level) {
+            self.link_mut(position, i).span += 1;
+        }
+
+        match self.node(id).levels[0].next {
+            Some(next) => self.node_mut(next).backward = Some(id),
+            None => self.tail = Some(id),
+        }
+        self.len += 1;
+    }
+
+    /**
+     * 删除元素，返回是否删除成功
+     */
+    fn remove(&mut self, score: f64, member: &[u8]) -> bool {
+        let (update, _) = self.find_update(score, member);
+        let id = match self.link(update[0], 0).next {
+            Some(id) if compare(self.node(id).score, &self.node(id).member, score, member) == Ordering::Equal => id,
+            _ => return false,
+        };
+
+        for (i, &position) in update.iter().enumerate().take(self.level) {
+            let previous = self.link(position, i);
+            if previous.next == Some(id) {
+                let removed = self.node(id).levels[i];
+                *self.link_mut(position, i) = Link { next: removed.next, span: previous.span + removed.span - 1 };
+            } else {
+                self.link_mut(position, i).span -= 1;
+            }
+        }
+
+        let node = self.nodes[id].take().unwrap();
+        match node.levels[0].next {
+            Some(next) => self.node_mut(next).backward = node.backward,
+            None => self.tail = node.backward,
+        }
+        while self.level > 1 && self.head[self.level - 1].next.is_none() {
+            self.level -= 1;
+        }
+        self.free.push(id);
+        self.len -= 1;
+        true
+    }
+
+    /**
+     * 满足条件的前缀长度，条件必须对有序序列的某个前缀成立
+     */
+    fn prefix_len(&self, predicate: impl Fn(&Node) -> bool) -> usize {
+        let mut traversed = 0;
+        let mut position = None;
+        for i in (0..self.level).rev() {
+            loop {
+                let link = self.link(position, i);
+                match link.next {
+                    Some(next) if predicate(self.node(next)) => {
+                        traversed += link.span;
+                        position = Some(next);
+                    },
+                    _ => break,
+                }
+            }
+        }
+        traversed
+    }
+
+    /**
+     * 按排名（从 0 开始）获取节点
+     */
+    fn node_by_rank(&self, rank: usize) -> Option<usize> {
+        if rank >= self.len {
+            return None;
+        }
+        let target = rank + 1;
+        let mut traversed = 0;
+        let mut position = None;
+        for i in (0..self.level).rev() {
+            loop {
+                let link = self.link(position, i);
+                match link.next {
+                    Some(next) if traversed + link.span <= target => {
+                        traversed += link.span;
+                        position = Some(next);
+                    },
+                    _ => break,
+                }
+            }
+            if traversed == target {
+                return position;
+            }
+        }
+        None
+    }
+}
+
+/**
+ * 有序集合
+ *
+ * 成员到分数的哈希表提供 O(1) 的分数查询，按 (分数, 成员) 排序的跳表提供 O(log n) 的排名与区间查询，
+ * ZSCAN 直接按哈希表的桶遍历。
+ */
+#[derive(Clone)]
+pub struct SortedSet {
+    scores: Dict<Vec<u8>, f64>,
+    index: SkipList,
+}
+
+impl Default for SortedSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SortedSet {
+
+    pub fn new() -> Self {
+        SortedSet { scores: Dict::new(), index: SkipList::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn score(&self, member: &[u8]) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    pub fn contains(&self, member: &[u8]) -> bool {
+        self.scores.contains_key(member)
+    }
+
+    /**
+     * 添加成员或更新分数
+     *
+     * @param member 成员
+     * @param score 分数
+     * @return 成员原有的分数
+     */
+    pub fn insert(&mut self, member: Vec<u8>, score: f64) -> Option<f64> {
+        let previous = self.scores.insert(member.clone(), score);
+        if let Some(previous) = previous {
+            if previous == score {
+                return Some(previous);
+            }
+            self.index.remove(previous, &member);
+        }
+        self.index.insert(score, member);
+        previous
+    }
+
+    /**
+     * 删除成员
+     *
+     * @param member 成员
+     * @return 成员的分数
+     */
+    pub fn remove(&mut self, member: &[u8]) -> Option<f64> {
+        let score = self.scores.remove(member)?;
+        self.index.remove(score, member);
+        Some(score)
+    }
+
+    /**
+     * 从游标开始遍历一部分成员
+     *
+     * @param cursor 游标，0 表示开始遍历
+     * @param count 期望返回的数量
+     * @return (下一次的游标, (成员, 分数))，游标为 0 表示遍历完成
+     */
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&[u8], f64)>) {
+        let (cursor, members) = self.scores.scan(cursor, count);
+        let members = members.into_iter().map(|(member, score)| (member.as_slice(), *score)).collect();
+        (cursor, members)
+    }
+
+    /**
+     * 成员的升序排名（从 0 开始）
+     *
+     * @param member 成员
+     */
+    pub fn rank(&self, member: &[u8]) -> Option<usize> {
+        let score = self.score(member)?;
+        Some(self.index.prefix_len(|node| compare(node.score, &node.member, score, member) == Ordering::Less))
+    }
+
+    /**
+     * 成员的降序排名（从 0 开始）
+     *
+     * @param member 成员
+     */
+    pub fn rev_rank(&self, member: &[u8]) -> Option<usize> {
+        self.rank(member).map(|rank| self.len() - 1 - rank)
+    }
+
+    /**
+     * 分数区间在升序排列中对应的排名区间 [start, end)
+     *
+     * @param min 下边界
+     * @param max 上边界
+     */
+    pub fn rank_range_by_score(&self, min: ScoreBound, max: ScoreBound) -> (usize, usize) {
+        let start = self.index.prefix_len(|node| min.below_min(node.score));
+        let end = self.index.prefix_len(|node| max.within_max(node.score));
+        (start, end.max(start))
+    }
+
+    /**
+     * 字典序区间在升序排列中对应的排名区间 [start, end)，要求所有成员的分数相同
+     *
+     * @param min 下边界
+     * @param max 上边界
+     */
+    pub fn rank_range_by_lex(&self, min: &LexBound, max: &LexBound) -> (usize, usize) {
+        let start = self.index.prefix_len(|node| min.below_min(&node.member));
+        let end = self.index.prefix_len(|node| max.within_max(&node.member));
+        (start, end.max(start))
+    }
+
+    /**
+     * 按升序排名（从 0 开始）获取成员及其分数
+     *
+     * @param rank 排名
+     */
+    pub fn get_by_rank(&self, rank: usize) -> Option<(&[u8], f64)> {
+        let node = self.index.node(self.index.node_by_rank(rank)?);
+        Some((node.member.as_slice(), node.score))
+    }
+
+    /**
+     * 弹出分数最低或最高的成员
+     *
+     * @param max 是否弹出分数最高的成员
+     */
+    pub fn pop(&mut self, max: bool) -> Option<(Vec<u8>, f64)> {
+        let position = if max { self.index.tail } else { self.index.head[0].next }?;
+        let member = self.index.node(position).member.clone();
+        let score = self.remove(&member)?;
+        Some((member, score))
+    }
+
+    /**
+     * 删除升序排名区间 [start, end) 内的成员
+     *
+     * @param start 起始排名
+     * @param end 结束排名（不包含）
+     * @return 删除的成员数量
+     */
+    pub fn remove_range(&mut self, start: usize, end: usize) -> usize {
+        let members = self.iter_from(start)
+            .take(end.saturating_sub(start))
+            .map(|(member, _)| member.to_vec())
+            .collect::<Vec<_>>();
+        for member in &members {
+            self.remove(member);
+        }
+        members.len()
+    }
+
+    /**
+     * 统计分数位于区间内的成员数量
+     *
+     * @param min 下边界
+     * @param max 上边界
+     */
+    pub fn count(&self, min: ScoreBound, max: ScoreBound) -> usize {
+        let (start, end) = self.rank_range_by_score(min, max);
+        end - start
+    }
+
+    /**
+     * 从指定的升序排名开始按升序迭代
+     *
+     * @param rank 起始排名
+     */
+    pub fn iter_from(&self, rank: usize) -> Iter<'_> {
+        Iter { set: self, position: self.index.node_by_rank(rank), reverse: false }
+    }
+
+    /**
+     * 从指定的升序排名开始按降序迭代
+     *
+     * @param rank 起始排名
+     */
+    pub fn iter_rev_from(&self, rank: usize) -> Iter<'_> {
+        Iter { set: self, position: self.index.node_by_rank(rank), reverse: true }
+    }
+
+    /**
+     * 按升序迭代所有成员
+     */
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { set: self, position: self.index.head[0].next, reverse: false }
+    }
+
+    /**
+     * 按降序迭代所有成员
+     */
+    pub fn iter_rev(&self) -> Iter<'_> {
+        Iter { set: self, position: self.index.tail, reverse: true }
+    }
+}
+
+/**
+ * 有序集合迭代器，返回 (成员, 分数)
+ */
+#[derive(Clone)]
+pub struct Iter<'a> {
+    set: &'a SortedSet,
+    position: Option<usize>,
+    reverse: bool,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a [u8], f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.set.index.node(self.position?);
+        self.position = if self.reverse { node.backward } else { node.levels[0].next };
+        Some((node.member.as_slice(), node.score))
+    }
+}
+
+impl FromIterator<(Vec<u8>, f64)> for SortedSet {
+    fn from_iter<T: IntoIterator<Item = (Vec<u8>, f64)>>(iter: T) -> Self {
+        let mut set = SortedSet::new();
+        for (member, score) in iter {
+            set.insert(member, score);
+        }
+        set
+    }
+}
+
+// 序列化为 成员 -> 分数 的映射，与旧版本的 BTreeMap 格式保持兼容
+impl Encode for SortedSet {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.len() as u64).encode(encoder)?;
+        for (member, score) in self.iter() {
+            member.encode(encoder)?;
+            score.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for SortedSet {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let pairs = Vec::<(Vec<u8>, f64)>::decode(decoder)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+bincode::impl_borrow_decode!(SortedSet);
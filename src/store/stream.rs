@@ -0,0 +1,475 @@
+use std::{collections::{BTreeMap, BTreeSet}, fmt};
+
+use bincode::{Decode, Encode};
+
+/**
+ * 消息 ID，由毫秒时间戳与同一毫秒内的序号组成，按 (ms, seq) 排序
+ */
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug, Encode, Decode)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+impl StreamId {
+
+    pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+    pub const MAX: StreamId = StreamId { ms: u64::MAX, seq: u64::MAX };
+
+    pub fn new(ms: u64, seq: u64) -> Self {
+        StreamId { ms, seq }
+    }
+
+    /**
+     * 解析 ms-seq 或 ms 格式的 ID
+     *
+     * @param id ID 参数
+     * @param default_seq 省略序号时使用的序号
+     */
+    pub fn parse(id: &str, default_seq: u64) -> Option<Self> {
+        match id.split_once('-') {
+            Some((ms, seq)) => Some(StreamId { ms: ms.parse().ok()?, seq: seq.parse().ok()? }),
+            None => Some(StreamId { ms: id.parse().ok()?, seq: default_seq }),
+        }
+    }
+
+    /**
+     * 紧随其后的 ID，已是最大值时返回 None
+     */
+    pub fn next(self) -> Option<Self> {
+        match self.seq.checked_add(1) {
+            Some(seq) => Some(StreamId { ms: self.ms, seq }),
+            None => self.ms.checked_add(1).map(|ms| StreamId { ms, seq: 0 }),
+        }
+    }
+
+    /**
+     * 紧挨着的前一个 ID，已是最小值时返回 None
+     */
+    pub fn prev(self) -> Option<Self> {
+        match self.seq.checked_sub(1) {
+            Some(seq) => Some(StreamId { ms: self.ms, seq }),
+            None => self.ms.checked_sub(1).map(|ms| StreamId { ms, seq: u64::MAX }),
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+/**
+ * 消息的字段与值
+ */
+pub type Fields = Vec<(Vec<u8>, Vec<u8>)>;
+
+/**
+ * 裁剪策略
+ */
+#[derive(Clone, Copy)]
+pub enum TrimStrategy {
+    MaxLen(u64), // 最多保留的消息数量
+    MinId(StreamId), // 删除 ID 小于该值的消息
+}
+
+/**
+ * 已投递但尚未确认的消息
+ *
+ * @param consumer 消息当前所属的消费者
+ * @param delivery_time 最后一次投递的毫秒时间戳
+ * @param delivery_count 投递次数
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct PendingEntry {
+    pub consumer: Vec<u8>,
+    pub delivery_time: u64,
+    pub delivery_count: u64,
+}
+
+/**
+ * 消费者
+ *
+ * @param seen_time 最后一次尝试读取或认领的毫秒时间戳
+ * @param active_time 最后一次成功读取或认领的毫秒时间戳，None 表示从未成功
+ * @param pending 属于该消费者的待确认消息
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct Consumer {
+    pub seen_time: u64,
+    pub active_time: Option<u64>,
+    pub pending: BTreeSet<StreamId>,
+}
+
+/**
+ * 消费者组
+ *
+ * @param last_delivered_id 最后投递给组内消费者的 ID
+ * @param entries_read 组已读取的消息数，用于计算 lag，None 表示无法确定
+ * @param pending 组内所有待确认的消息（PEL）
+ * @param consumers 消费者名称到消费者的映射
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct ConsumerGroup {
+    pub last_delivered_id: StreamId,
+    pub entries_read: Option<u64>,
+    pub pending: BTreeMap<StreamId, PendingEntry>,
+    pub consumers: BTreeMap<Vec<u8>, Consumer>,
+}
+
+impl ConsumerGroup {
+
+    pub fn new(last_delivered_id: StreamId, entries_read: Option<u64>) -> Self {
+        ConsumerGroup { last_delivered_id, entries_read, pending: BTreeMap::new(), consumers: BTreeMap::new() }
+    }
+
+    /**
+     * 创建消费者
+     *
+     * @param name 消费者名称
+     * @param now 当前毫秒时间戳
+     * @return 消费者已存在时返回 false
+     */
+    pub fn create_consumer(&mut self, name: &[u8], now: u64) -> bool {
+        if self.consumers.contains_key(name) {
+            return false;
+        }
+        self.consumers.insert(name.to_vec(), Consumer { seen_time: now, active_time: None, pending: BTreeSet::new() });
+        true
+    }
+
+    /**
+     * 获取消费者，不存在时自动创建，并更新最后交互时间
+     *
+     * @param name 消费者名称
+     * @param now 当前毫秒时间戳
+     */
+    pub fn touch_consumer(&mut self, name: &[u8], now: u64) -> &mut Consumer {
+        self.create_consumer(name, now);
+        let consumer = self.consumers.get_mut(name).unwrap();
+        consumer.seen_time = now;
+        consumer
+    }
+
+    /**
+     * 删除消费者及其所有待确认消息
+     *
+     * @param name 消费者名称
+     * @return 被删除的待确认消息数量，消费者不存在时返回 None
+     */
+    pub fn remove_consumer(&mut self, name: &[u8]) -> Option<usize> {
+        let consumer = self.consumers.remove(name)?;
+        for id in &consumer.pending {
+            self.pending.remove(id);
+        }
+        Some(consumer.pending.len())
+    }
+
+    /**
+     * 将消息分配给消费者，消息已属于其他消费者时转移
+     *
+     * @param id 消息 ID
+     * @param consumer 消费者名称，需已存在
+     * @param delivery_time 投递时间
+     * @param delivery_count 投递次数
+     */
+    pub fn assign(&mut self, id: StreamId, consumer: &[u8], delivery_time: u64, delivery_count: u64) {
+        let entry = PendingEntry { consumer: consumer.to_vec(), delivery_time, delivery_count };
+        if let Some(previous) = self.pending.insert(id, entry) {
+            if let Some(owner) = self.consumers.get_mut(&previous.consumer) {
+                owner.pending.remove(&id);
+            }
+        }
+        if let Some(owner) = self.consumers.get_mut(consumer) {
+            owner.pending.insert(id);
+        }
+    }
+
+    /**
+     * 确认消息，从组与消费者的待确认列表中移除
+     *
+     * @param id 消息 ID
+     * @return 消息是否处于待确认状态
+     */
+    pub fn ack(&mut self, id: &StreamId) -> bool {
+        match self.pending.remove(id) {
+            Some(entry) => {
+                if let Some(owner) = self.consumers.get_mut(&entry.consumer) {
+                    owner.pending.remove(id);
+                }
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/**
+ * 流
+ *
+ * 消息按 ID 有序保存在 B 树中，支持按 ID 区间查询。
+ *
+ * @param last_id 最后生成的 ID，删除消息后也不会回退
+ * @param max_deleted_id 被删除的最大 ID
+ * @param entries_added 添加过的消息总数
+ * @param groups 消费者组，随流一起持久化
+ */
+#[derive(Clone, Default, Encode, Decode)]
+pub struct Stream {
+    entries: BTreeMap<StreamId, Fields>,
+    last_id: StreamId,
+    max_deleted_id: StreamId,
+    entries_added: u64,
+    groups: BTreeMap<Vec<u8>, ConsumerGroup>,
+}
+
+impl Stream {
+
+    pub fn new() -> Self {
+        Stream::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    pub fn max_deleted_id(&self) -> StreamId {
+        self.max_deleted_id
+    }
+
+    pub fn entries_added(&self) -> u64 {
+        self.entries_added
+    }
+
+    pub fn first_entry(&self) -> Option<(&StreamId, &Fields)> {
+        self.entries.first_key_value()
+    }
+
+    pub fn last_entry(&self) -> Option<(&StreamId, &Fields)> {
+        self.entries.last_key_value()
+    }
+
+    pub fn get(&self, id: &StreamId) -> Option<&Fields> {
+        self.entries.get(id)
+    }
+
+    /**
+     * 生成下一个自动 ID
+     *
+     * @param now 当前毫秒时间戳
+     * @param ms 指定的毫秒时间戳，None 表示使用当前时间
+     * @return ID 已用尽时返回 None
+     */
+    pub fn next_id(&self, now: u64, ms: Option<u64>) -> Option<StreamId> {
+        match ms {
+            Some(ms) if ms == self.last_id.ms => self.last_id.seq.checked_add(1).map(|seq| StreamId::new(ms, seq)),
+            // 指定的时间戳较小时由调用方报告 ID 过小
+            Some(ms) => Some(StreamId::new(ms, 0)),
+            None if now > self.last_id.ms => Some(StreamId::new(now, 0)),
+            None => self.last_id.next(),
+        }
+    }
+
+    /**
+     * 追加消息，调用方需保证 ID 大于最后的 ID
+     *
+     * @param id 消息 ID
+     * @param fields 字段与值
+     */
+    pub fn append(&mut self, id: StreamId, fields: Fields) {
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        self.entries_added += 1;
+    }
+
+    /**
+     * 删除消息
+     *
+     * @param id 消息 ID
+     * @return 消息是否存在
+     */
+    pub fn remove(&mut self, id: &StreamId) -> bool {
+        if self.entries.remove(id).is_none() {
+            return false;
+        }
+        self.max_deleted_id = self.max_deleted_id.max(*id);
+        true
+    }
+
+    /**
+     * 按 ID 区间遍历消息
+     *
+     * @param start 起始 ID（包含）
+     * @param end 结束 ID（包含）
+     */
+    pub fn range(&self, start: StreamId, end: StreamId) -> impl DoubleEndedIterator<Item = (&StreamId, &Fields)> {
+        // 起始 ID 大于结束 ID 时区间为空
+        (start <= end).then(|| self.entries.range(start..=end)).into_iter().flatten()
+    }
+
+    /**
+     * 按策略删除最早的消息
+     *
+     * @param strategy 裁剪策略
+     * @param limit 最多删除的数量
+     * @return 删除的数量
+     */
+    pub fn trim(&mut self, strategy: TrimStrategy, limit: usize) -> usize {
+        let mut removed = 0;
+        while removed < limit {
+            let id = match self.entries.first_key_value() {
+                Some((id, _)) => *id,
+                None => break,
+            };
+            let expired = match strategy {
+                TrimStrategy::MaxLen(max_len) => self.entries.len() as u64 > max_len,
+                TrimStrategy::MinId(min_id) => id < min_id,
+            };
+            if !expired {
+                break;
+            }
+            self.remove(&id);
+            removed += 1;
+        }
+        removed
+    }
+
+    pub fn groups(&self) -> &BTreeMap<Vec<u8>, ConsumerGroup> {
+        &self.groups
+    }
+
+    pub fn group(&self, name: &[u8]) -> Option<&ConsumerGroup> {
+        self.groups.get(name)
+    }
+
+    pub fn group_mut(&mut self, name: &[u8]) -> Option<&mut ConsumerGroup> {
+        self.groups.get_mut(name)
+    }
+
+    /**
+     * 创建消费者组
+     *
+     * @param name 组名
+     * @param group 消费者组
+     * @return 组已存在时返回 false
+     */
+    pub fn create_group(&mut self, name: &[u8], group: ConsumerGroup) -> bool {
+        if self.groups.contains_key(name) {
+            return false;
+        }
+        self.groups.insert(name.to_vec(), group);
+        true
+    }
+
+    pub fn remove_group(&mut self, name: &[u8]) -> bool {
+        self.groups.remove(name).is_some()
+    }
+
+    /**
+     * 大于等于 id 的位置是否有被删除的消息，有时组的已读计数不能直接累加
+     */
+    fn has_tombstones_after(&self, id: StreamId) -> bool {
+        self.max_deleted_id != StreamId::MIN && self.max_deleted_id >= id
+    }
+
+    /**
+     * 估算读取到 id 时已读取的消息数
+     *
+     * @param id 消息 ID
+     * @return 中间有被删除的消息而无法确定时返回 None
+     */
+    pub fn estimate_entries_read(&self, id: StreamId) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        if self.is_empty() && id <= self.last_id {
+            return Some(self.entries_added);
+        }
+        match id.cmp(&self.last_id) {
+            std::cmp::Ordering::Equal => return Some(self.entries_added),
+            std::cmp::Ordering::Greater => return None,
+            std::cmp::Ordering::Less => {},
+        }
+        let first_id = *self.first_entry()?.0;
+        if self.max_deleted_id == StreamId::MIN || self.max_deleted_id < first_id {
+            // 现存消息之间没有空洞，之前的消息都已被读取或删除
+            let before_first = self.entries_added - self.len() as u64;
+            if id < first_id {
+                return Some(before_first);
+            }
+            if id == first_id {
+                return Some(before_first + 1);
+            }
+        }
+        None
+    }
+
+    /**
+     * 消费者组尚未读取的消息数
+     *
+     * @param group 消费者组
+     * @return 无法确定时返回 None
+     */
+    pub fn lag(&self, group: &ConsumerGroup) -> Option<u64> {
+        if self.entries_added == 0 {
+            return Some(0);
+        }
+        let first_id = self.first_entry().map(|(id, _)| *id).unwrap_or(StreamId::MIN);
+        let entries_read = match group.entries_read {
+            Some(read) if !self.has_tombstones_after(group.last_delivered_id) && group.last_delivered_id >= first_id => Some(read),
+            _ => self.estimate_entries_read(group.last_delivered_id),
+        };
+        entries_read.map(|read| self.entries_added.saturating_sub(read))
+    }
+
+    /**
+     * 消费者组读取最后投递的 ID 之后的新消息
+     *
+     * @param name 组名
+     * @param consumer 消费者名称，需已存在
+     * @param count 最多读取的数量
+     * @param noack 是否不加入待确认列表
+     * @param now 当前毫秒时间戳
+     * @return 读取到的消息，组不存在时为空
+     */
+    pub fn read_group(&mut self, name: &[u8], consumer: &[u8], count: usize, noack: bool, now: u64) -> Vec<(StreamId, Fields)> {
+        let group = match self.groups.get(name) {
+            Some(group) => group,
+            None => return Vec::new(),
+        };
+        let entries = match group.last_delivered_id.next() {
+            Some(start) => self.entries.range(start..).take(count).map(|(id, fields)| (*id, fields.clone())).collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        let (first, last) = match (entries.first(), entries.last()) {
+            (Some((first, _)), Some((last, _))) => (*first, *last),
+            _ => return entries,
+        };
+        let entries_read = match group.entries_read {
+            Some(read) if !self.has_tombstones_after(first) => Some(read + entries.len() as u64),
+            _ => self.estimate_entries_read(last),
+        };
+
+        let group = self.groups.get_mut(name).unwrap();
+        group.last_delivered_id = last;
+        group.entries_read = entries_read;
+        if !noack {
+            for (id, _) in &entries {
+                group.assign(*id, consumer, now, 1);
+            }
+        }
+        if let Some(consumer) = group.consumers.get_mut(consumer) {
+            consumer.active_time = Some(now);
+        }
+        entries
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use bincode::{Decode, Encode};
+
+use crate::store::hnsw::{Hnsw, Space};
+
+/**
+ * 相似度度量方式
+ */
+#[derive(Clone, Copy, PartialEq, Encode, Decode)]
+pub enum Metric {
+    Cosine, // 余弦相似度，越大越相似
+    L2, // 欧氏距离，越小越相似
+    Ip, // 内积，越大越相似
+}
+
+impl Metric {
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Cosine => "cosine",
+            Metric::L2 => "l2",
+            Metric::Ip => "ip",
+        }
+    }
+
+    pub fn parse(metric: &str) -> Option<Self> {
+        match metric.to_uppercase().as_str() {
+            "COSINE" => Some(Metric::Cosine),
+            "L2" => Some(Metric::L2),
+            "IP" => Some(Metric::Ip),
+            _ => None,
+        }
+    }
+
+    /**
+     * 按相似程度比较两个分数，更相似的排在前面
+     */
+    fn compare(self, a: f32, b: f32) -> std::cmp::Ordering {
+        match self {
+            Metric::L2 => a.total_cmp(&b),
+            Metric::Cosine | Metric::Ip => b.total_cmp(&a),
+        }
+    }
+}
+
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+pub fn norm(vector: &[f32]) -> f32 {
+    dot(vector, vector).sqrt()
+}
+
+/**
+ * 向量集合
+ *
+ * @param dimension 向量维度，集合中的所有向量维度相同
+ * @param vectors 元素名称到向量的映射
+ * @param norms 每个向量预先计算的模长，用于余弦相似度
+ * @param index 可选的 HNSW 近似最近邻索引，随集合一起持久化
+ */
+#[derive(Clone, Encode, Decode)]
+pub struct Vector {
+    pub dimension: usize,
+    pub vectors: HashMap<Vec<u8>, Vec<f32>>,
+    pub norms: HashMap<Vec<u8>, f32>,
+    index: Option<Hnsw>,
+}
+
+impl Vector {
+
+    pub fn new(dimension: usize) -> Self {
+        Vector { dimension, vectors: HashMap::new(), norms: HashMap::new(), index: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    pub fn get(&self, element: &[u8]) -> Option<&Vec<f32>> {
+        self.vectors.get(element)
+    }
+
+    /**
+     * 添加或替换向量，调用方需保证维度一致
+     *
+     * @param element 元素名称
+     * @param vector 向量
+     * @return 是否为新元素
+     */
+    pub fn insert(&mut self, element: Vec<u8>, vector: Vec<f32>) -> bool {
+        self.norms.insert(element.clone(), norm(&vector));
+        let added = self.vectors.insert(element.clone(), vector).is_none();
+        if let Some(index) = &mut self.index {
+            index.insert(&Space { vectors: &self.vectors, norms: &self.norms }, &element);
+        }
+        added
+    }
+
+    pub fn remove(&mut self, element: &[u8]) -> bool {
+        if let Some(index) = &mut self.index {
+            index.remove(&Space { vectors: &self.vectors, norms: &self.norms }, element);
+        }
+        self.norms.remove(element);
+        self.vectors.remove(element).is_some()
+    }
+
+    pub fn index(&self) -> Option<&Hnsw> {
+        self.index.as_ref()
+    }
+
+    /**
+     * 创建索引并插入所有已有的向量，已有索引时重新构建
+     *
+     * @param index 空的索引
+     */
+    pub fn create_index(&mut self, mut index: Hnsw) {
+        let space = Space { vectors: &self.vectors, norms: &self.norms };
+        // 按名称顺序插入，使相同的数据构建出相近的图
+        let mut elements = self.vectors.keys().collect::<Vec<_>>();
+        elements.sort_unstable();
+        for element in elements {
+            index.insert(&space, element);
+        }
+        self.index = Some(index);
+    }
+
+    pub fn drop_index(&mut self) -> bool {
+        self.index.take().is_some()
+    }
+
+    /**
+     * 计算查询向量与指定元素的分数
+     *
+     * @param query 查询向量
+     * @param query_norm 查询向量的模长
+     * @param element 元素名称
+     * @param metric 度量方式
+     */
+    pub fn score(&self, query: &[f32], query_norm: f32, element: &[u8], metric: Metric) -> Option<f32> {
+        let vector = self.vectors.get(element)?;
+        let score = match metric {
+            Metric::Cosine => {
+                let norm = self.norms.get(element).copied().unwrap_or_else(|| norm(vector));
+                // 零向量与任何向量都不相似
+                if norm == 0.0 || query_norm == 0.0 { 0.0 } else { dot(query, vector) / (query_norm * norm) }
+            },
+            Metric::L2 => query.iter().zip(vector).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt(),
+            Metric::Ip => dot(query, vector),
+        };
+        Some(score)
+    }
+
+    /**
+     * 逐个比较所有向量，返回最相似的 count 个元素
+     *
+     * @param query 查询向量
+     * @param metric 度量方式
+     * @param count 返回数量
+     * @return 按相似程度排列的 (元素, 分数)
+     */
+    pub fn search(&self, query: &[f32], metric: Metric, count: usize) -> Vec<(&[u8], f32)> {
+        let query_norm = norm(query);
+        let mut results = self.vectors.keys().filter_map(|element| {
+            self.score(query, query_norm, element, metric).map(|score| (element.as_slice(), score))
+        }).collect::<Vec<_>>();
+
+        let order = |a: &(&[u8], f32), b: &(&[u8], f32)| metric.compare(a.1, b.1).then_with(|| a.0.cmp(b.0));
+        if count < results.len() {
+            // 先选出前 count 个，再只对它们排序
+            results.select_nth_unstable_by(count, order);
+            results.truncate(count);
+        }
+        results.sort_unstable_by(order);
+        results
+    }
+
+    /**
+     * 使用索引查询近似的最相似元素，没有索引或度量方式与索引不一致时返回 None
+     *
+     * @param query 查询向量
+     * @param metric 度量方式
+     * @param count 返回数量
+     * @param ef 候选集大小，为 0 时使用索引的默认值
+     */
+    pub fn approximate_search(&self, query: &[f32], metric: Metric, count: usize, ef: usize) -> Option<Vec<(&[u8], f32)>> {
+        let index = self.index.as_ref().filter(|index| index.metric() == metric)?;
+        let ef = if ef == 0 { index.ef_search() } else { ef };
+        let query_norm = norm(query);
+        let space = Space { vectors: &self.vectors, norms: &self.norms };
+        let results = index.search(&space, query, count, ef).into_iter().filter_map(|element| {
+            self.score(query, query_norm, element, metric).map(|score| (element, score))
+        }).collect();
+        Some(results)
+    }
+}
@@ -0,0 +1,163 @@
+// 位图工具函数，位偏移 0 对应第一个字节的最高位（与 Redis 一致）
+
+// 最大位偏移（512MB）
+pub const MAX_BIT_OFFSET: u64 = 4 * 1024 * 1024 * 1024 - 1;
+
+/**
+ * 解析位偏移
+ *
+ * @param value 参数
+ */
+pub fn parse_offset(value: &str) -> Result<u64, anyhow::Error> {
+    match value.parse::<u64>() {
+        Ok(offset) if offset <= MAX_BIT_OFFSET => Ok(offset),
+        _ => Err(anyhow::Error::msg("ERR bit offset is not an integer or out of range")),
+    }
+}
+
+/**
+ * 获取指定位的值，超出长度的位视为 0
+ *
+ * @param bytes 字节数组
+ * @param offset 位偏移
+ */
+pub fn get_bit(bytes: &[u8], offset: u64) -> u8 {
+    match bytes.get((offset >> 3) as usize) {
+        Some(byte) => (byte >> (7 - (offset & 7))) & 1,
+        None => 0,
+    }
+}
+
+/**
+ * 设置指定位的值，长度不足时以 0 补齐
+ *
+ * @param bytes 字节数组
+ * @param offset 位偏移
+ * @param bit 位的值
+ * @return 原来的值
+ */
+pub fn set_bit(bytes: &mut Vec<u8>, offset: u64, bit: u8) -> u8 {
+    let index = (offset >> 3) as usize;
+    if bytes.len() <= index {
+        bytes.resize(index + 1, 0);
+    }
+    let mask = 1 << (7 - (offset & 7));
+    let old = (bytes[index] & mask != 0) as u8;
+    if bit == 1 {
+        bytes[index] |= mask;
+    } else {
+        bytes[index] &= !mask;
+    }
+    old
+}
+
+/**
+ * 统计位区间 [start, end] 中值为 1 的位数
+ *
+ * @param bytes 字节数组
+ * @param start 开始位偏移
+ * @param end 结束位偏移（包含）
+ */
+pub fn count_bits(bytes: &[u8], start: u64, end: u64) -> u64 {
+    let mut count = 0;
+    let mut offset = start;
+    while offset <= end {
+        if offset & 7 == 0 && offset + 7 <= end {
+            // 整字节统计
+            count += bytes.get((offset >> 3) as usize).map_or(0, |b| b.count_ones() as u64);
+            offset += 8;
+        } else {
+            count += get_bit(bytes, offset) as u64;
+            offset += 1;
+        }
+    }
+    count
+}
+
+/**
+ * 查找位区间 [start, end] 中第一个值为 bit 的位
+ *
+ * @param bytes 字节数组
+ * @param bit 要查找的值
+ * @param start 开始位偏移
+ * @param end 结束位偏移（包含）
+ */
+pub fn find_bit(bytes: &[u8], bit: u8, start: u64, end: u64) -> Option<u64> {
+    let skip = if bit == 1 { 0x00 } else { 0xff };
+    let mut offset = start;
+    while offset <= end {
+        if offset & 7 == 0 && offset + 7 <= end && bytes.get((offset >> 3) as usize) == Some(&skip) {
+            // 整字节跳过
+            offset += 8;
+            continue;
+        }
+        if get_bit(bytes, offset) == bit {
+            return Some(offset);
+        }
+        offset += 1;
+    }
+    None
+}
+
+/**
+ * 读取无符号整数位段
+ *
+ * @param bytes 字节数组
+ * @param offset 位偏移
+ * @param bits 位宽（1 ~ 64）
+ */
+pub fn get_unsigned(bytes: &[u8], offset: u64, bits: u32) -> u64 {
+    let mut value = 0u64;
+    for i in 0..bits as u64 {
+        value = (value << 1) | get_bit(bytes, offset + i) as u64;
+    }
+    value
+}
+
+/**
+ * 读取有符号整数位段
+ *
+ * @param bytes 字节数组
+ * @param offset 位偏移
+ * @param bits 位宽（1 ~ 64）
+ */
+pub fn get_signed(bytes: &[u8], offset: u64, bits: u32) -> i64 {
+    let value = get_unsigned(bytes, offset, bits);
+    if bits < 64 && value & (1 << (bits - 1)) != 0 {
+        (value | (u64::MAX << bits)) as i64
+    } else {
+        value as i64
+    }
+}
+
+/**
+ * 写入整数位段（取 value 的低 bits 位）
+ *
+ * @param bytes 字节数组
+ * @param offset 位偏移
+ * @param bits 位宽（1 ~ 64）
+ * @param value 值
+ */
+pub fn set_bits(bytes: &mut Vec<u8>, offset: u64, bits: u32, value: u64) {
+    for i in 0..bits as u64 {
+        let bit = ((value >> (bits as u64 - 1 - i)) & 1) as u8;
+        set_bit(bytes, offset + i, bit);
+    }
+}
+
+/**
+ * 将区间参数按 Redis 规则规范化（支持负数索引）
+ *
+ * @param start 开始位置
+ * @param end 结束位置
+ * @param len 总长度
+ * @return 规范化后的闭区间，区间为空时返回 None
+ */
+pub fn normalize_range(start: i64, end: i64, len: i64) -> Option<(u64, u64)> {
+    let start = if start < 0 { (len + start).max(0) } else { start };
+    let end = if end < 0 { len + end } else { end.min(len - 1) };
+    if len == 0 || end < 0 || start > end {
+        return None;
+    }
+    Some((start as u64, end as u64))
+}
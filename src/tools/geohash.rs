@@ -0,0 +1,204 @@
+// 地理位置编码工具函数，编码方式与 Redis 一致：经纬度各 26 位交错成 52 位整数，作为有序集合的分数
+
+// 墨卡托投影可表示的纬度范围
+pub const LAT_MIN: f64 = -85.05112878;
+pub const LAT_MAX: f64 = 85.05112878;
+pub const LON_MIN: f64 = -180.0;
+pub const LON_MAX: f64 = 180.0;
+
+const STEP: u32 = 26;
+const BITS: u32 = STEP * 2;
+const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+const BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/**
+ * 经纬度是否位于可编码的范围内
+ *
+ * @param lon 经度
+ * @param lat 纬度
+ */
+pub fn is_valid(lon: f64, lat: f64) -> bool {
+    (LON_MIN..=LON_MAX).contains(&lon) && (LAT_MIN..=LAT_MAX).contains(&lat)
+}
+
+/**
+ * 坐标在指定精度下所在的格子下标
+ */
+fn cell(value: f64, min: f64, max: f64, step: u32) -> u64 {
+    let cells = 1u64 << step;
+    let offset = ((value - min) / (max - min) * cells as f64) as u64;
+    offset.min(cells - 1)
+}
+
+/**
+ * 交错纬度与经度的格子下标，纬度占偶数位，经度占奇数位
+ */
+fn interleave(lat: u64, lon: u64, step: u32) -> u64 {
+    (0..step).fold(0, |hash, i| hash | ((lat >> i) & 1) << (2 * i) | ((lon >> i) & 1) << (2 * i + 1))
+}
+
+fn deinterleave(hash: u64, step: u32) -> (u64, u64) {
+    (0..step).fold((0, 0), |(lat, lon), i| (lat | ((hash >> (2 * i)) & 1) << i, lon | ((hash >> (2 * i + 1)) & 1) << i))
+}
+
+/**
+ * 将经纬度编码为 52 位整数
+ *
+ * @param lon 经度
+ * @param lat 纬度
+ */
+pub fn encode(lon: f64, lat: f64) -> u64 {
+    interleave(cell(lat, LAT_MIN, LAT_MAX, STEP), cell(lon, LON_MIN, LON_MAX, STEP), STEP)
+}
+
+/**
+ * 将 52 位整数解码为所在格子中心的经纬度
+ *
+ * @param hash 编码
+ * @return (经度, 纬度)
+ */
+pub fn decode(hash: u64) -> (f64, f64) {
+    let (lat, lon) = deinterleave(hash, STEP);
+    let cells = (1u64 << STEP) as f64;
+    let lat_unit = (LAT_MAX - LAT_MIN) / cells;
+    let lon_unit = (LON_MAX - LON_MIN) / cells;
+    let lat = LAT_MIN + (lat as f64 + 0.5) * lat_unit;
+    let lon = LON_MIN + (lon as f64 + 0.5) * lon_unit;
+    (lon.clamp(LON_MIN, LON_MAX), lat.clamp(LAT_MIN, LAT_MAX))
+}
+
+/**
+ * 标准的 11 位 base32 geohash 字符串（纬度范围为 -90 到 90）
+ *
+ * @param hash 编码
+ */
+pub fn to_base32(hash: u64) -> String {
+    let (lon, lat) = decode(hash);
+    let hash = interleave(cell(lat, -90.0, 90.0, STEP), cell(lon, LON_MIN, LON_MAX, STEP), STEP);
+    (0..11).map(|i| {
+        // 52 位不足 55 位，最后一个字符补 0
+        let index = if i == 10 { 0 } else { (hash >> (BITS - (i + 1) * 5)) & 0x1f };
+        BASE32[index as usize] as char
+    }).collect()
+}
+
+/**
+ * 两点纬度方向上的距离（米）
+ */
+pub fn lat_distance(lat1: f64, lat2: f64) -> f64 {
+    EARTH_RADIUS_IN_METERS * (lat2.to_radians() - lat1.to_radians()).abs()
+}
+
+/**
+ * 使用 haversine 公式计算两点的球面距离（米）
+ *
+ * @param lon1 经度
+ * @param lat1 纬度
+ * @param lon2 经度
+ * @param lat2 纬度
+ */
+pub fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let v = ((lon2.to_radians() - lon1.to_radians()) / 2.0).sin();
+    if v == 0.0 {
+        return lat_distance(lat1, lat2);
+    }
+    let u = ((lat2.to_radians() - lat1.to_radians()) / 2.0).sin();
+    let a = u * u + lat1.to_radians().cos() * lat2.to_radians().cos() * v * v;
+    2.0 * EARTH_RADIUS_IN_METERS * a.sqrt().asin()
+}
+
+/**
+ * 根据搜索半径估算格子精度，格子宽度至少是半径的两倍
+ */
+fn estimate_step(radius: f64, lat: f64) -> u32 {
+    if radius <= 0.0 {
+        return STEP;
+    }
+    let mut range = radius;
+    let mut step: i32 = 1;
+    while range < MERCATOR_MAX {
+        range *= 2.0;
+        step += 1;
+    }
+    step -= 2;
+    // 高纬度地区经度方向的格子更窄
+    if !(-66.0..=66.0).contains(&lat) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&lat) {
+            step -= 1;
+        }
+    }
+    step.clamp(0, STEP as i32) as u32
+}
+
+/**
+ * 计算覆盖搜索区域的编码区间
+ *
+ * 以中心所在的格子及其 8 个相邻格子覆盖搜索区域的外接矩形，精度不足以覆盖时逐步降低精度。
+ *
+ * @param lon 中心经度
+ * @param lat 中心纬度
+ * @param half_width 搜索区域宽度的一半（米）
+ * @param half_height 搜索区域高度的一半（米）
+ * @return 按顺序排列且互不重叠的 [start, end) 编码区间
+ */
+pub fn search_ranges(lon: f64, lat: f64, half_width: f64, half_height: f64) -> Vec<(u64, u64)> {
+    let lat_delta = (half_height / EARTH_RADIUS_IN_METERS).to_degrees();
+    let (min_lat, max_lat) = (lat - lat_delta, lat + lat_delta);
+    let widest = min_lat.abs().max(max_lat.abs());
+    let lon_delta = if widest >= 90.0 {
+        LON_MAX - LON_MIN
+    } else {
+        (half_width / EARTH_RADIUS_IN_METERS / widest.to_radians().cos()).to_degrees()
+    };
+    let (min_lon, max_lon) = (lon - lon_delta, lon + lon_delta);
+
+    let mut step = estimate_step(half_width.hypot(half_height), lat);
+    while step > 0 {
+        let cells = 1u64 << step;
+        let lat_unit = (LAT_MAX - LAT_MIN) / cells as f64;
+        let lon_unit = (LON_MAX - LON_MIN) / cells as f64;
+        let lat_cell = cell(lat, LAT_MIN, LAT_MAX, step) as f64;
+        let lon_cell = cell(lon, LON_MIN, LON_MAX, step) as f64;
+        let covered = LON_MIN + (lon_cell - 1.0) * lon_unit <= min_lon
+            && LON_MIN + (lon_cell + 2.0) * lon_unit >= max_lon
+            && LAT_MIN + (lat_cell - 1.0) * lat_unit <= min_lat.max(LAT_MIN)
+            && LAT_MIN + (lat_cell + 2.0) * lat_unit >= max_lat.min(LAT_MAX);
+        if covered && lon_unit * 3.0 < LON_MAX - LON_MIN {
+            break;
+        }
+        step -= 1;
+    }
+    if step == 0 {
+        return vec![(0, 1 << BITS)];
+    }
+
+    let cells = 1i64 << step;
+    let lat_cell = cell(lat, LAT_MIN, LAT_MAX, step) as i64;
+    let lon_cell = cell(lon, LON_MIN, LON_MAX, step) as i64;
+    let shift = BITS - step * 2;
+    let mut ranges = Vec::with_capacity(9);
+    for lat_offset in -1..=1 {
+        let lat_index = lat_cell + lat_offset;
+        if lat_index < 0 || lat_index >= cells {
+            continue;
+        }
+        for lon_offset in -1..=1 {
+            // 经度方向跨越 ±180 度时回绕
+            let lon_index = (lon_cell + lon_offset).rem_euclid(cells);
+            let hash = interleave(lat_index as u64, lon_index as u64, step);
+            ranges.push((hash << shift, (hash + 1) << shift));
+        }
+    }
+
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
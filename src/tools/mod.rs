@@ -1 +1,4 @@
-pub mod pattern;
\ No newline at end of file
+pub mod pattern;
+pub mod bitmap;
+pub mod random;
+pub mod geohash;
\ No newline at end of file
@@ -1,35 +1,155 @@
-use regex::Regex;
-
-pub fn is_match(key: &str, pattern: &str) -> bool {
-    fn convert_pattern(pattern: &str) -> String {
-        let mut regex_pattern = String::new();
-        let mut chars = pattern.chars().peekable();
-        while let Some(p) = chars.next() {
-            match p {
-                '*' => regex_pattern.push_str(".*"), 
-                '?' => regex_pattern.push('.'),    
-                '[' => {
-                    regex_pattern.push('[');
-                    if let Some(next) = chars.peek() {
-                        if *next == '^' {
-                            regex_pattern.push('^');
-                            chars.next(); // 跳过 '^'
-                        }
+// Glob 模式匹配，语义与 Redis 的 stringmatchlen 一致
+//
+// *      匹配任意长度的字节序列
+// ?      匹配任意一个字节
+// [abc]  匹配括号中的任意一个字节，[^abc] 取反，[a-z] 表示范围（起止颠倒时自动交换）
+// \x     转义，匹配字节 x 本身
+//
+// 模式先编译为 Pattern，同一个命令中匹配多个键时只需编译一次。
+
+/**
+ * 编译后的单个匹配单元
+ */
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Literal(u8),
+    Any,
+    Class([u64; 4]), // 256 位的字节集合，取反已在编译时处理
+    Star,
+}
+
+/**
+ * 编译后的 glob 模式
+ */
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+fn class_insert(class: &mut [u64; 4], byte: u8) {
+    class[(byte >> 6) as usize] |= 1 << (byte & 63);
+}
+
+fn class_contains(class: &[u64; 4], byte: u8) -> bool {
+    class[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+}
+
+impl Pattern {
+
+    /**
+     * 编译模式
+     *
+     * 任何输入都是合法的模式：末尾的 \ 匹配其本身，未闭合的 [ 到模式结尾为止
+     *
+     * @param pattern 模式
+     */
+    pub fn new(pattern: &[u8]) -> Self {
+        let mut tokens = Vec::new();
+        let mut index = 0;
+        while index < pattern.len() {
+            let token = match pattern[index] {
+                b'*' => {
+                    // 连续的 * 等价于一个
+                    if tokens.last() == Some(&Token::Star) {
+                        index += 1;
+                        continue;
                     }
-                    while let Some(ch) = chars.next() {
-                        if ch == ']' {
-                            break;
+                    Token::Star
+                },
+                b'?' => Token::Any,
+                b'[' => {
+                    index += 1;
+                    let negated = pattern.get(index) == Some(&b'^');
+                    if negated {
+                        index += 1;
+                    }
+                    let mut class = [0u64; 4];
+                    while index < pattern.len() && pattern[index] != b']' {
+                        if pattern[index] == b'\\' && index + 1 < pattern.len() {
+                            index += 1;
+                            class_insert(&mut class, pattern[index]);
+                        } else if index + 2 < pattern.len() && pattern[index + 1] == b'-' {
+                            let (start, end) = (pattern[index].min(pattern[index + 2]), pattern[index].max(pattern[index + 2]));
+                            for byte in start..=end {
+                                class_insert(&mut class, byte);
+                            }
+                            index += 2;
+                        } else {
+                            class_insert(&mut class, pattern[index]);
                         }
-                        regex_pattern.push(ch);
+                        index += 1;
+                    }
+                    if negated {
+                        class.iter_mut().for_each(|bits| *bits = !*bits);
                     }
-                    regex_pattern.push(']');
-                }
-                _ => regex_pattern.push(p)
+                    Token::Class(class)
+                },
+                b'\\' if index + 1 < pattern.len() => {
+                    index += 1;
+                    Token::Literal(pattern[index])
+                },
+                byte => Token::Literal(byte),
+            };
+            tokens.push(token);
+            index += 1;
+        }
+        Pattern { tokens }
+    }
+
+    /**
+     * 单个匹配单元是否匹配字节，* 由 is_match 处理
+     */
+    fn matches_byte(token: &Token, byte: u8) -> bool {
+        match token {
+            Token::Literal(literal) => *literal == byte,
+            Token::Any => true,
+            Token::Class(class) => class_contains(class, byte),
+            Token::Star => false,
+        }
+    }
+
+    /**
+     * 是否匹配整个字符串
+     *
+     * 除 * 外每个单元只匹配一个字节，失败时回溯到最近的 * 多吞一个字节即可，
+     * 最坏情况下的复杂度为 O(模式长度 × 字符串长度)
+     *
+     * @param string 字符串
+     */
+    pub fn is_match(&self, string: &[u8]) -> bool {
+        let (mut token, mut position) = (0, 0);
+        // 最近的 * 之后的单元位置，以及该 * 已吞到的字符串位置
+        let mut backtrack: Option<(usize, usize)> = None;
+        while position < string.len() {
+            match self.tokens.get(token) {
+                Some(Token::Star) => {
+                    token += 1;
+                    backtrack = Some((token, position));
+                    continue;
+                },
+                Some(current) if Self::matches_byte(current, string[position]) => {
+                    token += 1;
+                    position += 1;
+                    continue;
+                },
+                _ => {},
+            }
+            match backtrack {
+                Some((star_token, star_position)) => {
+                    token = star_token;
+                    position = star_position + 1;
+                    backtrack = Some((star_token, position));
+                },
+                None => return false,
             }
         }
-        regex_pattern
+        self.tokens[token..].iter().all(|token| *token == Token::Star)
+    }
+
+    /**
+     * 是否匹配任意字符串，可以跳过逐个匹配
+     */
+    pub fn matches_all(&self) -> bool {
+        self.tokens == [Token::Star]
     }
-    let regex_pattern = convert_pattern(pattern);
-    let regex = Regex::new(&regex_pattern).unwrap();
-    regex.is_match(key)
-}
\ No newline at end of file
+}
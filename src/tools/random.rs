@@ -0,0 +1,85 @@
+// 伪随机数工具函数（xorshift64*），用于随机抽取元素，不适用于安全场景
+
+use std::{cell::Cell, collections::HashSet, time::{SystemTime, UNIX_EPOCH}};
+
+thread_local! {
+    static STATE: Cell<u64> = Cell::new(seed());
+}
+
+fn seed() -> u64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+    let local = 0u8;
+    // 混入线程栈地址，避免同一时刻创建的线程得到相同的序列
+    (nanos ^ (&local as *const u8 as u64).rotate_left(32)) | 1
+}
+
+/**
+ * 生成下一个随机数
+ */
+pub fn next_u64() -> u64 {
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    })
+}
+
+/**
+ * 生成 [0, len) 范围内的随机下标
+ *
+ * @param len 长度，必须大于 0
+ */
+pub fn index(len: usize) -> usize {
+    (next_u64() % len as u64) as usize
+}
+
+/**
+ * 从 [0, len) 中无重复地抽取 count 个下标
+ *
+ * @param len 长度
+ * @param count 抽取数量，超过长度时返回全部下标
+ */
+pub fn sample(len: usize, count: usize) -> Vec<usize> {
+    let count = count.min(len);
+    if count == len {
+        return (0..len).collect();
+    }
+    // Floyd 抽样算法，时间与空间只与抽取数量相关
+    let mut chosen = HashSet::with_capacity(count);
+    let mut indexes = Vec::with_capacity(count);
+    for j in len - count..len {
+        let t = index(j + 1);
+        let pick = if chosen.contains(&t) { j } else { t };
+        chosen.insert(pick);
+        indexes.push(pick);
+    }
+    indexes
+}
+
+/**
+ * 从迭代器中无重复地随机抽取 count 个元素，只遍历一次
+ *
+ * @param iter 迭代器
+ * @param len 迭代器长度
+ * @param count 抽取数量，超过长度时返回全部元素
+ */
+pub fn choose_multiple<I: Iterator>(iter: I, len: usize, count: usize) -> Vec<I::Item> {
+    let mut indexes = sample(len, count);
+    indexes.sort_unstable();
+    let mut chosen = Vec::with_capacity(indexes.len());
+    let mut wanted = indexes.into_iter().peekable();
+    for (i, item) in iter.enumerate() {
+        match wanted.peek() {
+            Some(&index) if index == i => {
+                chosen.push(item);
+                wanted.next();
+            },
+            Some(_) => {},
+            None => break,
+        }
+    }
+    chosen
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+
+    use redis::{cmd, Client, Commands, Connection};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_setbit_getbit() {
+        let mut con = setup();
+        let _: () = con.del("setbit-test").unwrap();
+
+        let old: i64 = cmd("SETBIT").arg("setbit-test").arg(7).arg(1).query(&mut con).unwrap();
+        assert_eq!(old, 0);
+        let old: i64 = cmd("SETBIT").arg("setbit-test").arg(7).arg(0).query(&mut con).unwrap();
+        assert_eq!(old, 1);
+        let _: i64 = cmd("SETBIT").arg("setbit-test").arg(1).arg(1).query(&mut con).unwrap();
+        let value: Vec<u8> = con.get("setbit-test").unwrap();
+        assert_eq!(value, vec![0x40]);
+
+        let bit: i64 = cmd("GETBIT").arg("setbit-test").arg(1).query(&mut con).unwrap();
+        assert_eq!(bit, 1);
+        let bit: i64 = cmd("GETBIT").arg("setbit-test").arg(100).query(&mut con).unwrap();
+        assert_eq!(bit, 0);
+
+        let result: redis::RedisResult<i64> = cmd("SETBIT").arg("setbit-test").arg(0).arg(2).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_bitcount_bitpos() {
+        let mut con = setup();
+        let _: () = con.set("bitcount-test", "foobar").unwrap();
+
+        let count: i64 = cmd("BITCOUNT").arg("bitcount-test").query(&mut con).unwrap();
+        assert_eq!(count, 26);
+        let count: i64 = cmd("BITCOUNT").arg("bitcount-test").arg(1).arg(1).query(&mut con).unwrap();
+        assert_eq!(count, 6);
+        let count: i64 = cmd("BITCOUNT").arg("bitcount-test").arg(5).arg(30).arg("BIT").query(&mut con).unwrap();
+        assert_eq!(count, 17);
+
+        let _: () = con.set("bitpos-test", vec![0xffu8, 0xf0, 0x00]).unwrap();
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(0).query(&mut con).unwrap();
+        assert_eq!(pos, 12);
+        let _: () = con.set("bitpos-test", vec![0x00u8, 0xff, 0xf0]).unwrap();
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(1).arg(0).query(&mut con).unwrap();
+        assert_eq!(pos, 8);
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(1).arg(2).query(&mut con).unwrap();
+        assert_eq!(pos, 16);
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(1).arg(7).arg(15).arg("BIT").query(&mut con).unwrap();
+        assert_eq!(pos, 8);
+
+        let _: () = con.set("bitpos-test", vec![0xffu8, 0xff]).unwrap();
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(0).query(&mut con).unwrap();
+        assert_eq!(pos, 16);
+        let pos: i64 = cmd("BITPOS").arg("bitpos-test").arg(0).arg(0).arg(-1).query(&mut con).unwrap();
+        assert_eq!(pos, -1);
+    }
+
+    #[test]
+    fn test_bitop() {
+        let mut con = setup();
+        let _: () = con.set("bitop-a", "foobar").unwrap();
+        let _: () = con.set("bitop-b", "abcdef").unwrap();
+
+        let len: i64 = cmd("BITOP").arg("AND").arg("bitop-dest").arg("bitop-a").arg("bitop-b").query(&mut con).unwrap();
+        assert_eq!(len, 6);
+        let value: String = con.get("bitop-dest").unwrap();
+        assert_eq!(value, "`bc`ab");
+
+        let _: () = con.set("bitop-a", vec![0x0fu8]).unwrap();
+        let _: i64 = cmd("BITOP").arg("NOT").arg("bitop-dest").arg("bitop-a").query(&mut con).unwrap();
+        let value: Vec<u8> = con.get("bitop-dest").unwrap();
+        assert_eq!(value, vec![0xf0]);
+
+        let result: redis::RedisResult<i64> = cmd("BITOP").arg("NOT").arg("bitop-dest").arg("bitop-a").arg("bitop-b").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_bitfield() {
+        let mut con = setup();
+        let _: () = con.del("bitfield-test").unwrap();
+
+        let result: Vec<i64> = cmd("BITFIELD").arg("bitfield-test")
+            .arg("INCRBY").arg("i5").arg(100).arg(1)
+            .arg("GET").arg("u4").arg(0)
+            .query(&mut con).unwrap();
+        assert_eq!(result, vec![1, 0]);
+
+        let result: Vec<i64> = cmd("BITFIELD").arg("bitfield-test")
+            .arg("SET").arg("u8").arg("#1").arg(255)
+            .arg("GET").arg("u8").arg(8)
+            .arg("GET").arg("i8").arg(8)
+            .query(&mut con).unwrap();
+        assert_eq!(result, vec![0, 255, -1]);
+
+        let _: () = con.del("bitfield-overflow").unwrap();
+        let result: Vec<Option<i64>> = cmd("BITFIELD").arg("bitfield-overflow")
+            .arg("INCRBY").arg("u2").arg(100).arg(5)
+            .arg("OVERFLOW").arg("SAT").arg("INCRBY").arg("u2").arg(102).arg(5)
+            .arg("OVERFLOW").arg("FAIL").arg("INCRBY").arg("u2").arg(104).arg(5)
+            .arg("OVERFLOW").arg("WRAP").arg("INCRBY").arg("i8").arg(0).arg(130)
+            .query(&mut con).unwrap();
+        assert_eq!(result, vec![Some(1), Some(3), None, Some(-126)]);
+
+        let result: redis::RedisResult<Vec<i64>> = cmd("BITFIELD").arg("bitfield-test").arg("GET").arg("u64").arg(0).query(&mut con);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{collections::VecDeque, io::{Read, Write}, net::TcpStream, thread, time::{Duration, Instant}};
+
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{command::Command, frame::Frame, store::{blocking::{BlockingCommand, BlockingQueue}, db::{DatabaseSnapshot, Db, Structure}}};
+    use tokio::sync::oneshot;
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_blpop_ready_and_timeout() {
+        let mut con = setup();
+        let _: () = con.del(&["blpop-ready-a", "blpop-ready-b"]).unwrap();
+        let _: () = con.rpush("blpop-ready-b", &["x", "y"]).unwrap();
+
+        let value: (String, String) = cmd("BLPOP").arg("blpop-ready-a").arg("blpop-ready-b").arg(1).query(&mut con).unwrap();
+        assert_eq!(value, ("blpop-ready-b".to_string(), "x".to_string()));
+        let value: (String, String) = cmd("BRPOP").arg("blpop-ready-b").arg(1).query(&mut con).unwrap();
+        assert_eq!(value, ("blpop-ready-b".to_string(), "y".to_string()));
+
+        let start = Instant::now();
+        let value: Option<(String, String)> = cmd("BLPOP").arg("blpop-ready-a").arg(0.2).query(&mut con).unwrap();
+        assert_eq!(value, None);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let result: redis::RedisResult<()> = cmd("BLPOP").arg("blpop-ready-a").arg(-1).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_blpop_wakes_in_fifo_order() {
+        let mut con = setup();
+        let _: () = con.del("blpop-fifo-test").unwrap();
+
+        let mut handles = Vec::new();
+        for _ in 0..3 {
+            handles.push(thread::spawn(|| {
+                let mut con = setup();
+                let value: (String, String) = cmd("BLPOP").arg("blpop-fifo-test").arg(5).query(&mut con).unwrap();
+                value.1
+            }));
+            // 保证客户端按顺序进入等待队列
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        let info: String = cmd("INFO").arg("clients").query(&mut con).unwrap();
+        let blocked = info.lines()
+            .find_map(|line| line.strip_prefix("blocked_clients:"))
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap();
+        assert!(blocked >= 3);
+
+        let _: () = con.rpush("blpop-fifo-test", &["a", "b", "c"]).unwrap();
+        let values: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(values, vec!["a", "b", "c"]);
+
+        let exists: bool = con.exists("blpop-fifo-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_blmove_and_blmpop() {
+        let mut con = setup();
+        let _: () = con.del(&["blmove-src", "blmove-dst", "blmpop-a", "blmpop-b"]).unwrap();
+
+        let handle = thread::spawn(|| {
+            let mut con = setup();
+            let value: String = cmd("BLMOVE").arg("blmove-src").arg("blmove-dst").arg("LEFT").arg("RIGHT").arg(5).query(&mut con).unwrap();
+            value
+        });
+        thread::sleep(Duration::from_millis(100));
+        let _: () = con.rpush("blmove-src", "moved").unwrap();
+        assert_eq!(handle.join().unwrap(), "moved");
+        let values: Vec<String> = con.lrange("blmove-dst", 0, -1).unwrap();
+        assert_eq!(values, vec!["moved"]);
+
+        let _: () = con.rpush("blmpop-b", &["1", "2", "3"]).unwrap();
+        let value: (String, Vec<String>) = cmd("BLMPOP").arg(1).arg(2).arg("blmpop-a").arg("blmpop-b").arg("RIGHT").arg("COUNT").arg(2).query(&mut con).unwrap();
+        assert_eq!(value, ("blmpop-b".to_string(), vec!["3".to_string(), "2".to_string()]));
+
+        let value: Option<(String, Vec<String>)> = cmd("BLMPOP").arg(0.1).arg(1).arg("blmpop-a").arg("LEFT").query(&mut con).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_blpop_in_transaction_does_not_block() {
+        let mut con = setup();
+        let _: () = con.del("blpop-multi-test").unwrap();
+
+        let (value,): (Option<(String, String)>,) = redis::pipe()
+            .atomic()
+            .cmd("BLPOP").arg("blpop-multi-test").arg(0)
+            .query(&mut con)
+            .unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_disconnect_with_pipelined_commands() {
+        let mut con = setup();
+        let _: () = con.del("blpop-disconnect-test").unwrap();
+
+        // 阻塞期间又发送了命令，断开连接后不应再消费元素
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        stream.write_all(b"*3\r\n$5\r\nBLPOP\r\n$21\r\nblpop-disconnect-test\r\n$1\r\n0\r\n").unwrap();
+        thread::sleep(Duration::from_millis(100));
+        stream.write_all(b"*1\r\n$4\r\nPING\r\n").unwrap();
+        thread::sleep(Duration::from_millis(100));
+        drop(stream);
+        thread::sleep(Duration::from_millis(100));
+
+        let _: () = con.rpush("blpop-disconnect-test", "kept").unwrap();
+        let len: usize = con.llen("blpop-disconnect-test").unwrap();
+        assert_eq!(len, 1);
+    }
+
+    fn raw_request(stream: &mut TcpStream, args: &[&str]) -> Vec<u8> {
+        let frame = Frame::Array(args.iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        stream.write_all(&frame.as_bytes()).unwrap();
+        let mut buffer = [0u8; 1024];
+        let n = stream.read(&mut buffer).unwrap();
+        buffer[..n].to_vec()
+    }
+
+    #[test]
+    fn test_empty_replies_are_null_arrays() {
+        let mut con = setup();
+        let _: () = con.del(&["null-array-list", "null-array-stream"]).unwrap();
+        let _: () = cmd("XGROUP").arg("CREATE").arg("null-array-stream").arg("group").arg("$").arg("MKSTREAM").query(&mut con).unwrap();
+
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        // 阻塞超时与没有数据时回复 *-1，而不是 $-1
+        assert_eq!(raw_request(&mut stream, &["BLPOP", "null-array-list", "0.05"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["BRPOP", "null-array-list", "0.05"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["BLMPOP", "0.05", "1", "null-array-list", "LEFT"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["LPOP", "null-array-list", "2"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["RPOP", "null-array-list", "2"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["LPOP", "null-array-list"]), b"$-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["XREAD", "STREAMS", "null-array-stream", "0"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["XREAD", "BLOCK", "50", "STREAMS", "null-array-stream", "$"]), b"*-1\r\n");
+        assert_eq!(raw_request(&mut stream, &["XREADGROUP", "GROUP", "group", "consumer", "STREAMS", "null-array-stream", ">"]), b"*-1\r\n");
+
+        // RESP3 下统一为 Null
+        let hello = raw_request(&mut stream, &["HELLO", "3"]);
+        assert!(hello.starts_with(b"%"));
+        assert_eq!(raw_request(&mut stream, &["BLPOP", "null-array-list", "0.05"]), b"_\r\n");
+        assert_eq!(raw_request(&mut stream, &["LPOP", "null-array-list", "2"]), b"_\r\n");
+    }
+
+    fn blocking_command(args: &[&str]) -> BlockingCommand {
+        let frame = Frame::Array(args.iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        match BlockingCommand::from_command(Command::parse_from_frame(frame).unwrap()) {
+            Ok(command) => command,
+            Err(_) => panic!("not a blocking command"),
+        }
+    }
+
+    fn list(values: &[&str]) -> Structure {
+        Structure::List(values.iter().map(|value| value.as_bytes().to_vec()).collect::<VecDeque<_>>())
+    }
+
+    #[test]
+    fn test_blocking_propagates_effective_commands() {
+        let mut db = Db::new(DatabaseSnapshot::default());
+
+        // 立即执行时回复中附带实际执行的命令
+        db.insert(b"a".to_vec(), list(&["1", "2", "3"]));
+        let (sender, mut receiver) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, blocking_command(&["BLMPOP", "0", "1", "a", "RIGHT", "COUNT", "2"])).unwrap();
+        let reply = receiver.try_recv().unwrap();
+        assert_eq!(reply.propagation.len(), 1);
+        assert_eq!(reply.propagation[0].get_args(), vec!["RPOP", "a", "2"]);
+
+        // 被唤醒时由触发唤醒的命令负责传播
+        let (sender, mut blpop) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, blocking_command(&["BLPOP", "b", "0"])).unwrap();
+        let (sender, mut blmove) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, blocking_command(&["BLMOVE", "c", "b", "LEFT", "RIGHT", "0"])).unwrap();
+        db.insert(b"c".to_vec(), list(&["x"]));
+        let propagation = BlockingQueue::serve(&mut db).unwrap();
+        let propagation = propagation.iter().map(|frame| frame.get_args()).collect::<Vec<_>>();
+        assert_eq!(propagation, vec![vec!["LMOVE", "c", "b", "LEFT", "RIGHT"], vec!["LPOP", "b"]]);
+        assert!(blmove.try_recv().unwrap().propagation.is_empty());
+        assert_eq!(blpop.try_recv().unwrap().frame.get_args(), vec!["b", "x"]);
+
+        // 超时不传播任何命令
+        let (sender, mut receiver) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, blocking_command(&["BRPOP", "d", "0.01"])).unwrap();
+        db.blocking.expire(tokio::time::Instant::now() + Duration::from_secs(1));
+        let reply = receiver.try_recv().unwrap();
+        assert!(matches!(reply.frame, Frame::NullArray));
+        assert!(reply.propagation.is_empty());
+
+        // 已断开的客户端不会消费元素
+        let (sender, receiver) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, blocking_command(&["BLPOP", "e", "0"])).unwrap();
+        drop(receiver);
+        db.insert(b"e".to_vec(), list(&["kept"]));
+        assert!(BlockingQueue::serve(&mut db).unwrap().is_empty());
+        assert!(matches!(db.get(b"e"), Some(Structure::List(list)) if list.len() == 1));
+    }
+}
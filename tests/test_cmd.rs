@@ -54,7 +54,7 @@ mod tests {
 
         let _: () = con.set("exists-test", "Helloworld").unwrap();
         let key_exists: bool = con.exists("exists-test").unwrap();
-        assert_eq!(key_exists, true);
+        assert!(key_exists);
     }
 
     #[test]
@@ -69,7 +69,7 @@ mod tests {
 
         println!("是否存在：{}", key_exists);
 
-        assert_eq!(key_exists, true);
+        assert!(key_exists);
     }
 
     #[test]
@@ -194,4 +194,22 @@ mod tests {
         let exists: usize = con.hexists("test-hmset", "city").unwrap();
         assert_eq!(exists, 0);
     }
+
+    #[test]
+    fn test_binary_safe() {
+
+        let mut con = setup();
+
+        let key: &[u8] = b"binary-\xff\x00-test";
+        let value: &[u8] = b"\x00\x01\r\n\r\n\xfe\xff";
+
+        let _: () = con.set(key, value).unwrap();
+        let result: Vec<u8> = con.get(key).unwrap();
+        assert_eq!(result, value);
+
+        let _: () = con.del("binary-hash-test").unwrap();
+        let _: () = con.hset("binary-hash-test", b"\xc3\x28", value).unwrap();
+        let field: Vec<u8> = con.hget("binary-hash-test", b"\xc3\x28").unwrap();
+        assert_eq!(field, value);
+    }
 }
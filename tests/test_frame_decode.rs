@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use rudis_server::frame::{Frame, Protocol};
+
+    fn round_trip(frame: &Frame, protocol: Protocol) -> Frame {
+        let bytes = frame.as_bytes_with(protocol);
+        let (decoded, len) = Frame::decode(&bytes).unwrap().unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(decoded.as_bytes_with(protocol), bytes);
+        decoded
+    }
+
+    #[test]
+    fn test_decode_resp2_types() {
+        let frames = vec![
+            Frame::SimpleString("OK".to_string()),
+            Frame::Error("ERR unknown command".to_string()),
+            Frame::Integer(-42),
+            Frame::BulkString(b"hello\r\nworld".to_vec()),
+            Frame::Null,
+            Frame::NullArray,
+            Frame::Array(vec![]),
+            Frame::Array(vec![
+                Frame::Integer(1),
+                Frame::Array(vec![Frame::BulkString(b"nested".to_vec()), Frame::Null]),
+                Frame::Error("ERR inner".to_string()),
+            ]),
+        ];
+        for frame in &frames {
+            round_trip(frame, Protocol::Resp2);
+        }
+
+        assert!(matches!(Frame::parse_from_bytes(b":7\r\n").unwrap(), Frame::Integer(7)));
+        assert!(matches!(Frame::parse_from_bytes(b"$-1\r\n").unwrap(), Frame::Null));
+        assert!(matches!(Frame::parse_from_bytes(b"*-1\r\n").unwrap(), Frame::NullArray));
+        assert!(matches!(Frame::parse_from_bytes(b"-ERR boom\r\n").unwrap(), Frame::Error(e) if e == "ERR boom"));
+        assert!(Frame::parse_from_bytes(b"$5\r\nhel").is_err());
+        assert!(Frame::parse_from_bytes(b":abc\r\n").is_err());
+    }
+
+    #[test]
+    fn test_decode_resp3_types() {
+        let frames = vec![
+            Frame::Null,
+            Frame::Double(1.5),
+            Frame::Double(f64::INFINITY),
+            Frame::Boolean(true),
+            Frame::Boolean(false),
+            Frame::BigNumber("3492890328409238509324850943850943825024385".to_string()),
+            Frame::VerbatimString("txt".to_string(), "Some string".to_string()),
+            Frame::Set(vec![Frame::BulkString(b"a".to_vec()), Frame::Integer(2)]),
+            Frame::Push(vec![Frame::BulkString(b"message".to_vec())]),
+            Frame::Map(vec![
+                (Frame::BulkString(b"key".to_vec()), Frame::Array(vec![Frame::Double(0.25)])),
+            ]),
+            Frame::Attribute(vec![(Frame::SimpleString("ttl".to_string()), Frame::Integer(3600))]),
+        ];
+        for frame in &frames {
+            round_trip(frame, Protocol::Resp3);
+        }
+
+        let decoded = round_trip(&Frame::Map(vec![(Frame::Integer(1), Frame::Boolean(true))]), Protocol::Resp2);
+        assert!(matches!(decoded, Frame::Array(items) if items.len() == 2));
+    }
+
+    #[test]
+    fn test_decode_rdb_file() {
+        let frame = Frame::RDBFile(b"\x00\r\n\x01payload".to_vec());
+        let mut bytes = frame.as_bytes();
+        bytes.extend(b"*1\r\n$4\r\nPING\r\n");
+
+        assert!(Frame::decode_rdb_file(&bytes[..6]).unwrap().is_none());
+        let (decoded, len) = Frame::decode_rdb_file(&bytes).unwrap().unwrap();
+        assert!(matches!(decoded, Frame::RDBFile(data) if data == b"\x00\r\n\x01payload"));
+
+        let (next, _) = Frame::decode(&bytes[len..]).unwrap().unwrap();
+        assert_eq!(next.get_args(), vec!["PING"]);
+    }
+
+    #[test]
+    fn test_decode_nesting_depth() {
+        let mut nested = Frame::Integer(1);
+        for _ in 0..64 {
+            nested = Frame::Array(vec![nested]);
+        }
+        round_trip(&nested, Protocol::Resp3);
+
+        // 过深的嵌套返回协议错误，而不是耗尽栈空间
+        let mut bytes = b"*1\r\n".repeat(100_000);
+        bytes.extend_from_slice(b":1\r\n");
+        let error = Frame::decode(&bytes).err().unwrap();
+        assert!(error.to_string().starts_with("ERR Protocol error"));
+
+        let bytes = b"%1\r\n:1\r\n".repeat(100_000);
+        assert!(Frame::decode(&bytes).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+
+    use redis::{cmd, Client, Commands, Connection, Value};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn sicily(con: &mut Connection, key: &str) {
+        let _: () = con.del(key).unwrap();
+        let added: i64 = cmd("GEOADD").arg(key)
+            .arg(13.361389).arg(38.115556).arg("Palermo")
+            .arg(15.087269).arg(37.502669).arg("Catania")
+            .query(con).unwrap();
+        assert_eq!(added, 2);
+    }
+
+    #[test]
+    fn test_geoadd_geopos_geodist() {
+        let mut con = setup();
+        sicily(&mut con, "geo-basic-test");
+
+        let distance: String = cmd("GEODIST").arg("geo-basic-test").arg("Palermo").arg("Catania").query(&mut con).unwrap();
+        assert_eq!(distance, "166274.1516");
+        let distance: String = cmd("GEODIST").arg("geo-basic-test").arg("Palermo").arg("Catania").arg("km").query(&mut con).unwrap();
+        assert_eq!(distance, "166.2742");
+        let distance: String = cmd("GEODIST").arg("geo-basic-test").arg("Palermo").arg("Catania").arg("mi").query(&mut con).unwrap();
+        assert_eq!(distance, "103.3182");
+        let distance: Option<String> = cmd("GEODIST").arg("geo-basic-test").arg("Palermo").arg("Rome").query(&mut con).unwrap();
+        assert_eq!(distance, None);
+
+        let positions: Vec<Option<(f64, f64)>> = cmd("GEOPOS").arg("geo-basic-test").arg("Palermo").arg("Rome").query(&mut con).unwrap();
+        let (lon, lat) = positions[0].unwrap();
+        assert!((lon - 13.361389).abs() < 1e-5 && (lat - 38.115556).abs() < 1e-5);
+        assert_eq!(positions[1], None);
+
+        let hashes: Vec<Option<String>> = cmd("GEOHASH").arg("geo-basic-test").arg("Palermo").arg("Catania").arg("Rome").query(&mut con).unwrap();
+        assert_eq!(hashes, vec![Some("sqc8b49rny0".to_string()), Some("sqdtr74hyu0".to_string()), None]);
+
+        let key_type: String = cmd("TYPE").arg("geo-basic-test").query(&mut con).unwrap();
+        assert_eq!(key_type, "zset");
+    }
+
+    #[test]
+    fn test_geoadd_options_and_errors() {
+        let mut con = setup();
+        sicily(&mut con, "geo-options-test");
+
+        let added: i64 = cmd("GEOADD").arg("geo-options-test").arg("NX").arg(13.0).arg(38.0).arg("Palermo").query(&mut con).unwrap();
+        assert_eq!(added, 0);
+        let changed: i64 = cmd("GEOADD").arg("geo-options-test").arg("XX").arg("CH").arg(13.0).arg(38.0).arg("Palermo").arg(12.0).arg(41.0).arg("Rome").query(&mut con).unwrap();
+        assert_eq!(changed, 1);
+        let count: i64 = con.zcard("geo-options-test").unwrap();
+        assert_eq!(count, 2);
+
+        let result: redis::RedisResult<i64> = cmd("GEOADD").arg("geo-options-test").arg(200).arg(38.0).arg("Nowhere").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("invalid longitude,latitude pair 200.000000,38.000000"));
+        let result: redis::RedisResult<i64> = cmd("GEOADD").arg("geo-options-test").arg(13.0).arg(86.0).arg("Pole").query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<i64> = cmd("GEOADD").arg("geo-options-test").arg("NX").arg("XX").arg(13.0).arg(38.0).arg("A").query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<String> = cmd("GEODIST").arg("geo-options-test").arg("Palermo").arg("Rome").arg("yards").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_geosearch_by_radius() {
+        let mut con = setup();
+        sicily(&mut con, "geo-radius-test");
+
+        let members: Vec<(String, String)> = cmd("GEOSEARCH").arg("geo-radius-test")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(200).arg("km").arg("ASC").arg("WITHDIST")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec![("Catania".to_string(), "56.4413".to_string()), ("Palermo".to_string(), "190.4424".to_string())]);
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-radius-test")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(100).arg("km")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec!["Catania"]);
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-radius-test")
+            .arg("FROMMEMBER").arg("Palermo").arg("BYRADIUS").arg(200).arg("km").arg("DESC")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec!["Catania", "Palermo"]);
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-radius-test")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(500).arg("km").arg("COUNT").arg(1)
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec!["Catania"]);
+
+        let value: Value = cmd("GEOSEARCH").arg("geo-radius-test")
+            .arg("FROMMEMBER").arg("Catania").arg("BYRADIUS").arg(1).arg("m").arg("WITHHASH").arg("WITHCOORD")
+            .query(&mut con).unwrap();
+        let Value::Array(items) = value else { panic!("unexpected reply") };
+        let Value::Array(item) = &items[0] else { panic!("unexpected reply") };
+        assert_eq!(item.len(), 3);
+        assert_eq!(item[1], Value::Int(3479447370796909));
+    }
+
+    #[test]
+    fn test_geosearch_from_binary_member() {
+        let mut con = setup();
+        let _: () = con.del("geo-binary-test").unwrap();
+        let member: &[u8] = b"\xff\x01";
+        let _: i64 = cmd("GEOADD").arg("geo-binary-test").arg(13.361389).arg(38.115556).arg(member).arg(15.087269).arg(37.502669).arg(b"\xfe".as_slice())
+            .query(&mut con).unwrap();
+
+        let members: Vec<Vec<u8>> = cmd("GEOSEARCH").arg("geo-binary-test")
+            .arg("FROMMEMBER").arg(member).arg("BYRADIUS").arg(1).arg("km")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec![member.to_vec()]);
+
+        let members: Vec<Vec<u8>> = cmd("GEOSEARCH").arg("geo-binary-test")
+            .arg("FROMMEMBER").arg(member).arg("BYRADIUS").arg(200).arg("km").arg("ASC")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec![member.to_vec(), b"\xfe".to_vec()]);
+    }
+
+    #[test]
+    fn test_geosearch_by_box() {
+        let mut con = setup();
+        sicily(&mut con, "geo-box-test");
+        let _: () = cmd("GEOADD").arg("geo-box-test").arg(12.758489).arg(38.788135).arg("edge1").arg(17.241510).arg(38.788135).arg("edge2").query(&mut con).unwrap();
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-box-test")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYBOX").arg(400).arg(400).arg("km").arg("ASC")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec!["Catania", "Palermo", "edge2", "edge1"]);
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-box-test")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYBOX").arg(200).arg(400).arg("km").arg("ASC")
+            .query(&mut con).unwrap();
+        assert_eq!(members, vec!["Catania"]);
+    }
+
+    #[test]
+    fn test_geosearch_errors() {
+        let mut con = setup();
+        sicily(&mut con, "geo-error-test");
+
+        let result: redis::RedisResult<Vec<String>> = cmd("GEOSEARCH").arg("geo-error-test").arg("BYRADIUS").arg(1).arg("km").arg("ASC").arg("WITHDIST").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("FROMMEMBER or FROMLONLAT"));
+        let result: redis::RedisResult<Vec<String>> = cmd("GEOSEARCH").arg("geo-error-test").arg("FROMMEMBER").arg("Palermo").arg("ASC").arg("COUNT").arg(1).query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("BYRADIUS and BYBOX"));
+        let result: redis::RedisResult<Vec<String>> = cmd("GEOSEARCH").arg("geo-error-test").arg("FROMMEMBER").arg("Rome").arg("BYRADIUS").arg(1).arg("km").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("could not decode requested zset member"));
+        let result: redis::RedisResult<Vec<String>> = cmd("GEOSEARCH").arg("geo-error-test").arg("FROMMEMBER").arg("Palermo").arg("BYRADIUS").arg(1).arg("km").arg("COUNT").arg(0).query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<Vec<String>> = cmd("GEOSEARCH").arg("geo-error-test").arg("FROMMEMBER").arg("Palermo").arg("BYRADIUS").arg(1).arg("km").arg("ANY").query(&mut con);
+        assert!(result.is_err());
+
+        let members: Vec<String> = cmd("GEOSEARCH").arg("geo-error-missing").arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(1).arg("km").query(&mut con).unwrap();
+        assert!(members.is_empty());
+    }
+
+    #[test]
+    fn test_geosearchstore() {
+        let mut con = setup();
+        sicily(&mut con, "geo-store-src");
+        let _: () = con.del("geo-store-dst").unwrap();
+
+        let stored: i64 = cmd("GEOSEARCHSTORE").arg("geo-store-dst").arg("geo-store-src")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(200).arg("km")
+            .query(&mut con).unwrap();
+        assert_eq!(stored, 2);
+        let distance: String = cmd("GEODIST").arg("geo-store-dst").arg("Palermo").arg("Catania").query(&mut con).unwrap();
+        assert_eq!(distance, "166274.1516");
+
+        let stored: i64 = cmd("GEOSEARCHSTORE").arg("geo-store-dst").arg("geo-store-src")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(200).arg("km").arg("STOREDIST")
+            .query(&mut con).unwrap();
+        assert_eq!(stored, 2);
+        let score: f64 = con.zscore("geo-store-dst", "Catania").unwrap();
+        assert!((score - 56.4413).abs() < 1e-3);
+
+        let result: redis::RedisResult<i64> = cmd("GEOSEARCHSTORE").arg("geo-store-dst").arg("geo-store-src")
+            .arg("FROMLONLAT").arg(15).arg(37).arg("BYRADIUS").arg(200).arg("km").arg("WITHDIST")
+            .query(&mut con);
+        assert!(result.is_err());
+
+        let stored: i64 = cmd("GEOSEARCHSTORE").arg("geo-store-dst").arg("geo-store-src")
+            .arg("FROMLONLAT").arg(0).arg(0).arg("BYRADIUS").arg(1).arg("km")
+            .query(&mut con).unwrap();
+        assert_eq!(stored, 0);
+        let exists: bool = con.exists("geo-store-dst").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_geosearch_matches_brute_force() {
+        let mut con = setup();
+        let _: () = con.del("geo-grid-test").unwrap();
+        let mut command = cmd("GEOADD");
+        command.arg("geo-grid-test");
+        for i in 0..40 {
+            for j in 0..40 {
+                let lon = -179.5 + i as f64 * 0.025;
+                let lat = 10.0 + j as f64 * 0.025;
+                command.arg(lon).arg(lat).arg(format!("p{}-{}", i, j));
+            }
+        }
+        let _: () = command.query(&mut con).unwrap();
+
+        // 以靠近 -180 度经线的点为中心，验证经度回绕与过滤结果
+        for radius in [1.0, 5.0, 20.0, 80.0] {
+            let members: Vec<(String, String)> = cmd("GEOSEARCH").arg("geo-grid-test")
+                .arg("FROMLONLAT").arg(-179.99).arg(10.5).arg("BYRADIUS").arg(radius).arg("km").arg("WITHDIST")
+                .query(&mut con).unwrap();
+            let all: Vec<(String, String)> = cmd("GEOSEARCH").arg("geo-grid-test")
+                .arg("FROMLONLAT").arg(-179.99).arg(10.5).arg("BYRADIUS").arg(10000).arg("km").arg("WITHDIST")
+                .query(&mut con).unwrap();
+            assert_eq!(all.len(), 1600);
+            let expected = all.iter().filter(|(_, distance)| distance.parse::<f64>().unwrap() <= radius).count();
+            assert_eq!(members.len(), expected, "radius {}", radius);
+            assert!(members.iter().all(|(_, distance)| distance.parse::<f64>().unwrap() <= radius));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+
+    use redis::{Client, Commands, Connection};
+    use rudis_server::tools::pattern::Pattern;
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn is_match(pattern: &str, string: &str) -> bool {
+        Pattern::new(pattern.as_bytes()).is_match(string.as_bytes())
+    }
+
+    #[test]
+    fn test_wildcards_are_anchored() {
+        assert!(is_match("user:*", "user:1"));
+        assert!(is_match("user:*", "user:"));
+        assert!(!is_match("user:*", "xuser:1"));
+        assert!(!is_match("user", "user:1"));
+        assert!(is_match("*:1", "user:1"));
+        assert!(!is_match("*:1", "user:10"));
+        assert!(is_match("h?llo", "hello"));
+        assert!(!is_match("h?llo", "hllo"));
+        assert!(is_match("a*b*c", "axxbyybzzc"));
+        assert!(!is_match("a*b*c", "axxbyybzz"));
+        assert!(is_match("**", ""));
+        assert!(is_match("*", "anything"));
+        assert!(!is_match("", "a"));
+    }
+
+    #[test]
+    fn test_regex_metacharacters_are_literal() {
+        assert!(is_match("a.b", "a.b"));
+        assert!(!is_match("a.b", "axb"));
+        assert!(is_match("a+(b)", "a+(b)"));
+        assert!(!is_match("a+", "aa"));
+        assert!(is_match("$^{}|", "$^{}|"));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        assert!(is_match("h[ae]llo", "hallo"));
+        assert!(!is_match("h[ae]llo", "hillo"));
+        assert!(is_match("h[^e]llo", "hallo"));
+        assert!(!is_match("h[^e]llo", "hello"));
+        assert!(is_match("h[a-c]llo", "hbllo"));
+        assert!(!is_match("h[a-c]llo", "hdllo"));
+        // 起止颠倒的范围自动交换
+        assert!(is_match("h[c-a]llo", "hbllo"));
+        assert!(is_match("[\\]]", "]"));
+        assert!(is_match("[a\\-z]", "-"));
+        assert!(!is_match("[a\\-z]", "b"));
+        assert!(!is_match("[]", "a"));
+        // 未闭合的 [ 到模式结尾为止
+        assert!(is_match("[abc", "b"));
+        assert!(!is_match("[abc", "d"));
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert!(is_match("a\\*b", "a*b"));
+        assert!(!is_match("a\\*b", "axb"));
+        assert!(is_match("a\\?", "a?"));
+        assert!(is_match("\\[a]", "[a]"));
+        // 末尾的 \ 匹配其本身
+        assert!(is_match("a\\", "a\\"));
+    }
+
+    #[test]
+    fn test_binary_and_backtracking() {
+        let pattern = Pattern::new(b"\xff*\x00");
+        assert!(pattern.is_match(b"\xff\x01\x02\x00"));
+        assert!(!pattern.is_match(b"\xff\x01\x02"));
+
+        // 大量 * 也不会指数级回溯
+        let pattern = Pattern::new("a*".repeat(30).as_bytes());
+        assert!(!pattern.is_match(format!("{}b", "a".repeat(29)).as_bytes()));
+        assert!(pattern.is_match("a".repeat(40).as_bytes()));
+    }
+
+    #[test]
+    fn test_keys_pattern() {
+        let mut con = setup();
+        let _: () = con.del(&["glob:user:1", "glob:xuser:1", "glob:a.b", "glob:axb"]).unwrap();
+        for key in ["glob:user:1", "glob:xuser:1", "glob:a.b", "glob:axb"] {
+            let _: () = con.set(key, 1).unwrap();
+        }
+
+        let keys: Vec<String> = con.keys("glob:user:*").unwrap();
+        assert_eq!(keys, vec!["glob:user:1"]);
+        let keys: Vec<String> = con.keys("glob:a.b").unwrap();
+        assert_eq!(keys, vec!["glob:a.b"]);
+        let keys: Vec<String> = con.keys("glob:a+(").unwrap();
+        assert!(keys.is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use redis::{cmd, Client, Commands, Connection};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hset_multiple_fields() {
+        let mut con = setup();
+        let _: () = con.del("hset-multi-test").unwrap();
+
+        let added: i64 = cmd("HSET").arg("hset-multi-test").arg("a").arg("1").arg("b").arg("2").query(&mut con).unwrap();
+        assert_eq!(added, 2);
+        let added: i64 = cmd("HSET").arg("hset-multi-test").arg("b").arg("3").arg("c").arg("4").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let value: String = con.hget("hset-multi-test", "b").unwrap();
+        assert_eq!(value, "3");
+
+        let result: redis::RedisResult<i64> = cmd("HSET").arg("hset-multi-test").arg("a").query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<i64> = cmd("HSET").arg("hset-multi-test").arg("a").arg("1").arg("b").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hincrby() {
+        let mut con = setup();
+        let _: () = con.del("hincrby-test").unwrap();
+
+        let value: i64 = con.hincr("hincrby-test", "visits", 5).unwrap();
+        assert_eq!(value, 5);
+        let value: i64 = con.hincr("hincrby-test", "visits", -7).unwrap();
+        assert_eq!(value, -2);
+
+        let _: () = con.hset("hincrby-test", "max", i64::MAX.to_string()).unwrap();
+        let result: redis::RedisResult<i64> = con.hincr("hincrby-test", "max", 1);
+        assert!(result.unwrap_err().to_string().contains("overflow"));
+
+        let _: () = con.hset("hincrby-test", "name", "rudis").unwrap();
+        let result: redis::RedisResult<i64> = con.hincr("hincrby-test", "name", 1);
+        assert!(result.unwrap_err().to_string().contains("hash value is not an integer"));
+
+        let result: redis::RedisResult<i64> = cmd("HINCRBY").arg("hincrby-test").arg("visits").arg("1.5").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hincrbyfloat() {
+        let mut con = setup();
+        let _: () = con.del("hincrbyfloat-test").unwrap();
+
+        let value: String = cmd("HINCRBYFLOAT").arg("hincrbyfloat-test").arg("price").arg("10.5").query(&mut con).unwrap();
+        assert_eq!(value, "10.5");
+        let value: String = cmd("HINCRBYFLOAT").arg("hincrbyfloat-test").arg("price").arg("0.1").query(&mut con).unwrap();
+        assert_eq!(value, "10.6");
+        let value: String = cmd("HINCRBYFLOAT").arg("hincrbyfloat-test").arg("price").arg("-5e1").query(&mut con).unwrap();
+        assert_eq!(value, "-39.4");
+
+        let _: () = con.hset("hincrbyfloat-test", "name", "rudis").unwrap();
+        let result: redis::RedisResult<String> = cmd("HINCRBYFLOAT").arg("hincrbyfloat-test").arg("name").arg("1").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("hash value is not a float"));
+        let result: redis::RedisResult<String> = cmd("HINCRBYFLOAT").arg("hincrbyfloat-test").arg("price").arg("abc").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hrandfield() {
+        let mut con = setup();
+        let _: () = con.del(&["hrandfield-test", "hrandfield-missing"]).unwrap();
+        let _: () = cmd("HSET").arg("hrandfield-test").arg("a").arg("1").arg("b").arg("2").arg("c").arg("3").query(&mut con).unwrap();
+        let fields: HashSet<String> = ["a", "b", "c"].iter().map(|f| f.to_string()).collect();
+
+        let field: String = cmd("HRANDFIELD").arg("hrandfield-test").query(&mut con).unwrap();
+        assert!(fields.contains(&field));
+
+        // 正数返回不重复的字段，数量不超过哈希长度
+        let values: Vec<String> = cmd("HRANDFIELD").arg("hrandfield-test").arg(5).query(&mut con).unwrap();
+        assert_eq!(values.iter().cloned().collect::<HashSet<_>>(), fields);
+        let values: Vec<String> = cmd("HRANDFIELD").arg("hrandfield-test").arg(2).query(&mut con).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_ne!(values[0], values[1]);
+
+        // 负数允许重复
+        let values: Vec<String> = cmd("HRANDFIELD").arg("hrandfield-test").arg(-10).query(&mut con).unwrap();
+        assert_eq!(values.len(), 10);
+        assert!(values.iter().all(|f| fields.contains(f)));
+
+        let values: Vec<String> = cmd("HRANDFIELD").arg("hrandfield-test").arg(-4).arg("WITHVALUES").query(&mut con).unwrap();
+        assert_eq!(values.len(), 8);
+        for pair in values.chunks(2) {
+            let value: String = con.hget("hrandfield-test", &pair[0]).unwrap();
+            assert_eq!(value, pair[1]);
+        }
+
+        let value: Option<String> = cmd("HRANDFIELD").arg("hrandfield-missing").query(&mut con).unwrap();
+        assert_eq!(value, None);
+        let values: Vec<String> = cmd("HRANDFIELD").arg("hrandfield-missing").arg(3).query(&mut con).unwrap();
+        assert!(values.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{collections::HashMap, path::PathBuf, thread, time::{Duration, SystemTime}};
+
+    use bincode::{config, encode_to_vec, Encode};
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{
+        cmds::server::flushdb::Flushdb,
+        persistence::rdb_file::RdbFile,
+        store::db::{DatabaseSnapshot, Db, Structure},
+    };
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn reset(con: &mut Connection, key: &str) {
+        let _: () = con.del(key).unwrap();
+        let _: () = cmd("HSET").arg(key).arg("a").arg("1").arg("b").arg("2").arg("c").arg("3").query(&mut *con).unwrap();
+    }
+
+    #[test]
+    fn test_hexpire_and_httl() {
+        let mut con = setup();
+        reset(&mut con, "hexpire-test");
+
+        let result: Vec<i64> = cmd("HEXPIRE").arg("hexpire-test").arg(100).arg("FIELDS").arg(2).arg("a").arg("missing").query(&mut con).unwrap();
+        assert_eq!(result, vec![1, -2]);
+        let result: Vec<i64> = cmd("HTTL").arg("hexpire-test").arg("FIELDS").arg(3).arg("a").arg("b").arg("missing").query(&mut con).unwrap();
+        assert_eq!(result, vec![100, -1, -2]);
+        let result: Vec<i64> = cmd("HPTTL").arg("hexpire-test").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert!(result[0] > 99000 && result[0] <= 100000);
+
+        // NX、XX、GT、LT 条件
+        let result: Vec<i64> = cmd("HEXPIRE").arg("hexpire-test").arg(200).arg("NX").arg("FIELDS").arg(2).arg("a").arg("b").query(&mut con).unwrap();
+        assert_eq!(result, vec![0, 1]);
+        let result: Vec<i64> = cmd("HEXPIRE").arg("hexpire-test").arg(50).arg("GT").arg("FIELDS").arg(2).arg("a").arg("c").query(&mut con).unwrap();
+        assert_eq!(result, vec![0, 0]);
+        let result: Vec<i64> = cmd("HEXPIRE").arg("hexpire-test").arg(50).arg("LT").arg("FIELDS").arg(2).arg("a").arg("c").query(&mut con).unwrap();
+        assert_eq!(result, vec![1, 1]);
+        let result: Vec<i64> = cmd("HPEXPIRE").arg("hexpire-test").arg(1000).arg("XX").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(result, vec![1]);
+
+        let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() + 300;
+        let result: Vec<i64> = cmd("HEXPIREAT").arg("hexpire-test").arg(timestamp).arg("FIELDS").arg(1).arg("b").query(&mut con).unwrap();
+        assert_eq!(result, vec![1]);
+        let result: Vec<i64> = cmd("HEXPIRETIME").arg("hexpire-test").arg("FIELDS").arg(1).arg("b").query(&mut con).unwrap();
+        assert_eq!(result, vec![timestamp as i64]);
+
+        // 过去的时间直接删除字段
+        let result: Vec<i64> = cmd("HEXPIRE").arg("hexpire-test").arg(0).arg("FIELDS").arg(1).arg("c").query(&mut con).unwrap();
+        assert_eq!(result, vec![2]);
+        let exists: bool = con.hexists("hexpire-test", "c").unwrap();
+        assert!(!exists);
+
+        let result: Vec<i64> = cmd("HTTL").arg("hexpire-missing").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(result, vec![-2]);
+    }
+
+    #[test]
+    fn test_hexpire_argument_errors() {
+        let mut con = setup();
+        reset(&mut con, "hexpire-error-test");
+
+        let result: redis::RedisResult<Vec<i64>> = cmd("HEXPIRE").arg("hexpire-error-test").arg(10).arg("FIELDS").arg(2).arg("a").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("numfields"));
+        let result: redis::RedisResult<Vec<i64>> = cmd("HEXPIRE").arg("hexpire-error-test").arg(10).arg("a").arg(1).arg("a").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("FIELDS"));
+        let result: redis::RedisResult<Vec<i64>> = cmd("HEXPIRE").arg("hexpire-error-test").arg(-1).arg("FIELDS").arg(1).arg("a").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("invalid expire time"));
+    }
+
+    #[test]
+    fn test_field_expiry() {
+        let mut con = setup();
+        reset(&mut con, "field-expiry-test");
+
+        let _: Vec<i64> = cmd("HPEXPIRE").arg("field-expiry-test").arg(100).arg("FIELDS").arg(2).arg("a").arg("b").query(&mut con).unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        // 惰性删除
+        let value: Option<String> = con.hget("field-expiry-test", "a").unwrap();
+        assert_eq!(value, None);
+        let len: i64 = con.hlen("field-expiry-test").unwrap();
+        assert_eq!(len, 1);
+
+        // 所有字段过期后键被删除（定期删除）
+        let _: Vec<i64> = cmd("HPEXPIRE").arg("field-expiry-test").arg(100).arg("FIELDS").arg(1).arg("c").query(&mut con).unwrap();
+        thread::sleep(Duration::from_millis(400));
+        let keys: Vec<String> = con.keys("field-expiry-test").unwrap();
+        assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_hpersist_and_overwrite() {
+        let mut con = setup();
+        reset(&mut con, "hpersist-test");
+
+        let _: Vec<i64> = cmd("HEXPIRE").arg("hpersist-test").arg(100).arg("FIELDS").arg(2).arg("a").arg("b").query(&mut con).unwrap();
+        let result: Vec<i64> = cmd("HPERSIST").arg("hpersist-test").arg("FIELDS").arg(3).arg("a").arg("c").arg("missing").query(&mut con).unwrap();
+        assert_eq!(result, vec![1, -1, -2]);
+
+        // HSET 覆盖字段时清除过期时间，HDEL 删除字段时同样清除
+        let _: () = con.hset("hpersist-test", "b", "new").unwrap();
+        let _: Vec<i64> = cmd("HEXPIRE").arg("hpersist-test").arg(100).arg("FIELDS").arg(1).arg("c").query(&mut con).unwrap();
+        let _: () = con.hdel("hpersist-test", "c").unwrap();
+        let _: () = con.hset("hpersist-test", "c", "again").unwrap();
+        let result: Vec<i64> = cmd("HTTL").arg("hpersist-test").arg("FIELDS").arg(2).arg("b").arg("c").query(&mut con).unwrap();
+        assert_eq!(result, vec![-1, -1]);
+
+        // RENAME 保留字段过期时间
+        let _: Vec<i64> = cmd("HEXPIRE").arg("hpersist-test").arg(100).arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        let _: () = con.rename("hpersist-test", "hpersist-renamed").unwrap();
+        let result: Vec<i64> = cmd("HTTL").arg("hpersist-renamed").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(result, vec![100]);
+        let _: () = con.del("hpersist-renamed").unwrap();
+    }
+
+    #[test]
+    fn test_hgetex() {
+        let mut con = setup();
+        reset(&mut con, "hgetex-test");
+
+        let values: Vec<Option<String>> = cmd("HGETEX").arg("hgetex-test").arg("EX").arg(60).arg("FIELDS").arg(2).arg("a").arg("missing").query(&mut con).unwrap();
+        assert_eq!(values, vec![Some("1".to_string()), None]);
+        let result: Vec<i64> = cmd("HTTL").arg("hgetex-test").arg("FIELDS").arg(2).arg("a").arg("b").query(&mut con).unwrap();
+        assert_eq!(result, vec![60, -1]);
+
+        let values: Vec<Option<String>> = cmd("HGETEX").arg("hgetex-test").arg("PERSIST").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(values, vec![Some("1".to_string())]);
+        let result: Vec<i64> = cmd("HTTL").arg("hgetex-test").arg("FIELDS").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(result, vec![-1]);
+
+        let values: Vec<Option<String>> = cmd("HGETEX").arg("hgetex-test").arg("FIELDS").arg(1).arg("b").query(&mut con).unwrap();
+        assert_eq!(values, vec![Some("2".to_string())]);
+    }
+
+    #[test]
+    fn test_field_ttl_rdb_round_trip() {
+        let expire_time = SystemTime::now() + Duration::from_secs(60);
+        let mut snapshot = DatabaseSnapshot::default();
+        let hash = HashMap::from([(b"token".to_vec(), b"abc".to_vec())]);
+        snapshot.records.insert(b"session".to_vec(), Structure::Hash(hash.into()));
+        snapshot.field_expire_records.insert(b"session".to_vec(), HashMap::from([(b"token".to_vec(), expire_time)]));
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        let fields = restored.field_expire_records.get(b"session".as_slice()).unwrap();
+        assert_eq!(fields.get(b"token".as_slice()), Some(&expire_time));
+    }
+
+    #[test]
+    fn test_flushdb_clears_field_ttl() {
+        let mut snapshot = DatabaseSnapshot::default();
+        let hash = HashMap::from([(b"token".to_vec(), b"abc".to_vec())]);
+        snapshot.records.insert(b"session".to_vec(), Structure::Hash(hash.into()));
+        snapshot.field_expire_records.insert(b"session".to_vec(), HashMap::from([(b"token".to_vec(), SystemTime::now() + Duration::from_secs(60))]));
+        let mut db = Db::new(snapshot);
+
+        Flushdb::new().apply(&mut db).unwrap();
+        assert!(db.records.is_empty());
+        assert!(db.field_expire_records.is_empty());
+    }
+
+    #[derive(Encode)]
+    struct LegacyDatabaseSnapshot {
+        expire_records: HashMap<Vec<u8>, SystemTime>,
+        records: HashMap<Vec<u8>, Structure>,
+    }
+
+    #[derive(Encode)]
+    struct LegacyRdbFile {
+        databases: HashMap<usize, LegacyDatabaseSnapshot>,
+        last_save_time: SystemTime,
+        last_save_changes: u64,
+        path: PathBuf,
+    }
+
+    #[test]
+    fn test_load_rdb_without_field_ttl() {
+        let records = HashMap::from([(b"legacy".to_vec(), Structure::String(b"value".to_vec()))]);
+        let legacy = LegacyRdbFile {
+            databases: HashMap::from([(0, LegacyDatabaseSnapshot { expire_records: HashMap::new(), records })]),
+            last_save_time: SystemTime::now(),
+            last_save_changes: 7,
+            path: PathBuf::from("dump.rdb"),
+        };
+        let bytes = encode_to_vec(&legacy, config::standard()).unwrap();
+
+        let rdb_file = RdbFile::from_bytes(&bytes).unwrap();
+        assert_eq!(rdb_file.last_save_changes, 7);
+        let restored = rdb_file.get_database(0);
+        assert!(matches!(restored.records.get(b"legacy".as_slice()), Some(Structure::String(value)) if value == b"value"));
+        assert!(restored.field_expire_records.is_empty());
+    }
+}
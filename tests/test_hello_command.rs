@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use redis::{cmd, Client, Commands, Connection, Value};
+
+    fn setup(url: &str) -> Connection {
+        let client = Client::open(url).unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hello_negotiates_resp3() {
+        let mut con = setup("redis://127.0.0.1:6379/");
+
+        let reply: Value = cmd("HELLO").arg(3).query(&mut con).unwrap();
+        match reply {
+            Value::Map(pairs) => {
+                let proto = pairs.iter().find(|(k, _)| *k == Value::BulkString(b"proto".to_vec()));
+                assert_eq!(proto.map(|(_, v)| v.clone()), Some(Value::Int(3)));
+            },
+            other => panic!("Expected map reply, got {:?}", other),
+        }
+
+        let result: Result<Value, _> = cmd("HELLO").arg(4).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_resp3_native_types() {
+        let mut con = setup("redis://127.0.0.1:6379/?protocol=resp3");
+
+        let _: () = con.del("resp3-hash").unwrap();
+        let _: () = con.hset("resp3-hash", "name", "rudis").unwrap();
+        let hash: Value = cmd("HGETALL").arg("resp3-hash").query(&mut con).unwrap();
+        assert!(matches!(hash, Value::Map(_)));
+        let hash: HashMap<String, String> = con.hgetall("resp3-hash").unwrap();
+        assert_eq!(hash.get("name"), Some(&"rudis".to_string()));
+
+        let _: () = con.del("resp3-set").unwrap();
+        let _: () = con.sadd("resp3-set", "a").unwrap();
+        let set: Value = cmd("SMEMBERS").arg("resp3-set").query(&mut con).unwrap();
+        assert!(matches!(set, Value::Set(_)));
+        let set: HashSet<String> = con.smembers("resp3-set").unwrap();
+        assert!(set.contains("a"));
+
+        let _: () = con.del("resp3-zset").unwrap();
+        let _: () = con.zadd("resp3-zset", "a", 1.5).unwrap();
+        let score: Value = cmd("ZSCORE").arg("resp3-zset").arg("a").query(&mut con).unwrap();
+        assert_eq!(score, Value::Double(1.5));
+
+        let missing: Value = cmd("GET").arg("resp3-missing").query(&mut con).unwrap();
+        assert_eq!(missing, Value::Nil);
+    }
+}
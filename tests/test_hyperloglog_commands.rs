@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{
+        persistence::rdb_file::RdbFile,
+        store::{db::{DatabaseSnapshot, Structure}, hyperloglog::HyperLogLog},
+    };
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pfadd_pfcount() {
+        let mut con = setup();
+        let _: () = con.del("pf-test").unwrap();
+
+        let updated: i64 = cmd("PFADD").arg("pf-test").arg("a").arg("b").arg("c").query(&mut con).unwrap();
+        assert_eq!(updated, 1);
+        let updated: i64 = cmd("PFADD").arg("pf-test").arg("a").arg("b").query(&mut con).unwrap();
+        assert_eq!(updated, 0);
+        let count: i64 = cmd("PFCOUNT").arg("pf-test").query(&mut con).unwrap();
+        assert_eq!(count, 3);
+
+        let count: i64 = cmd("PFCOUNT").arg("pf-missing").query(&mut con).unwrap();
+        assert_eq!(count, 0);
+
+        let _: () = con.set("pf-string", "value").unwrap();
+        let result: redis::RedisResult<i64> = cmd("PFADD").arg("pf-string").arg("a").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pfmerge() {
+        let mut con = setup();
+        let _: () = con.del(&["pf-merge-a", "pf-merge-b", "pf-merge-dest"]).unwrap();
+
+        let _: i64 = cmd("PFADD").arg("pf-merge-a").arg("foo").arg("bar").arg("zap").arg("a").query(&mut con).unwrap();
+        let _: i64 = cmd("PFADD").arg("pf-merge-b").arg("a").arg("b").arg("c").arg("foo").query(&mut con).unwrap();
+
+        let count: i64 = cmd("PFCOUNT").arg("pf-merge-a").arg("pf-merge-b").query(&mut con).unwrap();
+        assert_eq!(count, 6);
+
+        let _: () = cmd("PFMERGE").arg("pf-merge-dest").arg("pf-merge-a").arg("pf-merge-b").query(&mut con).unwrap();
+        let count: i64 = cmd("PFCOUNT").arg("pf-merge-dest").query(&mut con).unwrap();
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn test_hyperloglog_accuracy() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..200 {
+            hll.add(format!("element-{}", i).as_bytes());
+        }
+        assert!(hll.is_sparse());
+
+        for i in 200..100000 {
+            hll.add(format!("element-{}", i).as_bytes());
+        }
+        assert!(!hll.is_sparse());
+
+        let count = hll.count() as f64;
+        let error = (count - 100000.0).abs() / 100000.0;
+        assert!(error < 0.03, "estimated {} for 100000 elements", count);
+    }
+
+    #[test]
+    fn test_hyperloglog_rdb_round_trip() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..5000 {
+            hll.add(format!("visitor-{}", i).as_bytes());
+        }
+        let expected = hll.count();
+
+        let mut snapshot = DatabaseSnapshot::default();
+        snapshot.records.insert(b"visitors".to_vec(), Structure::String(hll.to_bytes()));
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        match restored.records.get(b"visitors".as_slice()) {
+            Some(Structure::String(value)) => assert_eq!(HyperLogLog::from_bytes(value).unwrap().count(), expected),
+            _ => panic!("HyperLogLog was not restored"),
+        }
+    }
+
+    #[test]
+    fn test_hyperloglog_is_a_string() {
+        let mut con = setup();
+        let _: () = con.del("pf-type").unwrap();
+        let _: i64 = cmd("PFADD").arg("pf-type").arg("a").arg("b").query(&mut con).unwrap();
+
+        let kind: String = cmd("TYPE").arg("pf-type").query(&mut con).unwrap();
+        assert_eq!(kind, "string");
+        let value: Vec<u8> = con.get("pf-type").unwrap();
+        assert!(value.starts_with(b"HYLL"));
+
+        // 字符串命令可以读写 HyperLogLog，修改后的值不再是有效的 HyperLogLog
+        let _: () = con.set("pf-copy", &value).unwrap();
+        let count: i64 = cmd("PFCOUNT").arg("pf-copy").query(&mut con).unwrap();
+        assert_eq!(count, 2);
+        let _: () = con.append("pf-type", "garbage").unwrap();
+        let result: redis::RedisResult<i64> = cmd("PFCOUNT").arg("pf-type").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("not a valid HyperLogLog"));
+        let _: () = con.del(&["pf-type", "pf-copy"]).unwrap();
+    }
+
+    #[test]
+    fn test_hyperloglog_rejects_corrupted_registers() {
+        let mut hll = HyperLogLog::new();
+        for i in 0..5000 {
+            hll.add(format!("visitor-{}", i).as_bytes());
+        }
+        let mut bytes = hll.to_bytes();
+        assert!(HyperLogLog::from_bytes(&bytes).is_some());
+        // 寄存器的值超过哈希位数时无法参与估计
+        let len = bytes.len();
+        bytes[len - 100..].fill(0xff);
+        assert!(HyperLogLog::from_bytes(&bytes).is_none());
+        assert!(HyperLogLog::from_bytes(b"HYLL").is_none());
+        assert!(HyperLogLog::from_bytes(b"value").is_none());
+    }
+}
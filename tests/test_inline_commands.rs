@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    use rudis_server::frame::Frame;
+
+    fn read_reply(stream: &mut TcpStream) -> Frame {
+        let mut buffer = Vec::new();
+        let mut chunk = [0; 1024];
+        loop {
+            if let Some((frame, _)) = Frame::decode(&buffer).unwrap() {
+                return frame;
+            }
+            let n = stream.read(&mut chunk).unwrap();
+            assert!(n > 0, "connection closed before a complete reply");
+            buffer.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_args() {
+        let frame = Frame::parse_from_bytes(b"SET greeting \"hello world\"\r\n").unwrap();
+        assert_eq!(frame.get_args(), vec!["SET", "greeting", "hello world"]);
+
+        let frame = Frame::parse_from_bytes(b"SET k \"a\\tb\\x41\\\"\" 'it\\'s'\n").unwrap();
+        assert_eq!(frame.get_args_bytes(), vec![b"SET".to_vec(), b"k".to_vec(), b"a\tbA\"".to_vec(), b"it's".to_vec()]);
+
+        let frame = Frame::parse_from_bytes(b"  PING  ").unwrap();
+        assert_eq!(frame.get_args(), vec!["PING"]);
+
+        assert!(Frame::parse_from_bytes(b"SET k \"unterminated\r\n").is_err());
+        assert!(Frame::parse_from_bytes(b"SET k \"a\"b\r\n").is_err());
+    }
+
+    #[test]
+    fn test_decode_inline_skips_empty_lines() {
+        let bytes = b"\r\n\nPING\r\nECHO";
+        let (frame, len) = Frame::decode(bytes).unwrap().unwrap();
+        assert_eq!(frame.get_args(), vec!["PING"]);
+        assert_eq!(len, 9);
+        assert!(Frame::decode(&bytes[len..]).unwrap().is_none());
+
+        // 大量空行不会耗尽栈空间
+        let mut bytes = vec![b'\n'; 200_000];
+        assert!(Frame::decode(&bytes).unwrap().is_none());
+        bytes.extend_from_slice(b"*1\r\n$4\r\nPING\r\n");
+        let (frame, len) = Frame::decode(&bytes).unwrap().unwrap();
+        assert_eq!(frame.get_args(), vec!["PING"]);
+        assert_eq!(len, bytes.len());
+    }
+
+    #[test]
+    fn test_inline_commands() {
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+
+        stream.write_all(b"PING\r\n").unwrap();
+        assert!(matches!(read_reply(&mut stream), Frame::SimpleString(s) if s == "PONG"));
+
+        stream.write_all(b"SET inline-key \"hello \\\"rudis\\\"\"\n").unwrap();
+        assert!(matches!(read_reply(&mut stream), Frame::SimpleString(s) if s == "OK"));
+
+        stream.write_all(b"GET inline-key\r\n").unwrap();
+        assert!(matches!(read_reply(&mut stream), Frame::BulkString(v) if v == b"hello \"rudis\""));
+    }
+}
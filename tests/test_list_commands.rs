@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::VecDeque;
+
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{
+        persistence::rdb_file::RdbFile,
+        store::db::{DatabaseSnapshot, Structure},
+    };
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn reset(con: &mut Connection, key: &str, values: &[&str]) {
+        let _: () = con.del(key).unwrap();
+        let _: () = con.rpush(key, values).unwrap();
+    }
+
+    #[test]
+    fn test_pop_with_count() {
+        let mut con = setup();
+        reset(&mut con, "pop-count-test", &["a", "b", "c", "d"]);
+
+        let values: Vec<String> = cmd("LPOP").arg("pop-count-test").arg(2).query(&mut con).unwrap();
+        assert_eq!(values, vec!["a", "b"]);
+        let values: Vec<String> = cmd("RPOP").arg("pop-count-test").arg(5).query(&mut con).unwrap();
+        assert_eq!(values, vec!["d", "c"]);
+
+        // 列表为空后键会被删除
+        let exists: bool = con.exists("pop-count-test").unwrap();
+        assert!(!exists);
+        let values: Option<Vec<String>> = cmd("LPOP").arg("pop-count-test").arg(2).query(&mut con).unwrap();
+        assert_eq!(values, None);
+    }
+
+    #[test]
+    fn test_linsert_lrem() {
+        let mut con = setup();
+        reset(&mut con, "linsert-test", &["a", "c", "a", "b", "a"]);
+
+        let len: i64 = cmd("LINSERT").arg("linsert-test").arg("BEFORE").arg("c").arg("x").query(&mut con).unwrap();
+        assert_eq!(len, 6);
+        let len: i64 = cmd("LINSERT").arg("linsert-test").arg("after").arg("missing").arg("x").query(&mut con).unwrap();
+        assert_eq!(len, -1);
+
+        let removed: i64 = cmd("LREM").arg("linsert-test").arg(-2).arg("a").query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        let values: Vec<String> = con.lrange("linsert-test", 0, -1).unwrap();
+        assert_eq!(values, vec!["a", "x", "c", "b"]);
+
+        let removed: i64 = cmd("LREM").arg("linsert-test").arg(0).arg("x").query(&mut con).unwrap();
+        assert_eq!(removed, 1);
+    }
+
+    #[test]
+    fn test_ltrim() {
+        let mut con = setup();
+        reset(&mut con, "ltrim-test", &["one", "two", "three", "four"]);
+
+        let _: () = cmd("LTRIM").arg("ltrim-test").arg(1).arg(-2).query(&mut con).unwrap();
+        let values: Vec<String> = con.lrange("ltrim-test", 0, -1).unwrap();
+        assert_eq!(values, vec!["two", "three"]);
+
+        let _: () = cmd("LTRIM").arg("ltrim-test").arg(5).arg(10).query(&mut con).unwrap();
+        let exists: bool = con.exists("ltrim-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_lpos() {
+        let mut con = setup();
+        reset(&mut con, "lpos-test", &["a", "b", "c", "1", "2", "3", "c", "c"]);
+
+        let pos: Option<i64> = cmd("LPOS").arg("lpos-test").arg("c").query(&mut con).unwrap();
+        assert_eq!(pos, Some(2));
+        let pos: Option<i64> = cmd("LPOS").arg("lpos-test").arg("c").arg("RANK").arg(-1).query(&mut con).unwrap();
+        assert_eq!(pos, Some(7));
+        let pos: Vec<i64> = cmd("LPOS").arg("lpos-test").arg("c").arg("COUNT").arg(0).query(&mut con).unwrap();
+        assert_eq!(pos, vec![2, 6, 7]);
+        let pos: Vec<i64> = cmd("LPOS").arg("lpos-test").arg("c").arg("RANK").arg(2).arg("COUNT").arg(1).query(&mut con).unwrap();
+        assert_eq!(pos, vec![6]);
+        let pos: Option<i64> = cmd("LPOS").arg("lpos-test").arg("c").arg("MAXLEN").arg(2).query(&mut con).unwrap();
+        assert_eq!(pos, None);
+
+        let result: redis::RedisResult<i64> = cmd("LPOS").arg("lpos-test").arg("c").arg("RANK").arg(0).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_lmove_rpoplpush() {
+        let mut con = setup();
+        reset(&mut con, "lmove-src", &["one", "two", "three"]);
+        let _: () = con.del("lmove-dst").unwrap();
+
+        let value: String = cmd("LMOVE").arg("lmove-src").arg("lmove-dst").arg("RIGHT").arg("LEFT").query(&mut con).unwrap();
+        assert_eq!(value, "three");
+        let value: String = cmd("LMOVE").arg("lmove-src").arg("lmove-dst").arg("LEFT").arg("RIGHT").query(&mut con).unwrap();
+        assert_eq!(value, "one");
+        let values: Vec<String> = con.lrange("lmove-dst", 0, -1).unwrap();
+        assert_eq!(values, vec!["three", "one"]);
+
+        let value: String = cmd("RPOPLPUSH").arg("lmove-dst").arg("lmove-dst").query(&mut con).unwrap();
+        assert_eq!(value, "one");
+        let values: Vec<String> = con.lrange("lmove-dst", 0, -1).unwrap();
+        assert_eq!(values, vec!["one", "three"]);
+
+        let value: Option<String> = cmd("RPOPLPUSH").arg("lmove-missing").arg("lmove-dst").query(&mut con).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_push_order() {
+        let mut con = setup();
+        let _: () = con.del("push-order-test").unwrap();
+
+        let len: i64 = con.lpush("push-order-test", &["a", "b", "c"]).unwrap();
+        assert_eq!(len, 3);
+        let len: i64 = con.rpush("push-order-test", &["d", "e"]).unwrap();
+        assert_eq!(len, 5);
+        let values: Vec<String> = con.lrange("push-order-test", 0, -1).unwrap();
+        assert_eq!(values, vec!["c", "b", "a", "d", "e"]);
+        let values: Vec<String> = con.lrange("push-order-test", 1, 3).unwrap();
+        assert_eq!(values, vec!["b", "a", "d"]);
+    }
+
+    #[test]
+    fn test_lpush_multiple_values() {
+        let mut con = setup();
+        let _: () = con.del("lpush-multiple-test").unwrap();
+
+        // 与 Redis 一致：依次插入表头，最后一个值位于表头
+        let _: () = cmd("LPUSH").arg("lpush-multiple-test").arg("a").arg("b").arg("c").query(&mut con).unwrap();
+        let values: Vec<String> = cmd("LRANGE").arg("lpush-multiple-test").arg(0).arg(-1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["c", "b", "a"]);
+
+        // 已存在的列表同样如此
+        let _: () = cmd("LPUSH").arg("lpush-multiple-test").arg("d").arg("e").query(&mut con).unwrap();
+        let values: Vec<String> = cmd("LRANGE").arg("lpush-multiple-test").arg(0).arg(-1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["e", "d", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_large_list_head_operations() {
+        let mut con = setup();
+        let _: () = con.del("large-list-test").unwrap();
+
+        let values: Vec<String> = (0..200000).map(|i| i.to_string()).collect();
+        for chunk in values.chunks(10000) {
+            let _: () = con.rpush("large-list-test", chunk).unwrap();
+        }
+
+        // 表头弹出不应随列表长度线性增长
+        let mut pipe = redis::pipe();
+        for _ in 0..20000 {
+            pipe.cmd("LPOP").arg("large-list-test").ignore();
+            pipe.cmd("LPUSH").arg("large-list-test").arg("head").ignore();
+            pipe.cmd("LPOP").arg("large-list-test").ignore();
+        }
+        let _: () = pipe.query(&mut con).unwrap();
+
+        let len: i64 = con.llen("large-list-test").unwrap();
+        assert_eq!(len, 180000);
+        let value: String = con.lindex("large-list-test", 0).unwrap();
+        assert_eq!(value, "20000");
+        let value: String = con.lindex("large-list-test", -1).unwrap();
+        assert_eq!(value, "199999");
+        let _: () = con.del("large-list-test").unwrap();
+    }
+
+    #[test]
+    fn test_list_rdb_round_trip() {
+        let list = (0..1000).map(|i| format!("item-{}", i).into_bytes()).collect::<VecDeque<_>>();
+
+        let mut snapshot = DatabaseSnapshot::default();
+        snapshot.records.insert(b"queue".to_vec(), Structure::List(list.clone()));
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        match restored.records.get(b"queue".as_slice()) {
+            Some(Structure::List(restored)) => assert_eq!(*restored, list),
+            _ => panic!("List was not restored"),
+        }
+    }
+}
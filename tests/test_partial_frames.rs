@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::Duration;
+
+    use rudis_server::command::Command;
+    use rudis_server::frame::{Frame, ProtocolError};
+    use rudis_server::network::frame_decoder::FrameDecoder;
+    use tokio::io::AsyncWriteExt;
+    use redis::{Client, Commands, Connection};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn read_reply(stream: &mut TcpStream) -> Frame {
+        let mut buffer = Vec::new();
+        let mut chunk = [0; 1024];
+        loop {
+            if let Some((frame, _)) = Frame::decode(&buffer).unwrap() {
+                return frame;
+            }
+            let n = stream.read(&mut chunk).unwrap();
+            assert!(n > 0, "connection closed before a complete reply");
+            buffer.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    #[test]
+    fn test_decode_incomplete_frame() {
+        let bytes = b"*2\r\n$3\r\nGET\r\n$5\r\nhel";
+        assert!(Frame::decode(bytes).unwrap().is_none());
+        assert!(Frame::decode(b"").unwrap().is_none());
+
+        let bytes = b"*2\r\n$3\r\nGET\r\n$5\r\nhello\r\n*1";
+        let (frame, len) = Frame::decode(bytes).unwrap().unwrap();
+        assert_eq!(len, bytes.len() - 2);
+        assert_eq!(frame.get_args(), vec!["GET".to_string(), "hello".to_string()]);
+
+        assert!(Frame::decode(b"*1\r\n$abc\r\n").is_err());
+        assert!(Frame::decode(b"*1\r\n$3\r\nGETXX").is_err());
+        assert!(Frame::parse_from_bytes(b"").is_err());
+    }
+
+    #[test]
+    fn test_split_command_across_writes() {
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        let command = b"*3\r\n$3\r\nSET\r\n$11\r\nsplit-frame\r\n$5\r\nvalue\r\n";
+
+        for chunk in command.chunks(5) {
+            stream.write_all(chunk).unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(matches!(read_reply(&mut stream), Frame::SimpleString(s) if s == "OK"));
+
+        stream.write_all(b"*2\r\n$3\r\nGET\r\n$11\r\nsplit-frame\r\n*1\r\n$4\r\nPING\r\n").unwrap();
+        assert!(matches!(read_reply(&mut stream), Frame::BulkString(v) if v == b"value"));
+    }
+
+    #[test]
+    fn test_large_value() {
+        let mut con = setup();
+        let value: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+        let _: () = con.set("large-value", value.clone()).unwrap();
+        let result: Vec<u8> = con.get("large-value").unwrap();
+        assert_eq!(result, value);
+    }
+
+    #[test]
+    fn test_protocol_error() {
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        stream.write_all(b"*1\r\n$abc\r\n").unwrap();
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).unwrap();
+        assert!(reply.starts_with(b"-ERR Protocol error"));
+    }
+
+    #[test]
+    fn test_protocol_error_after_valid_frames() {
+        let error = Frame::decode(b"*1\r\n$abc\r\n").err().unwrap();
+        assert!(error.downcast_ref::<ProtocolError>().is_some());
+
+        // 同一批数据中错误之前的命令仍会执行并回复
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        stream.write_all(b"*1\r\n$4\r\nPING\r\n*2\r\n$4\r\nECHO\r\n$2\r\nhi\r\n*1\r\n$abc\r\n").unwrap();
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).unwrap();
+        assert!(reply.starts_with(b"+PONG\r\n$2\r\nhi\r\n-ERR Protocol error"));
+    }
+
+    #[test]
+    fn test_non_command_frames() {
+        for bytes in [&b"*0\r\n"[..], b"*-1\r\n", b":1\r\n"] {
+            let (frame, _) = Frame::decode(bytes).unwrap().unwrap();
+            let error = Command::parse_from_frame(frame).err().unwrap();
+            assert!(error.to_string().starts_with("ERR Protocol error"));
+        }
+
+        // 回复错误后连接仍然可用
+        let mut stream = TcpStream::connect("127.0.0.1:6379").unwrap();
+        for bytes in [&b"*0\r\n"[..], b"*-1\r\n", b":1\r\n"] {
+            stream.write_all(bytes).unwrap();
+            assert!(matches!(read_reply(&mut stream), Frame::Error(e) if e.starts_with("ERR Protocol error")));
+        }
+        stream.write_all(b"*1\r\n$4\r\nPING\r\n").unwrap();
+        assert!(matches!(read_reply(&mut stream), Frame::SimpleString(s) if s == "PONG"));
+    }
+
+    #[tokio::test]
+    async fn test_decoder_resumes_partial_aggregate() {
+        // 元素很多的命令分多次到达，已解析的元素不会被重新解析
+        let (mut client, mut server) = tokio::io::duplex(256);
+        let command = Frame::Array((0..2000).map(|i| Frame::BulkString(format!("value:{}", i).into_bytes())).collect());
+        let bytes = command.as_bytes();
+        let writer = tokio::spawn(async move {
+            for chunk in bytes.chunks(100) {
+                client.write_all(chunk).await.unwrap();
+            }
+            client.write_all(b"*1\r\n$4\r\nPING\r\n").await.unwrap();
+            client
+        });
+
+        let mut decoder = FrameDecoder::new();
+        let mut frames = Vec::new();
+        while frames.len() < 2 {
+            frames.extend(decoder.read_frames(&mut server).await.unwrap().unwrap());
+        }
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].get_args(), command.get_args());
+        assert_eq!(frames[1].get_args(), vec!["PING".to_string()]);
+        drop(writer.await.unwrap());
+        assert!(decoder.read_frames(&mut server).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_decoder_query_buffer_limit() {
+        let mut value = b"*2\r\n$3\r\nGET\r\n$2048\r\n".to_vec();
+        value.extend(vec![b'x'; 2048]);
+        value.extend(b"\r\n");
+
+        // 上限以内可以正常解析
+        let mut decoder = FrameDecoder::with_limit(4096);
+        let frames = decoder.read_frames(&mut value.as_slice()).await.unwrap().unwrap();
+        assert_eq!(frames.len(), 1);
+
+        // 超过上限时报告协议错误
+        let mut decoder = FrameDecoder::with_limit(1024);
+        let error = decoder.read_frames(&mut value.as_slice()).await.err().unwrap();
+        assert!(error.downcast_ref::<ProtocolError>().is_some());
+        assert!(error.to_string().contains("query buffer limit exceeded"));
+
+        // 阻塞期间收到的数据同样受上限约束
+        let mut decoder = FrameDecoder::with_limit(1024);
+        let error = decoder.wait_closed(&mut value.as_slice()).await.err().unwrap();
+        assert!(error.downcast_ref::<ProtocolError>().is_some());
+        let mut decoder = FrameDecoder::with_limit(4096);
+        assert!(decoder.wait_closed(&mut value.as_slice()).await.is_ok());
+    }
+}
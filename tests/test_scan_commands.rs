@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use redis::{cmd, Client, Commands, Connection, RedisResult};
+    use rudis_server::store::dict::Dict;
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn scan_all(con: &mut Connection, command: &str, key: Option<&str>, options: &[&str]) -> Vec<String> {
+        let mut cursor = "0".to_string();
+        let mut result = Vec::new();
+        loop {
+            let mut scan = cmd(command);
+            if let Some(key) = key {
+                scan.arg(key);
+            }
+            let (next, elements): (String, Vec<String>) = scan.arg(&cursor).arg(options).query(&mut *con).unwrap();
+            result.extend(elements);
+            if next == "0" {
+                return result;
+            }
+            cursor = next;
+        }
+    }
+
+    #[test]
+    fn test_scan_cursor() {
+        let elements = (0..100).map(|i| format!("element:{}", i).into_bytes()).collect::<Vec<_>>();
+        let mut dict = elements.iter().map(|element| (element.clone(), ())).collect::<Dict<_, _>>();
+        let mut seen = HashSet::new();
+        let mut cursor = 0;
+        let mut calls = 0;
+        loop {
+            let (next, batch) = dict.scan(cursor, 7);
+            for (element, _) in batch {
+                // 每个元素只返回一次
+                assert!(seen.insert(element.clone()));
+            }
+            calls += 1;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert_eq!(seen.len(), 100);
+        assert!(calls >= 10);
+        assert_eq!(Dict::<Vec<u8>, ()>::new().scan(0, 10), (0, Vec::new()));
+
+        // 遍历期间扩容，一直存在的元素仍然至少返回一次
+        let mut seen = HashSet::new();
+        let mut cursor = 0;
+        for round in 0.. {
+            let (next, batch) = dict.scan(cursor, 10);
+            seen.extend(batch.into_iter().map(|(element, _)| element.clone()));
+            for i in 0..50 {
+                dict.insert(format!("added:{}:{}", round, i).into_bytes(), ());
+            }
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert!(elements.iter().all(|element| seen.contains(element)));
+
+        // 遍历期间缩容
+        dict.retain(|element, _| elements.contains(element));
+        let mut seen = HashSet::new();
+        let mut cursor = 0;
+        for round in 0.. {
+            let (next, batch) = dict.scan(cursor, 10);
+            seen.extend(batch.into_iter().map(|(element, _)| element.clone()));
+            if round < 10 {
+                for i in 0..9 {
+                    dict.remove(&elements[90 - round * 9 + i]);
+                }
+            }
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert!(elements[..9].iter().all(|element| seen.contains(element)));
+    }
+
+    #[test]
+    fn test_scan() {
+        let mut con = setup();
+        // SCAN 遍历整个数据库，使用单独的数据库避免其他测试的键干扰
+        let _: () = cmd("SELECT").arg(11).query(&mut con).unwrap();
+        let _: () = cmd("FLUSHDB").query(&mut con).unwrap();
+        for i in 0..50 {
+            let _: () = con.set(format!("scan:string:{}", i), i).unwrap();
+        }
+        let _: () = con.hset("scan:hash", "f", "v").unwrap();
+        let _: () = con.sadd("scan:set", "m").unwrap();
+
+        let keys = scan_all(&mut con, "SCAN", None, &["COUNT", "7"]);
+        assert_eq!(keys.len(), 52);
+        assert_eq!(keys.iter().collect::<HashSet<_>>().len(), 52);
+
+        let keys = scan_all(&mut con, "SCAN", None, &["MATCH", "scan:string:1*"]);
+        assert_eq!(keys.len(), 11);
+        let keys = scan_all(&mut con, "SCAN", None, &["TYPE", "hash"]);
+        assert_eq!(keys, vec!["scan:hash"]);
+
+        let result: RedisResult<(String, Vec<String>)> = cmd("SCAN").arg("abc").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("invalid cursor"));
+        let result: RedisResult<(String, Vec<String>)> = cmd("SCAN").arg(0).arg("COUNT").arg(0).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<(String, Vec<String>)> = cmd("SCAN").arg(0).arg("MATCH").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_scan_while_mutating() {
+        let mut con = setup();
+        let _: () = con.del("scan-mutate:hash").unwrap();
+        for i in 0..200 {
+            let _: () = con.hset("scan-mutate:hash", format!("stable:{}", i), i).unwrap();
+        }
+
+        // 遍历期间不断增删字段，使哈希扩容，始终存在的字段仍然都会被返回
+        let mut cursor = "0".to_string();
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        loop {
+            let (next, elements): (String, Vec<String>) = cmd("HSCAN").arg("scan-mutate:hash").arg(&cursor).arg("COUNT").arg(10).query(&mut con).unwrap();
+            seen.extend(elements.chunks(2).map(|pair| pair[0].clone()));
+            for i in 0..50 {
+                let _: () = con.hset("scan-mutate:hash", format!("added:{}:{}", round, i), i).unwrap();
+            }
+            let _: () = con.hdel("scan-mutate:hash", format!("added:{}:0", round.max(1) - 1)).unwrap();
+            round += 1;
+            if next == "0" {
+                break;
+            }
+            cursor = next;
+        }
+        for i in 0..200 {
+            assert!(seen.contains(&format!("stable:{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_hscan_sscan_zscan() {
+        let mut con = setup();
+        let _: () = con.del(&["scan-hash", "scan-set", "scan-zset", "scan-string"]).unwrap();
+        for i in 0..30 {
+            let _: () = con.hset("scan-hash", format!("field:{}", i), format!("value:{}", i)).unwrap();
+            let _: () = con.sadd("scan-set", format!("member:{}", i)).unwrap();
+            let _: () = con.zadd("scan-zset", format!("member:{}", i), i).unwrap();
+        }
+        let _: () = con.set("scan-string", "value").unwrap();
+
+        let fields = scan_all(&mut con, "HSCAN", Some("scan-hash"), &["COUNT", "4"]);
+        assert_eq!(fields.len(), 60);
+        assert!(fields.chunks(2).all(|pair| pair[1] == pair[0].replace("field", "value")));
+        let fields = scan_all(&mut con, "HSCAN", Some("scan-hash"), &["MATCH", "field:2?"]);
+        assert_eq!(fields.len(), 20);
+
+        let members = scan_all(&mut con, "SSCAN", Some("scan-set"), &["COUNT", "4"]);
+        assert_eq!(members.iter().collect::<HashSet<_>>().len(), 30);
+
+        let members = scan_all(&mut con, "ZSCAN", Some("scan-zset"), &["MATCH", "member:1?"]);
+        assert_eq!(members.len(), 20);
+        assert!(members.chunks(2).all(|pair| pair[0] == format!("member:{}", pair[1])));
+
+        // 不存在的键返回空结果，类型错误时返回错误
+        let result: (String, Vec<String>) = cmd("SSCAN").arg("scan-missing").arg(0).query(&mut con).unwrap();
+        assert_eq!(result, ("0".to_string(), Vec::new()));
+        let result: RedisResult<(String, Vec<String>)> = cmd("ZSCAN").arg("scan-string").arg(0).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<(String, Vec<String>)> = cmd("HSCAN").arg("scan-hash").arg(0).arg("TYPE").arg("hash").query(&mut con);
+        assert!(result.is_err());
+    }
+}
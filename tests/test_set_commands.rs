@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{command::Command, frame::Frame};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn reset(con: &mut Connection, key: &str, members: &[&str]) {
+        let _: () = con.del(key).unwrap();
+        let _: () = con.sadd(key, members).unwrap();
+    }
+
+    fn set_of(members: &[&str]) -> HashSet<String> {
+        members.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn test_sdiff_sinter_store() {
+        let mut con = setup();
+        reset(&mut con, "salg-a", &["a", "b", "c", "d"]);
+        reset(&mut con, "salg-b", &["c"]);
+        reset(&mut con, "salg-c", &["a", "c", "e"]);
+        let _: () = con.del(&["salg-missing", "salg-dest"]).unwrap();
+
+        let diff: HashSet<String> = con.sdiff(&["salg-a", "salg-b", "salg-c"]).unwrap();
+        assert_eq!(diff, set_of(&["b", "d"]));
+        let diff: HashSet<String> = con.sdiff(&["salg-a", "salg-missing"]).unwrap();
+        assert_eq!(diff, set_of(&["a", "b", "c", "d"]));
+
+        let len: i64 = con.sdiffstore("salg-dest", &["salg-a", "salg-c"]).unwrap();
+        assert_eq!(len, 2);
+        let members: HashSet<String> = con.smembers("salg-dest").unwrap();
+        assert_eq!(members, set_of(&["b", "d"]));
+
+        let len: i64 = con.sinterstore("salg-dest", &["salg-a", "salg-c"]).unwrap();
+        assert_eq!(len, 2);
+        let members: HashSet<String> = con.smembers("salg-dest").unwrap();
+        assert_eq!(members, set_of(&["a", "c"]));
+
+        // 结果为空时删除目标键
+        let len: i64 = con.sinterstore("salg-dest", &["salg-a", "salg-missing"]).unwrap();
+        assert_eq!(len, 0);
+        let exists: bool = con.exists("salg-dest").unwrap();
+        assert!(!exists);
+
+        let _: () = con.set("salg-string", "x").unwrap();
+        let result: redis::RedisResult<HashSet<String>> = con.sdiff(&["salg-a", "salg-string"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sintercard() {
+        let mut con = setup();
+        reset(&mut con, "sintercard-a", &["a", "b", "c", "d"]);
+        reset(&mut con, "sintercard-b", &["b", "c", "d", "e"]);
+
+        let len: i64 = cmd("SINTERCARD").arg(2).arg("sintercard-a").arg("sintercard-b").query(&mut con).unwrap();
+        assert_eq!(len, 3);
+        let len: i64 = cmd("SINTERCARD").arg(2).arg("sintercard-a").arg("sintercard-b").arg("LIMIT").arg(2).query(&mut con).unwrap();
+        assert_eq!(len, 2);
+        let len: i64 = cmd("SINTERCARD").arg(2).arg("sintercard-a").arg("sintercard-missing").query(&mut con).unwrap();
+        assert_eq!(len, 0);
+
+        let result: redis::RedisResult<i64> = cmd("SINTERCARD").arg(3).arg("sintercard-a").arg("sintercard-b").query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<i64> = cmd("SINTERCARD").arg(0).arg("sintercard-a").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_smove_smismember() {
+        let mut con = setup();
+        reset(&mut con, "smove-src", &["a", "b"]);
+        let _: () = con.del("smove-dst").unwrap();
+
+        let moved: i64 = con.smove("smove-src", "smove-dst", "a").unwrap();
+        assert_eq!(moved, 1);
+        let moved: i64 = con.smove("smove-src", "smove-dst", "missing").unwrap();
+        assert_eq!(moved, 0);
+        let result: Vec<i64> = cmd("SMISMEMBER").arg("smove-dst").arg("a").arg("b").query(&mut con).unwrap();
+        assert_eq!(result, vec![1, 0]);
+        let result: Vec<i64> = cmd("SMISMEMBER").arg("smove-missing").arg("a").query(&mut con).unwrap();
+        assert_eq!(result, vec![0]);
+
+        // 源集合为空后被删除
+        let moved: i64 = con.smove("smove-src", "smove-dst", "b").unwrap();
+        assert_eq!(moved, 1);
+        let exists: bool = con.exists("smove-src").unwrap();
+        assert!(!exists);
+
+        let _: () = con.set("smove-string", "x").unwrap();
+        let result: redis::RedisResult<i64> = con.smove("smove-dst", "smove-string", "a");
+        assert!(result.is_err());
+        let members: HashSet<String> = con.smembers("smove-dst").unwrap();
+        assert_eq!(members, set_of(&["a", "b"]));
+    }
+
+    #[test]
+    fn test_srandmember() {
+        let mut con = setup();
+        reset(&mut con, "srandmember-test", &["a", "b", "c"]);
+        let all = set_of(&["a", "b", "c"]);
+
+        let member: String = con.srandmember("srandmember-test").unwrap();
+        assert!(all.contains(&member));
+
+        let members: Vec<String> = con.srandmember_multiple("srandmember-test", 2).unwrap();
+        assert_eq!(members.len(), 2);
+        assert_ne!(members[0], members[1]);
+        let members: Vec<String> = con.srandmember_multiple("srandmember-test", 10).unwrap();
+        assert_eq!(members.into_iter().collect::<HashSet<_>>(), all);
+
+        let members: Vec<String> = cmd("SRANDMEMBER").arg("srandmember-test").arg(-20).query(&mut con).unwrap();
+        assert_eq!(members.len(), 20);
+        assert!(members.iter().all(|m| all.contains(m)));
+
+        // 多次抽样应覆盖所有成员
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let member: String = con.srandmember("srandmember-test").unwrap();
+            seen.insert(member);
+        }
+        assert_eq!(seen, all);
+
+        let len: i64 = con.scard("srandmember-test").unwrap();
+        assert_eq!(len, 3);
+        let member: Option<String> = con.srandmember("srandmember-missing").unwrap();
+        assert_eq!(member, None);
+    }
+
+    #[test]
+    fn test_spop_random() {
+        let mut con = setup();
+        let members: Vec<String> = (0..50).map(|i| format!("m{}", i)).collect();
+
+        // 单个弹出的结果应当是随机的
+        let mut seen = HashSet::new();
+        for _ in 0..20 {
+            let _: () = con.del("spop-random-test").unwrap();
+            let _: () = con.sadd("spop-random-test", &members).unwrap();
+            let member: String = con.spop("spop-random-test").unwrap();
+            seen.insert(member);
+        }
+        assert!(seen.len() > 1);
+
+        let popped: Vec<String> = cmd("SPOP").arg("spop-random-test").arg(10).query(&mut con).unwrap();
+        assert_eq!(popped.len(), 10);
+        let len: i64 = con.scard("spop-random-test").unwrap();
+        assert_eq!(len, 39);
+        let popped: Vec<String> = cmd("SPOP").arg("spop-random-test").arg(100).query(&mut con).unwrap();
+        assert_eq!(popped.len(), 39);
+        let exists: bool = con.exists("spop-random-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_spop_propagates_popped_members() {
+        let frame = Frame::Array(["SPOP", "colors", "2"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = Command::parse_from_frame(frame.clone()).unwrap();
+        assert!(command.propagate_aof_if_needed());
+        let propagated = command.propagation_frame(frame);
+        let reply = Frame::Array(vec![Frame::BulkString(b"red".to_vec()), Frame::BulkString(b"blue".to_vec())]);
+        let resolved = Command::resolve_propagation_frame(propagated.clone(), &reply);
+        assert_eq!(resolved.get_args(), vec!["SREM", "colors", "red", "blue"]);
+
+        let frame = Frame::Array(["SPOP", "colors"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let resolved = Command::resolve_propagation_frame(frame, &Frame::BulkString(b"green".to_vec()));
+        assert_eq!(resolved.get_args(), vec!["SREM", "colors", "green"]);
+
+        // 没有弹出成员时原样传播，重放时同样不做任何修改
+        let resolved = Command::resolve_propagation_frame(propagated, &Frame::Array(Vec::new()));
+        assert_eq!(resolved.get_args(), vec!["SPOP", "colors", "2"]);
+    }
+}
@@ -0,0 +1,482 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{collections::BTreeMap, thread, time::{Duration, Instant}};
+
+    use bincode::{config, decode_from_slice, encode_to_vec};
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{command::Command, frame::Frame};
+    use rudis_server::store::{blocking::{BlockingCommand, BlockingQueue}, db::{DatabaseSnapshot, Db, Structure}, sorted_set::{ScoreBound, SortedSet}};
+    use tokio::sync::oneshot;
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zrank_orders_by_score_then_member() {
+        let mut con = setup();
+        let _: () = con.del("zrank-order-test").unwrap();
+        let added: i64 = cmd("ZADD").arg("zrank-order-test").arg(2).arg("b").arg(1).arg("c").arg(2).arg("a").query(&mut con).unwrap();
+        assert_eq!(added, 3);
+
+        let rank: Option<i64> = con.zrank("zrank-order-test", "c").unwrap();
+        assert_eq!(rank, Some(0));
+        let rank: Option<i64> = con.zrank("zrank-order-test", "a").unwrap();
+        assert_eq!(rank, Some(1));
+        let rank: Option<i64> = con.zrank("zrank-order-test", "b").unwrap();
+        assert_eq!(rank, Some(2));
+        let rank: Option<i64> = con.zrank("zrank-order-test", "missing").unwrap();
+        assert_eq!(rank, None);
+
+        // 更新分数后排名随之变化
+        let added: i64 = con.zadd("zrank-order-test", "c", 10).unwrap();
+        assert_eq!(added, 0);
+        let rank: Option<i64> = con.zrank("zrank-order-test", "c").unwrap();
+        assert_eq!(rank, Some(2));
+        let score: f64 = con.zscore("zrank-order-test", "c").unwrap();
+        assert_eq!(score, 10.0);
+    }
+
+    #[test]
+    fn test_zcount_bounds() {
+        let mut con = setup();
+        let _: () = con.del("zcount-bounds-test").unwrap();
+        let _: () = cmd("ZADD").arg("zcount-bounds-test").arg(1).arg("a").arg(2).arg("b").arg(3).arg("c").arg(4).arg("d").query(&mut con).unwrap();
+
+        let count: i64 = con.zcount("zcount-bounds-test", 2, 3).unwrap();
+        assert_eq!(count, 2);
+        let count: i64 = con.zcount("zcount-bounds-test", "(2", "+inf").unwrap();
+        assert_eq!(count, 2);
+        let count: i64 = con.zcount("zcount-bounds-test", "-inf", "(3").unwrap();
+        assert_eq!(count, 2);
+        let count: i64 = con.zcount("zcount-bounds-test", "(2", "(3").unwrap();
+        assert_eq!(count, 0);
+        let count: i64 = con.zcount("zcount-bounds-test", 5, 1).unwrap();
+        assert_eq!(count, 0);
+
+        let result: redis::RedisResult<i64> = con.zcount("zcount-bounds-test", "abc", 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zrem_deletes_empty_key() {
+        let mut con = setup();
+        let _: () = con.del("zrem-empty-test").unwrap();
+        let _: () = con.zadd("zrem-empty-test", "a", 1).unwrap();
+        let removed: i64 = con.zrem("zrem-empty-test", "a").unwrap();
+        assert_eq!(removed, 1);
+        let exists: bool = con.exists("zrem-empty-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_large_leaderboard_rank() {
+        let mut con = setup();
+        let _: () = con.del("zrank-leaderboard-test").unwrap();
+        let total = 50000;
+        for chunk in (0..total).collect::<Vec<i64>>().chunks(1000) {
+            let mut command = cmd("ZADD");
+            command.arg("zrank-leaderboard-test");
+            for i in chunk {
+                command.arg(*i).arg(format!("player-{}", i));
+            }
+            let _: () = command.query(&mut con).unwrap();
+        }
+
+        let start = Instant::now();
+        for i in (0..total).step_by(50) {
+            let rank: i64 = con.zrank("zrank-leaderboard-test", format!("player-{}", i)).unwrap();
+            assert_eq!(rank, i);
+        }
+        let count: i64 = con.zcount("zrank-leaderboard-test", "(100", total).unwrap();
+        assert_eq!(count, total - 101);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    fn leaderboard(con: &mut Connection, key: &str) {
+        let _: () = con.del(key).unwrap();
+        let _: () = cmd("ZADD").arg(key).arg(1).arg("a").arg(2).arg("b").arg(3).arg("c").arg(4).arg("d").arg(5).arg("e").query(con).unwrap();
+    }
+
+    #[test]
+    fn test_zrange_by_rank() {
+        let mut con = setup();
+        leaderboard(&mut con, "zrange-rank-test");
+
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-rank-test").arg(0).arg(-1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["a", "b", "c", "d", "e"]);
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-rank-test").arg(1).arg(2).arg("REV").query(&mut con).unwrap();
+        assert_eq!(values, vec!["d", "c"]);
+        let values: Vec<String> = cmd("ZREVRANGE").arg("zrange-rank-test").arg(0).arg(1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["e", "d"]);
+        let values: Vec<(String, f64)> = cmd("ZRANGE").arg("zrange-rank-test").arg(-2).arg(100).arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("d".to_string(), 4.0), ("e".to_string(), 5.0)]);
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-rank-test").arg(3).arg(1).query(&mut con).unwrap();
+        assert!(values.is_empty());
+
+        let result: redis::RedisResult<Vec<String>> = cmd("ZRANGE").arg("zrange-rank-test").arg(0).arg(1).arg("LIMIT").arg(0).arg(1).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zrange_by_score_and_lex() {
+        let mut con = setup();
+        leaderboard(&mut con, "zrange-score-test");
+
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-score-test").arg("(1").arg(4).arg("BYSCORE").query(&mut con).unwrap();
+        assert_eq!(values, vec!["b", "c", "d"]);
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-score-test").arg("+inf").arg("-inf").arg("BYSCORE").arg("REV").arg("LIMIT").arg(1).arg(2).query(&mut con).unwrap();
+        assert_eq!(values, vec!["d", "c"]);
+        let values: Vec<String> = cmd("ZRANGEBYSCORE").arg("zrange-score-test").arg(2).arg("+inf").arg("LIMIT").arg(1).arg(-1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["c", "d", "e"]);
+        let values: Vec<(String, f64)> = cmd("ZREVRANGEBYSCORE").arg("zrange-score-test").arg(5).arg("(3").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("e".to_string(), 5.0), ("d".to_string(), 4.0)]);
+
+        let _: () = con.del("zrange-lex-test").unwrap();
+        let _: () = cmd("ZADD").arg("zrange-lex-test").arg(0).arg("apple").arg(0).arg("banana").arg(0).arg("cherry").arg(0).arg("date").query(&mut con).unwrap();
+        let values: Vec<String> = cmd("ZRANGE").arg("zrange-lex-test").arg("[b").arg("(d").arg("BYLEX").query(&mut con).unwrap();
+        assert_eq!(values, vec!["banana", "cherry"]);
+        let values: Vec<String> = cmd("ZREVRANGEBYLEX").arg("zrange-lex-test").arg("+").arg("-").arg("LIMIT").arg(0).arg(2).query(&mut con).unwrap();
+        assert_eq!(values, vec!["date", "cherry"]);
+        let count: i64 = cmd("ZLEXCOUNT").arg("zrange-lex-test").arg("-").arg("[cherry").query(&mut con).unwrap();
+        assert_eq!(count, 3);
+
+        let result: redis::RedisResult<Vec<String>> = cmd("ZRANGE").arg("zrange-lex-test").arg("b").arg("d").arg("BYLEX").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zrangestore() {
+        let mut con = setup();
+        leaderboard(&mut con, "zrangestore-src");
+        let stored: i64 = cmd("ZRANGESTORE").arg("zrangestore-dst").arg("zrangestore-src").arg(2).arg(4).arg("BYSCORE").query(&mut con).unwrap();
+        assert_eq!(stored, 3);
+        let values: Vec<(String, f64)> = cmd("ZRANGE").arg("zrangestore-dst").arg(0).arg(-1).arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("b".to_string(), 2.0), ("c".to_string(), 3.0), ("d".to_string(), 4.0)]);
+
+        let stored: i64 = cmd("ZRANGESTORE").arg("zrangestore-dst").arg("zrangestore-src").arg(10).arg(20).arg("BYSCORE").query(&mut con).unwrap();
+        assert_eq!(stored, 0);
+        let exists: bool = con.exists("zrangestore-dst").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_zrevrank_zincrby_zmscore() {
+        let mut con = setup();
+        leaderboard(&mut con, "zincrby-test");
+
+        let rank: Option<i64> = cmd("ZREVRANK").arg("zincrby-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(rank, Some(4));
+        let score: f64 = cmd("ZINCRBY").arg("zincrby-test").arg(10.5).arg("a").query(&mut con).unwrap();
+        assert_eq!(score, 11.5);
+        let rank: Option<i64> = cmd("ZREVRANK").arg("zincrby-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(rank, Some(0));
+        let score: f64 = cmd("ZINCRBY").arg("zincrby-test").arg(-1).arg("new").query(&mut con).unwrap();
+        assert_eq!(score, -1.0);
+
+        let scores: Vec<Option<f64>> = cmd("ZMSCORE").arg("zincrby-test").arg("a").arg("missing").arg("new").query(&mut con).unwrap();
+        assert_eq!(scores, vec![Some(11.5), None, Some(-1.0)]);
+
+        let _: () = cmd("ZADD").arg("zincrby-test").arg("+inf").arg("inf").query(&mut con).unwrap();
+        let result: redis::RedisResult<f64> = cmd("ZINCRBY").arg("zincrby-test").arg("-inf").arg("inf").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zrandmember() {
+        let mut con = setup();
+        leaderboard(&mut con, "zrandmember-test");
+
+        let member: String = cmd("ZRANDMEMBER").arg("zrandmember-test").query(&mut con).unwrap();
+        assert!(["a", "b", "c", "d", "e"].contains(&member.as_str()));
+        let members: Vec<String> = cmd("ZRANDMEMBER").arg("zrandmember-test").arg(10).query(&mut con).unwrap();
+        let mut sorted = members.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec!["a", "b", "c", "d", "e"]);
+        let members: Vec<String> = cmd("ZRANDMEMBER").arg("zrandmember-test").arg(-8).query(&mut con).unwrap();
+        assert_eq!(members.len(), 8);
+        let pairs: Vec<(String, f64)> = cmd("ZRANDMEMBER").arg("zrandmember-test").arg(2).arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.iter().all(|(member, score)| (member.as_bytes()[0] - b'a' + 1) as f64 == *score));
+    }
+
+    #[test]
+    fn test_zremrange() {
+        let mut con = setup();
+        leaderboard(&mut con, "zremrange-test");
+        let removed: i64 = cmd("ZREMRANGEBYRANK").arg("zremrange-test").arg(0).arg(1).query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        let removed: i64 = cmd("ZREMRANGEBYSCORE").arg("zremrange-test").arg("(3").arg(4).query(&mut con).unwrap();
+        assert_eq!(removed, 1);
+        let values: Vec<String> = cmd("ZRANGE").arg("zremrange-test").arg(0).arg(-1).query(&mut con).unwrap();
+        assert_eq!(values, vec!["c", "e"]);
+
+        let _: () = con.del("zremrange-lex-test").unwrap();
+        let _: () = cmd("ZADD").arg("zremrange-lex-test").arg(0).arg("a").arg(0).arg("b").arg(0).arg("c").query(&mut con).unwrap();
+        let removed: i64 = cmd("ZREMRANGEBYLEX").arg("zremrange-lex-test").arg("-").arg("+").query(&mut con).unwrap();
+        assert_eq!(removed, 3);
+        let exists: bool = con.exists("zremrange-lex-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_zpopmin_zpopmax() {
+        let mut con = setup();
+        leaderboard(&mut con, "zpop-test");
+        let values: Vec<(String, f64)> = cmd("ZPOPMIN").arg("zpop-test").query(&mut con).unwrap();
+        assert_eq!(values, vec![("a".to_string(), 1.0)]);
+        let values: Vec<(String, f64)> = cmd("ZPOPMAX").arg("zpop-test").arg(2).query(&mut con).unwrap();
+        assert_eq!(values, vec![("e".to_string(), 5.0), ("d".to_string(), 4.0)]);
+        let values: Vec<(String, f64)> = cmd("ZPOPMIN").arg("zpop-test").arg(10).query(&mut con).unwrap();
+        assert_eq!(values, vec![("b".to_string(), 2.0), ("c".to_string(), 3.0)]);
+        let exists: bool = con.exists("zpop-test").unwrap();
+        assert!(!exists);
+        let values: Vec<(String, f64)> = cmd("ZPOPMIN").arg("zpop-test").query(&mut con).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_bzpopmin_wakes_waiting_client() {
+        let mut con = setup();
+        let _: () = con.del(&["bzpop-a", "bzpop-b"]).unwrap();
+
+        let handle = thread::spawn(|| {
+            let mut con = setup();
+            let value: (String, String, f64) = cmd("BZPOPMIN").arg("bzpop-a").arg("bzpop-b").arg(5).query(&mut con).unwrap();
+            value
+        });
+        thread::sleep(Duration::from_millis(100));
+        let _: () = cmd("ZADD").arg("bzpop-b").arg(2).arg("high").arg(1).arg("low").query(&mut con).unwrap();
+        assert_eq!(handle.join().unwrap(), ("bzpop-b".to_string(), "low".to_string(), 1.0));
+
+        let value: (String, String, f64) = cmd("BZPOPMAX").arg("bzpop-a").arg("bzpop-b").arg(1).query(&mut con).unwrap();
+        assert_eq!(value, ("bzpop-b".to_string(), "high".to_string(), 2.0));
+        let value: Option<(String, String, f64)> = cmd("BZPOPMAX").arg("bzpop-a").arg(0.1).query(&mut con).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_bzpop_propagates_effective_commands() {
+        let mut db = Db::new(DatabaseSnapshot::default());
+        let mut set = SortedSet::new();
+        set.insert(b"low".to_vec(), 1.0);
+        set.insert(b"high".to_vec(), 2.0);
+        db.insert(b"bzpop".to_vec(), Structure::SortedSet(set));
+
+        for (name, expected) in [("BZPOPMAX", "ZPOPMAX"), ("BZPOPMIN", "ZPOPMIN")] {
+            let frame = Frame::Array([name, "bzpop", "0"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+            let command = Command::parse_from_frame(frame).unwrap();
+            assert!(command.propagates_effects());
+            let (sender, mut receiver) = oneshot::channel();
+            BlockingQueue::block(&mut db, sender, BlockingCommand::from_command(command).ok().unwrap()).unwrap();
+            let reply = receiver.try_recv().unwrap();
+            assert_eq!(reply.propagation.len(), 1);
+            assert_eq!(reply.propagation[0].get_args(), vec![expected, "bzpop"]);
+        }
+
+        // 超时不传播任何命令
+        let frame = Frame::Array(["BZPOPMIN", "bzpop", "0.01"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = BlockingCommand::from_command(Command::parse_from_frame(frame).unwrap()).ok().unwrap();
+        let (sender, mut receiver) = oneshot::channel();
+        BlockingQueue::block(&mut db, sender, command).unwrap();
+        db.blocking.expire(tokio::time::Instant::now() + Duration::from_secs(1));
+        let reply = receiver.try_recv().unwrap();
+        assert!(matches!(reply.frame, Frame::NullArray));
+        assert!(reply.propagation.is_empty());
+    }
+
+    #[test]
+    fn test_zunion_weights_and_aggregate() {
+        let mut con = setup();
+        let _: () = con.del(&["zunion-a", "zunion-b", "zunion-set"]).unwrap();
+        let _: () = cmd("ZADD").arg("zunion-a").arg(1).arg("x").arg(2).arg("y").query(&mut con).unwrap();
+        let _: () = cmd("ZADD").arg("zunion-b").arg(10).arg("y").arg(20).arg("z").query(&mut con).unwrap();
+        let _: () = con.sadd("zunion-set", &["x", "w"]).unwrap();
+
+        let values: Vec<(String, f64)> = cmd("ZUNION").arg(2).arg("zunion-a").arg("zunion-b").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("x".to_string(), 1.0), ("y".to_string(), 12.0), ("z".to_string(), 20.0)]);
+        let values: Vec<(String, f64)> = cmd("ZUNION").arg(2).arg("zunion-a").arg("zunion-b").arg("WEIGHTS").arg(2).arg(0.5).arg("AGGREGATE").arg("MAX").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("x".to_string(), 2.0), ("y".to_string(), 5.0), ("z".to_string(), 10.0)]);
+
+        // 普通集合的成员分数视为 1
+        let values: Vec<(String, f64)> = cmd("ZUNION").arg(2).arg("zunion-a").arg("zunion-set").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("w".to_string(), 1.0), ("x".to_string(), 2.0), ("y".to_string(), 2.0)]);
+
+        let result: redis::RedisResult<Vec<String>> = cmd("ZUNION").arg(0).arg("zunion-a").query(&mut con);
+        assert!(result.is_err());
+        let result: redis::RedisResult<Vec<String>> = cmd("ZUNION").arg(2).arg("zunion-a").arg("zunion-b").arg("AGGREGATE").arg("AVG").query(&mut con);
+        assert!(result.is_err());
+        let _: () = con.set("zunion-string", "v").unwrap();
+        let result: redis::RedisResult<Vec<String>> = cmd("ZUNION").arg(2).arg("zunion-a").arg("zunion-string").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zinter_and_zdiff() {
+        let mut con = setup();
+        let _: () = con.del(&["zinter-a", "zinter-b", "zinter-missing"]).unwrap();
+        let _: () = cmd("ZADD").arg("zinter-a").arg(1).arg("x").arg(2).arg("y").arg(3).arg("z").query(&mut con).unwrap();
+        let _: () = cmd("ZADD").arg("zinter-b").arg(5).arg("y").arg(7).arg("z").query(&mut con).unwrap();
+
+        let values: Vec<(String, f64)> = cmd("ZINTER").arg(2).arg("zinter-a").arg("zinter-b").arg("AGGREGATE").arg("MIN").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("y".to_string(), 2.0), ("z".to_string(), 3.0)]);
+        let values: Vec<String> = cmd("ZINTER").arg(2).arg("zinter-a").arg("zinter-missing").query(&mut con).unwrap();
+        assert!(values.is_empty());
+
+        let values: Vec<(String, f64)> = cmd("ZDIFF").arg(2).arg("zinter-a").arg("zinter-b").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("x".to_string(), 1.0)]);
+        let result: redis::RedisResult<Vec<String>> = cmd("ZDIFF").arg(2).arg("zinter-a").arg("zinter-b").arg("WEIGHTS").arg(1).arg(2).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zunionstore_zinterstore_zdiffstore() {
+        let mut con = setup();
+        let _: () = con.del(&["zstore-a", "zstore-b", "zstore-dst"]).unwrap();
+        let _: () = cmd("ZADD").arg("zstore-a").arg(1).arg("x").arg(2).arg("y").query(&mut con).unwrap();
+        let _: () = cmd("ZADD").arg("zstore-b").arg(3).arg("y").arg(4).arg("z").query(&mut con).unwrap();
+
+        let stored: i64 = cmd("ZUNIONSTORE").arg("zstore-dst").arg(2).arg("zstore-a").arg("zstore-b").arg("WEIGHTS").arg(1).arg(2).query(&mut con).unwrap();
+        assert_eq!(stored, 3);
+        let values: Vec<(String, f64)> = cmd("ZRANGE").arg("zstore-dst").arg(0).arg(-1).arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(values, vec![("x".to_string(), 1.0), ("y".to_string(), 8.0), ("z".to_string(), 8.0)]);
+
+        let stored: i64 = cmd("ZINTERSTORE").arg("zstore-dst").arg(2).arg("zstore-a").arg("zstore-b").query(&mut con).unwrap();
+        assert_eq!(stored, 1);
+        let score: f64 = con.zscore("zstore-dst", "y").unwrap();
+        assert_eq!(score, 5.0);
+
+        let stored: i64 = cmd("ZDIFFSTORE").arg("zstore-dst").arg(2).arg("zstore-a").arg("zstore-a").query(&mut con).unwrap();
+        assert_eq!(stored, 0);
+        let exists: bool = con.exists("zstore-dst").unwrap();
+        assert!(!exists);
+
+        let result: redis::RedisResult<i64> = cmd("ZUNIONSTORE").arg("zstore-dst").arg(1).arg("zstore-a").arg("WITHSCORES").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_zadd_nx_xx_ch() {
+        let mut con = setup();
+        let _: () = con.del("zadd-flags-test").unwrap();
+
+        let added: i64 = cmd("ZADD").arg("zadd-flags-test").arg("XX").arg(1).arg("a").query(&mut con).unwrap();
+        assert_eq!(added, 0);
+        let exists: bool = con.exists("zadd-flags-test").unwrap();
+        assert!(!exists);
+
+        let added: i64 = cmd("ZADD").arg("zadd-flags-test").arg("NX").arg(1).arg("a").arg(2).arg("b").query(&mut con).unwrap();
+        assert_eq!(added, 2);
+        let added: i64 = cmd("ZADD").arg("zadd-flags-test").arg("nx").arg(10).arg("a").arg(3).arg("c").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let score: f64 = con.zscore("zadd-flags-test", "a").unwrap();
+        assert_eq!(score, 1.0);
+
+        let changed: i64 = cmd("ZADD").arg("zadd-flags-test").arg("XX").arg("CH").arg(5).arg("a").arg(2).arg("b").arg(9).arg("d").query(&mut con).unwrap();
+        assert_eq!(changed, 1);
+        let exists: Option<f64> = con.zscore("zadd-flags-test", "d").unwrap();
+        assert_eq!(exists, None);
+        let changed: i64 = cmd("ZADD").arg("zadd-flags-test").arg("CH").arg(6).arg("a").arg(4).arg("e").query(&mut con).unwrap();
+        assert_eq!(changed, 2);
+    }
+
+    #[test]
+    fn test_zadd_gt_lt_incr() {
+        let mut con = setup();
+        let _: () = con.del("zadd-gt-test").unwrap();
+        let _: () = cmd("ZADD").arg("zadd-gt-test").arg(10).arg("player").query(&mut con).unwrap();
+
+        let changed: i64 = cmd("ZADD").arg("zadd-gt-test").arg("GT").arg("CH").arg(5).arg("player").query(&mut con).unwrap();
+        assert_eq!(changed, 0);
+        let changed: i64 = cmd("ZADD").arg("zadd-gt-test").arg("GT").arg("CH").arg(15).arg("player").arg(1).arg("newcomer").query(&mut con).unwrap();
+        assert_eq!(changed, 2);
+        let changed: i64 = cmd("ZADD").arg("zadd-gt-test").arg("LT").arg("CH").arg(20).arg("player").query(&mut con).unwrap();
+        assert_eq!(changed, 0);
+        let score: f64 = con.zscore("zadd-gt-test", "player").unwrap();
+        assert_eq!(score, 15.0);
+
+        let score: f64 = cmd("ZADD").arg("zadd-gt-test").arg("INCR").arg(2.5).arg("player").query(&mut con).unwrap();
+        assert_eq!(score, 17.5);
+        let score: Option<f64> = cmd("ZADD").arg("zadd-gt-test").arg("GT").arg("INCR").arg(-1).arg("player").query(&mut con).unwrap();
+        assert_eq!(score, None);
+        let score: Option<f64> = cmd("ZADD").arg("zadd-gt-test").arg("NX").arg("INCR").arg(1).arg("player").query(&mut con).unwrap();
+        assert_eq!(score, None);
+        let score: Option<f64> = cmd("ZADD").arg("zadd-gt-test").arg("XX").arg("INCR").arg(1).arg("missing").query(&mut con).unwrap();
+        assert_eq!(score, None);
+        let score: f64 = cmd("ZADD").arg("zadd-gt-test").arg("INCR").arg(3).arg("fresh").query(&mut con).unwrap();
+        assert_eq!(score, 3.0);
+    }
+
+    #[test]
+    fn test_zadd_incompatible_flags() {
+        let mut con = setup();
+        let _: () = con.del("zadd-error-test").unwrap();
+        let cases: Vec<(Vec<&str>, &str)> = vec![
+            (vec!["NX", "XX", "1", "a"], "XX and NX options at the same time are not compatible"),
+            (vec!["GT", "LT", "1", "a"], "GT, LT, and/or NX options at the same time are not compatible"),
+            (vec!["NX", "GT", "1", "a"], "GT, LT, and/or NX options at the same time are not compatible"),
+            (vec!["INCR", "1", "a", "2", "b"], "INCR option supports a single increment-element pair"),
+            (vec!["NX", "1"], "syntax error"),
+            (vec!["abc", "a"], "value is not a valid float"),
+        ];
+        for (args, message) in cases {
+            let result: redis::RedisResult<i64> = cmd("ZADD").arg("zadd-error-test").arg(&args).query(&mut con);
+            let error = result.unwrap_err();
+            assert!(error.to_string().contains(message), "{:?}: {}", args, error);
+        }
+        let exists: bool = con.exists("zadd-error-test").unwrap();
+        assert!(!exists);
+    }
+
+    #[test]
+    fn test_sorted_set_matches_naive_order() {
+        let mut set = SortedSet::new();
+        let mut naive = BTreeMap::new();
+        for i in 0..2000u64 {
+            let member = format!("m{}", i.wrapping_mul(7919) % 500).into_bytes();
+            if i % 5 == 0 {
+                assert_eq!(set.remove(&member), naive.remove(&member));
+            } else {
+                let score = (i.wrapping_mul(104729) % 97) as f64;
+                assert_eq!(set.insert(member.clone(), score), naive.insert(member, score));
+            }
+        }
+
+        let mut expected = naive.iter().map(|(member, score)| (member.clone(), *score)).collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then_with(|| a.0.cmp(&b.0)));
+        let actual = set.iter().map(|(member, score)| (member.to_vec(), score)).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        for (rank, (member, _)) in expected.iter().enumerate() {
+            assert_eq!(set.rank(member), Some(rank));
+            assert_eq!(set.iter_from(rank).next().map(|(member, _)| member.to_vec()), Some(member.clone()));
+        }
+        let reversed = set.iter_rev().map(|(member, _)| member.to_vec()).collect::<Vec<_>>();
+        assert_eq!(reversed, expected.iter().rev().map(|(member, _)| member.clone()).collect::<Vec<_>>());
+
+        let count = expected.iter().filter(|(_, score)| *score > 10.0 && *score <= 50.0).count();
+        assert_eq!(set.count(ScoreBound::Exclusive(10.0), ScoreBound::Inclusive(50.0)), count);
+    }
+
+    #[test]
+    fn test_sorted_set_decodes_legacy_encoding() {
+        let legacy = BTreeMap::from([(b"a".to_vec(), 3.0), (b"b".to_vec(), 1.0), (b"c".to_vec(), 2.0)]);
+        let bytes = encode_to_vec(&legacy, config::standard()).unwrap();
+        let (set, _): (SortedSet, usize) = decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.rank(b"a"), Some(2));
+        assert_eq!(set.score(b"c"), Some(2.0));
+
+        let bytes = encode_to_vec(&set, config::standard()).unwrap();
+        let (decoded, _): (BTreeMap<Vec<u8>, f64>, usize) = decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(decoded, legacy);
+    }
+}
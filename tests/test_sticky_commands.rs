@@ -8,7 +8,7 @@ mod tests {
         // 模拟redis-rust客户端发送的粘连命令
         // CLIENT SETINFO LIB-NAME redis-rs
         // CLIENT SETINFO LIB-VER 1.0.0-rc.4
-        let bytes = b"*3\r\n$6\r\nCLIENT\r\n$7\r\nSETINFO\r\n$8\r\nLIB-NAME\r\n*3\r\n$6\r\nCLIENT\r\n$7\r\nSETINFO\r\n$8\r\nLIB-VER\r\n";
+        let bytes = b"*3\r\n$6\r\nCLIENT\r\n$7\r\nSETINFO\r\n$8\r\nLIB-NAME\r\n*3\r\n$6\r\nCLIENT\r\n$7\r\nSETINFO\r\n$7\r\nLIB-VER\r\n";
         
         let frames = Frame::parse_multiple_frames(bytes)?;
         
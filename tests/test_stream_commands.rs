@@ -0,0 +1,291 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{collections::HashMap, thread, time::{Duration, Instant}};
+
+    use redis::{cmd, Client, Commands, Connection, RedisResult};
+    use rudis_server::{command::Command, frame::Frame, persistence::rdb_file::RdbFile, store::{db::{DatabaseSnapshot, Structure}, stream::{Stream, StreamId}}};
+
+    type Entry = (String, Vec<String>);
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn xadd(con: &mut Connection, key: &str, id: &str, fields: &[(&str, &str)]) -> RedisResult<String> {
+        let mut command = cmd("XADD");
+        command.arg(key).arg(id);
+        for (field, value) in fields {
+            command.arg(*field).arg(*value);
+        }
+        command.query(con)
+    }
+
+    fn ids(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_xadd_ids() {
+        let mut con = setup();
+        let _: () = con.del("stream-id-test").unwrap();
+
+        let id = xadd(&mut con, "stream-id-test", "1-1", &[("a", "1")]).unwrap();
+        assert_eq!(id, "1-1");
+        // 省略序号时序号为 0，ms-* 自动生成序号
+        assert_eq!(xadd(&mut con, "stream-id-test", "5", &[("a", "2")]).unwrap(), "5-0");
+        assert_eq!(xadd(&mut con, "stream-id-test", "5-*", &[("a", "3")]).unwrap(), "5-1");
+        assert_eq!(xadd(&mut con, "stream-id-test", "7-*", &[("a", "4")]).unwrap(), "7-0");
+
+        let error = xadd(&mut con, "stream-id-test", "7-0", &[("a", "5")]).unwrap_err();
+        assert!(error.to_string().contains("equal or smaller"));
+        let error = xadd(&mut con, "stream-id-test", "6-*", &[("a", "5")]).unwrap_err();
+        assert!(error.to_string().contains("equal or smaller"));
+        let error = xadd(&mut con, "stream-id-missing", "0-0", &[("a", "5")]).unwrap_err();
+        assert!(error.to_string().contains("greater than 0-0"));
+        assert!(xadd(&mut con, "stream-id-test", "abc", &[("a", "5")]).is_err());
+        let result: RedisResult<String> = cmd("XADD").arg("stream-id-test").arg("*").arg("field").query(&mut con);
+        assert!(result.is_err());
+        let exists: bool = con.exists("stream-id-missing").unwrap();
+        assert!(!exists);
+
+        // 自动生成的 ID 使用当前时间并保持递增
+        let auto = xadd(&mut con, "stream-id-test", "*", &[("a", "6")]).unwrap();
+        let auto = StreamId::parse(&auto, 0).unwrap();
+        assert!(auto > StreamId::new(7, 0));
+        let next = StreamId::parse(&xadd(&mut con, "stream-id-test", "*", &[("a", "7")]).unwrap(), 0).unwrap();
+        assert!(next > auto);
+
+        // ID 位于未来时自动生成的 ID 递增序号
+        let _: () = con.del("stream-future-test").unwrap();
+        xadd(&mut con, "stream-future-test", "99999999999999-5", &[("a", "1")]).unwrap();
+        assert_eq!(xadd(&mut con, "stream-future-test", "*", &[("a", "2")]).unwrap(), "99999999999999-6");
+
+        let len: i64 = cmd("XLEN").arg("stream-id-test").query(&mut con).unwrap();
+        assert_eq!(len, 6);
+        let kind: String = cmd("TYPE").arg("stream-id-test").query(&mut con).unwrap();
+        assert_eq!(kind, "stream");
+
+        // NOMKSTREAM 不创建流
+        let result: Option<String> = cmd("XADD").arg("stream-id-nomk").arg("NOMKSTREAM").arg("*").arg("a").arg("1").query(&mut con).unwrap();
+        assert_eq!(result, None);
+        let exists: bool = con.exists("stream-id-nomk").unwrap();
+        assert!(!exists);
+
+        let _: () = con.set("stream-id-string", "v").unwrap();
+        assert!(xadd(&mut con, "stream-id-string", "*", &[("a", "1")]).is_err());
+        let result: RedisResult<i64> = cmd("XLEN").arg("stream-id-string").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xrange_xrevrange() {
+        let mut con = setup();
+        let _: () = con.del("stream-range-test").unwrap();
+        for (ms, seq) in [(1, 0), (1, 1), (2, 0), (3, 0), (3, 5)] {
+            xadd(&mut con, "stream-range-test", &format!("{}-{}", ms, seq), &[("n", &format!("{}", ms)), ("s", &format!("{}", seq))]).unwrap();
+        }
+
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("-").arg("+").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["1-0", "1-1", "2-0", "3-0", "3-5"]);
+        assert_eq!(entries[4].1, vec!["n", "3", "s", "5"]);
+
+        // 省略序号的起点从 0 开始，终点到最大序号为止
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("1").arg("1").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["1-0", "1-1"]);
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("(1-1").arg("(3-5").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["2-0", "3-0"]);
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("-").arg("+").arg("COUNT").arg(2).query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["1-0", "1-1"]);
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("3").arg("1").query(&mut con).unwrap();
+        assert!(entries.is_empty());
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-test").arg("-").arg("+").arg("COUNT").arg(0).query(&mut con).unwrap();
+        assert!(entries.is_empty());
+
+        let entries: Vec<Entry> = cmd("XREVRANGE").arg("stream-range-test").arg("+").arg("-").arg("COUNT").arg(3).query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["3-5", "3-0", "2-0"]);
+        let entries: Vec<Entry> = cmd("XREVRANGE").arg("stream-range-test").arg("(3-5").arg("1-1").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["3-0", "2-0", "1-1"]);
+
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-range-missing").arg("-").arg("+").query(&mut con).unwrap();
+        assert!(entries.is_empty());
+        let result: RedisResult<Vec<Entry>> = cmd("XRANGE").arg("stream-range-test").arg("x").arg("+").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<Entry>> = cmd("XRANGE").arg("stream-range-test").arg("-").arg("+").arg("LIMIT").arg(1).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xdel_xtrim() {
+        let mut con = setup();
+        let _: () = con.del("stream-trim-test").unwrap();
+        for i in 1..=10 {
+            xadd(&mut con, "stream-trim-test", &format!("{}-0", i), &[("i", &i.to_string())]).unwrap();
+        }
+
+        let removed: i64 = cmd("XDEL").arg("stream-trim-test").arg("2-0").arg("3").arg("42-0").query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        let removed: i64 = cmd("XTRIM").arg("stream-trim-test").arg("MAXLEN").arg(6).query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-trim-test").arg("-").arg("+").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["5-0", "6-0", "7-0", "8-0", "9-0", "10-0"]);
+
+        let removed: i64 = cmd("XTRIM").arg("stream-trim-test").arg("MINID").arg("=").arg("7").query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        // 近似裁剪受 LIMIT 限制
+        let removed: i64 = cmd("XTRIM").arg("stream-trim-test").arg("MAXLEN").arg("~").arg(0).arg("LIMIT").arg(1).query(&mut con).unwrap();
+        assert_eq!(removed, 1);
+        let len: i64 = cmd("XLEN").arg("stream-trim-test").query(&mut con).unwrap();
+        assert_eq!(len, 3);
+
+        // XADD 同时裁剪
+        let _: String = cmd("XADD").arg("stream-trim-test").arg("MAXLEN").arg("=").arg(2).arg("11-0").arg("i").arg("11").query(&mut con).unwrap();
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-trim-test").arg("-").arg("+").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["10-0", "11-0"]);
+        let _: String = cmd("XADD").arg("stream-trim-test").arg("MINID").arg("~").arg("11").arg("LIMIT").arg(10).arg("12-0").arg("i").arg("12").query(&mut con).unwrap();
+        let entries: Vec<Entry> = cmd("XRANGE").arg("stream-trim-test").arg("-").arg("+").query(&mut con).unwrap();
+        assert_eq!(ids(&entries), vec!["11-0", "12-0"]);
+
+        // 删除所有消息后流仍然存在，且新 ID 仍需大于最后的 ID
+        let removed: i64 = cmd("XTRIM").arg("stream-trim-test").arg("MAXLEN").arg(0).query(&mut con).unwrap();
+        assert_eq!(removed, 2);
+        let exists: bool = con.exists("stream-trim-test").unwrap();
+        assert!(exists);
+        assert!(xadd(&mut con, "stream-trim-test", "12-0", &[("i", "x")]).is_err());
+
+        let result: RedisResult<i64> = cmd("XTRIM").arg("stream-trim-test").arg("MAXLEN").arg(5).arg("LIMIT").arg(1).query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("LIMIT"));
+        let result: RedisResult<i64> = cmd("XTRIM").arg("stream-trim-test").arg("MAXLEN").arg(-1).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("XTRIM").arg("stream-trim-test").arg("SIZE").arg(1).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("XDEL").arg("stream-trim-test").arg("bad").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xread() {
+        let mut con = setup();
+        let _: () = con.del(&["stream-read-a", "stream-read-b"]).unwrap();
+        for i in 1..=3 {
+            xadd(&mut con, "stream-read-a", &format!("{}-0", i), &[("a", &i.to_string())]).unwrap();
+        }
+        xadd(&mut con, "stream-read-b", "5-0", &[("b", "5")]).unwrap();
+
+        let result: Vec<(String, Vec<Entry>)> = cmd("XREAD").arg("COUNT").arg(2).arg("STREAMS").arg("stream-read-a").arg("stream-read-b").arg("0").arg("4-0").query(&mut con).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, "stream-read-a");
+        assert_eq!(ids(&result[0].1), vec!["1-0", "2-0"]);
+        assert_eq!(ids(&result[1].1), vec!["5-0"]);
+
+        // 没有新消息的流不出现在结果中
+        let result: Vec<(String, Vec<Entry>)> = cmd("XREAD").arg("STREAMS").arg("stream-read-a").arg("stream-read-b").arg("1-0").arg("5-0").query(&mut con).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(ids(&result[0].1), vec!["2-0", "3-0"]);
+        let result: Option<Vec<(String, Vec<Entry>)>> = cmd("XREAD").arg("STREAMS").arg("stream-read-a").arg("$").query(&mut con).unwrap();
+        assert_eq!(result, None);
+
+        let result: RedisResult<Vec<(String, Vec<Entry>)>> = cmd("XREAD").arg("STREAMS").arg("stream-read-a").arg("stream-read-b").arg("0").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("Unbalanced"));
+        let result: RedisResult<Vec<(String, Vec<Entry>)>> = cmd("XREAD").arg("BLOCK").arg(-1).arg("STREAMS").arg("stream-read-a").arg("0").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<(String, Vec<Entry>)>> = cmd("XREAD").arg("COUNT").arg(1).arg("stream-read-a").arg("0").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xread_block() {
+        let mut con = setup();
+        let _: () = con.del(&["stream-block-a", "stream-block-b"]).unwrap();
+        xadd(&mut con, "stream-block-a", "1-0", &[("a", "old")]).unwrap();
+
+        // 多个客户端以 $ 阻塞在多个流上，都能收到同一条新消息
+        let waiters = (0..2).map(|_| thread::spawn(|| {
+            let mut con = setup();
+            let result: Vec<(String, Vec<Entry>)> = cmd("XREAD").arg("BLOCK").arg(5000).arg("STREAMS").arg("stream-block-a").arg("stream-block-b").arg("$").arg("$").query(&mut con).unwrap();
+            result
+        })).collect::<Vec<_>>();
+        thread::sleep(Duration::from_millis(100));
+        xadd(&mut con, "stream-block-b", "7-0", &[("b", "new")]).unwrap();
+        for waiter in waiters {
+            let result = waiter.join().unwrap();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].0, "stream-block-b");
+            assert_eq!(result[0].1, vec![("7-0".to_string(), vec!["b".to_string(), "new".to_string()])]);
+        }
+
+        // 有可读消息时立即返回
+        let result: Vec<(String, Vec<Entry>)> = cmd("XREAD").arg("BLOCK").arg(0).arg("STREAMS").arg("stream-block-a").arg("0").query(&mut con).unwrap();
+        assert_eq!(ids(&result[0].1), vec!["1-0"]);
+
+        // 等待未来的 ID 不影响其他客户端
+        let future = thread::spawn(|| {
+            let mut con = setup();
+            let result: Option<Vec<(String, Vec<Entry>)>> = cmd("XREAD").arg("BLOCK").arg(300).arg("STREAMS").arg("stream-block-a").arg("100-0").query(&mut con).unwrap();
+            result
+        });
+        thread::sleep(Duration::from_millis(50));
+        let current = thread::spawn(|| {
+            let mut con = setup();
+            let result: Vec<(String, Vec<Entry>)> = cmd("XREAD").arg("BLOCK").arg(5000).arg("STREAMS").arg("stream-block-a").arg("$").query(&mut con).unwrap();
+            result
+        });
+        thread::sleep(Duration::from_millis(50));
+        xadd(&mut con, "stream-block-a", "2-0", &[("a", "next")]).unwrap();
+        assert_eq!(ids(&current.join().unwrap()[0].1), vec!["2-0"]);
+
+        let start = Instant::now();
+        assert_eq!(future.join().unwrap(), None);
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_xadd_propagates_generated_id() {
+        let frame = Frame::Array(["XADD", "events", "MAXLEN", "~", "100", "*", "type", "click"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = Command::parse_from_frame(frame.clone()).unwrap();
+        assert!(command.propagate_aof_if_needed());
+        let propagated = command.propagation_frame(frame);
+        let resolved = Command::resolve_propagation_frame(propagated, &Frame::BulkString(b"1700000000000-3".to_vec()));
+        assert_eq!(resolved.get_args(), vec!["XADD", "events", "MAXLEN", "~", "100", "LIMIT", "10000", "1700000000000-3", "type", "click"]);
+
+        let frame = Frame::Array(["XADD", "events", "NOMKSTREAM", "5-*", "a", "b"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = Command::parse_from_frame(frame.clone()).unwrap();
+        let propagated = command.propagation_frame(frame);
+        let resolved = Command::resolve_propagation_frame(propagated.clone(), &Frame::BulkString(b"5-2".to_vec()));
+        assert_eq!(resolved.get_args(), vec!["XADD", "events", "NOMKSTREAM", "5-2", "a", "b"]);
+        // 没有生成 ID 时原样传播
+        let resolved = Command::resolve_propagation_frame(propagated, &Frame::Null);
+        assert_eq!(resolved.get_args(), vec!["XADD", "events", "NOMKSTREAM", "5-*", "a", "b"]);
+    }
+
+    #[test]
+    fn test_stream_persists_in_rdb() {
+        let mut stream = Stream::new();
+        stream.append(StreamId::new(1, 0), vec![(b"a".to_vec(), b"1".to_vec())]);
+        stream.append(StreamId::new(2, 0), vec![(b"b".to_vec(), b"2".to_vec())]);
+        stream.remove(&StreamId::new(2, 0));
+
+        let snapshot = DatabaseSnapshot {
+            records: HashMap::from([(b"events".to_vec(), Structure::Stream(stream))]),
+            ..Default::default()
+        };
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        let stream = match restored.records.get(b"events".as_slice()) {
+            Some(Structure::Stream(stream)) => stream,
+            _ => panic!("stream not restored"),
+        };
+        assert_eq!(stream.len(), 1);
+        assert_eq!(stream.last_id(), StreamId::new(2, 0));
+        assert_eq!(stream.max_deleted_id(), StreamId::new(2, 0));
+        assert_eq!(stream.entries_added(), 2);
+        assert_eq!(stream.get(&StreamId::new(1, 0)), Some(&vec![(b"a".to_vec(), b"1".to_vec())]));
+    }
+}
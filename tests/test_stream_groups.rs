@@ -0,0 +1,345 @@
+#[cfg(test)]
+mod tests {
+
+    use std::{collections::HashMap, thread, time::Duration};
+
+    use redis::{cmd, Client, Commands, Connection, RedisResult, Value};
+    use rudis_server::{command::Command, frame::Frame, persistence::rdb_file::RdbFile, store::{blocking::{BlockingCommand, BlockingQueue}, db::{DatabaseSnapshot, Db, Structure}, stream::{ConsumerGroup, Stream, StreamId}}};
+    use tokio::sync::oneshot;
+
+    type Entry = (String, Vec<String>);
+    type Summary = (i64, Option<String>, Option<String>, Option<Vec<(String, String)>>);
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn reset(con: &mut Connection, key: &str, count: usize) {
+        let _: () = con.del(key).unwrap();
+        for i in 1..=count {
+            let _: String = cmd("XADD").arg(key).arg(format!("{}-0", i)).arg("n").arg(i).query(&mut *con).unwrap();
+        }
+        let _: () = cmd("XGROUP").arg("CREATE").arg(key).arg("workers").arg("0").arg("MKSTREAM").query(&mut *con).unwrap();
+    }
+
+    fn readgroup(con: &mut Connection, key: &str, consumer: &str, id: &str) -> Option<Vec<(String, Vec<Entry>)>> {
+        cmd("XREADGROUP").arg("GROUP").arg("workers").arg(consumer).arg("STREAMS").arg(key).arg(id).query(con).unwrap()
+    }
+
+    fn ids(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    fn info(value: &Value) -> HashMap<String, Value> {
+        match value {
+            Value::Array(items) => items.chunks(2).map(|pair| (redis::from_redis_value::<String>(&pair[0]).unwrap(), pair[1].clone())).collect(),
+            Value::Map(items) => items.iter().map(|(name, value)| (redis::from_redis_value::<String>(name).unwrap(), value.clone())).collect(),
+            _ => panic!("unexpected info reply"),
+        }
+    }
+
+    #[test]
+    fn test_xgroup() {
+        let mut con = setup();
+        let _: () = con.del("group-create-test").unwrap();
+
+        let result: RedisResult<()> = cmd("XGROUP").arg("CREATE").arg("group-create-test").arg("workers").arg("$").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("MKSTREAM"));
+        let _: () = cmd("XGROUP").arg("CREATE").arg("group-create-test").arg("workers").arg("$").arg("MKSTREAM").query(&mut con).unwrap();
+        let result: RedisResult<()> = cmd("XGROUP").arg("CREATE").arg("group-create-test").arg("workers").arg("0").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("BUSYGROUP"));
+
+        let created: i64 = cmd("XGROUP").arg("CREATECONSUMER").arg("group-create-test").arg("workers").arg("alice").query(&mut con).unwrap();
+        assert_eq!(created, 1);
+        let created: i64 = cmd("XGROUP").arg("CREATECONSUMER").arg("group-create-test").arg("workers").arg("alice").query(&mut con).unwrap();
+        assert_eq!(created, 0);
+        let result: RedisResult<i64> = cmd("XGROUP").arg("CREATECONSUMER").arg("group-create-test").arg("missing").arg("alice").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("NOGROUP"));
+
+        let destroyed: i64 = cmd("XGROUP").arg("DESTROY").arg("group-create-test").arg("workers").query(&mut con).unwrap();
+        assert_eq!(destroyed, 1);
+        let destroyed: i64 = cmd("XGROUP").arg("DESTROY").arg("group-create-test").arg("workers").query(&mut con).unwrap();
+        assert_eq!(destroyed, 0);
+    }
+
+    #[test]
+    fn test_xreadgroup_and_xack() {
+        let mut con = setup();
+        reset(&mut con, "group-read-test", 3);
+
+        // 新消息只投递给组内的一个消费者
+        let result: Vec<(String, Vec<Entry>)> = cmd("XREADGROUP").arg("GROUP").arg("workers").arg("alice").arg("COUNT").arg(2).arg("STREAMS").arg("group-read-test").arg(">").query(&mut con).unwrap();
+        assert_eq!(ids(&result[0].1), vec!["1-0", "2-0"]);
+        let result = readgroup(&mut con, "group-read-test", "bob", ">").unwrap();
+        assert_eq!(ids(&result[0].1), vec!["3-0"]);
+        assert_eq!(readgroup(&mut con, "group-read-test", "bob", ">"), None);
+
+        // 历史消息只包含该消费者的待确认消息
+        let result = readgroup(&mut con, "group-read-test", "alice", "0").unwrap();
+        assert_eq!(ids(&result[0].1), vec!["1-0", "2-0"]);
+
+        let acked: i64 = cmd("XACK").arg("group-read-test").arg("workers").arg("1-0").arg("3-0").arg("9-0").query(&mut con).unwrap();
+        assert_eq!(acked, 2);
+        let result = readgroup(&mut con, "group-read-test", "alice", "0").unwrap();
+        assert_eq!(ids(&result[0].1), vec!["2-0"]);
+        let result = readgroup(&mut con, "group-read-test", "bob", "0").unwrap();
+        assert!(result[0].1.is_empty());
+
+        let result: RedisResult<Vec<(String, Vec<Entry>)>> = cmd("XREADGROUP").arg("GROUP").arg("missing").arg("alice").arg("STREAMS").arg("group-read-test").arg(">").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("NOGROUP"));
+    }
+
+    #[test]
+    fn test_xreadgroup_block() {
+        let mut con = setup();
+        reset(&mut con, "group-block-test", 0);
+
+        let waiter = thread::spawn(|| {
+            let mut con = setup();
+            let result: Vec<(String, Vec<Entry>)> = cmd("XREADGROUP").arg("GROUP").arg("workers").arg("alice").arg("BLOCK").arg(5000).arg("STREAMS").arg("group-block-test").arg(">").query(&mut con).unwrap();
+            result
+        });
+        thread::sleep(Duration::from_millis(100));
+        let _: String = cmd("XADD").arg("group-block-test").arg("1-0").arg("a").arg("1").query(&mut con).unwrap();
+        assert_eq!(ids(&waiter.join().unwrap()[0].1), vec!["1-0"]);
+
+        let pending: (i64, String, String, Vec<(String, String)>) = cmd("XPENDING").arg("group-block-test").arg("workers").query(&mut con).unwrap();
+        assert_eq!(pending, (1, "1-0".to_string(), "1-0".to_string(), vec![("alice".to_string(), "1".to_string())]));
+    }
+
+    #[test]
+    fn test_xpending() {
+        let mut con = setup();
+        reset(&mut con, "group-pending-test", 3);
+        let pending: Summary = cmd("XPENDING").arg("group-pending-test").arg("workers").query(&mut con).unwrap();
+        assert_eq!(pending, (0, None, None, None));
+
+        readgroup(&mut con, "group-pending-test", "alice", ">");
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-pending-test").arg("workers").arg("-").arg("+").arg(10).query(&mut con).unwrap();
+        assert_eq!(result.iter().map(|(id, consumer, _, count)| (id.as_str(), consumer.as_str(), *count)).collect::<Vec<_>>(), vec![("1-0", "alice", 1), ("2-0", "alice", 1), ("3-0", "alice", 1)]);
+
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-pending-test").arg("workers").arg("(1-0").arg("+").arg(1).query(&mut con).unwrap();
+        assert_eq!(result[0].0, "2-0");
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-pending-test").arg("workers").arg("-").arg("+").arg(10).arg("bob").query(&mut con).unwrap();
+        assert!(result.is_empty());
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-pending-test").arg("workers").arg("IDLE").arg(60000).arg("-").arg("+").arg(10).query(&mut con).unwrap();
+        assert!(result.is_empty());
+
+        let result: RedisResult<Value> = cmd("XPENDING").arg("group-pending-test").arg("missing").query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("NOGROUP"));
+    }
+
+    #[test]
+    fn test_xclaim() {
+        let mut con = setup();
+        reset(&mut con, "group-claim-test", 2);
+        readgroup(&mut con, "group-claim-test", "alice", ">");
+
+        // 空闲时间不足时不认领
+        let result: Vec<Entry> = cmd("XCLAIM").arg("group-claim-test").arg("workers").arg("bob").arg(60000).arg("1-0").query(&mut con).unwrap();
+        assert!(result.is_empty());
+        thread::sleep(Duration::from_millis(20));
+        let result: Vec<Entry> = cmd("XCLAIM").arg("group-claim-test").arg("workers").arg("bob").arg(10).arg("1-0").arg("9-0").query(&mut con).unwrap();
+        assert_eq!(ids(&result), vec!["1-0"]);
+
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-claim-test").arg("workers").arg("-").arg("+").arg(10).query(&mut con).unwrap();
+        assert_eq!(result.iter().map(|(id, consumer, _, count)| (id.as_str(), consumer.as_str(), *count)).collect::<Vec<_>>(), vec![("1-0", "bob", 2), ("2-0", "alice", 1)]);
+
+        // JUSTID 不累加投递次数，RETRYCOUNT 直接设置
+        let result: Vec<String> = cmd("XCLAIM").arg("group-claim-test").arg("workers").arg("carol").arg(0).arg("2-0").arg("JUSTID").query(&mut con).unwrap();
+        assert_eq!(result, vec!["2-0"]);
+        let _: Vec<String> = cmd("XCLAIM").arg("group-claim-test").arg("workers").arg("carol").arg(0).arg("1-0").arg("RETRYCOUNT").arg(7).arg("IDLE").arg(5000).arg("JUSTID").query(&mut con).unwrap();
+        let result: Vec<(String, String, i64, i64)> = cmd("XPENDING").arg("group-claim-test").arg("workers").arg("-").arg("+").arg(10).query(&mut con).unwrap();
+        assert_eq!(result[0].1, "carol");
+        assert!(result[0].2 >= 5000);
+        assert_eq!(result[0].3, 7);
+        assert_eq!(result[1].3, 1);
+
+        let result: RedisResult<Vec<Entry>> = cmd("XCLAIM").arg("group-claim-test").arg("workers").arg("bob").arg(0).arg("1-0").arg("BOGUS").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xautoclaim() {
+        let mut con = setup();
+        reset(&mut con, "group-autoclaim-test", 3);
+        readgroup(&mut con, "group-autoclaim-test", "alice", ">");
+        let _: i64 = cmd("XDEL").arg("group-autoclaim-test").arg("2-0").query(&mut con).unwrap();
+
+        let (cursor, claimed, deleted): (String, Vec<Entry>, Vec<String>) = cmd("XAUTOCLAIM").arg("group-autoclaim-test").arg("workers").arg("bob").arg(0).arg("0").arg("COUNT").arg(1).query(&mut con).unwrap();
+        assert_eq!(cursor, "2-0");
+        assert_eq!(ids(&claimed), vec!["1-0"]);
+        assert!(deleted.is_empty());
+
+        // 已被删除的消息从待确认列表中移除
+        let (cursor, claimed, deleted): (String, Vec<String>, Vec<String>) = cmd("XAUTOCLAIM").arg("group-autoclaim-test").arg("workers").arg("bob").arg(0).arg(&cursor).arg("JUSTID").query(&mut con).unwrap();
+        assert_eq!(cursor, "0-0");
+        assert_eq!(claimed, vec!["3-0"]);
+        assert_eq!(deleted, vec!["2-0"]);
+
+        let pending: (i64, String, String, Vec<(String, String)>) = cmd("XPENDING").arg("group-autoclaim-test").arg("workers").query(&mut con).unwrap();
+        assert_eq!(pending, (2, "1-0".to_string(), "3-0".to_string(), vec![("bob".to_string(), "2".to_string())]));
+    }
+
+    #[test]
+    fn test_xinfo() {
+        let mut con = setup();
+        reset(&mut con, "group-info-test", 3);
+        readgroup(&mut con, "group-info-test", "alice", ">");
+        let _: i64 = cmd("XACK").arg("group-info-test").arg("workers").arg("1-0").query(&mut con).unwrap();
+
+        let value: Value = cmd("XINFO").arg("STREAM").arg("group-info-test").query(&mut con).unwrap();
+        let stream = info(&value);
+        assert_eq!(stream["length"], Value::Int(3));
+        assert_eq!(stream["groups"], Value::Int(1));
+
+        let groups: Vec<Value> = cmd("XINFO").arg("GROUPS").arg("group-info-test").query(&mut con).unwrap();
+        let group = info(&groups[0]);
+        assert_eq!(redis::from_redis_value::<String>(&group["name"]).unwrap(), "workers");
+        assert_eq!(group["pending"], Value::Int(2));
+        assert_eq!(group["entries-read"], Value::Int(3));
+        assert_eq!(group["lag"], Value::Int(0));
+
+        let consumers: Vec<Value> = cmd("XINFO").arg("CONSUMERS").arg("group-info-test").arg("workers").query(&mut con).unwrap();
+        let consumer = info(&consumers[0]);
+        assert_eq!(redis::from_redis_value::<String>(&consumer["name"]).unwrap(), "alice");
+        assert_eq!(consumer["pending"], Value::Int(2));
+
+        let value: Value = cmd("XINFO").arg("STREAM").arg("group-info-test").arg("FULL").query(&mut con).unwrap();
+        let stream = info(&value);
+        match &stream["groups"] {
+            Value::Array(groups) => assert_eq!(info(&groups[0])["pel-count"], Value::Int(2)),
+            _ => panic!("groups not returned"),
+        }
+
+        let result: RedisResult<Value> = cmd("XINFO").arg("STREAM").arg("group-info-missing").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_xclaim_propagates_claimed_ids() {
+        let frame = Frame::Array(["XCLAIM", "events", "workers", "bob", "1000", "1-0", "2-0", "IDLE", "0", "JUSTID"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = Command::parse_from_frame(frame.clone()).unwrap();
+        assert!(command.propagate_aof_if_needed());
+        let propagated = command.propagation_frame(frame);
+        let args = propagated.get_args();
+        assert_eq!(args[7], "TIME");
+        let reply = Frame::Array(vec![Frame::BulkString(b"2-0".to_vec())]);
+        let resolved = Command::resolve_propagation_frame(propagated, &reply);
+        assert_eq!(resolved.get_args()[..6], ["XCLAIM", "events", "workers", "bob", "0", "2-0"]);
+        assert_eq!(resolved.get_args()[6], "TIME");
+
+        let frame = Frame::Array(["XAUTOCLAIM", "events", "workers", "bob", "1000", "0", "JUSTID"].iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let reply = Frame::Array(vec![
+            Frame::BulkString(b"0-0".to_vec()),
+            Frame::Array(vec![Frame::BulkString(b"1-0".to_vec())]),
+            Frame::Array(vec![Frame::BulkString(b"3-0".to_vec())]),
+        ]);
+        let resolved = Command::resolve_propagation_frame(frame, &reply);
+        assert_eq!(resolved.get_args(), vec!["XCLAIM", "events", "workers", "bob", "0", "1-0", "3-0", "JUSTID"]);
+    }
+
+    fn command(args: &[&str]) -> Command {
+        Command::parse_from_frame(Frame::Array(args.iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect())).unwrap()
+    }
+
+    fn group(db: &mut Db) -> ConsumerGroup {
+        match db.get(b"events") {
+            Some(Structure::Stream(stream)) => stream.group(b"workers").unwrap().clone(),
+            _ => panic!("stream not found"),
+        }
+    }
+
+    #[test]
+    fn test_xreadgroup_propagates_delivery_state() {
+        let mut stream = Stream::new();
+        stream.append(StreamId::new(1, 0), vec![(b"a".to_vec(), b"1".to_vec())]);
+        stream.append(StreamId::new(2, 0), vec![(b"b".to_vec(), b"2".to_vec())]);
+        stream.create_group(b"workers", ConsumerGroup::new(StreamId::MIN, Some(0)));
+        let snapshot = DatabaseSnapshot {
+            records: HashMap::from([(b"events".to_vec(), Structure::Stream(stream))]),
+            ..Default::default()
+        };
+        let mut master = Db::new(snapshot.clone());
+        let mut replica = Db::new(snapshot);
+        let mut propagation = Vec::new();
+
+        // 阻塞读取立即得到新消息
+        let readgroup = command(&["XREADGROUP", "GROUP", "workers", "alice", "COUNT", "1", "BLOCK", "0", "STREAMS", "events", ">"]);
+        assert!(readgroup.propagates_effects());
+        let (sender, mut receiver) = oneshot::channel();
+        BlockingQueue::block(&mut master, sender, BlockingCommand::from_command(readgroup).ok().unwrap()).unwrap();
+        let reply = receiver.try_recv().unwrap();
+        let args = reply.propagation.iter().map(|frame| frame.get_args()).collect::<Vec<_>>();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0][..6], ["XCLAIM", "events", "workers", "alice", "0", "1-0"]);
+        assert_eq!(args[0][8..], ["RETRYCOUNT", "1", "FORCE", "JUSTID"]);
+        assert_eq!(args[1], vec!["XGROUP", "SETID", "events", "workers", "1-0", "ENTRIESREAD", "1"]);
+        propagation.extend(reply.propagation);
+
+        // 重新读取历史消息累加投递次数，NOACK 只推进最后投递 ID
+        for args in [
+            &["XREADGROUP", "GROUP", "workers", "alice", "STREAMS", "events", "0"][..],
+            &["XREADGROUP", "GROUP", "workers", "bob", "NOACK", "STREAMS", "events", ">"],
+        ] {
+            let xreadgroup = match command(args) {
+                Command::Xreadgroup(xreadgroup) => xreadgroup,
+                _ => unreachable!(),
+            };
+            let reply = xreadgroup.apply(&mut master).unwrap();
+            propagation.extend(xreadgroup.propagation_frames(&mut master, &reply));
+        }
+        assert_eq!(propagation.len(), 4);
+        assert_eq!(propagation[2].get_args()[9], "2");
+        assert_eq!(propagation[3].get_args(), vec!["XGROUP", "SETID", "events", "workers", "2-0", "ENTRIESREAD", "2"]);
+
+        for frame in propagation {
+            match Command::parse_from_frame(frame).unwrap() {
+                Command::Xclaim(xclaim) => { xclaim.apply(&mut replica).unwrap(); },
+                Command::Xgroup(xgroup) => { xgroup.apply(&mut replica).unwrap(); },
+                _ => panic!("unexpected propagated command"),
+            }
+        }
+        let (expected, actual) = (group(&mut master), group(&mut replica));
+        assert_eq!(actual.last_delivered_id, expected.last_delivered_id);
+        assert_eq!(actual.entries_read, expected.entries_read);
+        assert_eq!(actual.pending.len(), 1);
+        let (expected, actual) = (&expected.pending[&StreamId::new(1, 0)], &actual.pending[&StreamId::new(1, 0)]);
+        assert_eq!(actual.consumer, expected.consumer);
+        assert_eq!(actual.delivery_time, expected.delivery_time);
+        assert_eq!(actual.delivery_count, 2);
+    }
+
+    #[test]
+    fn test_groups_persist_in_rdb() {
+        let mut stream = Stream::new();
+        stream.append(StreamId::new(1, 0), vec![(b"a".to_vec(), b"1".to_vec())]);
+        stream.create_group(b"workers", ConsumerGroup::new(StreamId::MIN, Some(0)));
+        stream.group_mut(b"workers").unwrap().touch_consumer(b"alice", 1000);
+        stream.read_group(b"workers", b"alice", 10, false, 1000);
+
+        let snapshot = DatabaseSnapshot {
+            records: HashMap::from([(b"events".to_vec(), Structure::Stream(stream))]),
+            ..Default::default()
+        };
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        let stream = match restored.records.get(b"events".as_slice()) {
+            Some(Structure::Stream(stream)) => stream,
+            _ => panic!("stream not restored"),
+        };
+        let group = stream.group(b"workers").unwrap();
+        assert_eq!(group.last_delivered_id, StreamId::new(1, 0));
+        assert_eq!(group.entries_read, Some(1));
+        let entry = group.pending.get(&StreamId::new(1, 0)).unwrap();
+        assert_eq!((entry.consumer.as_slice(), entry.delivery_time, entry.delivery_count), (b"alice".as_slice(), 1000, 1));
+        assert!(group.consumers[b"alice".as_slice()].pending.contains(&StreamId::new(1, 0)));
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use redis::{cmd, Client, Commands, Connection};
+    use rudis_server::{command::Command, frame::Frame};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_nx_xx() {
+        let mut con = setup();
+        let _: () = con.del("set-nx-test").unwrap();
+
+        let result: Option<String> = cmd("SET").arg("set-nx-test").arg("lock").arg("nx").arg("px").arg(30000).query(&mut con).unwrap();
+        assert_eq!(result, Some("OK".to_string()));
+        let result: Option<String> = cmd("SET").arg("set-nx-test").arg("other").arg("NX").query(&mut con).unwrap();
+        assert_eq!(result, None);
+        let ttl: i64 = con.pttl("set-nx-test").unwrap();
+        assert!(ttl > 0 && ttl <= 30000);
+
+        let result: Option<String> = cmd("SET").arg("set-xx-missing").arg("v").arg("XX").query(&mut con).unwrap();
+        assert_eq!(result, None);
+        let result: Option<String> = cmd("SET").arg("set-nx-test").arg("updated").arg("XX").query(&mut con).unwrap();
+        assert_eq!(result, Some("OK".to_string()));
+        let value: String = con.get("set-nx-test").unwrap();
+        assert_eq!(value, "updated");
+
+        let result: redis::RedisResult<()> = cmd("SET").arg("set-nx-test").arg("v").arg("NX").arg("XX").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_get_and_ttl() {
+        let mut con = setup();
+        let _: () = con.del("set-get-test").unwrap();
+
+        let old: Option<String> = cmd("SET").arg("set-get-test").arg("first").arg("GET").arg("EX").arg(100).query(&mut con).unwrap();
+        assert_eq!(old, None);
+        let old: Option<String> = cmd("SET").arg("set-get-test").arg("second").arg("GET").arg("KEEPTTL").query(&mut con).unwrap();
+        assert_eq!(old, Some("first".to_string()));
+        let ttl: i64 = con.ttl("set-get-test").unwrap();
+        assert!(ttl > 0);
+
+        // 覆盖写入会清除过期时间
+        let _: () = con.set("set-get-test", "third").unwrap();
+        let ttl: i64 = con.ttl("set-get-test").unwrap();
+        assert_eq!(ttl, -1);
+
+        let at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 100;
+        let _: () = cmd("SET").arg("set-get-test").arg("v").arg("exat").arg(at).query(&mut con).unwrap();
+        let ttl: i64 = con.ttl("set-get-test").unwrap();
+        assert!(ttl > 90 && ttl <= 100);
+
+        let result: redis::RedisResult<()> = cmd("SET").arg("set-get-test").arg("v").arg("EX").arg(0).query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_setnx_setex_psetex() {
+        let mut con = setup();
+        let _: () = con.del("setnx-test").unwrap();
+
+        let added: i64 = cmd("SETNX").arg("setnx-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let added: i64 = cmd("SETNX").arg("setnx-test").arg("b").query(&mut con).unwrap();
+        assert_eq!(added, 0);
+
+        let _: () = cmd("SETEX").arg("setnx-test").arg(100).arg("c").query(&mut con).unwrap();
+        let ttl: i64 = con.ttl("setnx-test").unwrap();
+        assert!(ttl > 90);
+
+        let _: () = cmd("PSETEX").arg("setnx-test").arg(5000).arg("d").query(&mut con).unwrap();
+        let ttl: i64 = con.pttl("setnx-test").unwrap();
+        assert!(ttl > 0 && ttl <= 5000);
+        let value: String = con.get("setnx-test").unwrap();
+        assert_eq!(value, "d");
+    }
+
+    #[test]
+    fn test_getex_getdel() {
+        let mut con = setup();
+        let _: () = con.set("getex-test", "value").unwrap();
+
+        let value: String = cmd("GETEX").arg("getex-test").arg("EX").arg(100).query(&mut con).unwrap();
+        assert_eq!(value, "value");
+        let ttl: i64 = con.ttl("getex-test").unwrap();
+        assert!(ttl > 90);
+
+        let _: String = cmd("GETEX").arg("getex-test").arg("PERSIST").query(&mut con).unwrap();
+        let ttl: i64 = con.ttl("getex-test").unwrap();
+        assert_eq!(ttl, -1);
+
+        let value: Option<String> = cmd("GETDEL").arg("getex-test").query(&mut con).unwrap();
+        assert_eq!(value, Some("value".to_string()));
+        let value: Option<String> = cmd("GETDEL").arg("getex-test").query(&mut con).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_msetnx() {
+        let mut con = setup();
+        let _: () = con.del(&["msetnx-a", "msetnx-b"]).unwrap();
+
+        let result: i64 = cmd("MSETNX").arg("msetnx-a").arg("1").arg("msetnx-b").arg("2").query(&mut con).unwrap();
+        assert_eq!(result, 1);
+        let result: i64 = cmd("MSETNX").arg("msetnx-b").arg("3").arg("msetnx-c").arg("4").query(&mut con).unwrap();
+        assert_eq!(result, 0);
+        let missing: bool = con.exists("msetnx-c").unwrap();
+        assert!(!missing);
+    }
+
+    fn propagated(args: &[&str]) -> Vec<String> {
+        let frame = Frame::Array(args.iter().map(|arg| Frame::BulkString(arg.as_bytes().to_vec())).collect());
+        let command = Command::parse_from_frame(frame.clone()).unwrap();
+        assert!(command.propagate_aof_if_needed());
+        command.propagation_frame(frame).get_args()
+    }
+
+    #[test]
+    fn test_relative_ttl_propagates_as_timestamp() {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+        let expire_at = |args: &[String]| args.last().unwrap().parse::<u64>().unwrap();
+
+        let args = propagated(&["SET", "k", "v", "NX", "EX", "100"]);
+        assert_eq!(args[..5], ["SET", "k", "v", "NX", "PXAT"]);
+        assert!((now + 100_000..now + 101_000).contains(&expire_at(&args)));
+
+        let args = propagated(&["SET", "k", "v", "GET", "PX", "1500"]);
+        assert_eq!(args[..5], ["SET", "k", "v", "GET", "PXAT"]);
+        assert!((now + 1500..now + 2500).contains(&expire_at(&args)));
+
+        assert_eq!(propagated(&["SET", "k", "v", "EXAT", "2000000000"]), ["SET", "k", "v", "PXAT", "2000000000000"]);
+        assert_eq!(propagated(&["SET", "k", "v", "KEEPTTL"]), ["SET", "k", "v", "KEEPTTL"]);
+        assert_eq!(propagated(&["SET", "k", "v"]), ["SET", "k", "v"]);
+
+        let args = propagated(&["SETEX", "k", "10", "v"]);
+        assert_eq!(args[..4], ["SET", "k", "v", "PXAT"]);
+        assert!((now + 10_000..now + 11_000).contains(&expire_at(&args)));
+
+        let args = propagated(&["PSETEX", "k", "500", "v"]);
+        assert_eq!(args[..4], ["SET", "k", "v", "PXAT"]);
+        assert!((now + 500..now + 1500).contains(&expire_at(&args)));
+
+        let args = propagated(&["GETEX", "k", "EX", "20"]);
+        assert_eq!(args[..3], ["GETEX", "k", "PXAT"]);
+        assert!((now + 20_000..now + 21_000).contains(&expire_at(&args)));
+        assert_eq!(propagated(&["GETEX", "k", "PERSIST"]), ["GETEX", "k", "PERSIST"]);
+    }
+}
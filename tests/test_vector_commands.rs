@@ -0,0 +1,370 @@
+#[cfg(test)]
+mod tests {
+
+    use std::collections::{HashMap, HashSet};
+
+    use redis::{cmd, Client, Commands, Connection, RedisResult};
+    use rudis_server::{persistence::rdb_file::RdbFile, store::{db::{DatabaseSnapshot, Structure}, hnsw::Hnsw, vector::{Metric, Vector}}};
+
+    fn setup() -> Connection {
+        let client = Client::open("redis://127.0.0.1:6379/").unwrap();
+        match client.get_connection() {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Failed to get connection: {}", e);
+                panic!("Failed to get connection: {}", e);
+            }
+        }
+    }
+
+    fn vadd(con: &mut Connection, key: &str, element: &str, values: &[f32]) -> RedisResult<i64> {
+        let mut command = cmd("VADD");
+        command.arg(key).arg("VALUES").arg(values.len());
+        for value in values {
+            command.arg(*value);
+        }
+        command.arg(element).query(con)
+    }
+
+    #[test]
+    fn test_vadd_vemb_vcard_vdim_vrem() {
+        let mut con = setup();
+        let _: () = con.del("vector-basic-test").unwrap();
+
+        assert_eq!(vadd(&mut con, "vector-basic-test", "a", &[1.0, 0.0, 0.0]).unwrap(), 1);
+        assert_eq!(vadd(&mut con, "vector-basic-test", "b", &[0.0, 1.0, 0.0]).unwrap(), 1);
+        // 替换已有元素
+        assert_eq!(vadd(&mut con, "vector-basic-test", "a", &[0.5, 0.25, 2.0]).unwrap(), 0);
+
+        let kind: String = cmd("TYPE").arg("vector-basic-test").query(&mut con).unwrap();
+        assert_eq!(kind, "vector");
+        let card: i64 = cmd("VCARD").arg("vector-basic-test").query(&mut con).unwrap();
+        assert_eq!(card, 2);
+        let dim: i64 = cmd("VDIM").arg("vector-basic-test").query(&mut con).unwrap();
+        assert_eq!(dim, 3);
+        let embedding: Vec<f64> = cmd("VEMB").arg("vector-basic-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(embedding, vec![0.5, 0.25, 2.0]);
+        let embedding: Option<Vec<f64>> = cmd("VEMB").arg("vector-basic-test").arg("missing").query(&mut con).unwrap();
+        assert_eq!(embedding, None);
+
+        // FP32 格式
+        let blob = [3.0f32, 4.0, 0.0].iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<u8>>();
+        let added: i64 = cmd("VADD").arg("vector-basic-test").arg("FP32").arg(blob).arg("c").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let embedding: Vec<f64> = cmd("VEMB").arg("vector-basic-test").arg("c").query(&mut con).unwrap();
+        assert_eq!(embedding, vec![3.0, 4.0, 0.0]);
+
+        let removed: i64 = cmd("VREM").arg("vector-basic-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(removed, 1);
+        let removed: i64 = cmd("VREM").arg("vector-basic-test").arg("a").query(&mut con).unwrap();
+        assert_eq!(removed, 0);
+        let _: i64 = cmd("VREM").arg("vector-basic-test").arg("b").query(&mut con).unwrap();
+        let _: i64 = cmd("VREM").arg("vector-basic-test").arg("c").query(&mut con).unwrap();
+        // 删除最后一个元素后键不再存在
+        let exists: bool = con.exists("vector-basic-test").unwrap();
+        assert!(!exists);
+        let card: i64 = cmd("VCARD").arg("vector-basic-test").query(&mut con).unwrap();
+        assert_eq!(card, 0);
+        let result: RedisResult<i64> = cmd("VDIM").arg("vector-basic-test").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_vector_binary_elements() {
+        let mut con = setup();
+        let _: () = con.del("vector-binary-test").unwrap();
+
+        // 非 UTF-8 的元素名称不能合并为同一个元素
+        let added: i64 = cmd("VADD").arg("vector-binary-test").arg("VALUES").arg(2).arg(1).arg(0).arg(b"\xff").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let added: i64 = cmd("VADD").arg("vector-binary-test").arg("VALUES").arg(2).arg(0).arg(1).arg(b"\xfe").query(&mut con).unwrap();
+        assert_eq!(added, 1);
+        let card: i64 = cmd("VCARD").arg("vector-binary-test").query(&mut con).unwrap();
+        assert_eq!(card, 2);
+
+        let embedding: Vec<f64> = cmd("VEMB").arg("vector-binary-test").arg(b"\xfe").query(&mut con).unwrap();
+        assert_eq!(embedding, vec![0.0, 1.0]);
+        let result: Vec<Vec<u8>> = cmd("VSIM").arg("vector-binary-test").arg("ELE").arg(b"\xff").query(&mut con).unwrap();
+        assert_eq!(result, vec![b"\xff".to_vec(), b"\xfe".to_vec()]);
+
+        let removed: i64 = cmd("VREM").arg("vector-binary-test").arg(b"\xff").query(&mut con).unwrap();
+        assert_eq!(removed, 1);
+        let result: Vec<Vec<u8>> = cmd("VSIM").arg("vector-binary-test").arg("VALUES").arg(2).arg(1).arg(0).query(&mut con).unwrap();
+        assert_eq!(result, vec![b"\xfe".to_vec()]);
+    }
+
+    #[test]
+    fn test_vector_errors() {
+        let mut con = setup();
+        let _: () = con.del("vector-error-test").unwrap();
+        let _: () = con.set("vector-error-string", "value").unwrap();
+        vadd(&mut con, "vector-error-test", "a", &[1.0, 2.0]).unwrap();
+
+        // 维度不一致
+        let error = vadd(&mut con, "vector-error-test", "b", &[1.0, 2.0, 3.0]).unwrap_err();
+        assert!(error.to_string().contains("dimension mismatch"));
+        let result: RedisResult<Vec<String>> = cmd("VSIM").arg("vector-error-test").arg("VALUES").arg(3).arg(1).arg(2).arg(3).query(&mut con);
+        assert!(result.unwrap_err().to_string().contains("dimension mismatch"));
+        let card: i64 = cmd("VCARD").arg("vector-error-test").query(&mut con).unwrap();
+        assert_eq!(card, 1);
+
+        // 参数错误
+        let result: RedisResult<i64> = cmd("VADD").arg("vector-error-test").arg("VALUES").arg(2).arg(1).arg("x").arg("b").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("VADD").arg("vector-error-test").arg("VALUES").arg(3).arg(1).arg(2).arg("b").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("VADD").arg("vector-error-test").arg("FP32").arg(vec![0u8; 5]).arg("b").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("VADD").arg("vector-error-test").arg("VALUES").arg(2).arg("nan").arg(1).arg("b").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<String>> = cmd("VSIM").arg("vector-error-test").arg("ELE").arg("a").arg("METRIC").arg("hamming").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<String>> = cmd("VSIM").arg("vector-error-test").arg("ELE").arg("a").arg("COUNT").arg(0).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<String>> = cmd("VSIM").arg("vector-error-test").arg("ELE").arg("missing").query(&mut con);
+        assert!(result.is_err());
+
+        // 类型错误
+        let result = vadd(&mut con, "vector-error-string", "a", &[1.0]);
+        assert!(result.is_err());
+        let result: RedisResult<i64> = cmd("VCARD").arg("vector-error-string").query(&mut con);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_vsim_metrics() {
+        let mut con = setup();
+        let _: () = con.del("vector-sim-test").unwrap();
+        vadd(&mut con, "vector-sim-test", "x", &[1.0, 0.0]).unwrap();
+        vadd(&mut con, "vector-sim-test", "far-x", &[10.0, 1.0]).unwrap();
+        vadd(&mut con, "vector-sim-test", "diagonal", &[1.0, 1.0]).unwrap();
+        vadd(&mut con, "vector-sim-test", "y", &[0.0, 1.0]).unwrap();
+        vadd(&mut con, "vector-sim-test", "minus-x", &[-1.0, 0.0]).unwrap();
+
+        // 余弦相似度只关心方向
+        let result: Vec<String> = cmd("VSIM").arg("vector-sim-test").arg("VALUES").arg(2).arg(2).arg(0).query(&mut con).unwrap();
+        assert_eq!(result, vec!["x", "far-x", "diagonal", "y", "minus-x"]);
+        let result: Vec<(String, f64)> = cmd("VSIM").arg("vector-sim-test").arg("ELE").arg("x").arg("WITHSCORES").arg("COUNT").arg(3).query(&mut con).unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0], ("x".to_string(), 1.0));
+        assert_eq!(result[1].0, "far-x");
+        assert!((result[2].1 - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+
+        // 欧氏距离按升序返回
+        let result: Vec<(String, f64)> = cmd("VSIM").arg("vector-sim-test").arg("VALUES").arg(2).arg(2).arg(0).arg("METRIC").arg("L2").arg("WITHSCORES").arg("COUNT").arg(2).query(&mut con).unwrap();
+        assert_eq!(result, vec![("x".to_string(), 1.0), ("diagonal".to_string(), 2f64.sqrt() as f32 as f64)]);
+
+        // 内积偏向模长较大的向量
+        let result: Vec<(String, f64)> = cmd("VSIM").arg("vector-sim-test").arg("ELE").arg("x").arg("METRIC").arg("ip").arg("WITHSCORES").query(&mut con).unwrap();
+        assert_eq!(result[0], ("far-x".to_string(), 10.0));
+        assert_eq!(result.last().unwrap(), &("minus-x".to_string(), -1.0));
+
+        let result: Vec<String> = cmd("VSIM").arg("vector-sim-missing").arg("VALUES").arg(2).arg(1).arg(0).query(&mut con).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_vsim_matches_brute_force() {
+        let mut con = setup();
+        let _: () = con.del("vector-brute-test").unwrap();
+        let vectors = (0..200).map(|i| {
+            let i = i as f32;
+            vec![(i * 0.37).sin(), (i * 1.13).cos(), (i * 0.71).sin() * 2.0, i / 200.0]
+        }).collect::<Vec<_>>();
+        for (i, vector) in vectors.iter().enumerate() {
+            vadd(&mut con, "vector-brute-test", &format!("e{}", i), vector).unwrap();
+        }
+
+        let query = [0.3f32, -0.2, 1.0, 0.5];
+        let query_norm = query.iter().map(|v| v * v).sum::<f32>().sqrt();
+        let mut expected = vectors.iter().enumerate().map(|(i, vector)| {
+            let dot = vector.iter().zip(&query).map(|(a, b)| a * b).sum::<f32>();
+            let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+            (dot / (norm * query_norm), format!("e{}", i))
+        }).collect::<Vec<_>>();
+        expected.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let expected = expected.into_iter().take(15).map(|(_, element)| element).collect::<Vec<_>>();
+
+        let mut command = cmd("VSIM");
+        command.arg("vector-brute-test").arg("VALUES").arg(4);
+        for value in query {
+            command.arg(value);
+        }
+        let result: Vec<String> = command.arg("COUNT").arg(15).query(&mut con).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    /**
+     * 生成可重复的伪随机向量
+     */
+    fn random_vectors(count: usize, dimension: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 40) as f32 / (1u64 << 24) as f32) * 2.0 - 1.0
+        };
+        (0..count).map(|_| (0..dimension).map(|_| next()).collect()).collect()
+    }
+
+    fn recall(expected: &[(&[u8], f32)], actual: &[(&[u8], f32)]) -> f64 {
+        let expected = expected.iter().map(|(element, _)| *element).collect::<HashSet<_>>();
+        actual.iter().filter(|(element, _)| expected.contains(element)).count() as f64 / expected.len() as f64
+    }
+
+    #[test]
+    fn test_hnsw_recall_with_inserts_and_deletes() {
+        for metric in [Metric::Cosine, Metric::L2, Metric::Ip] {
+            let mut collection = Vector::new(16);
+            for (i, vector) in random_vectors(1000, 16, 7).into_iter().enumerate() {
+                collection.insert(format!("e{}", i).into_bytes(), vector);
+            }
+            // 先为一半元素建索引，其余元素增量插入
+            collection.create_index(Hnsw::new(8, 100, 64, metric));
+            for (i, vector) in random_vectors(1000, 16, 11).into_iter().enumerate() {
+                collection.insert(format!("f{}", i).into_bytes(), vector);
+            }
+            assert_eq!(collection.index().unwrap().len(), 2000);
+
+            let queries = random_vectors(50, 16, 13);
+            let mut total = 0.0;
+            for query in &queries {
+                let expected = collection.search(query, metric, 10);
+                let actual = collection.approximate_search(query, metric, 10, 0).unwrap();
+                assert_eq!(actual.len(), 10);
+                total += recall(&expected, &actual);
+            }
+            assert!(total / queries.len() as f64 >= 0.9, "recall {} too low for metric {}", total / queries.len() as f64, metric.name());
+
+            // 删除一半元素后，结果不包含已删除的元素
+            for i in 0..1000 {
+                if i % 2 == 0 {
+                    assert!(collection.remove(format!("e{}", i).as_bytes()));
+                    assert!(collection.remove(format!("f{}", i).as_bytes()));
+                }
+            }
+            assert_eq!(collection.index().unwrap().len(), 1000);
+            let mut total = 0.0;
+            for query in &queries {
+                let expected = collection.search(query, metric, 10);
+                let actual = collection.approximate_search(query, metric, 10, 0).unwrap();
+                assert!(actual.iter().all(|(element, _)| collection.get(element).is_some()));
+                total += recall(&expected, &actual);
+            }
+            assert!(total / queries.len() as f64 >= 0.85, "recall {} too low after deletes for metric {}", total / queries.len() as f64, metric.name());
+
+            // 度量方式与索引不一致时不使用索引
+            let other = if metric == Metric::L2 { Metric::Ip } else { Metric::L2 };
+            assert!(collection.approximate_search(&queries[0], other, 10, 0).is_none());
+        }
+    }
+
+    #[test]
+    fn test_hnsw_remove_all_and_reinsert() {
+        let mut collection = Vector::new(4);
+        collection.create_index(Hnsw::new(4, 32, 32, Metric::L2));
+        let vectors = random_vectors(200, 4, 21);
+        for (i, vector) in vectors.iter().enumerate() {
+            collection.insert(format!("e{}", i).into_bytes(), vector.clone());
+        }
+        for i in 0..200 {
+            collection.remove(format!("e{}", i).as_bytes());
+        }
+        assert!(collection.index().unwrap().is_empty());
+        assert_eq!(collection.index().unwrap().max_level(), None);
+        assert!(collection.approximate_search(&vectors[0], Metric::L2, 5, 0).unwrap().is_empty());
+
+        // 复用空出的节点位置
+        for (i, vector) in vectors.iter().enumerate().take(50) {
+            collection.insert(format!("e{}", i).into_bytes(), vector.clone());
+        }
+        let result = collection.approximate_search(&vectors[3], Metric::L2, 1, 0).unwrap();
+        assert_eq!(result[0], (b"e3".as_slice(), 0.0));
+    }
+
+    #[test]
+    fn test_hnsw_index_persists_in_rdb() {
+        let mut collection = Vector::new(8);
+        for (i, vector) in random_vectors(300, 8, 5).into_iter().enumerate() {
+            collection.insert(format!("e{}", i).into_bytes(), vector);
+        }
+        collection.create_index(Hnsw::new(12, 80, 40, Metric::Cosine));
+        let query = random_vectors(1, 8, 9).remove(0);
+        let before = collection.approximate_search(&query, Metric::Cosine, 5, 0).unwrap().into_iter()
+            .map(|(element, score)| (element.to_vec(), score)).collect::<Vec<_>>();
+
+        let snapshot = DatabaseSnapshot {
+            records: HashMap::from([(b"vectors".to_vec(), Structure::VectorCollection(collection))]),
+            ..Default::default()
+        };
+        let bytes = RdbFile::from_snapshots(vec![snapshot]).serialize().unwrap();
+        let restored = RdbFile::from_bytes(&bytes).unwrap().get_database(0);
+        let collection = match restored.records.get(b"vectors".as_slice()) {
+            Some(Structure::VectorCollection(collection)) => collection,
+            _ => panic!("vector collection not restored"),
+        };
+
+        let index = collection.index().expect("index not restored");
+        assert_eq!((index.m(), index.ef_construction(), index.ef_search(), index.len()), (12, 80, 40, 300));
+        let after = collection.approximate_search(&query, Metric::Cosine, 5, 0).unwrap().into_iter()
+            .map(|(element, score)| (element.to_vec(), score)).collect::<Vec<_>>();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_vindex_vinfo_vsim() {
+        let mut con = setup();
+        let _: () = con.del("vector-index-test").unwrap();
+        let vectors = random_vectors(500, 8, 3);
+        for (i, vector) in vectors.iter().enumerate() {
+            vadd(&mut con, "vector-index-test", &format!("e{}", i), vector).unwrap();
+        }
+
+        let info: Vec<redis::Value> = cmd("VINFO").arg("vector-index-test").query(&mut con).unwrap();
+        assert_eq!(info.len(), 6);
+        let result: String = cmd("VINDEX").arg("vector-index-test").arg("CREATE").arg("M").arg(8).arg("EFCONSTRUCTION").arg(64).arg("EFSEARCH").arg(50).arg("METRIC").arg("L2").query(&mut con).unwrap();
+        assert_eq!(result, "OK");
+        let info: HashMap<String, redis::Value> = cmd("VINFO").arg("vector-index-test").query(&mut con).unwrap();
+        assert_eq!(info["index"], redis::Value::BulkString(b"hnsw".to_vec()));
+        assert_eq!(info["metric"], redis::Value::BulkString(b"l2".to_vec()));
+        assert_eq!(info["m"], redis::Value::Int(8));
+        assert_eq!(info["ef-construction"], redis::Value::Int(64));
+        assert_eq!(info["ef-search"], redis::Value::Int(50));
+        assert_eq!(info["size"], redis::Value::Int(500));
+
+        // 索引的度量方式作为默认值，查询自身时最近的是自己
+        let result: Vec<(String, f64)> = cmd("VSIM").arg("vector-index-test").arg("ELE").arg("e42").arg("WITHSCORES").arg("COUNT").arg(5).query(&mut con).unwrap();
+        assert_eq!(result[0], ("e42".to_string(), 0.0));
+        assert!(result.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        let exact: Vec<String> = cmd("VSIM").arg("vector-index-test").arg("ELE").arg("e42").arg("COUNT").arg(5).arg("TRUTH").query(&mut con).unwrap();
+        let approximate: Vec<String> = cmd("VSIM").arg("vector-index-test").arg("ELE").arg("e42").arg("COUNT").arg(5).arg("EF").arg(500).query(&mut con).unwrap();
+        assert_eq!(approximate, exact);
+
+        // 增量更新
+        let _: i64 = cmd("VREM").arg("vector-index-test").arg("e42").query(&mut con).unwrap();
+        vadd(&mut con, "vector-index-test", "moved", &vectors[42]).unwrap();
+        let result: Vec<String> = cmd("VSIM").arg("vector-index-test").arg("ELE").arg("moved").arg("COUNT").arg(1).query(&mut con).unwrap();
+        assert_eq!(result, vec!["moved"]);
+
+        let dropped: i64 = cmd("VINDEX").arg("vector-index-test").arg("DROP").query(&mut con).unwrap();
+        assert_eq!(dropped, 1);
+        let dropped: i64 = cmd("VINDEX").arg("vector-index-test").arg("DROP").query(&mut con).unwrap();
+        assert_eq!(dropped, 0);
+        let info: HashMap<String, redis::Value> = cmd("VINFO").arg("vector-index-test").query(&mut con).unwrap();
+        assert_eq!(info["index"], redis::Value::BulkString(b"none".to_vec()));
+
+        let info: Option<Vec<String>> = cmd("VINFO").arg("vector-index-missing").query(&mut con).unwrap();
+        assert_eq!(info, None);
+        let result: RedisResult<String> = cmd("VINDEX").arg("vector-index-missing").arg("CREATE").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<String> = cmd("VINDEX").arg("vector-index-test").arg("CREATE").arg("M").arg(1).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<String> = cmd("VINDEX").arg("vector-index-test").arg("CREATE").arg("EFSEARCH").arg(0).query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<String> = cmd("VINDEX").arg("vector-index-test").arg("CREATE").arg("M").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<String> = cmd("VINDEX").arg("vector-index-test").arg("REBUILD").query(&mut con);
+        assert!(result.is_err());
+        let result: RedisResult<Vec<String>> = cmd("VSIM").arg("vector-index-test").arg("ELE").arg("moved").arg("EF").arg(0).query(&mut con);
+        assert!(result.is_err());
+    }
+}